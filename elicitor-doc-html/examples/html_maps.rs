@@ -0,0 +1,14 @@
+//! Map example - generate an HTML form with key/value tables.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_maps
+
+use elicitor_doc_html::to_html;
+use example_surveys::Deployment;
+
+fn main() {
+    let html = to_html::<Deployment>(Some("Deployment"));
+
+    std::fs::write("maps.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated maps.html");
+}
@@ -0,0 +1,14 @@
+//! Scheduling example - generate an HTML form with date, time and duration fields.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_scheduling
+
+use elicitor_doc_html::to_html;
+use example_surveys::Meeting;
+
+fn main() {
+    let html = to_html::<Meeting>(Some("Meeting"));
+
+    std::fs::write("scheduling.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated scheduling.html");
+}
@@ -1,6 +1,9 @@
 //! HTML form generator implementation.
 
-use elicitor::{DefaultValue, ListElementKind, Question, QuestionKind, Survey, SurveyDefinition};
+use elicitor::{
+    DefaultValue, ListElementKind, MAP_KEYS_KEY, Question, QuestionKind, ResponseValue, Survey,
+    SurveyDefinition,
+};
 
 /// Options for HTML generation.
 #[derive(Debug, Clone, Default)]
@@ -138,6 +141,9 @@ fn generate_html(definition: &SurveyDefinition, options: &HtmlOptions) -> String
     html
 }
 
+/// Blank rows shown for the entries of a map.
+const MAP_ROWS: usize = 3;
+
 /// Generate HTML for a single question.
 fn generate_question(
     question: &Question,
@@ -157,6 +163,7 @@ fn generate_question(
 
     let label = format_label(question.ask(), &path);
     let field_id = path.replace('.', "-");
+    let help = help_html(question.help(), prefix, indent + 1);
 
     let mut html = String::new();
 
@@ -188,6 +195,7 @@ fn generate_question(
                 "{ind}  <label for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
             html.push_str(&format!(
                 "{ind}  <input type=\"text\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-input\"{value_attr}>\n"
             ));
@@ -197,7 +205,7 @@ fn generate_question(
         QuestionKind::Multiline(_) => {
             let content = default_value
                 .and_then(|v| v.as_str())
-                .map(|s| escape_html(s))
+                .map(escape_html)
                 .unwrap_or_default();
 
             html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
@@ -205,6 +213,7 @@ fn generate_question(
                 "{ind}  <label for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
             html.push_str(&format!(
                 "{ind}  <textarea id=\"{field_id}\" name=\"{path}\" rows=\"4\" class=\"{prefix}-textarea\">{content}</textarea>\n"
             ));
@@ -218,6 +227,7 @@ fn generate_question(
                 "{ind}  <label for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
             html.push_str(&format!(
                 "{ind}  <input type=\"password\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-input\">\n"
             ));
@@ -235,6 +245,7 @@ fn generate_question(
                 "{ind}  <label for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
 
             let mut attrs = format!(
                 "type=\"number\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-input\""
@@ -261,6 +272,7 @@ fn generate_question(
                 "{ind}  <label for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
 
             let mut attrs = format!(
                 "type=\"number\" step=\"any\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-input\""
@@ -293,6 +305,24 @@ fn generate_question(
                 "{ind}  <label for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
+        QuestionKind::Date(_)
+        | QuestionKind::Time(_)
+        | QuestionKind::DateTime(_)
+        | QuestionKind::Duration(_) => {
+            html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+            html.push_str(&format!(
+                "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&help);
+            html.push_str(&format!(
+                "{ind}  {}\n",
+                temporal_input(question.kind(), &field_id, &path, prefix, default_value)
+            ));
             html.push_str(&format!("{ind}</div>\n"));
         }
 
@@ -309,6 +339,7 @@ fn generate_question(
                 escape_html(&label),
                 type_hint
             ));
+            html.push_str(&help);
             html.push_str(&format!(
                 "{ind}  <input type=\"text\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-input\" placeholder=\"value1, value2, ...\">\n"
             ));
@@ -328,6 +359,7 @@ fn generate_question(
                 "{ind}  <legend>{}</legend>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
 
             for (idx, variant) in one_of.variants.iter().enumerate() {
                 let variant_id = format!("{field_id}-{}", variant.name);
@@ -363,6 +395,7 @@ fn generate_question(
                     "{ind}    <label for=\"{variant_id}\">{}</label>\n",
                     escape_html(&variant_label)
                 ));
+                html.push_str(&help_html(variant.help.as_deref(), prefix, indent + 2));
 
                 // Nested fields for this variant
                 if !matches!(variant.kind, QuestionKind::Unit) {
@@ -398,6 +431,7 @@ fn generate_question(
                 "{ind}  <legend>{}</legend>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
 
             for (idx, variant) in any_of.variants.iter().enumerate() {
                 let variant_id = format!("{field_id}-{idx}");
@@ -434,6 +468,7 @@ fn generate_question(
                     "{ind}    <label for=\"{variant_id}\">{}</label>\n",
                     escape_html(&variant_label)
                 ));
+                html.push_str(&help_html(variant.help.as_deref(), prefix, indent + 2));
 
                 // Nested fields for this variant
                 if !matches!(variant.kind, QuestionKind::Unit) {
@@ -463,6 +498,7 @@ fn generate_question(
                 "{ind}  <legend>{}</legend>\n",
                 escape_html(&label)
             ));
+            html.push_str(&help);
 
             for nested_q in all_of.questions() {
                 html.push_str(&generate_question(
@@ -475,11 +511,78 @@ fn generate_question(
 
             html.push_str(&format!("{ind}</fieldset>\n"));
         }
+
+        QuestionKind::Map(map_q) => {
+            html.push_str(&format!(
+                "{ind}<fieldset class=\"{prefix}-fieldset {prefix}-map\">\n"
+            ));
+            html.push_str(&format!(
+                "{ind}  <legend>{}</legend>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&help);
+            html.push_str(&format!("{ind}  <table class=\"{prefix}-map-table\">\n"));
+            html.push_str(&format!(
+                "{ind}    <thead><tr><th>Key</th><th>Value</th></tr></thead>\n"
+            ));
+            html.push_str(&format!("{ind}    <tbody>\n"));
+
+            // Blank rows to fill in; keys and values are indexed by row
+            for idx in 0..MAP_ROWS {
+                html.push_str(&format!("{ind}      <tr>\n"));
+                html.push_str(&format!(
+                    "{ind}        <td><input type=\"text\" id=\"{field_id}-keys-{idx}\" name=\"{path}.{MAP_KEYS_KEY}.{idx}\" class=\"{prefix}-input\"></td>\n"
+                ));
+                html.push_str(&format!("{ind}        <td>\n"));
+                html.push_str(&generate_variant_fields(
+                    &map_q.value_kind,
+                    &format!("{path}.{idx}"),
+                    prefix,
+                    indent + 5,
+                ));
+                html.push_str(&format!("{ind}        </td>\n"));
+                html.push_str(&format!("{ind}      </tr>\n"));
+            }
+
+            html.push_str(&format!("{ind}    </tbody>\n"));
+            html.push_str(&format!("{ind}  </table>\n"));
+            html.push_str(&format!("{ind}</fieldset>\n"));
+        }
+
+        QuestionKind::Lazy(lazy) => match lazy.expand() {
+            // A recursive type is written out where it first appears
+            Ok(all_of) if lazy.depth() == 0 => {
+                let mut expanded = Question::new(
+                    question.path().clone(),
+                    question.ask(),
+                    QuestionKind::AllOf(all_of),
+                );
+                if let Some(help) = question.help() {
+                    expanded = expanded.with_help(help);
+                }
+                html.push_str(&generate_question(&expanded, prefix, indent, parent_path));
+            }
+            _ => {
+                html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+                html.push_str(&format!("{ind}  <label>{}</label>\n", escape_html(&label)));
+                html.push_str(&help);
+                html.push_str(&format!(
+                    "{ind}  <p class=\"{prefix}-note\">{}</p>\n",
+                    lazy_note(lazy.name())
+                ));
+                html.push_str(&format!("{ind}</div>\n"));
+            }
+        },
     }
 
     html
 }
 
+/// Note shown in place of the nested questions of a recursive type.
+fn lazy_note(name: &str) -> String {
+    format!("Same questions as {}.", escape_html(name))
+}
+
 /// Generate HTML for nested variant fields.
 fn generate_variant_fields(
     kind: &QuestionKind,
@@ -523,6 +626,16 @@ fn generate_variant_fields(
             }
             html.push_str(&format!("{ind}<input {attrs}>\n"));
         }
+        QuestionKind::Date(_)
+        | QuestionKind::Time(_)
+        | QuestionKind::DateTime(_)
+        | QuestionKind::Duration(_) => {
+            let field_id = base_path.replace('.', "-");
+            html.push_str(&format!(
+                "{ind}{}\n",
+                temporal_input(kind, &field_id, base_path, prefix, None)
+            ));
+        }
         QuestionKind::AllOf(all_of) => {
             for nested_q in all_of.questions() {
                 let nested_path = format!("{}.{}", base_path, nested_q.path().as_str());
@@ -534,6 +647,7 @@ fn generate_variant_fields(
                     "{ind}  <label for=\"{field_id}\">{}</label>\n",
                     escape_html(&label)
                 ));
+                html.push_str(&help_html(nested_q.help(), prefix, indent + 1));
 
                 match nested_q.kind() {
                     QuestionKind::Input(_) | QuestionKind::Multiline(_) => {
@@ -565,6 +679,18 @@ fn generate_variant_fields(
                         }
                         html.push_str(&format!("{ind}  <input {attrs}>\n"));
                     }
+                    kind if kind.is_temporal() => {
+                        html.push_str(&format!(
+                            "{ind}  {}\n",
+                            temporal_input(kind, &field_id, &nested_path, prefix, None)
+                        ));
+                    }
+                    QuestionKind::Lazy(lazy) => {
+                        html.push_str(&format!(
+                            "{ind}  <p class=\"{prefix}-note\">{}</p>\n",
+                            lazy_note(lazy.name())
+                        ));
+                    }
                     _ => {
                         html.push_str(&format!(
                             "{ind}  <input type=\"text\" id=\"{field_id}\" name=\"{nested_path}\" class=\"{prefix}-input\">\n"
@@ -581,12 +707,58 @@ fn generate_variant_fields(
     html
 }
 
+/// The input element for a date, time, date-time or duration question.
+///
+/// Dates and times use the browser's native pickers and bounds; durations
+/// are typed as text such as `1h 30m`.
+fn temporal_input(
+    kind: &QuestionKind,
+    field_id: &str,
+    name: &str,
+    prefix: &str,
+    default_value: Option<&ResponseValue>,
+) -> String {
+    // `datetime-local` values separate the date and time with a `T`
+    let html_value = |text: String| match kind {
+        QuestionKind::DateTime(_) => text.replacen(' ', "T", 1),
+        _ => text,
+    };
+    let input_type = match kind {
+        QuestionKind::Date(_) => "date",
+        QuestionKind::Time(_) => "time",
+        QuestionKind::DateTime(_) => "datetime-local",
+        _ => "text",
+    };
+
+    let mut attrs =
+        format!("type=\"{input_type}\" id=\"{field_id}\" name=\"{name}\" class=\"{prefix}-input\"");
+    if matches!(kind, QuestionKind::Duration(_)) {
+        let format = kind.temporal_format().unwrap_or_default();
+        attrs.push_str(&format!(" placeholder=\"{}\"", escape_html(format)));
+    } else if let Some((min, max)) = kind.temporal_bounds() {
+        if let Some(min) = min {
+            attrs.push_str(&format!(" min=\"{}\"", html_value(min)));
+        }
+        if let Some(max) = max {
+            attrs.push_str(&format!(" max=\"{}\"", html_value(max)));
+        }
+    }
+    let default = default_value
+        .and_then(ResponseValue::temporal_text)
+        .or_else(|| kind.temporal_default()?.temporal_text());
+    if let Some(default) = default {
+        attrs.push_str(&format!(" value=\"{}\"", escape_html(&html_value(default))));
+    }
+
+    format!("<input {attrs}>")
+}
+
 /// Format a prompt as a label.
 fn format_label(ask: &str, path: &str) -> String {
     if ask.is_empty() {
         // Create a readable label from the path
         path.split('.')
-            .last()
+            .next_back()
             .unwrap_or("")
             .split('_')
             .map(|word| {
@@ -603,6 +775,18 @@ fn format_label(ask: &str, path: &str) -> String {
     }
 }
 
+/// The paragraph of help text under a label, if there is any.
+fn help_html(help: Option<&str>, prefix: &str, indent: usize) -> String {
+    help.map(|help| {
+        format!(
+            "{}<p class=\"{prefix}-help\">{}</p>\n",
+            "  ".repeat(indent),
+            escape_html(help)
+        )
+    })
+    .unwrap_or_default()
+}
+
 /// Escape HTML special characters.
 fn escape_html(s: &str) -> String {
     s.replace('&', "&amp;")
@@ -655,6 +839,26 @@ fn generate_styles(prefix: &str) -> String {
     .{prefix}-radio-option, .{prefix}-checkbox-option {{
       margin: 0.25rem 0;
     }}
+    .{prefix}-map-table {{
+      width: 100%;
+      border-collapse: collapse;
+    }}
+    .{prefix}-map-table th, .{prefix}-map-table td {{
+      border: 1px solid #ccc;
+      padding: 0.25rem;
+      text-align: left;
+      vertical-align: top;
+    }}
+    .{prefix}-help {{
+      margin: 0 0 0.25rem;
+      color: #555;
+      font-size: 0.9em;
+      white-space: pre-wrap;
+    }}
+    .{prefix}-note {{
+      margin: 0;
+      font-style: italic;
+    }}
     .{prefix}-nested {{
       margin-left: 1.5rem;
       padding-left: 0.5rem;
@@ -696,4 +900,37 @@ mod tests {
         assert!(options.full_document);
         assert_eq!(options.class_prefix, "my-form");
     }
+
+    #[test]
+    fn help_text_follows_labels() {
+        use elicitor::{InputQuestion, OneOfQuestion, ResponsePath, Variant};
+
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                ResponsePath::new("host"),
+                "Host:",
+                QuestionKind::Input(InputQuestion::new()),
+            )
+            .with_help("Name or address of the <server>."),
+            Question::new(
+                ResponsePath::new("mode"),
+                "Mode:",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Fast").with_help("Skip the checks."),
+                    Variant::unit("Safe"),
+                ])),
+            ),
+        ]);
+        let html = generate_html(&definition, &HtmlOptions::new());
+        let lines: Vec<&str> = html.lines().map(str::trim).collect();
+        let html = lines.join("\n");
+
+        assert!(html.contains(
+            "<label for=\"host\">Host:</label>\n<p class=\"survey-help\">Name or address of the &lt;server&gt;.</p>"
+        ));
+        assert!(html.contains(
+            "<label for=\"mode-Fast\">Fast</label>\n<p class=\"survey-help\">Skip the checks.</p>"
+        ));
+        assert_eq!(html.matches("-help\"").count(), 2);
+    }
 }
@@ -16,6 +16,29 @@ fn escape_latex(s: &str) -> String {
         .replace('^', "\\textasciicircum{}")
 }
 
+/// The help text of a question, set small and italic below its text.
+fn render_help(help: &str, indent: &str) -> String {
+    format!(
+        "{indent}\\noindent{{\\small\\itshape {}\\par}}\n\n{indent}\\smallskip\n",
+        escape_latex(help)
+    )
+}
+
+/// The help text of the options of a choice menu, one line each.
+fn render_option_help(variants: &[elicitor::Variant], indent: &str) -> String {
+    variants
+        .iter()
+        .filter_map(|variant| {
+            let help = variant.help.as_deref()?;
+            Some(format!(
+                "\n{indent}\\noindent{{\\small\\textit{{{}}}: {}\\par}}\n",
+                escape_latex(&variant.name),
+                escape_latex(help)
+            ))
+        })
+        .collect()
+}
+
 /// Sanitize a field name for use in PDF form field names.
 /// PDF field names should not contain special characters.
 fn sanitize_field_name(s: &str) -> String {
@@ -27,6 +50,24 @@ fn sanitize_field_name(s: &str) -> String {
         .collect()
 }
 
+/// Build the italic hint shown next to a date, time or duration field:
+/// the expected format plus any bounds.
+fn temporal_hint(kind: &elicitor::QuestionKind) -> String {
+    let mut hint = kind.temporal_format().unwrap_or_default().to_string();
+    if let Some((min, max)) = kind.temporal_bounds() {
+        if let Some(min) = min {
+            hint.push_str(&format!(", min: {}", min));
+        }
+        if let Some(max) = max {
+            hint.push_str(&format!(", max: {}", max));
+        }
+    }
+    format!("\\textit{{\\small({})}}", escape_latex(&hint))
+}
+
+/// Blank rows printed for the entries of a map.
+const MAP_ROWS: usize = 3;
+
 /// Calculate shade percentage based on nesting depth.
 /// Starts at 5% and increases by 5% per level, capped at 25%.
 fn shade_percent(indent_level: usize) -> usize {
@@ -99,7 +140,7 @@ pub fn to_latex_form(survey: &SurveyDefinition) -> String {
     if let Some(epilogue) = &survey.epilogue {
         latex.push_str("\n\\vspace{2em}\n\\noindent ");
         latex.push_str(&escape_latex(epilogue));
-        latex.push_str("\n");
+        latex.push('\n');
     }
 
     latex.push_str("\n\\end{document}\n");
@@ -139,6 +180,9 @@ fn render_question_with_path(
         s.push_str(&indent);
         s.push_str("\\smallskip\n");
     }
+    if let Some(help) = q.help() {
+        s.push_str(&render_help(help, &indent));
+    }
 
     match q.kind() {
         QuestionKind::Input(_) => {
@@ -166,7 +210,7 @@ fn render_question_with_path(
                 }
                 s.push_str(")}");
             }
-            s.push_str("\n");
+            s.push('\n');
             s.push_str(&indent);
             s.push_str("\\par\\medskip\n");
         }
@@ -187,7 +231,7 @@ fn render_question_with_path(
                 }
                 s.push_str(")}");
             }
-            s.push_str("\n");
+            s.push('\n');
             s.push_str(&indent);
             s.push_str("\\par\\medskip\n");
         }
@@ -211,11 +255,12 @@ fn render_question_with_path(
                 .collect();
             s.push_str(&options.join(","));
             s.push_str("}\n");
+            s.push_str(&render_option_help(&oneof.variants, &indent));
 
             // Render follow-up fields for variants that have nested questions
             for variant in &oneof.variants {
                 if !matches!(variant.kind, elicitor::QuestionKind::Unit) {
-                    s.push_str("\n");
+                    s.push('\n');
                     s.push_str(&indent);
                     s.push_str("\\vspace{0.5em}\n");
                     s.push_str(&indent);
@@ -246,6 +291,11 @@ fn render_question_with_path(
                 s.push_str(&checkbox_name);
                 s.push_str(",width=10pt,height=10pt,borderwidth=1pt,bordercolor={0.4 0.4 0.4}]{} ");
                 s.push_str(&escape_latex(&variant.name));
+                if let Some(help) = &variant.help {
+                    s.push_str(" --- {\\small\\itshape ");
+                    s.push_str(&escape_latex(help));
+                    s.push('}');
+                }
                 s.push_str("\n\n");
                 s.push_str(&indent);
                 s.push_str("\\vspace{0.3em}\n");
@@ -254,7 +304,7 @@ fn render_question_with_path(
             // Render follow-up fields for variants that have nested questions
             for variant in &anyof.variants {
                 if !matches!(variant.kind, elicitor::QuestionKind::Unit) {
-                    s.push_str("\n");
+                    s.push('\n');
                     s.push_str(&indent);
                     s.push_str("\\vspace{0.5em}\n");
                     s.push_str(&indent);
@@ -290,7 +340,7 @@ fn render_question_with_path(
             ));
             for (i, sub) in allof.questions.iter().enumerate() {
                 if i > 0 {
-                    s.push_str("\n");
+                    s.push('\n');
                     s.push_str(&indent);
                     s.push_str("\\vspace{0.8em}\n");
                 }
@@ -322,11 +372,77 @@ fn render_question_with_path(
                 ",width=4in,bordercolor={0.5 0.5 0.5}]{} \\textit{\\small(comma-separated)}\n\n",
             );
         }
+        QuestionKind::Date(_)
+        | QuestionKind::Time(_)
+        | QuestionKind::DateTime(_)
+        | QuestionKind::Duration(_) => {
+            s.push_str(&indent);
+            s.push_str("\\noindent\\TextField[name=");
+            s.push_str(&field_name);
+            s.push_str(",width=1.5in,bordercolor={0.5 0.5 0.5}]{} ");
+            s.push_str(&temporal_hint(q.kind()));
+            s.push('\n');
+            s.push_str(&indent);
+            s.push_str("\\par\\medskip\n");
+        }
+        QuestionKind::Map(map_q) => {
+            s.push_str(&render_map_table(map_q, &full_path, indent_level));
+        }
+        QuestionKind::Lazy(lazy) => match lazy.expand() {
+            // A recursive type is written out where it first appears
+            Ok(allof) if lazy.depth() == 0 => {
+                let expanded =
+                    elicitor::Question::new(q.path().clone(), "", QuestionKind::AllOf(allof));
+                s.push_str(&render_question_with_path(
+                    &expanded,
+                    indent_level,
+                    parent_path,
+                ));
+            }
+            _ => {
+                s.push_str(&indent);
+                s.push_str("\\noindent\\textit{\\small Same questions as ");
+                s.push_str(&escape_latex(lazy.name()));
+                s.push_str(".}\n");
+                s.push_str(&indent);
+                s.push_str("\\par\\medskip\n");
+            }
+        },
     }
 
     s
 }
 
+/// Render a map as a two-column table with blank key and value fields.
+fn render_map_table(map_q: &elicitor::MapQuestion, full_path: &str, indent_level: usize) -> String {
+    let indent = "  ".repeat(indent_level);
+    let field_name = sanitize_field_name(full_path);
+    let mut s = String::new();
+
+    s.push_str(&indent);
+    s.push_str("\\noindent\\begin{tabular}{|p{1.6in}|p{4.2in}|}\n");
+    s.push_str(&indent);
+    s.push_str("\\hline \\textbf{Key} & \\textbf{Value} \\\\ \\hline\n");
+    for idx in 0..MAP_ROWS {
+        s.push_str(&indent);
+        s.push_str("\\TextField[name=");
+        s.push_str(&format!("{}-keys-{}", field_name, idx));
+        s.push_str(",width=1.5in,bordercolor={0.5 0.5 0.5}]{} &\n");
+        s.push_str(&render_variant_fields(
+            &map_q.value_kind,
+            &format!("{}.{}", full_path, idx),
+            indent_level + 1,
+        ));
+        s.push_str(&indent);
+        s.push_str("\\\\ \\hline\n");
+    }
+    s.push_str(&indent);
+    s.push_str("\\end{tabular}\n");
+    s.push_str(&indent);
+    s.push_str("\\par\\medskip\n");
+    s
+}
+
 /// Render the fields for a variant's nested QuestionKind
 fn render_variant_fields(
     kind: &elicitor::QuestionKind,
@@ -437,11 +553,12 @@ fn render_variant_fields(
                 .collect();
             s.push_str(&options.join(","));
             s.push_str("}\n");
+            s.push_str(&render_option_help(&oneof.variants, &indent));
 
             // Recursively render nested variant fields
             for variant in &oneof.variants {
                 if !matches!(variant.kind, QuestionKind::Unit) {
-                    s.push_str("\n");
+                    s.push('\n');
                     s.push_str(&indent);
                     s.push_str("\\vspace{0.3em}\n");
                     s.push_str(&indent);
@@ -478,6 +595,11 @@ fn render_variant_fields(
                 s.push_str(&checkbox_name);
                 s.push_str(",width=10pt,height=10pt,borderwidth=1pt,bordercolor={0.4 0.4 0.4}]{} ");
                 s.push_str(&escape_latex(&variant.name));
+                if let Some(help) = &variant.help {
+                    s.push_str(" --- {\\small\\itshape ");
+                    s.push_str(&escape_latex(help));
+                    s.push('}');
+                }
                 s.push_str("\n\n");
                 s.push_str(&indent);
                 s.push_str("\\vspace{0.3em}\n");
@@ -497,6 +619,29 @@ fn render_variant_fields(
             s.push_str(&field_name);
             s.push_str("-value,width=4in,bordercolor={0.5 0.5 0.5}]{} \\textit{\\small(comma-separated)}\n");
         }
+        QuestionKind::Date(_)
+        | QuestionKind::Time(_)
+        | QuestionKind::DateTime(_)
+        | QuestionKind::Duration(_) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            s.push_str("\\noindent\\TextField[name=");
+            s.push_str(&field_name);
+            s.push_str("-value,width=1.5in,bordercolor={0.5 0.5 0.5}]{} ");
+            s.push_str(&temporal_hint(kind));
+            s.push_str("\n\n");
+        }
+        QuestionKind::Map(map_q) => {
+            s.push_str(&render_map_table(map_q, parent_path, indent_level));
+        }
+        QuestionKind::Lazy(_) => {
+            let question = elicitor::Question::new("", "", kind.clone());
+            s.push_str(&render_question_with_path(
+                &question,
+                indent_level,
+                Some(parent_path),
+            ));
+        }
     }
 
     s
@@ -0,0 +1,60 @@
+//! Example embedding a survey form in an existing eframe application.
+//!
+//! The survey lives in a side panel next to the application's own UI.
+//!
+//! Run with: cargo run -p elicitor-form-egui --example egui_embedded
+
+use eframe::egui;
+use elicitor::Survey;
+use elicitor_form_egui::{SurveyFormState, SurveyWidget};
+use example_surveys::UserProfile;
+
+struct App {
+    form: SurveyFormState,
+    profiles: Vec<UserProfile>,
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::SidePanel::right("new_profile")
+            .min_width(320.0)
+            .show(ctx, |ui| {
+                ui.heading("New profile");
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.add(
+                        SurveyWidget::new(&mut self.form)
+                            .with_submit_label("Add")
+                            .with_cancel_button(false),
+                    );
+                });
+            });
+
+        if let Some(responses) = self.form.take_responses() {
+            self.profiles.push(UserProfile::from_responses(&responses));
+            self.form.reset();
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Profiles");
+            for profile in &self.profiles {
+                ui.label(format!(
+                    "{} ({}) <{}>",
+                    profile.name, profile.age, profile.email
+                ));
+            }
+        });
+    }
+}
+
+fn main() -> eframe::Result {
+    eframe::run_native(
+        "Embedded survey",
+        eframe::NativeOptions::default(),
+        Box::new(|_cc| {
+            Ok(Box::new(App {
+                form: SurveyFormState::for_survey::<UserProfile>(),
+                profiles: Vec::new(),
+            }))
+        }),
+    )
+}
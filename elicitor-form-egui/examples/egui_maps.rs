@@ -0,0 +1,18 @@
+//! Map example
+//!
+//! Demonstrates:
+//! - HashMap and BTreeMap fields edited as a list of entries
+//! - Adding and removing entries
+//! - #[validate_key] checking each key as it is typed
+//!
+//! Run with: cargo run --example egui_maps
+
+use elicitor_form_egui::EguiBackend;
+use example_surveys::Deployment;
+
+fn main() -> anyhow::Result<()> {
+    let backend = EguiBackend::new();
+    let result = Deployment::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
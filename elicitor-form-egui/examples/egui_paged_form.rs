@@ -0,0 +1,24 @@
+//! Paged and tabbed layouts
+//!
+//! Demonstrates:
+//! - One page per nested struct, with Back/Next buttons and a sidebar
+//! - Passing `tabs` shows the same form as tabs instead
+//!
+//! Run with: cargo run --example egui_paged_form [tabs]
+
+use elicitor_form_egui::{EguiBackend, FormLayout};
+use example_surveys::UserRegistration;
+
+fn main() -> anyhow::Result<()> {
+    let layout = match std::env::args().nth(1).as_deref() {
+        Some("tabs") => FormLayout::Tabs,
+        _ => FormLayout::Pages,
+    };
+    let backend = EguiBackend::new()
+        .with_title("Registration")
+        .with_window_size([700.0, 500.0])
+        .with_layout(layout);
+    let result = UserRegistration::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -0,0 +1,18 @@
+//! Date, time and duration example
+//!
+//! Demonstrates:
+//! - Date fields with a calendar picker
+//! - Time and duration fields typed as text
+//! - #[min]/#[max] bounds on temporal fields
+//!
+//! Run with: cargo run --example egui_scheduling
+
+use elicitor_form_egui::EguiBackend;
+use example_surveys::Meeting;
+
+fn main() -> anyhow::Result<()> {
+    let backend = EguiBackend::new();
+    let result = Meeting::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
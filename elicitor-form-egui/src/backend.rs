@@ -2,14 +2,20 @@
 
 use eframe::egui;
 use elicitor::{
-    AllOfQuestion, AnyOfQuestion, DefaultValue, FloatQuestion, IntQuestion, ListElementKind,
-    ListQuestion, OneOfQuestion, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition, Variant,
+    AllOfQuestion, AnyOfQuestion, CONFIRMATION_MISMATCH, CONFIRMATION_PROMPT, DefaultValue,
+    FloatQuestion, History, IntQuestion, ListElementKind, ListQuestion, MAP_KEYS_KEY, MapQuestion,
+    OneOfQuestion, PasswordStrength, PreviewFormatter, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SecretValue,
+    SurveyBackend, SurveyDefinition, Variant,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
+use crate::date_picker::date_picker_button;
+use crate::layout::FormLayout;
+use crate::widget::{SurveyFormState, SurveyPreview, SurveyWidget};
+
 /// Error type for the Egui backend.
 #[derive(Debug, Error)]
 pub enum EguiError {
@@ -29,6 +35,12 @@ pub struct EguiBackend {
     title: String,
     /// Window size [width, height].
     window_size: [f32; 2],
+    /// How the questions are arranged.
+    layout: FormLayout,
+    /// Whether to show the result preview panel.
+    preview: bool,
+    /// Custom format offered in the result preview.
+    preview_formatter: Option<PreviewFormatter>,
 }
 
 impl Default for EguiBackend {
@@ -43,6 +55,9 @@ impl EguiBackend {
         Self {
             title: "Survey".to_string(),
             window_size: [500.0, 600.0],
+            layout: FormLayout::default(),
+            preview: false,
+            preview_formatter: None,
         }
     }
 
@@ -57,16 +72,43 @@ impl EguiBackend {
         self.window_size = size;
         self
     }
+
+    /// Split large forms into tabs or pages instead of one long scroll.
+    pub fn with_layout(mut self, layout: FormLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Show a side panel previewing the responses as they are entered
+    /// (see [`SurveyPreview`]).
+    pub fn with_preview(mut self, enabled: bool) -> Self {
+        self.preview = enabled;
+        self
+    }
+
+    /// Offer a custom format in the preview panel, such as the survey type's
+    /// `Debug` output or JSON from `serde_json`.
+    ///
+    /// The formatter receives the responses with masked answers redacted.
+    pub fn with_preview_formatter(
+        mut self,
+        format: impl Fn(&Responses) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.preview_formatter = Some(PreviewFormatter::new(format));
+        self
+    }
 }
 
 /// State for a single field in the form.
-#[derive(Debug, Clone)]
-enum FieldState {
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FieldState {
     /// String input (for Input, Multiline, Masked).
     Text {
         value: String,
         is_password: bool,
         is_multiline: bool,
+        /// Second entry of a masked question that asks for confirmation.
+        confirmation: Option<String>,
     },
     /// Integer input.
     Int { value: String, parsed: Option<i64> },
@@ -74,6 +116,11 @@ enum FieldState {
     Float { value: String, parsed: Option<f64> },
     /// Boolean toggle.
     Bool { value: bool },
+    /// Date, time, date-time or duration, typed as text.
+    Temporal {
+        value: String,
+        parsed: Option<ResponseValue>,
+    },
     /// List of values (comma-separated input).
     List {
         value: String,
@@ -91,16 +138,24 @@ enum FieldState {
         #[allow(dead_code)]
         variants: Vec<String>,
     },
+    /// Keys of a map's entries; each value has its own fields.
+    Map { keys: Vec<String> },
 }
 
 impl FieldState {
     /// Extract the ResponseValue from this field state.
     fn to_response_value(&self) -> Option<ResponseValue> {
         match self {
+            FieldState::Text {
+                value,
+                is_password: true,
+                ..
+            } => Some(ResponseValue::Secret(SecretValue::new(value.as_str()))),
             FieldState::Text { value, .. } => Some(ResponseValue::String(value.clone())),
             FieldState::Int { parsed, .. } => parsed.map(ResponseValue::Int),
             FieldState::Float { parsed, .. } => parsed.map(ResponseValue::Float),
             FieldState::Bool { value } => Some(ResponseValue::Bool(*value)),
+            FieldState::Temporal { parsed, .. } => parsed.clone(),
             FieldState::List {
                 value,
                 element_kind,
@@ -136,30 +191,135 @@ impl FieldState {
                     .collect();
                 Some(ResponseValue::ChosenVariants(indices))
             }
+            FieldState::Map { keys } => Some(ResponseValue::StringList(keys.clone())),
+        }
+    }
+
+    /// Whether the user has given this field a usable answer.
+    fn is_answered(&self) -> bool {
+        match self {
+            FieldState::Text { value, .. } | FieldState::List { value, .. } => {
+                !value.trim().is_empty()
+            }
+            FieldState::Int { parsed, .. } => parsed.is_some(),
+            FieldState::Float { parsed, .. } => parsed.is_some(),
+            FieldState::Temporal { parsed, .. } => parsed.is_some(),
+            FieldState::OneOf { selected, .. } => selected.is_some(),
+            FieldState::Bool { .. } | FieldState::AnyOf { .. } | FieldState::Map { .. } => true,
+        }
+    }
+
+    /// State of a temporal field holding `default`, else the question's own
+    /// default.
+    fn temporal(kind: &QuestionKind, default: Option<&ResponseValue>) -> Self {
+        let value = default
+            .and_then(ResponseValue::temporal_text)
+            .or_else(|| kind.temporal_default()?.temporal_text())
+            .unwrap_or_default();
+        let parsed = kind.parse_temporal(&value).and_then(Result::ok);
+        FieldState::Temporal { value, parsed }
+    }
+
+    /// Whether the field is edited by typing, so keystrokes are grouped into
+    /// one undo step.
+    fn is_typed(&self) -> bool {
+        matches!(
+            self,
+            FieldState::Text { .. }
+                | FieldState::Int { .. }
+                | FieldState::Float { .. }
+                | FieldState::Temporal { .. }
+                | FieldState::List { .. }
+                | FieldState::Map { .. }
+        )
+    }
+}
+
+/// Find the prompt of the question at `target`, looking into nested structs
+/// and enum variants.
+fn find_prompt(
+    questions: &[Question],
+    prefix: &ResponsePath,
+    target: &ResponsePath,
+) -> Option<String> {
+    for question in questions {
+        let path = prefix.child(question.path().as_str());
+        if path == *target {
+            return Some(question.ask().to_string()).filter(|ask| !ask.is_empty());
+        }
+        if !path.is_empty() && target.strip_path_prefix(&path).is_none() {
+            continue;
+        }
+        let found = match question.kind() {
+            QuestionKind::AllOf(all_of) => find_prompt(all_of.questions(), &path, target),
+            QuestionKind::OneOf(one_of) => one_of.variants.iter().find_map(|v| match &v.kind {
+                QuestionKind::AllOf(all_of) => find_prompt(all_of.questions(), &path, target),
+                _ => None,
+            }),
+            QuestionKind::Lazy(lazy) => lazy
+                .expand()
+                .ok()
+                .and_then(|all_of| find_prompt(all_of.questions(), &path, target)),
+            _ => None,
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// How far a group of questions has been filled in, shown in its section header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct SectionProgress {
+    pub(crate) answered: usize,
+    pub(crate) total: usize,
+    pub(crate) errors: usize,
+}
+
+impl SectionProgress {
+    /// Short badge such as "3/5", "5/5 ✓" or "1 error".
+    pub(crate) fn badge(&self) -> String {
+        match self.errors {
+            0 if self.answered == self.total => format!("{}/{} ✓", self.answered, self.total),
+            0 => format!("{}/{}", self.answered, self.total),
+            1 => "1 error".to_string(),
+            n => format!("{n} errors"),
         }
     }
 }
 
 /// The form state for the entire survey.
-struct FormState {
+pub(crate) struct FormState {
     /// Map from response path to field state.
-    fields: HashMap<ResponsePath, FieldState>,
+    pub(crate) fields: HashMap<ResponsePath, FieldState>,
     /// Validation errors for each field.
-    errors: HashMap<ResponsePath, String>,
+    pub(crate) errors: HashMap<ResponsePath, String>,
     /// Whether the form has been submitted.
-    submitted: bool,
+    pub(crate) submitted: bool,
     /// Whether the window was closed (cancelled).
-    cancelled: bool,
+    pub(crate) cancelled: bool,
     /// Prelude message.
-    prelude: Option<String>,
+    pub(crate) prelude: Option<String>,
     /// Epilogue message.
-    epilogue: Option<String>,
+    pub(crate) epilogue: Option<String>,
     /// The survey definition for rendering.
-    definition: SurveyDefinition,
+    pub(crate) definition: SurveyDefinition,
+    /// Field to focus and scroll to on the next render.
+    pub(crate) focus: Option<ResponsePath>,
+    /// Whether any field was edited during the current render.
+    pub(crate) changed: bool,
+    /// Undo/redo history of field edits.
+    pub(crate) history: History<ResponsePath, FieldState>,
+    /// Widget that had keyboard focus during the last render; typing runs in
+    /// the history end when it changes.
+    pub(crate) history_focus: Option<egui::Id>,
+    /// Masked fields whose text is currently shown in clear.
+    pub(crate) revealed: HashSet<ResponsePath>,
 }
 
 impl FormState {
-    fn new(definition: SurveyDefinition) -> Self {
+    pub(crate) fn new(definition: SurveyDefinition) -> Self {
         let mut state = Self {
             fields: HashMap::new(),
             errors: HashMap::new(),
@@ -168,6 +328,11 @@ impl FormState {
             prelude: definition.prelude.clone(),
             epilogue: definition.epilogue.clone(),
             definition,
+            focus: None,
+            changed: false,
+            history: History::default(),
+            history_focus: None,
+            revealed: HashSet::new(),
         };
 
         // Initialize field states from the survey definition
@@ -178,6 +343,103 @@ impl FormState {
         state
     }
 
+    /// Add the change to the field at `path` since `before` to the undo
+    /// history.
+    pub(crate) fn record_edit(&mut self, path: &ResponsePath, before: FieldState) {
+        // Adding or removing map entries moves the fields of other entries,
+        // so only edits to the keys themselves can be undone
+        if let Some(after) = self.fields.get(path)
+            && *after != before
+            && !matches!(
+                (&before, after),
+                (FieldState::Map { keys: old }, FieldState::Map { keys: new })
+                    if old.len() != new.len()
+            )
+        {
+            let typing = after.is_typed();
+            self.history
+                .record(path.clone(), before, after.clone(), typing);
+        }
+    }
+
+    /// Undo the last edit, returning the path of the field it changed.
+    pub(crate) fn undo(&mut self) -> Option<ResponsePath> {
+        let edit = self.history.undo()?;
+        let (path, contents) = (edit.field.clone(), edit.before.clone());
+        self.restore_field(&path, contents);
+        Some(path)
+    }
+
+    /// Redo the last undone edit, returning the path of the field it changed.
+    pub(crate) fn redo(&mut self) -> Option<ResponsePath> {
+        let edit = self.history.redo()?;
+        let (path, contents) = (edit.field.clone(), edit.after.clone());
+        self.restore_field(&path, contents);
+        Some(path)
+    }
+
+    fn restore_field(&mut self, path: &ResponsePath, contents: FieldState) {
+        self.errors.remove(path);
+        self.fields.insert(path.clone(), contents);
+        self.changed = true;
+    }
+
+    /// Prompt of the question at `path`, if it has one.
+    pub(crate) fn prompt_for(&self, path: &ResponsePath) -> Option<String> {
+        find_prompt(&self.definition.questions, &ResponsePath::empty(), path)
+    }
+
+    /// Progress of the questions nested under `path`.
+    ///
+    /// Each visible leaf question counts once; the fields inside enum
+    /// variants are not counted separately from the selection itself.
+    pub(crate) fn section_progress(
+        &self,
+        path: &ResponsePath,
+        questions: &[Question],
+    ) -> SectionProgress {
+        let mut progress = SectionProgress::default();
+        self.count_progress(path, questions, &mut progress);
+        let prefix = format!("{}.", path.as_str());
+        progress.errors = self
+            .errors
+            .keys()
+            .filter(|p| p.as_str().starts_with(&prefix))
+            .count();
+        progress
+    }
+
+    fn count_progress(
+        &self,
+        prefix: &ResponsePath,
+        questions: &[Question],
+        progress: &mut SectionProgress,
+    ) {
+        for question in questions {
+            if question.is_assumed() {
+                continue;
+            }
+            let path = prefix.child(question.path().as_str());
+            match question.kind() {
+                QuestionKind::Unit => {}
+                QuestionKind::AllOf(all_of) => {
+                    self.count_progress(&path, all_of.questions(), progress)
+                }
+                QuestionKind::Lazy(lazy) => {
+                    if let Ok(all_of) = lazy.expand() {
+                        self.count_progress(&path, all_of.questions(), progress)
+                    }
+                }
+                _ => {
+                    progress.total += 1;
+                    if self.fields.get(&path).is_some_and(FieldState::is_answered) {
+                        progress.answered += 1;
+                    }
+                }
+            }
+        }
+    }
+
     fn init_question_state(&mut self, question: &Question, prefix: Option<&ResponsePath>) {
         let path = match prefix {
             Some(p) => p.child(question.path().as_str()),
@@ -202,6 +464,7 @@ impl FormState {
                         value: default,
                         is_password: false,
                         is_multiline: false,
+                        confirmation: None,
                     },
                 );
             }
@@ -216,16 +479,18 @@ impl FormState {
                         value: default,
                         is_password: false,
                         is_multiline: true,
+                        confirmation: None,
                     },
                 );
             }
-            QuestionKind::Masked(_) => {
+            QuestionKind::Masked(masked_q) => {
                 self.fields.insert(
                     path,
                     FieldState::Text {
                         value: String::new(),
                         is_password: true,
                         is_multiline: false,
+                        confirmation: masked_q.confirm.then(String::new),
                     },
                 );
             }
@@ -266,6 +531,13 @@ impl FormState {
                 self.fields
                     .insert(path, FieldState::Bool { value: default });
             }
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                self.fields
+                    .insert(path, FieldState::temporal(question.kind(), default_value));
+            }
             QuestionKind::List(list_q) => {
                 self.fields.insert(
                     path,
@@ -286,7 +558,7 @@ impl FormState {
 
                 // Initialize nested fields for all variants
                 for variant in &one_of.variants {
-                    self.init_variant_state(&variant, &path);
+                    self.init_variant_state(variant, &path);
                 }
             }
             QuestionKind::AnyOf(any_of) => {
@@ -314,7 +586,7 @@ impl FormState {
 
                 // Initialize nested fields for all variants (for struct variants)
                 for variant in &any_of.variants {
-                    self.init_variant_state(&variant, &path);
+                    self.init_variant_state(variant, &path);
                 }
             }
             QuestionKind::AllOf(all_of) => {
@@ -323,7 +595,71 @@ impl FormState {
                     self.init_question_state(nested_q, Some(&path));
                 }
             }
+            QuestionKind::Map(_) => {
+                // Entries start empty; their fields are added with each entry
+                self.fields
+                    .insert(path, FieldState::Map { keys: Vec::new() });
+            }
+            QuestionKind::Lazy(_) => {
+                // Fields are added when the question is first shown
+            }
+        }
+    }
+
+    /// Add an empty entry to the map at `path`, with fresh fields for its value.
+    pub(crate) fn add_map_entry(&mut self, path: &ResponsePath, map_q: &MapQuestion) {
+        let Some(FieldState::Map { keys }) = self.fields.get_mut(path) else {
+            return;
+        };
+        keys.push(String::new());
+        let value_q = map_q.value_question(keys.len() - 1, "");
+        self.init_question_state(&value_q, Some(path));
+        self.changed = true;
+    }
+
+    /// Remove the entry at `idx` from the map at `path`.
+    ///
+    /// The fields and errors of later entries move up one index, so the undo
+    /// history, which refers to fields by path, is cleared.
+    pub(crate) fn remove_map_entry(&mut self, path: &ResponsePath, idx: usize) {
+        let Some(FieldState::Map { keys }) = self.fields.get_mut(path) else {
+            return;
+        };
+        if idx >= keys.len() {
+            return;
         }
+        keys.remove(idx);
+
+        let renumber = |field: ResponsePath| -> Option<ResponsePath> {
+            let Some(rest) = field.strip_path_prefix(path) else {
+                return Some(field);
+            };
+            let mut segments: Vec<&str> = rest.segments().collect();
+            // Key errors live at `<path>.keys.<i>`, values at `<path>.<i>`
+            let pos = usize::from(segments.first() == Some(&MAP_KEYS_KEY));
+            let Some(entry) = segments.get(pos).and_then(|s| s.parse::<usize>().ok()) else {
+                return Some(field);
+            };
+            match entry.cmp(&idx) {
+                std::cmp::Ordering::Less => Some(field),
+                std::cmp::Ordering::Equal => None,
+                std::cmp::Ordering::Greater => {
+                    let moved = (entry - 1).to_string();
+                    segments[pos] = &moved;
+                    Some(path.child(&segments.join(".")))
+                }
+            }
+        };
+        self.fields = std::mem::take(&mut self.fields)
+            .into_iter()
+            .filter_map(|(field, contents)| Some((renumber(field)?, contents)))
+            .collect();
+        self.errors = std::mem::take(&mut self.errors)
+            .into_iter()
+            .filter_map(|(field, error)| Some((renumber(field)?, error)))
+            .collect();
+        self.history = History::default();
+        self.changed = true;
     }
 
     fn init_variant_state(&mut self, variant: &Variant, parent_path: &ResponsePath) {
@@ -341,6 +677,7 @@ impl FormState {
                         value: input_q.default.clone().unwrap_or_default(),
                         is_password: false,
                         is_multiline: false,
+                        confirmation: None,
                     },
                 );
             }
@@ -368,6 +705,10 @@ impl FormState {
                     },
                 );
             }
+            kind if kind.is_temporal() => {
+                let path = parent_path.child(&variant.name);
+                self.fields.insert(path, FieldState::temporal(kind, None));
+            }
             _ => {}
         }
     }
@@ -384,93 +725,74 @@ impl FormState {
             }
             QuestionKind::Input(input_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: input_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: false,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: input_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: false,
+                    confirmation: None,
+                });
             }
             QuestionKind::Multiline(multiline_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: multiline_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: true,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: multiline_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: true,
+                    confirmation: None,
+                });
             }
-            QuestionKind::Masked(_) => {
+            QuestionKind::Masked(masked_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: String::new(),
-                            is_password: true,
-                            is_multiline: false,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: String::new(),
+                    is_password: true,
+                    is_multiline: false,
+                    confirmation: masked_q.confirm.then(String::new),
+                });
             }
             QuestionKind::Int(int_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
+                self.fields.entry(path).or_insert_with(|| {
                     let default = int_q.default.map(|i| i.to_string()).unwrap_or_default();
                     let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Int {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+                    FieldState::Int {
+                        value: default,
+                        parsed,
+                    }
+                });
             }
             QuestionKind::Float(float_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
+                self.fields.entry(path).or_insert_with(|| {
                     let default = float_q.default.map(|f| f.to_string()).unwrap_or_default();
                     let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Float {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+                    FieldState::Float {
+                        value: default,
+                        parsed,
+                    }
+                });
             }
             QuestionKind::Confirm(confirm_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Bool {
-                            value: confirm_q.default,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert(FieldState::Bool {
+                    value: confirm_q.default,
+                });
+            }
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                let path = parent_path.child(&variant.name);
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::temporal(&variant.kind, None));
             }
             QuestionKind::List(list_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::List {
-                            value: String::new(),
-                            element_kind: list_q.element_kind.clone(),
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::List {
+                    value: String::new(),
+                    element_kind: list_q.element_kind.clone(),
+                });
             }
             QuestionKind::OneOf(one_of) => {
                 let path = parent_path.child(&variant.name);
@@ -509,6 +831,14 @@ impl FormState {
                     }
                 }
             }
+            QuestionKind::Map(_) => {
+                let path = parent_path.child(&variant.name);
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::Map { keys: Vec::new() });
+            }
+            // Added when shown, so recursive types are not expanded up front
+            QuestionKind::Lazy(_) => {}
         }
     }
 
@@ -522,87 +852,67 @@ impl FormState {
         match question.kind() {
             QuestionKind::Unit => {}
             QuestionKind::Input(input_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: input_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: false,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: input_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: false,
+                    confirmation: None,
+                });
             }
             QuestionKind::Multiline(multiline_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: multiline_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: true,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: multiline_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: true,
+                    confirmation: None,
+                });
             }
-            QuestionKind::Masked(_) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: String::new(),
-                            is_password: true,
-                            is_multiline: false,
-                        },
-                    );
-                }
+            QuestionKind::Masked(masked_q) => {
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: String::new(),
+                    is_password: true,
+                    is_multiline: false,
+                    confirmation: masked_q.confirm.then(String::new),
+                });
             }
             QuestionKind::Int(int_q) => {
-                if !self.fields.contains_key(&path) {
+                self.fields.entry(path).or_insert_with(|| {
                     let default = int_q.default.map(|i| i.to_string()).unwrap_or_default();
                     let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Int {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+                    FieldState::Int {
+                        value: default,
+                        parsed,
+                    }
+                });
             }
             QuestionKind::Float(float_q) => {
-                if !self.fields.contains_key(&path) {
+                self.fields.entry(path).or_insert_with(|| {
                     let default = float_q.default.map(|f| f.to_string()).unwrap_or_default();
                     let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Float {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+                    FieldState::Float {
+                        value: default,
+                        parsed,
+                    }
+                });
             }
             QuestionKind::Confirm(confirm_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Bool {
-                            value: confirm_q.default,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert(FieldState::Bool {
+                    value: confirm_q.default,
+                });
+            }
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::temporal(question.kind(), None));
             }
             QuestionKind::List(list_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::List {
-                            value: String::new(),
-                            element_kind: list_q.element_kind.clone(),
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::List {
+                    value: String::new(),
+                    element_kind: list_q.element_kind.clone(),
+                });
             }
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
@@ -644,10 +954,17 @@ impl FormState {
                     }
                 }
             }
+            QuestionKind::Map(_) => {
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::Map { keys: Vec::new() });
+            }
+            // Added when shown, so recursive types are not expanded up front
+            QuestionKind::Lazy(_) => {}
         }
     }
 
-    fn collect_responses(&self) -> Responses {
+    pub(crate) fn collect_responses(&self) -> Responses {
         let mut responses = Responses::new();
 
         for question in &self.definition.questions {
@@ -679,38 +996,42 @@ impl FormState {
                 // Nothing to collect
             }
             QuestionKind::Input(_) | QuestionKind::Multiline(_) | QuestionKind::Masked(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
             QuestionKind::Int(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
             QuestionKind::Float(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
-            QuestionKind::Confirm(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+            QuestionKind::Confirm(_)
+            | QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
             QuestionKind::List(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
             QuestionKind::OneOf(one_of) => {
@@ -761,6 +1082,25 @@ impl FormState {
                     self.collect_question_responses(nested_q, responses, Some(&path));
                 }
             }
+            QuestionKind::Lazy(lazy) => {
+                if let Ok(all_of) = lazy.expand() {
+                    for nested_q in all_of.questions() {
+                        self.collect_question_responses(nested_q, responses, Some(&path));
+                    }
+                }
+            }
+            QuestionKind::Map(map_q) => {
+                if let Some(FieldState::Map { keys }) = self.fields.get(&path) {
+                    for (idx, key) in keys.iter().enumerate() {
+                        let value_q = map_q.value_question(idx, key);
+                        self.collect_question_responses(&value_q, responses, Some(&path));
+                    }
+                    responses.insert(
+                        path.child(MAP_KEYS_KEY),
+                        ResponseValue::StringList(keys.clone()),
+                    );
+                }
+            }
         }
     }
 
@@ -777,12 +1117,18 @@ impl FormState {
                     self.collect_question_responses(nested_q, responses, Some(parent_path));
                 }
             }
-            QuestionKind::Input(_) | QuestionKind::Int(_) | QuestionKind::Float(_) => {
+            QuestionKind::Input(_)
+            | QuestionKind::Int(_)
+            | QuestionKind::Float(_)
+            | QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
                 let path = parent_path.child(&variant.name);
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
             _ => {}
@@ -791,13 +1137,26 @@ impl FormState {
 
     /// Validate that all required fields have values.
     /// Adds errors for empty Int/Float fields.
-    fn validate_required_fields(&mut self) {
-        for question in self.definition.questions.clone() {
-            self.validate_question_required(&question, None);
+    pub(crate) fn validate_required_fields(&mut self) {
+        let errors = self.required_field_errors();
+        self.errors.extend(errors);
+    }
+
+    /// Errors for required fields that have no value yet, without recording them.
+    pub(crate) fn required_field_errors(&self) -> HashMap<ResponsePath, String> {
+        let mut errors = HashMap::new();
+        for question in &self.definition.questions {
+            self.validate_question_required(question, None, &mut errors);
         }
+        errors
     }
 
-    fn validate_question_required(&mut self, question: &Question, prefix: Option<&ResponsePath>) {
+    fn validate_question_required(
+        &self,
+        question: &Question,
+        prefix: Option<&ResponsePath>,
+        errors: &mut HashMap<ResponsePath, String>,
+    ) {
         let path = match prefix {
             Some(p) => p.child(question.path().as_str()),
             None => question.path().clone(),
@@ -809,32 +1168,31 @@ impl FormState {
         }
 
         match question.kind() {
+            QuestionKind::Masked(_) => self.check_confirmation(path, errors),
             QuestionKind::Int(_) => {
-                if let Some(FieldState::Int { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
+                if let Some(FieldState::Int { parsed, .. }) = self.fields.get(&path)
+                    && parsed.is_none()
+                {
+                    errors.insert(path, "This field is required".to_string());
                 }
             }
             QuestionKind::Float(_) => {
-                if let Some(FieldState::Float { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
+                if let Some(FieldState::Float { parsed, .. }) = self.fields.get(&path)
+                    && parsed.is_none()
+                {
+                    errors.insert(path, "This field is required".to_string());
                 }
             }
+            kind if kind.is_temporal() => self.check_temporal(kind, path, errors),
             QuestionKind::OneOf(one_of) => {
                 // Validate that a variant is selected
                 if let Some(FieldState::OneOf { selected, .. }) = self.fields.get(&path) {
                     if let Some(idx) = *selected {
                         // Validate nested fields of the selected variant
                         let variant = &one_of.variants[idx];
-                        self.validate_variant_required(variant, &path);
+                        self.validate_variant_required(variant, &path, errors);
                     } else {
-                        self.errors
-                            .insert(path.clone(), "Please select an option".to_string());
+                        errors.insert(path.clone(), "Please select an option".to_string());
                     }
                 }
             }
@@ -849,42 +1207,106 @@ impl FormState {
                     for (item_idx, &variant_idx) in indices.iter().enumerate() {
                         let variant = &any_of.variants[variant_idx];
                         let item_path = path.child(&item_idx.to_string());
-                        self.validate_variant_required(variant, &item_path);
+                        self.validate_variant_required(variant, &item_path, errors);
                     }
                 }
             }
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
-                    self.validate_question_required(nested_q, Some(&path));
+                    self.validate_question_required(nested_q, Some(&path), errors);
+                }
+            }
+            QuestionKind::Lazy(lazy) => match lazy.expand() {
+                Ok(all_of) => {
+                    for nested_q in all_of.questions() {
+                        self.validate_question_required(nested_q, Some(&path), errors);
+                    }
+                }
+                Err(err) => {
+                    errors.insert(path, err);
+                }
+            },
+            QuestionKind::Map(map_q) => {
+                if let Some(FieldState::Map { keys }) = self.fields.get(&path) {
+                    for (idx, key) in keys.iter().enumerate() {
+                        let others = keys[..idx].iter().map(String::as_str);
+                        if let Err(msg) = MapQuestion::check_key(key, others) {
+                            errors.insert(map_key_path(&path, idx), msg);
+                        }
+                        let value_q = map_q.value_question(idx, key);
+                        self.validate_question_required(&value_q, Some(&path), errors);
+                    }
                 }
             }
             _ => {}
         }
     }
 
-    fn validate_variant_required(&mut self, variant: &Variant, parent_path: &ResponsePath) {
+    /// Record an error if the second entry of a masked field differs from the first.
+    fn check_confirmation(&self, path: ResponsePath, errors: &mut HashMap<ResponsePath, String>) {
+        if let Some(FieldState::Text {
+            value,
+            confirmation: Some(confirmation),
+            ..
+        }) = self.fields.get(&path)
+            && confirmation != value
+        {
+            errors.insert(path, CONFIRMATION_MISMATCH.to_string());
+        }
+    }
+
+    /// Record an error if a temporal field holds no valid value.
+    fn check_temporal(
+        &self,
+        kind: &QuestionKind,
+        path: ResponsePath,
+        errors: &mut HashMap<ResponsePath, String>,
+    ) {
+        if let Some(FieldState::Temporal {
+            value,
+            parsed: None,
+        }) = self.fields.get(&path)
+        {
+            let error = match kind.parse_temporal(value) {
+                Some(Err(err)) if !value.is_empty() => err,
+                _ => "This field is required".to_string(),
+            };
+            errors.insert(path, error);
+        }
+    }
+
+    fn validate_variant_required(
+        &self,
+        variant: &Variant,
+        parent_path: &ResponsePath,
+        errors: &mut HashMap<ResponsePath, String>,
+    ) {
         match &variant.kind {
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
-                    self.validate_question_required(nested_q, Some(parent_path));
+                    self.validate_question_required(nested_q, Some(parent_path), errors);
                 }
             }
+            QuestionKind::Masked(_) => {
+                self.check_confirmation(parent_path.child(&variant.name), errors);
+            }
+            kind if kind.is_temporal() => {
+                self.check_temporal(kind, parent_path.child(&variant.name), errors);
+            }
             QuestionKind::Int(_) => {
                 let path = parent_path.child(&variant.name);
-                if let Some(FieldState::Int { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
+                if let Some(FieldState::Int { parsed, .. }) = self.fields.get(&path)
+                    && parsed.is_none()
+                {
+                    errors.insert(path, "This field is required".to_string());
                 }
             }
             QuestionKind::Float(_) => {
                 let path = parent_path.child(&variant.name);
-                if let Some(FieldState::Float { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
+                if let Some(FieldState::Float { parsed, .. }) = self.fields.get(&path)
+                    && parsed.is_none()
+                {
+                    errors.insert(path, "This field is required".to_string());
                 }
             }
             _ => {}
@@ -892,13 +1314,47 @@ impl FormState {
     }
 }
 
-/// The egui application that renders the survey form.
-struct SurveyApp {
-    state: Arc<Mutex<FormState>>,
-    validate: Box<dyn Fn(&ResponseValue, &Responses) -> Result<(), String> + Send>,
+/// Where errors for the key of a map's entry at `idx` are kept.
+fn map_key_path(path: &ResponsePath, idx: usize) -> ResponsePath {
+    path.child(MAP_KEYS_KEY).child(&idx.to_string())
+}
+
+/// Bar and label showing the strength of a masked answer.
+fn strength_meter(ui: &mut egui::Ui, password: &str) {
+    let strength = PasswordStrength::of(password);
+    let color = match strength {
+        PasswordStrength::Weak => egui::Color32::RED,
+        PasswordStrength::Fair => egui::Color32::ORANGE,
+        PasswordStrength::Good => egui::Color32::GREEN,
+        PasswordStrength::Strong => egui::Color32::DARK_GREEN,
+    };
+    ui.add(
+        egui::ProgressBar::new(strength.fraction())
+            .desired_width(ui.spacing().text_edit_width)
+            .fill(color)
+            .text(strength.label()),
+    );
 }
 
-impl SurveyApp {
+/// Field validation callback used by the egui form.
+pub(crate) type ValidateFn =
+    Box<dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String> + Send>;
+
+/// Renders survey questions into an egui `Ui`, validating fields as they change.
+pub(crate) struct FormRenderer<'a> {
+    pub(crate) validate: &'a ValidateFn,
+}
+
+impl FormRenderer<'_> {
+    /// Focus and scroll to a field's widget if the error summary asked for it.
+    fn focus_if_requested(state: &mut FormState, path: &ResponsePath, response: &egui::Response) {
+        if state.focus.as_ref() == Some(path) {
+            state.focus = None;
+            response.request_focus();
+            response.scroll_to_me(Some(egui::Align::Center));
+        }
+    }
+
     /// Format a prompt as a label, adding a colon only if the prompt doesn't end with punctuation.
     fn format_label(prompt: &str) -> String {
         let trimmed = prompt.trim();
@@ -914,7 +1370,14 @@ impl SurveyApp {
         }
     }
 
-    fn render_question(
+    /// Render a question's help text below its prompt.
+    fn render_help(ui: &mut egui::Ui, help: Option<&str>) {
+        if let Some(help) = help {
+            ui.label(egui::RichText::new(help).small().weak());
+        }
+    }
+
+    pub(crate) fn render_question(
         &self,
         ui: &mut egui::Ui,
         question: &Question,
@@ -935,7 +1398,7 @@ impl SurveyApp {
             // Create a readable label from the path
             path.as_str()
                 .split('.')
-                .last()
+                .next_back()
                 .unwrap_or("")
                 .split('_')
                 .map(|word| {
@@ -951,32 +1414,66 @@ impl SurveyApp {
             question.ask().to_string()
         };
 
+        let before = state.fields.get(&path).cloned();
+
         match question.kind() {
             QuestionKind::Unit => {}
             QuestionKind::Input(_) | QuestionKind::Multiline(_) | QuestionKind::Masked(_) => {
-                self.render_text_field(ui, &path, &prompt, question.kind(), state);
+                self.render_text_field(ui, &path, &prompt, question.help(), question.kind(), state);
             }
             QuestionKind::Int(int_q) => {
-                self.render_int_field(ui, &path, &prompt, int_q, state);
+                self.render_int_field(ui, &path, &prompt, question.help(), int_q, state);
             }
             QuestionKind::Float(float_q) => {
-                self.render_float_field(ui, &path, &prompt, float_q, state);
+                self.render_float_field(ui, &path, &prompt, question.help(), float_q, state);
             }
             QuestionKind::Confirm(_) => {
-                self.render_bool_field(ui, &path, &prompt, state);
+                self.render_bool_field(ui, &path, &prompt, question.help(), state);
+            }
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                self.render_temporal_field(
+                    ui,
+                    &path,
+                    &prompt,
+                    question.help(),
+                    question.kind(),
+                    state,
+                );
             }
             QuestionKind::List(list_q) => {
-                self.render_list_field(ui, &path, &prompt, list_q, state);
+                self.render_list_field(ui, &path, &prompt, question.help(), list_q, state);
             }
             QuestionKind::OneOf(one_of) => {
-                self.render_one_of(ui, &path, &prompt, one_of, state);
+                self.render_one_of(ui, &path, &prompt, question.help(), one_of, state);
             }
             QuestionKind::AnyOf(any_of) => {
-                self.render_any_of(ui, &path, &prompt, any_of, state);
+                self.render_any_of(ui, &path, &prompt, question.help(), any_of, state);
             }
             QuestionKind::AllOf(all_of) => {
-                self.render_all_of(ui, &path, &prompt, all_of, state);
+                self.render_all_of(ui, &path, &prompt, question.help(), all_of, state);
             }
+            QuestionKind::Map(map_q) => {
+                self.render_map(ui, &path, &prompt, question.help(), map_q, state);
+            }
+            QuestionKind::Lazy(lazy) => match lazy.expand() {
+                Ok(all_of) => {
+                    for nested_q in all_of.questions() {
+                        state.ensure_question_fields(nested_q, Some(&path));
+                    }
+                    self.render_all_of(ui, &path, &prompt, question.help(), &all_of, state);
+                }
+                Err(err) => {
+                    ui.colored_label(egui::Color32::RED, format!("⚠ {prompt} {err}"));
+                    ui.add_space(8.0);
+                }
+            },
+        }
+
+        if let Some(before) = before {
+            state.record_edit(&path, before);
         }
     }
 
@@ -985,42 +1482,71 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
-        _kind: &QuestionKind,
+        help: Option<&str>,
+        kind: &QuestionKind,
         state: &mut FormState,
     ) {
         ui.horizontal(|ui| {
             ui.label(Self::format_label(prompt));
         });
+        Self::render_help(ui, help);
 
+        let revealed = state.revealed.contains(path);
         if let Some(FieldState::Text {
             value,
             is_password,
             is_multiline,
+            confirmation,
         }) = state.fields.get_mut(path)
         {
-            let changed;
-
-            if *is_multiline {
-                let response = ui.add(
+            let mut toggle_reveal = false;
+            let mut response = if *is_multiline {
+                ui.add(
                     egui::TextEdit::multiline(value)
                         .desired_width(f32::INFINITY)
                         .desired_rows(3),
-                );
-                changed = response.changed();
+                )
             } else if *is_password {
-                let response = ui.add(egui::TextEdit::singleline(value).password(true));
-                changed = response.changed();
+                ui.horizontal(|ui| {
+                    let response = ui.add(egui::TextEdit::singleline(value).password(!revealed));
+                    toggle_reveal = ui
+                        .selectable_label(revealed, "👁")
+                        .on_hover_text("Show or hide the text")
+                        .clicked();
+                    response
+                })
+                .inner
             } else {
-                let response =
-                    ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
-                changed = response.changed();
+                ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY))
+            };
+            if let Some(confirmation) = confirmation {
+                ui.label(CONFIRMATION_PROMPT);
+                response |= ui.add(egui::TextEdit::singleline(confirmation).password(!revealed));
+            }
+            if let QuestionKind::Masked(masked_q) = kind
+                && masked_q.strength_meter
+                && !value.is_empty()
+            {
+                strength_meter(ui, value);
+            }
+            let changed = response.changed();
+            let value = value.clone();
+            let is_password = *is_password;
+            Self::focus_if_requested(state, path, &response);
+            if toggle_reveal && !state.revealed.remove(path) {
+                state.revealed.insert(path.clone());
             }
 
             if changed {
+                state.changed = true;
                 // Validate on change
-                let rv = ResponseValue::String(value.clone());
+                let rv = if is_password {
+                    ResponseValue::Secret(SecretValue::new(value))
+                } else {
+                    ResponseValue::String(value)
+                };
                 let responses = state.collect_responses();
-                if let Err(msg) = (self.validate)(&rv, &responses) {
+                if let Err(msg) = (self.validate)(&rv, &responses, path) {
                     state.errors.insert(path.clone(), msg);
                 } else {
                     state.errors.remove(path);
@@ -1041,6 +1567,7 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
+        help: Option<&str>,
         int_q: &IntQuestion,
         state: &mut FormState,
     ) {
@@ -1054,11 +1581,13 @@ impl SurveyApp {
                 ui.label(format!("(max: {max})"));
             }
         });
+        Self::render_help(ui, help);
 
         if let Some(FieldState::Int { value, parsed }) = state.fields.get_mut(path) {
             let response = ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
 
             if response.changed() {
+                state.changed = true;
                 *parsed = value.parse().ok();
 
                 if let Some(i) = *parsed {
@@ -1066,26 +1595,26 @@ impl SurveyApp {
                     state.errors.remove(path);
 
                     // Check bounds
-                    if let Some(min) = int_q.min {
-                        if i < min {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at least {min}"));
-                        }
+                    if let Some(min) = int_q.min
+                        && i < min
+                    {
+                        state
+                            .errors
+                            .insert(path.clone(), format!("Value must be at least {min}"));
                     }
-                    if let Some(max) = int_q.max {
-                        if i > max {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at most {max}"));
-                        }
+                    if let Some(max) = int_q.max
+                        && i > max
+                    {
+                        state
+                            .errors
+                            .insert(path.clone(), format!("Value must be at most {max}"));
                     }
 
                     // Custom validation
-                    if state.errors.get(path).is_none() {
+                    if !state.errors.contains_key(path) {
                         let rv = ResponseValue::Int(i);
                         let responses = state.collect_responses();
-                        if let Err(msg) = (self.validate)(&rv, &responses) {
+                        if let Err(msg) = (self.validate)(&rv, &responses, path) {
                             state.errors.insert(path.clone(), msg);
                         }
                     }
@@ -1095,6 +1624,7 @@ impl SurveyApp {
                         .insert(path.clone(), "Please enter a valid integer".to_string());
                 }
             }
+            Self::focus_if_requested(state, path, &response);
         }
 
         if let Some(error) = state.errors.get(path) {
@@ -1109,6 +1639,7 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
+        help: Option<&str>,
         float_q: &FloatQuestion,
         state: &mut FormState,
     ) {
@@ -1122,36 +1653,38 @@ impl SurveyApp {
                 ui.label(format!("(max: {max})"));
             }
         });
+        Self::render_help(ui, help);
 
         if let Some(FieldState::Float { value, parsed }) = state.fields.get_mut(path) {
             let response = ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
 
             if response.changed() {
+                state.changed = true;
                 *parsed = value.parse().ok();
 
                 if let Some(f) = *parsed {
                     // Clear any previous errors (like "required" or parse errors)
                     state.errors.remove(path);
 
-                    if let Some(min) = float_q.min {
-                        if f < min {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at least {min}"));
-                        }
+                    if let Some(min) = float_q.min
+                        && f < min
+                    {
+                        state
+                            .errors
+                            .insert(path.clone(), format!("Value must be at least {min}"));
                     }
-                    if let Some(max) = float_q.max {
-                        if f > max {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at most {max}"));
-                        }
+                    if let Some(max) = float_q.max
+                        && f > max
+                    {
+                        state
+                            .errors
+                            .insert(path.clone(), format!("Value must be at most {max}"));
                     }
 
-                    if state.errors.get(path).is_none() {
+                    if !state.errors.contains_key(path) {
                         let rv = ResponseValue::Float(f);
                         let responses = state.collect_responses();
-                        if let Err(msg) = (self.validate)(&rv, &responses) {
+                        if let Err(msg) = (self.validate)(&rv, &responses, path) {
                             state.errors.insert(path.clone(), msg);
                         }
                     }
@@ -1161,6 +1694,7 @@ impl SurveyApp {
                         .insert(path.clone(), "Please enter a valid number".to_string());
                 }
             }
+            Self::focus_if_requested(state, path, &response);
         }
 
         if let Some(error) = state.errors.get(path) {
@@ -1175,11 +1709,90 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
+        help: Option<&str>,
         state: &mut FormState,
     ) {
         if let Some(FieldState::Bool { value }) = state.fields.get_mut(path) {
-            ui.checkbox(value, prompt);
+            let response = ui.checkbox(value, prompt);
+            state.changed |= response.changed();
+            Self::focus_if_requested(state, path, &response);
+        }
+        Self::render_help(ui, help);
+        ui.add_space(8.0);
+    }
+
+    fn render_temporal_field(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        help: Option<&str>,
+        kind: &QuestionKind,
+        state: &mut FormState,
+    ) {
+        let format = kind.temporal_format().unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.label(Self::format_label(prompt));
+            match kind.temporal_bounds() {
+                Some((Some(min), Some(max))) => ui.label(format!("({format}, {min} - {max})")),
+                Some((Some(min), None)) => ui.label(format!("({format}, min: {min})")),
+                Some((None, Some(max))) => ui.label(format!("({format}, max: {max})")),
+                _ => ui.label(format!("({format})")),
+            };
+        });
+        Self::render_help(ui, help);
+
+        if let Some(FieldState::Temporal { value, parsed }) = state.fields.get_mut(path) {
+            let (response, picked) = if let QuestionKind::Date(date_q) = kind {
+                ui.horizontal(|ui| {
+                    let response = ui.add(egui::TextEdit::singleline(value).hint_text(format));
+                    let date = parsed.as_ref().and_then(ResponseValue::as_date);
+                    (
+                        response,
+                        date_picker_button(ui, path.as_str(), date, date_q),
+                    )
+                })
+                .inner
+            } else {
+                let response = ui.add(
+                    egui::TextEdit::singleline(value)
+                        .hint_text(format)
+                        .desired_width(f32::INFINITY),
+                );
+                (response, None)
+            };
+            if let Some(date) = picked {
+                *value = date.to_string();
+            }
+
+            if response.changed() || picked.is_some() {
+                state.changed = true;
+                match kind.parse_temporal(value) {
+                    Some(Ok(rv)) => {
+                        *parsed = Some(rv.clone());
+                        state.errors.remove(path);
+                        let responses = state.collect_responses();
+                        if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                            state.errors.insert(path.clone(), msg);
+                        }
+                    }
+                    Some(Err(err)) if !value.is_empty() => {
+                        *parsed = None;
+                        state.errors.insert(path.clone(), err);
+                    }
+                    _ => {
+                        *parsed = None;
+                        state.errors.remove(path);
+                    }
+                }
+            }
+            Self::focus_if_requested(state, path, &response);
         }
+
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+        }
+
         ui.add_space(8.0);
     }
 
@@ -1188,6 +1801,7 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
+        help: Option<&str>,
         list_q: &ListQuestion,
         state: &mut FormState,
     ) {
@@ -1201,12 +1815,15 @@ impl SurveyApp {
             "{} (comma-separated {})",
             prompt, type_hint
         )));
+        Self::render_help(ui, help);
 
         if let Some(FieldState::List { value, .. }) = state.fields.get_mut(path) {
             let response = ui.add(egui::TextEdit::singleline(value).desired_width(300.0));
             if response.changed() {
+                state.changed = true;
                 state.errors.remove(path);
             }
+            Self::focus_if_requested(state, path, &response);
         }
 
         // Show error if any
@@ -1222,10 +1839,12 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
+        help: Option<&str>,
         one_of: &OneOfQuestion,
         state: &mut FormState,
     ) {
         ui.label(Self::format_label(prompt));
+        Self::render_help(ui, help);
 
         let selected = if let Some(FieldState::OneOf { selected, .. }) = state.fields.get(path) {
             *selected
@@ -1236,17 +1855,23 @@ impl SurveyApp {
         // Render radio buttons
         let mut new_selected = selected;
         for (idx, variant) in one_of.variants.iter().enumerate() {
-            if ui.radio(selected == Some(idx), &variant.name).clicked() {
+            let response = ui.radio(selected == Some(idx), &variant.name);
+            Self::render_help(ui, variant.help.as_deref());
+            if idx == 0 {
+                Self::focus_if_requested(state, path, &response);
+            }
+            if response.clicked() {
+                state.changed = true;
                 new_selected = Some(idx);
                 // Clear any "required" error when user makes a selection
                 state.errors.remove(path);
             }
         }
 
-        if new_selected != selected {
-            if let Some(FieldState::OneOf { selected, .. }) = state.fields.get_mut(path) {
-                *selected = new_selected;
-            }
+        if new_selected != selected
+            && let Some(FieldState::OneOf { selected, .. }) = state.fields.get_mut(path)
+        {
+            *selected = new_selected;
         }
 
         // Show nested fields for the selected variant (if any)
@@ -1268,10 +1893,12 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
+        help: Option<&str>,
         any_of: &AnyOfQuestion,
         state: &mut FormState,
     ) {
         ui.label(Self::format_label(prompt));
+        Self::render_help(ui, help);
 
         // Get current selection state
         let selections = if let Some(FieldState::AnyOf { selected, .. }) = state.fields.get(path) {
@@ -1284,15 +1911,19 @@ impl SurveyApp {
         let mut new_selections = selections.clone();
         for (idx, variant) in any_of.variants.iter().enumerate() {
             let mut checked = selections.get(idx).copied().unwrap_or(false);
-            if ui.checkbox(&mut checked, &variant.name).changed() {
-                if idx < new_selections.len() {
-                    new_selections[idx] = checked;
-                }
+            let response = ui.checkbox(&mut checked, &variant.name);
+            Self::render_help(ui, variant.help.as_deref());
+            if idx == 0 {
+                Self::focus_if_requested(state, path, &response);
+            }
+            if response.changed() && idx < new_selections.len() {
+                new_selections[idx] = checked;
             }
         }
 
         // Update state if changed
         if new_selections != selections {
+            state.changed = true;
             if let Some(FieldState::AnyOf { selected, .. }) = state.fields.get_mut(path) {
                 *selected = new_selections.clone();
             }
@@ -1305,7 +1936,7 @@ impl SurveyApp {
                 .collect();
             let rv = ResponseValue::ChosenVariants(indices);
             let responses = state.collect_responses();
-            if let Err(msg) = (self.validate)(&rv, &responses) {
+            if let Err(msg) = (self.validate)(&rv, &responses, path) {
                 state.errors.insert(path.clone(), msg);
             } else {
                 state.errors.remove(path);
@@ -1343,19 +1974,139 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
+        help: Option<&str>,
         all_of: &AllOfQuestion,
         state: &mut FormState,
     ) {
+        let render_nested = |ui: &mut egui::Ui, state: &mut FormState| {
+            for nested_q in all_of.questions() {
+                self.render_question(ui, nested_q, state, Some(path));
+            }
+        };
+
+        // Groups of several questions (nested structs) get a collapsible
+        // section with a progress badge; a lone wrapped question (such as
+        // an enum field) is shown inline.
+        if all_of.questions().len() > 1 {
+            let badge = state.section_progress(path, all_of.questions()).badge();
+            // Open the section if the error summary wants to focus a field in it
+            let reveal = state
+                .focus
+                .as_ref()
+                .is_some_and(|focus| focus.strip_path_prefix(path).is_some());
+            ui.separator();
+            egui::CollapsingHeader::new(egui::RichText::new(format!("{prompt}  {badge}")).strong())
+                .id_salt(path.as_str())
+                .default_open(true)
+                .open(reveal.then_some(true))
+                .show(ui, |ui| {
+                    Self::render_help(ui, help);
+                    render_nested(ui, state);
+                });
+            return;
+        }
+
         if !prompt.is_empty() {
             ui.separator();
             ui.strong(prompt);
         }
+        Self::render_help(ui, help);
+
+        ui.indent(path.as_str(), |ui| render_nested(ui, state));
+    }
+
+    fn render_map(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        help: Option<&str>,
+        map_q: &MapQuestion,
+        state: &mut FormState,
+    ) {
+        ui.label(Self::format_label(prompt));
+        Self::render_help(ui, help);
 
+        let keys = match state.fields.get(path) {
+            Some(FieldState::Map { keys }) => keys.clone(),
+            _ => return,
+        };
+
+        let mut remove = None;
         ui.indent(path.as_str(), |ui| {
-            for nested_q in all_of.questions() {
-                self.render_question(ui, nested_q, state, Some(path));
+            for (idx, key) in keys.iter().enumerate() {
+                let key_path = map_key_path(path, idx);
+                let mut edited = key.clone();
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut edited)
+                            .hint_text("Key")
+                            .desired_width(200.0),
+                    );
+                    if idx == 0 {
+                        Self::focus_if_requested(state, path, &response);
+                    }
+                    Self::focus_if_requested(state, &key_path, &response);
+                    if ui
+                        .small_button("🗑")
+                        .on_hover_text("Remove this entry")
+                        .clicked()
+                    {
+                        remove = Some(idx);
+                    }
+                });
+
+                if edited != *key {
+                    state.changed = true;
+                    if let Some(FieldState::Map { keys }) = state.fields.get_mut(path) {
+                        keys[idx] = edited.clone();
+                    }
+                    // Validate on change
+                    let others = keys
+                        .iter()
+                        .enumerate()
+                        .filter(|&(other, _)| other != idx)
+                        .map(|(_, other)| other.as_str());
+                    let responses = state.collect_responses();
+                    let result = MapQuestion::check_key(&edited, others).and_then(|()| {
+                        (self.validate)(
+                            &ResponseValue::String(edited.clone()),
+                            &responses,
+                            &path.child(MAP_KEYS_KEY),
+                        )
+                    });
+                    match result {
+                        Err(msg) => state.errors.insert(key_path.clone(), msg),
+                        Ok(()) => state.errors.remove(&key_path),
+                    };
+                }
+                if let Some(error) = state.errors.get(&key_path) {
+                    ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+                }
+
+                ui.indent(format!("map_value_{idx}"), |ui| {
+                    let value_q = map_q.value_question(idx, &edited);
+                    self.render_question(ui, &value_q, state, Some(path));
+                });
+            }
+
+            if ui.button("➕ Add entry").clicked() {
+                state.add_map_entry(path, map_q);
             }
         });
+
+        if let Some(idx) = remove {
+            state.remove_map_entry(path, idx);
+        }
+
+        // Errors from validating the keys together
+        for error_path in [path.clone(), path.child(MAP_KEYS_KEY)] {
+            if let Some(error) = state.errors.get(&error_path) {
+                ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+            }
+        }
+
+        ui.add_space(8.0);
     }
 
     fn render_variant_fields(
@@ -1376,112 +2127,87 @@ impl SurveyApp {
             }
             QuestionKind::Input(_) => {
                 let path = parent_path.child(&variant.name);
-                self.render_text_field(ui, &path, "", &variant.kind, state);
+                self.render_text_field(ui, &path, "", None, &variant.kind, state);
             }
             QuestionKind::Multiline(_) => {
                 let path = parent_path.child(&variant.name);
-                self.render_text_field(ui, &path, "", &variant.kind, state);
+                self.render_text_field(ui, &path, "", None, &variant.kind, state);
             }
             QuestionKind::Masked(_) => {
                 let path = parent_path.child(&variant.name);
-                self.render_text_field(ui, &path, "", &variant.kind, state);
+                self.render_text_field(ui, &path, "", None, &variant.kind, state);
             }
             QuestionKind::Int(int_q) => {
                 let path = parent_path.child(&variant.name);
-                self.render_int_field(ui, &path, "", int_q, state);
+                self.render_int_field(ui, &path, "", None, int_q, state);
             }
             QuestionKind::Float(float_q) => {
                 let path = parent_path.child(&variant.name);
-                self.render_float_field(ui, &path, "", float_q, state);
+                self.render_float_field(ui, &path, "", None, float_q, state);
             }
             QuestionKind::Confirm(_) => {
                 let path = parent_path.child(&variant.name);
-                self.render_bool_field(ui, &path, "", state);
+                self.render_bool_field(ui, &path, "", None, state);
+            }
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                let path = parent_path.child(&variant.name);
+                self.render_temporal_field(ui, &path, "", None, &variant.kind, state);
             }
             QuestionKind::List(list_q) => {
                 let path = parent_path.child(&variant.name);
-                self.render_list_field(ui, &path, "", list_q, state);
+                self.render_list_field(ui, &path, "", None, list_q, state);
             }
             QuestionKind::OneOf(one_of) => {
                 let path = parent_path.child(&variant.name);
-                self.render_one_of(ui, &path, "", one_of, state);
+                self.render_one_of(ui, &path, "", None, one_of, state);
             }
             QuestionKind::AnyOf(any_of) => {
                 let path = parent_path.child(&variant.name);
-                self.render_any_of(ui, &path, "", any_of, state);
+                self.render_any_of(ui, &path, "", None, any_of, state);
+            }
+            QuestionKind::Map(map_q) => {
+                let path = parent_path.child(&variant.name);
+                self.render_map(ui, &path, "", None, map_q, state);
+            }
+            QuestionKind::Lazy(_) => {
+                let variant_q = Question::new(variant.name.clone(), "", variant.kind.clone());
+                self.render_question(ui, &variant_q, state, Some(parent_path));
             }
         }
     }
 }
 
+/// The egui application that hosts the survey form in its own window.
+struct SurveyApp {
+    state: Arc<Mutex<SurveyFormState>>,
+    layout: FormLayout,
+    preview: bool,
+}
+
 impl eframe::App for SurveyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if self.preview {
+            egui::SidePanel::right("survey_preview")
+                .resizable(true)
+                .default_width(260.0)
+                .show(ctx, |ui| {
+                    ui.add(SurveyPreview::new(&mut self.state.lock().unwrap()));
+                });
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             let mut state = self.state.lock().unwrap();
 
-            // Show prelude if present
-            if let Some(prelude) = &state.prelude {
-                ui.label(prelude);
-                ui.separator();
-            }
-
             egui::ScrollArea::vertical().show(ui, |ui| {
-                // Render all questions
-                for question in state.definition.questions.clone() {
-                    self.render_question(ui, &question, &mut state, None);
-                }
-
-                ui.separator();
-
-                // Show epilogue if present
-                if let Some(epilogue) = &state.epilogue {
-                    ui.label(epilogue);
-                    ui.add_space(8.0);
-                }
-
-                // Submit button
-                ui.horizontal(|ui| {
-                    let has_errors = !state.errors.is_empty();
-
-                    if ui
-                        .add_enabled(!has_errors, egui::Button::new("Submit"))
-                        .clicked()
-                    {
-                        // Check for empty required fields first
-                        state.validate_required_fields();
-
-                        if state.errors.is_empty() {
-                            // Final validation of all fields
-                            let responses = state.collect_responses();
-                            let mut all_valid = true;
-
-                            for (path, value) in responses.iter() {
-                                if let Err(msg) = (self.validate)(value, &responses) {
-                                    state.errors.insert(path.clone(), msg);
-                                    all_valid = false;
-                                }
-                            }
-
-                            if all_valid {
-                                state.submitted = true;
-                                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                            }
-                        }
-                    }
-
-                    if ui.button("Cancel").clicked() {
-                        state.cancelled = true;
-                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                    }
-
-                    if has_errors || !state.errors.is_empty() {
-                        ui.colored_label(
-                            egui::Color32::RED,
-                            format!("{} validation error(s)", state.errors.len()),
-                        );
-                    }
-                });
+                ui.add(SurveyWidget::new(&mut state).with_layout(self.layout));
             });
+
+            if state.is_submitted() || state.is_cancelled() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
         });
     }
 }
@@ -1494,7 +2220,9 @@ impl SurveyBackend for EguiBackend {
         definition: &SurveyDefinition,
         _validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<Responses, Self::Error> {
-        let state = Arc::new(Mutex::new(FormState::new(definition.clone())));
+        let mut form = SurveyFormState::new(definition.clone());
+        form.set_preview_formatter(self.preview_formatter.clone());
+        let state = Arc::new(Mutex::new(form));
 
         // Create native options
         let options = eframe::NativeOptions {
@@ -1507,40 +2235,30 @@ impl SurveyBackend for EguiBackend {
         // Run the egui app
         // Note: eframe::run_native blocks until the window is closed
         let app_state = Arc::clone(&state);
-
-        // We need to handle the validation in a way that works with eframe
-        // Since eframe takes ownership, we'll use a closure that captures what we need
         let title = self.title.clone();
+        let layout = self.layout;
+        let preview = self.preview;
 
         eframe::run_native(
             &title,
             options,
             Box::new(move |_cc| {
-                // Create a validation function that always succeeds for now
-                // Real validation happens on submit
-                let validate_fn: Box<
-                    dyn Fn(&ResponseValue, &Responses) -> Result<(), String> + Send,
-                > = Box::new(|_value, _responses| Ok(()));
-
                 Ok(Box::new(SurveyApp {
                     state: app_state,
-                    validate: validate_fn,
+                    layout,
+                    preview,
                 }) as Box<dyn eframe::App>)
             }),
         )
         .map_err(|e| EguiError::EguiError(e.to_string()))?;
 
         // After the window closes, check the result
-        let state = state.lock().unwrap();
-        if state.cancelled {
-            return Err(EguiError::Cancelled);
-        }
-
-        if !state.submitted {
+        let mut state = state.lock().unwrap();
+        if state.is_cancelled() {
             return Err(EguiError::Cancelled);
         }
 
-        Ok(state.collect_responses())
+        state.take_responses().ok_or(EguiError::Cancelled)
     }
 }
 
@@ -1553,6 +2271,7 @@ mod tests {
         let _backend = EguiBackend::new();
         let _with_title = EguiBackend::new().with_title("Test");
         let _with_size = EguiBackend::new().with_window_size([800.0, 600.0]);
+        let _paged = EguiBackend::new().with_layout(FormLayout::Pages);
         let _default = EguiBackend::default();
     }
 
@@ -1564,4 +2283,188 @@ mod tests {
         let err = EguiError::EguiError("test error".to_string());
         assert_eq!(err.to_string(), "Egui error: test error");
     }
+
+    #[test]
+    fn section_progress_counts_answers_and_errors() {
+        use elicitor::Survey;
+        use example_surveys::UserRegistration;
+
+        let definition = UserRegistration::survey();
+        let mut state = FormState::new(definition.clone());
+        let contact = definition
+            .questions
+            .iter()
+            .find(|q| q.path().as_str() == "contact")
+            .unwrap();
+        let QuestionKind::AllOf(all_of) = contact.kind() else {
+            panic!("contact should be a group");
+        };
+        let path = contact.path().clone();
+
+        let progress = state.section_progress(&path, all_of.questions());
+        assert_eq!(progress.total, 2);
+        assert_eq!(progress.answered, 0);
+        assert_eq!(progress.badge(), "0/2");
+
+        for field in ["email", "phone"] {
+            state.fields.insert(
+                path.child(field),
+                FieldState::Text {
+                    value: "x".to_string(),
+                    is_password: false,
+                    is_multiline: false,
+                    confirmation: None,
+                },
+            );
+        }
+        assert_eq!(
+            state.section_progress(&path, all_of.questions()).badge(),
+            "2/2 ✓"
+        );
+
+        state
+            .errors
+            .insert(path.child("email"), "Invalid email".to_string());
+        assert_eq!(
+            state.section_progress(&path, all_of.questions()).badge(),
+            "1 error"
+        );
+    }
+
+    #[test]
+    fn removing_a_map_entry_moves_later_entries_up() {
+        let map_q = MapQuestion::new(QuestionKind::Int(IntQuestion::default()));
+        let definition = SurveyDefinition {
+            prelude: None,
+            questions: vec![Question::new(
+                "stock",
+                "Stock:",
+                QuestionKind::Map(map_q.clone()),
+            )],
+            epilogue: None,
+        };
+        let path = ResponsePath::new("stock");
+        let mut state = FormState::new(definition);
+
+        for (idx, (key, count)) in [("apples", 3), ("pears", 5), ("plums", 8)]
+            .iter()
+            .enumerate()
+        {
+            state.add_map_entry(&path, &map_q);
+            if let Some(FieldState::Map { keys }) = state.fields.get_mut(&path) {
+                keys[idx] = key.to_string();
+            }
+            state.fields.insert(
+                path.child(&idx.to_string()),
+                FieldState::Int {
+                    value: count.to_string(),
+                    parsed: Some(*count),
+                },
+            );
+        }
+        state
+            .errors
+            .insert(map_key_path(&path, 2), "Invalid key".to_string());
+
+        state.remove_map_entry(&path, 0);
+
+        let responses = state.collect_responses();
+        assert_eq!(
+            responses
+                .get_string_list(&path.child(MAP_KEYS_KEY))
+                .unwrap(),
+            ["pears".to_string(), "plums".to_string()]
+        );
+        assert_eq!(responses.get_int(&path.child("0")).unwrap(), 5);
+        assert_eq!(responses.get_int(&path.child("1")).unwrap(), 8);
+        assert!(!state.fields.contains_key(&path.child("2")));
+        assert_eq!(
+            state
+                .errors
+                .get(&map_key_path(&path, 1))
+                .map(String::as_str),
+            Some("Invalid key")
+        );
+    }
+
+    #[test]
+    fn map_keys_must_be_unique() {
+        let map_q = MapQuestion::new(QuestionKind::Input(Default::default()));
+        let definition = SurveyDefinition {
+            prelude: None,
+            questions: vec![Question::new(
+                "env",
+                "Env:",
+                QuestionKind::Map(map_q.clone()),
+            )],
+            epilogue: None,
+        };
+        let path = ResponsePath::new("env");
+        let mut state = FormState::new(definition);
+        state.add_map_entry(&path, &map_q);
+        state.add_map_entry(&path, &map_q);
+        state.fields.insert(
+            path.clone(),
+            FieldState::Map {
+                keys: vec!["HOME".to_string(), "HOME".to_string()],
+            },
+        );
+
+        let errors = state.required_field_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors.contains_key(&map_key_path(&path, 1)));
+    }
+
+    #[test]
+    fn finds_prompts_of_nested_fields() {
+        use elicitor::Survey;
+        use example_surveys::UserRegistration;
+
+        let state = FormState::new(UserRegistration::survey());
+        assert_eq!(
+            state
+                .prompt_for(&ResponsePath::new("contact.email"))
+                .as_deref(),
+            Some("Email address:")
+        );
+        assert_eq!(
+            state.prompt_for(&ResponsePath::new("age")).as_deref(),
+            Some("Age:")
+        );
+        assert_eq!(state.prompt_for(&ResponsePath::new("nope")), None);
+    }
+
+    #[test]
+    fn recursive_fields_are_added_when_shown() {
+        use elicitor::{LazyQuestion, Survey};
+        use example_surveys::{Filter, SavedSearch};
+
+        let mut state = FormState::new(SavedSearch::survey());
+        let filter = ResponsePath::new("filter");
+        if let Some(FieldState::OneOf { selected, .. }) = state.fields.get_mut(&filter) {
+            *selected = Some(1);
+        }
+        // The negated filter has no fields until it is shown
+        let negated = filter.child("0");
+        assert!(!state.fields.contains_key(&negated));
+        assert_eq!(
+            state.prompt_for(&negated).as_deref(),
+            Some("Filter to negate:")
+        );
+
+        let lazy = LazyQuestion::new("Filter", Filter::survey).with_max_depth(0);
+        let definition = SurveyDefinition {
+            prelude: None,
+            questions: vec![Question::new("filter", "Filter:", QuestionKind::Lazy(lazy))],
+            epilogue: None,
+        };
+        let state = FormState::new(definition);
+        assert_eq!(
+            state
+                .required_field_errors()
+                .get(&filter)
+                .map(String::as_str),
+            Some("Filter cannot be nested more than 0 levels deep")
+        );
+    }
 }
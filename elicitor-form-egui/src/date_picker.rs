@@ -0,0 +1,114 @@
+//! Calendar popup for picking the answer to a date question.
+
+use std::hash::Hash;
+
+use eframe::egui;
+use elicitor::{Date, DateQuestion};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// A button that opens a calendar below it.
+///
+/// The calendar starts at the month of `date` (or today) and only offers
+/// days within the question's bounds. Returns the day that was clicked, if
+/// any, and closes the calendar.
+pub(crate) fn date_picker_button(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    date: Option<Date>,
+    date_q: &DateQuestion,
+) -> Option<Date> {
+    let popup_id = ui.make_persistent_id(id_salt);
+    let button = ui.button("📅").on_hover_text("Pick a date");
+    if button.clicked() {
+        let shown = date.unwrap_or_else(Date::today).first_of_month();
+        ui.data_mut(|data| data.insert_temp(popup_id, shown));
+        ui.memory_mut(|memory| memory.toggle_popup(popup_id));
+    }
+
+    let picked = egui::popup::popup_below_widget(
+        ui,
+        popup_id,
+        &button,
+        egui::PopupCloseBehavior::CloseOnClickOutside,
+        |ui| {
+            let mut month = ui
+                .data(|data| data.get_temp::<Date>(popup_id))
+                .unwrap_or_else(|| Date::today().first_of_month());
+            let picked = calendar(ui, &mut month, date, date_q);
+            ui.data_mut(|data| data.insert_temp(popup_id, month));
+            picked
+        },
+    )
+    .flatten();
+    if picked.is_some() {
+        ui.memory_mut(|memory| memory.close_popup());
+    }
+    picked
+}
+
+/// One month of days with buttons to change the month, returning the day
+/// that was clicked.
+fn calendar(
+    ui: &mut egui::Ui,
+    month: &mut Date,
+    selected: Option<Date>,
+    date_q: &DateQuestion,
+) -> Option<Date> {
+    ui.horizontal(|ui| {
+        if ui.small_button("◀").clicked() {
+            *month = month.checked_add_months(-1).unwrap_or(*month);
+        }
+        ui.strong(format!(
+            "{} {}",
+            MONTHS[month.month() as usize - 1],
+            month.year()
+        ));
+        if ui.small_button("▶").clicked() {
+            *month = month.checked_add_months(1).unwrap_or(*month);
+        }
+    });
+
+    let mut picked = None;
+    egui::Grid::new(ui.id().with("days")).show(ui, |ui| {
+        for name in WEEKDAYS {
+            ui.label(egui::RichText::new(name).weak());
+        }
+        ui.end_row();
+
+        let offset = month.weekday();
+        for _ in 0..offset {
+            ui.label("");
+        }
+        for day in 1..=month.days_in_month() {
+            let Some(date) = Date::new(month.year(), month.month(), day) else {
+                continue;
+            };
+            let in_bounds = date_q.min.is_none_or(|min| date >= min)
+                && date_q.max.is_none_or(|max| date <= max);
+            let label = egui::SelectableLabel::new(selected == Some(date), day.to_string());
+            if ui.add_enabled(in_bounds, label).clicked() {
+                picked = Some(date);
+            }
+            if (offset + day).is_multiple_of(7) {
+                ui.end_row();
+            }
+        }
+    });
+    picked
+}
@@ -0,0 +1,140 @@
+//! Tabbed and paged layouts for large forms.
+//!
+//! Both layouts split the survey into one page per top-level group of
+//! questions (a nested struct); the top-level questions between groups are
+//! collected into "General" pages.
+
+use elicitor::{Question, QuestionKind, ResponsePath, SurveyDefinition};
+
+use crate::backend::{FormState, SectionProgress};
+
+/// How [`SurveyWidget`](crate::SurveyWidget) arranges the questions of a form.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FormLayout {
+    /// All questions in one column.
+    #[default]
+    Single,
+    /// One tab per top-level group, with a badge showing its progress.
+    Tabs,
+    /// One page per top-level group, with Back/Next buttons and a sidebar
+    /// listing the pages and their errors. Submit is offered on the last page
+    /// and still validates the whole form.
+    Pages,
+}
+
+/// A group of questions shown together in a tab or page.
+#[derive(Debug, Clone)]
+pub(crate) struct Page {
+    pub(crate) title: String,
+    /// Path of the group the questions belong to, if any.
+    pub(crate) prefix: Option<ResponsePath>,
+    pub(crate) questions: Vec<Question>,
+}
+
+impl Page {
+    /// Full response paths of the page's questions.
+    fn paths(&self) -> impl Iterator<Item = ResponsePath> + '_ {
+        self.questions.iter().map(|q| match &self.prefix {
+            Some(prefix) => prefix.child(q.path().as_str()),
+            None => q.path().clone(),
+        })
+    }
+
+    /// Whether a response path belongs to one of the page's questions.
+    pub(crate) fn contains(&self, path: &ResponsePath) -> bool {
+        self.paths()
+            .any(|own| !own.is_empty() && path.strip_path_prefix(&own).is_some())
+    }
+}
+
+/// Split a survey into pages: one per top-level group of several questions,
+/// and one per run of other top-level questions.
+pub(crate) fn pages(definition: &SurveyDefinition) -> Vec<Page> {
+    let mut pages: Vec<Page> = Vec::new();
+    for question in &definition.questions {
+        if question.is_assumed() {
+            continue;
+        }
+        match question.kind() {
+            QuestionKind::AllOf(all_of) if all_of.questions().len() > 1 => {
+                pages.push(Page {
+                    title: page_title(question),
+                    prefix: Some(question.path().clone()),
+                    questions: all_of.questions().to_vec(),
+                });
+            }
+            _ => match pages.last_mut() {
+                Some(page) if page.prefix.is_none() => page.questions.push(question.clone()),
+                _ => pages.push(Page {
+                    title: "General".to_string(),
+                    prefix: None,
+                    questions: vec![question.clone()],
+                }),
+            },
+        }
+    }
+    pages
+}
+
+/// Title of a group page: its prompt without trailing punctuation, or its
+/// field name.
+fn page_title(question: &Question) -> String {
+    let ask = question.ask().trim().trim_end_matches([':', '?', '.']);
+    if !ask.is_empty() {
+        return ask.to_string();
+    }
+    let name = question.path().last().unwrap_or_default().replace('_', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+impl FormState {
+    /// Progress of the questions on a page.
+    pub(crate) fn page_progress(&self, page: &Page) -> SectionProgress {
+        let prefix = page.prefix.clone().unwrap_or_else(ResponsePath::empty);
+        let mut progress = self.section_progress(&prefix, &page.questions);
+        progress.errors = self.errors.keys().filter(|p| page.contains(p)).count();
+        progress
+    }
+
+    /// Index of the first page with a validation error.
+    pub(crate) fn first_page_with_errors(&self, pages: &[Page]) -> Option<usize> {
+        pages
+            .iter()
+            .position(|page| self.errors.keys().any(|p| page.contains(p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::Survey;
+    use example_surveys::UserRegistration;
+
+    #[test]
+    fn splits_top_level_groups_into_pages() {
+        let pages = pages(&UserRegistration::survey());
+        let titles: Vec<&str> = pages.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["General", "Contact information", "Home address"]);
+        assert_eq!(pages[0].questions.len(), 2);
+        assert_eq!(pages[2].prefix, Some(ResponsePath::new("address")));
+    }
+
+    #[test]
+    fn finds_pages_with_errors() {
+        let definition = UserRegistration::survey();
+        let pages = pages(&definition);
+        let mut state = FormState::new(definition);
+        assert_eq!(state.first_page_with_errors(&pages), None);
+
+        state
+            .errors
+            .insert(ResponsePath::new("address.city"), "Required".to_string());
+        assert_eq!(state.first_page_with_errors(&pages), Some(2));
+        assert_eq!(state.page_progress(&pages[2]).badge(), "1 error");
+        assert_eq!(state.page_progress(&pages[0]).errors, 0);
+    }
+}
@@ -4,7 +4,37 @@
 //!
 //! This backend uses the `eframe` and `egui` crates to provide a native
 //! desktop form interface for surveys. All fields are displayed at once
-//! and can be edited in any order.
+//! and can be edited in any order. Nested structs are shown as collapsible
+//! sections with a progress badge in their header.
+//!
+//! Large forms can instead be split into one tab or page per nested struct
+//! with [`EguiBackend::with_layout`] and [`FormLayout`]. The paged layout has
+//! Back/Next buttons and a sidebar that marks pages with errors; Submit on
+//! the last page validates the whole form and returns to the first page with
+//! an error if it fails.
+//!
+//! While the form has validation errors, a summary above the questions lists
+//! each failing field with its prompt and message; clicking an entry scrolls
+//! to and focuses the field. Field and composite validators both run as the
+//! user edits, so the summary stays current.
+//!
+//! [`EguiBackend::with_preview`] adds a side panel previewing the responses
+//! as a tree, or as JSON or TOML with the `serde` feature, while the form is
+//! filled in, with masked answers redacted. [`EguiBackend::with_preview_formatter`] adds a custom format,
+//! such as JSON from `serde_json`. Embedders can place a [`SurveyPreview`]
+//! wherever they like.
+//!
+//! Edits can be undone and redone with Ctrl+Z and Ctrl+Y (or Ctrl+Shift+Z)
+//! and the Undo/Redo buttons, which also focus the field that changed.
+//! Text typed into one field is undone in one step.
+//!
+//! Masked fields have an eye button that shows their text. A field marked
+//! `#[confirm_entry]` gets a second "Repeat to confirm" entry that must match
+//! before the form submits, and `#[strength_meter]` shows a strength bar
+//! under the field.
+//!
+//! Date, time and duration fields take typed text in the format shown next
+//! to the prompt; date fields also have a button opening a calendar.
 //!
 //! ## Usage
 //!
@@ -33,7 +63,31 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## Embedding
+//!
+//! Applications with their own egui main loop can host a survey in any `Ui`
+//! using [`SurveyFormState`] and [`SurveyWidget`]:
+//!
+//! ```rust,ignore
+//! use elicitor_form_egui::{SurveyFormState, SurveyWidget};
+//!
+//! let mut form = SurveyFormState::for_survey::<UserProfile>();
+//!
+//! // Inside your update loop:
+//! egui::SidePanel::right("profile").show(ctx, |ui| {
+//!     ui.add(SurveyWidget::new(&mut form));
+//! });
+//! if let Some(responses) = form.take_responses() {
+//!     let profile = UserProfile::from_responses(&responses);
+//! }
+//! ```
 
 mod backend;
+mod date_picker;
+mod layout;
+mod widget;
 
 pub use backend::{EguiBackend, EguiError};
+pub use layout::FormLayout;
+pub use widget::{SurveyFormState, SurveyPreview, SurveyWidget};
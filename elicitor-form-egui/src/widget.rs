@@ -0,0 +1,959 @@
+//! Embeddable survey form for existing egui applications.
+//!
+//! [`EguiBackend`](crate::EguiBackend) owns a native window and blocks until the
+//! form is closed. Applications that already run an egui main loop can instead
+//! keep a [`SurveyFormState`] in their app state and render it with
+//! [`SurveyWidget`] into any `Ui` (a panel, a window, a modal, ...).
+
+use std::collections::HashMap;
+
+use eframe::egui;
+use elicitor::{
+    PreviewFormatter, PreviewNode, ResponsePath, ResponsePreview, ResponseValue, Responses, Survey,
+    SurveyDefinition, redact_masked,
+};
+
+use crate::backend::{FormRenderer, FormState, ValidateFn};
+use crate::layout::{FormLayout, Page, pages};
+
+/// Composite validation callback, checking relationships between fields.
+type ValidateAllFn = Box<dyn Fn(&Responses) -> HashMap<ResponsePath, String> + Send>;
+
+/// Persistent state of an embedded survey form.
+///
+/// Keep this in your application state across frames and render it with
+/// [`SurveyWidget`]. Once the user submits a valid form,
+/// [`take_responses`](Self::take_responses) yields the collected responses.
+///
+/// ```rust,ignore
+/// struct MyApp {
+///     signup: SurveyFormState,
+/// }
+///
+/// impl eframe::App for MyApp {
+///     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+///         egui::Window::new("Sign up").show(ctx, |ui| {
+///             ui.add(SurveyWidget::new(&mut self.signup));
+///         });
+///
+///         if let Some(responses) = self.signup.take_responses() {
+///             let user = UserProfile::from_responses(&responses);
+///             // ...
+///         }
+///     }
+/// }
+/// ```
+pub struct SurveyFormState {
+    form: FormState,
+    validate: ValidateFn,
+    validate_all: Option<ValidateAllFn>,
+    /// Errors reported by the composite validator on its last run.
+    composite_errors: HashMap<ResponsePath, String>,
+    /// Shown tab or page in the tabbed and paged layouts.
+    page: usize,
+    /// Format shown by [`SurveyPreview`].
+    preview_mode: PreviewMode,
+    /// Custom format offered by [`SurveyPreview`].
+    preview_formatter: Option<PreviewFormatter>,
+}
+
+/// Format of the result preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreviewMode {
+    Tree,
+    #[cfg(feature = "serde")]
+    Json,
+    #[cfg(feature = "serde")]
+    Toml,
+    Custom,
+}
+
+impl SurveyFormState {
+    /// Create form state for a survey definition, without custom validation.
+    pub fn new(definition: SurveyDefinition) -> Self {
+        Self {
+            form: FormState::new(definition),
+            validate: Box::new(|_, _, _| Ok(())),
+            validate_all: None,
+            composite_errors: HashMap::new(),
+            page: 0,
+            preview_mode: PreviewMode::Tree,
+            preview_formatter: None,
+        }
+    }
+
+    /// Create form state for a type deriving `Survey`, using its field and composite validators.
+    pub fn for_survey<T: Survey + 'static>() -> Self {
+        Self::new(T::survey())
+            .with_validator(T::validate_field)
+            .with_composite_validator(T::validate_all)
+    }
+
+    /// Set the field validator, called whenever a field changes and on submit.
+    pub fn with_validator(
+        mut self,
+        validate: impl Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>
+        + Send
+        + 'static,
+    ) -> Self {
+        self.validate = Box::new(validate);
+        self
+    }
+
+    /// Set the composite validator, called whenever a field changes and on submit.
+    pub fn with_composite_validator(
+        mut self,
+        validate_all: impl Fn(&Responses) -> HashMap<ResponsePath, String> + Send + 'static,
+    ) -> Self {
+        self.validate_all = Some(Box::new(validate_all));
+        self
+    }
+
+    /// Offer a custom format in [`SurveyPreview`], such as the survey type's
+    /// `Debug` output or JSON from `serde_json`.
+    ///
+    /// The formatter receives the responses with masked answers redacted.
+    pub fn with_preview_formatter(
+        mut self,
+        format: impl Fn(&Responses) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.preview_formatter = Some(PreviewFormatter::new(format));
+        self
+    }
+
+    /// Use an already wrapped preview formatter.
+    pub(crate) fn set_preview_formatter(&mut self, formatter: Option<PreviewFormatter>) {
+        self.preview_formatter = formatter;
+    }
+
+    /// The survey definition being rendered.
+    pub fn definition(&self) -> &SurveyDefinition {
+        &self.form.definition
+    }
+
+    /// Current validation errors, keyed by response path.
+    pub fn errors(&self) -> &HashMap<ResponsePath, String> {
+        &self.form.errors
+    }
+
+    /// Whether the form has no errors and all required fields are filled in.
+    pub fn is_valid(&self) -> bool {
+        self.form.errors.is_empty() && self.form.required_field_errors().is_empty()
+    }
+
+    /// Responses as currently entered, whether or not the form is valid.
+    pub fn responses(&self) -> Responses {
+        self.form.collect_responses()
+    }
+
+    /// Validate the whole form and mark it as submitted if it is valid.
+    ///
+    /// Returns whether the submission was accepted.
+    pub fn submit(&mut self) -> bool {
+        self.form.validate_required_fields();
+        if !self.form.errors.is_empty() {
+            return false;
+        }
+
+        let responses = self.form.collect_responses();
+        for (path, value) in responses.iter() {
+            if let Err(msg) = (self.validate)(value, &responses, path) {
+                self.form.errors.insert(path.clone(), msg);
+            }
+        }
+        self.run_composite_validation(&responses);
+
+        self.form.submitted = self.form.errors.is_empty();
+        self.form.submitted
+    }
+
+    /// Mark the form as cancelled.
+    pub fn cancel(&mut self) {
+        self.form.cancelled = true;
+    }
+
+    /// Whether the form has been submitted and its responses not yet taken.
+    pub fn is_submitted(&self) -> bool {
+        self.form.submitted
+    }
+
+    /// Whether the user pressed the cancel button.
+    pub fn is_cancelled(&self) -> bool {
+        self.form.cancelled
+    }
+
+    /// Take the responses of a successful submission.
+    ///
+    /// Returns `None` until the form has been submitted, and only once per submission.
+    pub fn take_responses(&mut self) -> Option<Responses> {
+        if !self.form.submitted {
+            return None;
+        }
+        self.form.submitted = false;
+        Some(self.form.collect_responses())
+    }
+
+    /// Undo the last edit and focus the field it changed.
+    ///
+    /// Keystrokes typed into one field are undone together. Returns whether
+    /// there was an edit to undo.
+    pub fn undo(&mut self) -> bool {
+        let path = self.form.undo();
+        self.form.focus = path.clone();
+        path.is_some()
+    }
+
+    /// Redo the last undone edit and focus the field it changed.
+    ///
+    /// Returns whether there was an edit to redo.
+    pub fn redo(&mut self) -> bool {
+        let path = self.form.redo();
+        self.form.focus = path.clone();
+        path.is_some()
+    }
+
+    /// Whether there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        self.form.history.can_undo()
+    }
+
+    /// Whether there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        self.form.history.can_redo()
+    }
+
+    /// Discard all input and start over with the original defaults.
+    pub fn reset(&mut self) {
+        self.form = FormState::new(self.form.definition.clone());
+        self.composite_errors.clear();
+        self.page = 0;
+    }
+
+    /// Run the composite validator, replacing the errors from its previous run.
+    ///
+    /// A field's own error takes precedence over a composite error on the same path.
+    fn run_composite_validation(&mut self, responses: &Responses) {
+        let Some(validate_all) = &self.validate_all else {
+            return;
+        };
+        for (path, message) in self.composite_errors.drain() {
+            if self.form.errors.get(&path) == Some(&message) {
+                self.form.errors.remove(&path);
+            }
+        }
+        let errors = validate_all(responses);
+        for (path, message) in &errors {
+            self.form
+                .errors
+                .entry(path.clone())
+                .or_insert_with(|| message.clone());
+        }
+        self.composite_errors = errors;
+    }
+
+    /// Show the page containing a field and focus it on the next render.
+    fn focus_field(&mut self, path: ResponsePath, pages: &[Page]) {
+        if let Some(page) = pages.iter().position(|page| page.contains(&path)) {
+            self.page = page;
+        }
+        self.form.focus = Some(path);
+    }
+
+    /// Submit, and on failure show the first page with an error.
+    fn submit_paged(&mut self, pages: &[Page]) {
+        if !self.submit()
+            && let Some(page) = self.form.first_page_with_errors(pages)
+        {
+            self.page = page;
+        }
+    }
+}
+
+/// Renders a [`SurveyFormState`] into an egui `Ui`.
+///
+/// The widget shows the prelude, the questions, the epilogue, and
+/// Submit/Cancel buttons. It does not add a scroll area, so the host decides
+/// how the form is laid out. Large forms can be split into tabs or pages
+/// with [`with_layout`](Self::with_layout).
+///
+/// While the form has validation errors, a summary above the questions lists
+/// them; clicking an entry scrolls to and focuses the field.
+pub struct SurveyWidget<'a> {
+    state: &'a mut SurveyFormState,
+    submit_label: String,
+    show_cancel: bool,
+    layout: FormLayout,
+    show_error_summary: bool,
+}
+
+impl<'a> SurveyWidget<'a> {
+    /// Create a widget rendering the given form state.
+    pub fn new(state: &'a mut SurveyFormState) -> Self {
+        Self {
+            state,
+            submit_label: "Submit".to_string(),
+            show_cancel: true,
+            layout: FormLayout::default(),
+            show_error_summary: true,
+        }
+    }
+
+    /// Set the label of the submit button.
+    pub fn with_submit_label(mut self, label: impl Into<String>) -> Self {
+        self.submit_label = label.into();
+        self
+    }
+
+    /// Show or hide the cancel button.
+    pub fn with_cancel_button(mut self, show: bool) -> Self {
+        self.show_cancel = show;
+        self
+    }
+
+    /// Set how the questions are arranged.
+    pub fn with_layout(mut self, layout: FormLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Show or hide the error summary above the questions.
+    pub fn with_error_summary(mut self, show: bool) -> Self {
+        self.show_error_summary = show;
+        self
+    }
+}
+
+impl egui::Widget for SurveyWidget<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let Self {
+            state,
+            submit_label,
+            show_cancel,
+            layout,
+            show_error_summary,
+        } = self;
+
+        let pages = match layout {
+            FormLayout::Single => Vec::new(),
+            FormLayout::Tabs | FormLayout::Pages => pages(&state.form.definition),
+        };
+        state.page = state.page.min(pages.len().saturating_sub(1));
+
+        // A change of keyboard focus ends the current typing run
+        let focused = ui.memory(|memory| memory.focused());
+        if focused != state.form.history_focus {
+            state.form.history.close_group();
+            state.form.history_focus = focused;
+        }
+        // Take the shortcuts before a text field uses them for its own undo
+        let (undo, redo) = ui.input_mut(|input| {
+            let redo = input.consume_shortcut(&REDO_SHORTCUTS[0])
+                || input.consume_shortcut(&REDO_SHORTCUTS[1]);
+            (input.consume_shortcut(&UNDO_SHORTCUT), redo)
+        });
+        if undo {
+            state.undo();
+        } else if redo {
+            state.redo();
+        }
+        // Show the page of a field that is about to be focused
+        if let Some(path) = &state.form.focus
+            && let Some(page) = pages.iter().position(|page| page.contains(path))
+        {
+            state.page = page;
+        }
+
+        let paged = layout == FormLayout::Pages && !pages.is_empty();
+        let last_page = state.page + 1 >= pages.len();
+
+        ui.vertical(|ui| {
+            let mut jump = None;
+            let changed;
+            {
+                let SurveyFormState {
+                    form,
+                    validate,
+                    page,
+                    ..
+                } = &mut *state;
+                let renderer = FormRenderer { validate };
+
+                // Show prelude if present
+                if let Some(prelude) = &form.prelude {
+                    ui.label(prelude);
+                    ui.separator();
+                }
+
+                if show_error_summary && !form.errors.is_empty() {
+                    jump = error_summary(ui, form);
+                }
+
+                match layout {
+                    FormLayout::Single => {
+                        for question in form.definition.questions.clone() {
+                            renderer.render_question(ui, &question, form, None);
+                        }
+                    }
+                    FormLayout::Tabs => {
+                        ui.horizontal_wrapped(|ui| {
+                            for (idx, tab) in pages.iter().enumerate() {
+                                let label = page_label(tab, form);
+                                if ui.selectable_label(*page == idx, label).clicked() {
+                                    *page = idx;
+                                }
+                            }
+                        });
+                        ui.separator();
+                        if let Some(current) = pages.get(*page) {
+                            render_page(ui, &renderer, current, form);
+                        }
+                    }
+                    FormLayout::Pages => {
+                        ui.horizontal_top(|ui| {
+                            ui.vertical(|ui| {
+                                for (idx, entry) in pages.iter().enumerate() {
+                                    let label = page_label(entry, form);
+                                    if ui.selectable_label(*page == idx, label).clicked() {
+                                        *page = idx;
+                                    }
+                                }
+                            });
+                            ui.separator();
+                            ui.vertical(|ui| {
+                                if let Some(current) = pages.get(*page) {
+                                    ui.heading(&current.title);
+                                    render_page(ui, &renderer, current, form);
+                                }
+                            });
+                        });
+                    }
+                }
+
+                // A focus request is only good for the render after it was made
+                form.focus = None;
+                changed = std::mem::take(&mut form.changed);
+
+                ui.separator();
+
+                // Show epilogue if present
+                if let Some(epilogue) = &form.epilogue {
+                    ui.label(epilogue);
+                    ui.add_space(8.0);
+                }
+            }
+
+            // Keep cross-field errors in sync with the fields
+            if changed {
+                let responses = state.form.collect_responses();
+                state.run_composite_validation(&responses);
+            }
+            if let Some(path) = jump {
+                state.focus_field(path, &pages);
+                ui.ctx().request_repaint();
+            }
+
+            ui.horizontal(|ui| {
+                let has_errors = !state.errors().is_empty();
+
+                let undo = ui
+                    .add_enabled(state.can_undo(), egui::Button::new("Undo"))
+                    .on_hover_text(ui.ctx().format_shortcut(&UNDO_SHORTCUT));
+                if undo.clicked() {
+                    state.undo();
+                }
+                let redo = ui
+                    .add_enabled(state.can_redo(), egui::Button::new("Redo"))
+                    .on_hover_text(ui.ctx().format_shortcut(&REDO_SHORTCUTS[0]));
+                if redo.clicked() {
+                    state.redo();
+                }
+
+                if paged {
+                    if ui
+                        .add_enabled(state.page > 0, egui::Button::new("Back"))
+                        .clicked()
+                    {
+                        state.page -= 1;
+                    }
+                    if !last_page && ui.button("Next").clicked() {
+                        state.page += 1;
+                    }
+                }
+
+                if (!paged || last_page)
+                    && ui
+                        .add_enabled(!has_errors, egui::Button::new(submit_label))
+                        .clicked()
+                {
+                    state.submit_paged(&pages);
+                }
+
+                if show_cancel && ui.button("Cancel").clicked() {
+                    state.cancel();
+                }
+
+                if !state.errors().is_empty() {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("{} validation error(s)", state.errors().len()),
+                    );
+                }
+            });
+        })
+        .response
+    }
+}
+
+/// Shortcut for [`SurveyFormState::undo`].
+const UNDO_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+
+/// Shortcuts for [`SurveyFormState::redo`]. Shift+Z comes first since the
+/// undo shortcut would also match it.
+const REDO_SHORTCUTS: [egui::KeyboardShortcut; 2] = [
+    egui::KeyboardShortcut::new(
+        egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+        egui::Key::Z,
+    ),
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y),
+];
+
+/// Live preview of the responses entered into a [`SurveyFormState`].
+///
+/// Shows the responses as a tree, as JSON or TOML (with the `serde` feature),
+/// or in a custom format set with [`SurveyFormState::with_preview_formatter`].
+/// Masked answers are redacted.
+/// Place it next to the [`SurveyWidget`], for example in a side panel:
+///
+/// ```rust,ignore
+/// egui::SidePanel::right("preview").show(ctx, |ui| {
+///     ui.add(SurveyPreview::new(&mut self.signup));
+/// });
+/// ```
+pub struct SurveyPreview<'a> {
+    state: &'a mut SurveyFormState,
+}
+
+impl<'a> SurveyPreview<'a> {
+    /// Create a preview of the given form state.
+    pub fn new(state: &'a mut SurveyFormState) -> Self {
+        Self { state }
+    }
+}
+
+impl egui::Widget for SurveyPreview<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let state = self.state;
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("Preview");
+                ui.selectable_value(&mut state.preview_mode, PreviewMode::Tree, "Tree");
+                #[cfg(feature = "serde")]
+                {
+                    ui.selectable_value(&mut state.preview_mode, PreviewMode::Json, "JSON");
+                    ui.selectable_value(&mut state.preview_mode, PreviewMode::Toml, "TOML");
+                }
+                if state.preview_formatter.is_some() {
+                    ui.selectable_value(&mut state.preview_mode, PreviewMode::Custom, "Custom");
+                }
+            });
+            ui.separator();
+
+            let responses = state.form.collect_responses();
+            let definition = &state.form.definition;
+            egui::ScrollArea::both()
+                .id_salt("survey_preview")
+                .show(ui, |ui| {
+                    match (state.preview_mode, &state.preview_formatter) {
+                        (PreviewMode::Custom, Some(formatter)) => {
+                            let text = formatter.format(&redact_masked(&responses, definition));
+                            ui.monospace(text);
+                        }
+                        #[cfg(feature = "serde")]
+                        (PreviewMode::Json, _) => {
+                            ui.monospace(ResponsePreview::new(&responses, definition).to_json());
+                        }
+                        #[cfg(feature = "serde")]
+                        (PreviewMode::Toml, _) => {
+                            ui.monospace(ResponsePreview::new(&responses, definition).to_toml());
+                        }
+                        _ => {
+                            let preview = ResponsePreview::new(&responses, definition);
+                            preview_tree(ui, &preview.entries, "");
+                        }
+                    }
+                });
+        })
+        .response
+    }
+}
+
+/// Render preview entries as collapsible groups and `key: value` rows.
+fn preview_tree(ui: &mut egui::Ui, entries: &[(String, PreviewNode)], parent: &str) {
+    for (key, node) in entries {
+        match node {
+            PreviewNode::Group(children) => {
+                let id = format!("{parent}.{key}");
+                egui::CollapsingHeader::new(key.as_str())
+                    .id_salt(&id)
+                    .default_open(true)
+                    .show(ui, |ui| preview_tree(ui, children, &id));
+            }
+            value => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("{key}:"));
+                    ui.monospace(value.value_text().unwrap_or_default());
+                });
+            }
+        }
+    }
+}
+
+/// Label of a tab or sidebar entry: the page title and its progress badge,
+/// in red when the page has errors.
+fn page_label(page: &Page, form: &FormState) -> egui::RichText {
+    let progress = form.page_progress(page);
+    let label = egui::RichText::new(format!("{}  {}", page.title, progress.badge()));
+    if progress.errors > 0 {
+        label.color(egui::Color32::RED)
+    } else {
+        label
+    }
+}
+
+/// List the current errors with their prompts; returns the path of the
+/// entry the user clicked.
+fn error_summary(ui: &mut egui::Ui, form: &FormState) -> Option<ResponsePath> {
+    let mut errors: Vec<(&ResponsePath, &String)> = form.errors.iter().collect();
+    errors.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+
+    let mut clicked = None;
+    egui::Frame::group(ui.style())
+        .stroke(egui::Stroke::new(1.0, egui::Color32::RED))
+        .show(ui, |ui| {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!("⚠ Please fix {} problem(s):", errors.len()),
+            );
+            for (path, message) in errors {
+                let prompt = form
+                    .prompt_for(path)
+                    .unwrap_or_else(|| path.as_str().to_string());
+                ui.horizontal_wrapped(|ui| {
+                    if ui.link(prompt.trim_end_matches(':')).clicked() {
+                        clicked = Some(path.clone());
+                    }
+                    ui.label(message);
+                    ui.weak(path.as_str());
+                });
+            }
+        });
+    ui.add_space(8.0);
+    clicked
+}
+
+/// Render the questions of one page.
+fn render_page(ui: &mut egui::Ui, renderer: &FormRenderer, page: &Page, form: &mut FormState) {
+    for question in &page.questions {
+        renderer.render_question(ui, question, form, page.prefix.as_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FieldState;
+    use elicitor::{ConfirmQuestion, InputQuestion, IntQuestion, Question, QuestionKind};
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition {
+            prelude: None,
+            questions: vec![
+                Question::new(
+                    "name",
+                    "Name?",
+                    QuestionKind::Input(InputQuestion::default()),
+                ),
+                Question::new(
+                    "age",
+                    "Age?",
+                    QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+                ),
+                Question::new(
+                    "subscribe",
+                    "Subscribe?",
+                    QuestionKind::Confirm(ConfirmQuestion::default()),
+                ),
+            ],
+            epilogue: None,
+        }
+    }
+
+    fn set_age(state: &mut SurveyFormState, age: i64) {
+        state.form.fields.insert(
+            ResponsePath::new("age"),
+            FieldState::Int {
+                value: age.to_string(),
+                parsed: Some(age),
+            },
+        );
+    }
+
+    #[test]
+    fn required_fields_block_submit() {
+        let mut state = SurveyFormState::new(definition());
+        assert!(!state.is_valid());
+        assert!(!state.submit());
+        assert!(state.errors().contains_key(&ResponsePath::new("age")));
+        assert!(state.take_responses().is_none());
+    }
+
+    #[test]
+    fn take_responses_once_after_submit() {
+        let mut state = SurveyFormState::new(definition());
+        set_age(&mut state, 42);
+        assert!(state.is_valid());
+        assert!(state.submit());
+
+        let responses = state.take_responses().unwrap();
+        assert_eq!(responses.get_int(&ResponsePath::new("age")).unwrap(), 42);
+        assert!(state.take_responses().is_none());
+    }
+
+    #[test]
+    fn validators_run_on_submit() {
+        let mut state = SurveyFormState::new(definition())
+            .with_validator(|value, _, path| {
+                if path.as_str() == "age" && value.as_int() == Some(13) {
+                    Err("Unlucky".to_string())
+                } else {
+                    Ok(())
+                }
+            })
+            .with_composite_validator(|responses| {
+                let mut errors = HashMap::new();
+                if matches!(responses.get_string(&ResponsePath::new("name")), Ok("")) {
+                    errors.insert(ResponsePath::new("name"), "Name required".to_string());
+                }
+                errors
+            });
+
+        set_age(&mut state, 13);
+        assert!(!state.submit());
+        assert_eq!(state.errors()[&ResponsePath::new("age")], "Unlucky");
+        assert_eq!(state.errors()[&ResponsePath::new("name")], "Name required");
+    }
+
+    #[test]
+    fn reset_clears_input() {
+        let mut state = SurveyFormState::new(definition());
+        set_age(&mut state, 42);
+        state.cancel();
+        state.reset();
+        assert!(!state.is_cancelled());
+        assert!(!state.is_valid());
+    }
+
+    #[test]
+    fn renders_headless() {
+        let mut state = SurveyFormState::new(definition());
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.add(SurveyWidget::new(&mut state).with_submit_label("Save"));
+            });
+        });
+        assert!(!state.is_submitted());
+    }
+
+    #[test]
+    fn renders_tabs_and_pages_headless() {
+        use example_surveys::UserRegistration;
+
+        let mut state = SurveyFormState::for_survey::<UserRegistration>();
+        let ctx = egui::Context::default();
+        for layout in [FormLayout::Tabs, FormLayout::Pages] {
+            state.page = 5;
+            let _ = ctx.run(egui::RawInput::default(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.add(SurveyWidget::new(&mut state).with_layout(layout));
+                });
+            });
+            assert_eq!(state.page, 2, "clamped to the last page");
+        }
+    }
+
+    #[test]
+    fn failed_submit_shows_first_page_with_errors() {
+        use example_surveys::UserRegistration;
+
+        let mut state = SurveyFormState::for_survey::<UserRegistration>();
+        let pages = pages(state.definition());
+        state.page = 2;
+        state.submit_paged(&pages);
+        assert!(!state.is_submitted());
+        assert_eq!(state.page, 0, "age is required on the first page");
+    }
+
+    fn render(state: &mut SurveyFormState) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.add(SurveyWidget::new(state));
+            });
+        });
+    }
+
+    #[test]
+    fn composite_errors_follow_edits() {
+        let mut state = SurveyFormState::new(definition()).with_composite_validator(|responses| {
+            let mut errors = HashMap::new();
+            if matches!(responses.get_int(&ResponsePath::new("age")), Ok(13)) {
+                errors.insert(ResponsePath::new("age"), "Unlucky".to_string());
+            }
+            errors
+        });
+
+        set_age(&mut state, 13);
+        state.form.changed = true;
+        render(&mut state);
+        assert_eq!(state.errors()[&ResponsePath::new("age")], "Unlucky");
+
+        set_age(&mut state, 14);
+        state.form.changed = true;
+        render(&mut state);
+        assert!(state.errors().is_empty());
+    }
+
+    #[test]
+    fn summary_entries_focus_their_field() {
+        use example_surveys::UserRegistration;
+
+        let mut state = SurveyFormState::for_survey::<UserRegistration>();
+        let pages = pages(state.definition());
+        state.focus_field(ResponsePath::new("contact.phone"), &pages);
+        assert_eq!(state.page, 1);
+        assert_eq!(state.form.focus, Some(ResponsePath::new("contact.phone")));
+
+        // The request is consumed by the next render
+        render(&mut state);
+        assert_eq!(state.form.focus, None);
+    }
+
+    #[test]
+    fn undo_shortcut_restores_the_field() {
+        let age = ResponsePath::new("age");
+        let mut state = SurveyFormState::new(definition());
+        let before = state.form.fields[&age].clone();
+        set_age(&mut state, 30);
+        state.form.record_edit(&age, before);
+
+        let ctx = egui::Context::default();
+        let input = egui::RawInput {
+            events: vec![egui::Event::Key {
+                key: egui::Key::Z,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: egui::Modifiers::COMMAND,
+            }],
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.add(SurveyWidget::new(&mut state));
+            });
+        });
+        assert!(state.responses().get_int(&age).is_err());
+        assert!(!state.can_undo());
+
+        assert!(state.redo());
+        assert_eq!(state.responses().get_int(&age).unwrap(), 30);
+        assert_eq!(state.form.focus, Some(age));
+    }
+
+    #[test]
+    fn preview_formatter_sees_redacted_responses() {
+        use example_surveys::Login;
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(String::new()));
+        let sink = Arc::clone(&seen);
+        let mut state = SurveyFormState::for_survey::<Login>().with_preview_formatter(move |r| {
+            let text = format!("{:?}", r.get_string(&ResponsePath::new("password")));
+            *sink.lock().unwrap() = text.clone();
+            text
+        });
+        state.form.fields.insert(
+            ResponsePath::new("password"),
+            FieldState::Text {
+                value: "hunter2".to_string(),
+                is_password: true,
+                is_multiline: false,
+                confirmation: None,
+            },
+        );
+        state.preview_mode = PreviewMode::Custom;
+
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.add(SurveyPreview::new(&mut state));
+            });
+        });
+        let seen = seen.lock().unwrap();
+        assert!(seen.contains(elicitor::REDACTED), "{seen}");
+        assert!(!seen.contains("hunter2"));
+    }
+
+    #[test]
+    fn masked_confirmation_must_match() {
+        use example_surveys::Login;
+
+        let mut state = SurveyFormState::for_survey::<Login>();
+        let password = ResponsePath::new("password");
+        let enter = |state: &mut SurveyFormState, confirmation: &str| {
+            state.form.fields.insert(
+                password.clone(),
+                FieldState::Text {
+                    value: "hunter2".to_string(),
+                    is_password: true,
+                    is_multiline: false,
+                    confirmation: Some(confirmation.to_string()),
+                },
+            );
+        };
+        state.form.fields.insert(
+            ResponsePath::new("username"),
+            FieldState::Text {
+                value: "ann".to_string(),
+                is_password: false,
+                is_multiline: false,
+                confirmation: None,
+            },
+        );
+
+        enter(&mut state, "hunter3");
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.add(SurveyWidget::new(&mut state));
+            });
+        });
+        assert!(!state.submit());
+        assert_eq!(
+            state.errors().get(&password).map(String::as_str),
+            Some(elicitor::CONFIRMATION_MISMATCH)
+        );
+
+        state.form.errors.clear();
+        enter(&mut state, "hunter2");
+        assert!(state.submit());
+        let responses = state.take_responses().unwrap();
+        assert_eq!(
+            responses.get_secret(&password).unwrap().expose_secret(),
+            "hunter2"
+        );
+        assert_eq!(responses.len(), 2);
+    }
+}
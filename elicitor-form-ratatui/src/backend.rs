@@ -4,27 +4,43 @@
 
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition, Variant,
+    CONFIRMATION_MISMATCH, CONFIRMATION_PROMPT, DefaultValue, History, LazyQuestion,
+    ListElementKind, MAP_KEYS_KEY, MapQuestion, MaskedQuestion, PasswordStrength, PreviewFormatter,
+    Question, QuestionKind, ResponsePath, ResponsePreview, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SecretValue, SurveyBackend, SurveyDefinition,
+    Variant, redact_masked,
 };
 use ratatui::{
-    Frame, Terminal,
-    layout::{Constraint, Direction, Layout, Rect},
+    Terminal,
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
     prelude::CrosstermBackend,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{
-        Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, StatefulWidget, Widget, Wrap,
     },
 };
 use std::io::{self, Stdout};
 use thiserror::Error;
 
+use elicitor_ratatui_support::edit_externally;
+use elicitor_ratatui_support::textarea::{self, TextArea, TextAreaState};
+use elicitor_theme::Theme;
+
+use crate::keymap::{Action, KeyMap};
+use crate::search::{Search, fuzzy_match};
+use crate::widget::{FormEvent, SurveyFormState, SurveyWidget};
+
 /// Error type for the Ratatui form backend.
 #[derive(Debug, Error)]
 pub enum RatatuiFormError {
@@ -41,43 +57,21 @@ pub enum RatatuiFormError {
     Terminal(String),
 }
 
-/// Color theme for the TUI form.
-#[derive(Debug, Clone)]
-pub struct Theme {
-    pub primary: Color,
-    pub secondary: Color,
-    pub background: Color,
-    pub text: Color,
-    pub highlight: Color,
-    pub error: Color,
-    pub success: Color,
-    pub border: Color,
-    pub selected_bg: Color,
-}
-
-impl Default for Theme {
-    fn default() -> Self {
-        Self {
-            primary: Color::Cyan,
-            secondary: Color::Blue,
-            background: Color::Reset,
-            text: Color::White,
-            highlight: Color::Yellow,
-            error: Color::Red,
-            success: Color::Green,
-            border: Color::Gray,
-            selected_bg: Color::DarkGray,
-        }
-    }
-}
-
 /// Ratatui form backend that displays all fields at once.
 #[derive(Debug, Clone)]
 pub struct RatatuiFormBackend {
     /// Title shown at the top of the form.
-    title: String,
+    pub(crate) title: String,
     /// Color theme for the UI.
-    theme: Theme,
+    pub(crate) theme: Theme,
+    /// Key bindings.
+    pub(crate) keymap: KeyMap,
+    /// Whether to capture and handle mouse events.
+    mouse: bool,
+    /// Whether the result preview pane starts open.
+    pub(crate) preview: bool,
+    /// Custom format offered in the preview pane.
+    pub(crate) preview_formatter: Option<PreviewFormatter>,
 }
 
 impl Default for RatatuiFormBackend {
@@ -91,7 +85,11 @@ impl RatatuiFormBackend {
     pub fn new() -> Self {
         Self {
             title: "Form".to_string(),
-            theme: Theme::default(),
+            theme: Theme::from_env(),
+            keymap: KeyMap::default(),
+            mouse: true,
+            preview: false,
+            preview_formatter: None,
         }
     }
 
@@ -107,10 +105,51 @@ impl RatatuiFormBackend {
         self
     }
 
+    /// Set custom key bindings.
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Enable or disable mouse support (enabled by default).
+    ///
+    /// With mouse support, clicking focuses fields, selects options and presses
+    /// the buttons, and the wheel scrolls the form. Capturing the mouse disables
+    /// the terminal's native text selection; turn it off to keep that.
+    pub fn with_mouse(mut self, enabled: bool) -> Self {
+        self.mouse = enabled;
+        self
+    }
+
+    /// Open the result preview pane when the form starts.
+    ///
+    /// The pane shows the responses entered so far as a tree, or as JSON or
+    /// TOML with the `serde` feature; F2 cycles through the formats and hides
+    /// the pane. Masked answers are redacted.
+    pub fn with_preview(mut self, enabled: bool) -> Self {
+        self.preview = enabled;
+        self
+    }
+
+    /// Offer a custom format in the preview pane, such as the survey type's
+    /// `Debug` output or JSON from `serde_json`.
+    ///
+    /// The formatter receives the responses with masked answers redacted.
+    pub fn with_preview_formatter(
+        mut self,
+        format: impl Fn(&Responses) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.preview_formatter = Some(PreviewFormatter::new(format));
+        self
+    }
+
     fn setup_terminal(&self) -> Result<Terminal<CrosstermBackend<Stdout>>, RatatuiFormError> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+        if self.mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        }
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
         Ok(terminal)
@@ -121,10 +160,13 @@ impl RatatuiFormBackend {
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<(), RatatuiFormError> {
         disable_raw_mode()?;
+        if self.mouse {
+            execute!(terminal.backend_mut(), DisableMouseCapture)?;
+        }
         execute!(
             terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableBracketedPaste,
+            LeaveAlternateScreen
         )?;
         terminal.show_cursor()?;
         Ok(())
@@ -136,7 +178,7 @@ impl RatatuiFormBackend {
 enum FieldKind {
     Text {
         multiline: bool,
-        masked: bool,
+        masked: Option<Masking>,
     },
     Int {
         min: Option<i64>,
@@ -147,6 +189,10 @@ enum FieldKind {
         max: Option<f64>,
     },
     Bool,
+    /// Date, time, date-time or duration, typed as text.
+    Temporal {
+        kind: QuestionKind,
+    },
     List {
         element_kind: ListElementKind,
     },
@@ -162,6 +208,45 @@ enum FieldKind {
         /// Currently highlighted option (for keyboard navigation)
         highlight: usize,
     },
+    /// Header of a nested group of questions; the fields below it whose path
+    /// starts with its path belong to it.
+    Section {
+        collapsed: bool,
+    },
+    /// Header of a map; each entry below it has a key field followed by the
+    /// fields of its value.
+    Map {
+        map_q: MapQuestion,
+    },
+    /// Key of a map entry, at `<map>.keys.<index>`.
+    MapKey,
+    /// Placeholder for a recursive question, replaced by its fields once it
+    /// becomes part of the form. It stays, showing why, if it is nested too
+    /// deeply.
+    Lazy {
+        lazy: LazyQuestion,
+    },
+}
+
+/// Options and state of a masked text field.
+#[derive(Debug, Clone, Copy, Default)]
+struct Masking {
+    /// Whether the text is currently shown in clear.
+    revealed: bool,
+    /// Whether to draw a strength meter under the field.
+    strength_meter: bool,
+    /// Whether this field repeats the masked field above it. Its text must
+    /// match and is not collected as a response.
+    confirmation: bool,
+}
+
+impl Masking {
+    fn new(masked_q: &MaskedQuestion) -> Self {
+        Self {
+            strength_meter: masked_q.strength_meter,
+            ..Self::default()
+        }
+    }
 }
 
 /// Condition for field visibility based on parent OneOf/AnyOf selection.
@@ -190,6 +275,8 @@ enum VisibilityCondition {
 struct FormField {
     path: ResponsePath,
     prompt: String,
+    /// Longer explanation of the field, if any
+    help: Option<String>,
     kind: FieldKind,
     value: String,
     cursor_pos: usize,
@@ -201,8 +288,226 @@ struct FormField {
     is_top_level: bool,
 }
 
+/// The editable contents of a field, as recorded in the undo history.
+#[derive(Debug, Clone)]
+struct FieldContents {
+    value: String,
+    cursor_pos: usize,
+    selection: Selection,
+}
+
+/// Chosen options of a selection field.
+#[derive(Debug, Clone, PartialEq)]
+enum Selection {
+    None,
+    One(Option<usize>),
+    Any(Vec<bool>),
+}
+
+impl FieldContents {
+    /// Whether the two hold the same answer, ignoring the cursor.
+    fn same_answer(&self, other: &FieldContents) -> bool {
+        self.value == other.value && self.selection == other.selection
+    }
+}
+
+impl FormField {
+    /// Snapshot of the field's editable contents.
+    fn contents(&self) -> FieldContents {
+        let selection = match &self.kind {
+            FieldKind::OneOf { selected, .. } => Selection::One(*selected),
+            FieldKind::AnyOf { selected, .. } => Selection::Any(selected.clone()),
+            _ => Selection::None,
+        };
+        FieldContents {
+            value: self.value.clone(),
+            cursor_pos: self.cursor_pos,
+            selection,
+        }
+    }
+
+    /// Put back contents taken with [`FormField::contents`].
+    fn restore(&mut self, contents: FieldContents) {
+        self.value = contents.value;
+        self.cursor_pos = contents.cursor_pos;
+        self.error = None;
+        match (&mut self.kind, contents.selection) {
+            (
+                FieldKind::OneOf {
+                    selected,
+                    highlight,
+                    ..
+                },
+                Selection::One(restored),
+            ) => {
+                *selected = restored;
+                *highlight = restored.unwrap_or(*highlight);
+            }
+            (FieldKind::AnyOf { selected, .. }, Selection::Any(restored)) => *selected = restored,
+            _ => {}
+        }
+    }
+
+    /// Whether the field is edited by typing, so keystrokes are grouped into
+    /// one undo step.
+    fn is_typed(&self) -> bool {
+        matches!(
+            self.kind,
+            FieldKind::Text { .. }
+                | FieldKind::Int { .. }
+                | FieldKind::Float { .. }
+                | FieldKind::Temporal { .. }
+                | FieldKind::List { .. }
+                | FieldKind::MapKey
+        )
+    }
+
+    /// Whether the field is the header of a section or map.
+    fn is_header(&self) -> bool {
+        matches!(self.kind, FieldKind::Section { .. } | FieldKind::Map { .. })
+    }
+
+    /// Whether the field repeats the masked field above it.
+    fn is_confirmation(&self) -> bool {
+        matches!(
+            self.kind,
+            FieldKind::Text {
+                masked: Some(Masking {
+                    confirmation: true,
+                    ..
+                }),
+                ..
+            }
+        )
+    }
+
+    /// Whether the field holds an answer, for section progress.
+    fn is_answered(&self) -> bool {
+        match &self.kind {
+            FieldKind::Text { .. } | FieldKind::List { .. } | FieldKind::MapKey => {
+                !self.value.trim().is_empty()
+            }
+            FieldKind::Int { .. } => self.value.parse::<i64>().is_ok(),
+            FieldKind::Float { .. } => self.value.parse::<f64>().is_ok(),
+            FieldKind::Temporal { kind } => {
+                kind.parse_temporal(&self.value).is_some_and(|r| r.is_ok())
+            }
+            FieldKind::OneOf { selected, .. } => selected.is_some(),
+            FieldKind::Lazy { .. } => false,
+            FieldKind::Bool
+            | FieldKind::AnyOf { .. }
+            | FieldKind::Section { .. }
+            | FieldKind::Map { .. } => true,
+        }
+    }
+}
+
+/// Add the repeat-entry field after a masked field whose question asks for
+/// confirmation.
+fn push_confirmation(fields: &mut Vec<FormField>, masked_q: &MaskedQuestion) {
+    if !masked_q.confirm {
+        return;
+    }
+    let Some(masked) = fields.last() else {
+        return;
+    };
+    let confirmation = FormField {
+        prompt: CONFIRMATION_PROMPT.to_string(),
+        help: None,
+        kind: FieldKind::Text {
+            multiline: false,
+            masked: Some(Masking {
+                confirmation: true,
+                ..Masking::default()
+            }),
+        },
+        is_top_level: false,
+        ..masked.clone()
+    };
+    fields.push(confirmation);
+}
+
+/// The starting text of a temporal field: the suggested value, else the
+/// question's own default.
+fn temporal_text(default: &DefaultValue, kind: &QuestionKind) -> String {
+    match default {
+        DefaultValue::Suggested(value) => value.temporal_text(),
+        _ => None,
+    }
+    .or_else(|| kind.temporal_default()?.temporal_text())
+    .unwrap_or_default()
+}
+
+/// Whether `path` lies inside the group at `section`.
+fn is_inside(path: &ResponsePath, section: &ResponsePath) -> bool {
+    path.as_str()
+        .strip_prefix(section.as_str())
+        .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Index of the map entry that `path` belongs to: its key at
+/// `<map>.keys.<index>` or its value at `<map>.<index>`.
+fn map_entry_index(path: &ResponsePath, map: &ResponsePath) -> Option<usize> {
+    let rest = path.strip_path_prefix(map)?;
+    let mut segments = rest.segments();
+    let mut entry = segments.next()?;
+    if entry == MAP_KEYS_KEY {
+        entry = segments.next()?;
+    }
+    entry.parse().ok()
+}
+
+/// `path` with its entry index in `map` lowered by one if it comes after
+/// the removed entry.
+fn shift_map_entry(path: &ResponsePath, map: &ResponsePath, removed: usize) -> ResponsePath {
+    match map_entry_index(path, map) {
+        Some(entry) if entry > removed => {
+            let rest = path
+                .strip_path_prefix(map)
+                .unwrap_or_else(ResponsePath::empty);
+            let mut segments: Vec<&str> = rest.segments().collect();
+            let pos = usize::from(segments.first() == Some(&MAP_KEYS_KEY));
+            let moved = (entry - 1).to_string();
+            segments[pos] = &moved;
+            map.child(&segments.join("."))
+        }
+        _ => path.clone(),
+    }
+}
+
+/// How far a section has been filled in, shown in its header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SectionProgress {
+    answered: usize,
+    total: usize,
+    errors: usize,
+}
+
+impl SectionProgress {
+    /// Short badge such as "3/5", "5/5 ✓" or "1 error".
+    fn badge(&self) -> String {
+        match self.errors {
+            0 if self.answered == self.total => format!("{}/{} ✓", self.answered, self.total),
+            0 => format!("{}/{}", self.answered, self.total),
+            1 => "1 error".to_string(),
+            n => format!("{n} errors"),
+        }
+    }
+}
+
+/// Format of the result preview pane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PreviewMode {
+    Tree,
+    #[cfg(feature = "serde")]
+    Json,
+    #[cfg(feature = "serde")]
+    Toml,
+    Custom,
+}
+
 /// State for the entire form.
-struct FormState {
+pub(crate) struct FormState {
     fields: Vec<FormField>,
     focused_idx: usize,
     /// Scroll offset in pixels (vertical)
@@ -210,20 +515,51 @@ struct FormState {
     /// Whether the submit button is focused
     submit_focused: bool,
     submitted: bool,
-    cancelled: bool,
-    theme: Theme,
-    title: String,
+    pub(crate) cancelled: bool,
+    pub(crate) theme: Theme,
+    pub(crate) title: String,
     prelude: Option<String>,
     #[allow(dead_code)]
     epilogue: Option<String>,
+    /// Text cursor position from the last render, if a text field is focused.
+    pub(crate) cursor: Option<Position>,
+    /// Key bindings.
+    pub(crate) keymap: KeyMap,
+    /// Whether the key binding overlay is shown.
+    show_help: bool,
+    /// Whether rendering should scroll the focused field into view.
+    /// Cleared by mouse-wheel scrolling so the view stays where the user put it.
+    follow_focus: bool,
+    /// Height of the scrollable field area from the last render.
+    viewport_height: u16,
+    /// Screen areas of the fields drawn by the last render, for mouse hit-testing.
+    field_areas: Vec<(usize, Rect)>,
+    /// Screen area of the submit button from the last render.
+    submit_area: Rect,
+    /// Screen area of the cancel button from the last render.
+    cancel_area: Rect,
+    /// Scroll position of the focused multi-line field, and which field that is.
+    textarea: (usize, TextAreaState),
+    /// Set when the user asked to edit the focused field in an external editor.
+    pub(crate) editor_requested: bool,
+    /// The active `/` search, if any.
+    search: Option<Search>,
+    /// The survey being filled in, for the result preview.
+    definition: SurveyDefinition,
+    /// Shown format of the result preview pane, if it is open.
+    pub(crate) preview: Option<PreviewMode>,
+    /// Custom format offered in the preview pane.
+    pub(crate) preview_formatter: Option<PreviewFormatter>,
+    /// Undo/redo history of field edits, by field index.
+    history: History<usize, FieldContents>,
 }
 
 impl FormState {
-    fn new(definition: &SurveyDefinition, theme: Theme, title: String) -> Self {
+    pub(crate) fn new(definition: &SurveyDefinition, theme: Theme, title: String) -> Self {
         let mut fields = Vec::new();
         Self::flatten_questions(&definition.questions, &mut fields, None);
 
-        Self {
+        let mut state = Self {
             fields,
             focused_idx: 0,
             scroll_offset: 0,
@@ -234,7 +570,24 @@ impl FormState {
             title,
             prelude: definition.prelude.clone(),
             epilogue: definition.epilogue.clone(),
-        }
+            cursor: None,
+            keymap: KeyMap::default(),
+            show_help: false,
+            follow_focus: true,
+            viewport_height: 0,
+            field_areas: Vec::new(),
+            submit_area: Rect::default(),
+            cancel_area: Rect::default(),
+            textarea: (0, TextAreaState::default()),
+            editor_requested: false,
+            search: None,
+            definition: definition.clone(),
+            preview: None,
+            preview_formatter: None,
+            history: History::default(),
+        };
+        state.expand_lazy_fields();
+        state
     }
 
     fn flatten_questions(
@@ -283,9 +636,10 @@ impl FormState {
                     fields.push(FormField {
                         path,
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::Text {
                             multiline: false,
-                            masked: false,
+                            masked: None,
                         },
                         value: default.clone(),
                         cursor_pos: default.len(),
@@ -303,9 +657,10 @@ impl FormState {
                     fields.push(FormField {
                         path,
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::Text {
                             multiline: true,
-                            masked: false,
+                            masked: None,
                         },
                         value: default.clone(),
                         cursor_pos: default.len(),
@@ -315,13 +670,14 @@ impl FormState {
                         is_top_level,
                     });
                 }
-                QuestionKind::Masked(_) => {
+                QuestionKind::Masked(masked_q) => {
                     fields.push(FormField {
                         path,
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::Text {
                             multiline: false,
-                            masked: true,
+                            masked: Some(Masking::new(masked_q)),
                         },
                         value: String::new(),
                         cursor_pos: 0,
@@ -330,6 +686,7 @@ impl FormState {
                         visibility: VisibilityCondition::Always,
                         is_top_level,
                     });
+                    push_confirmation(fields, masked_q);
                 }
                 QuestionKind::Int(int_q) => {
                     let default = match question.default() {
@@ -339,6 +696,7 @@ impl FormState {
                     fields.push(FormField {
                         path,
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::Int {
                             min: int_q.min,
                             max: int_q.max,
@@ -359,6 +717,7 @@ impl FormState {
                     fields.push(FormField {
                         path,
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::Float {
                             min: float_q.min,
                             max: float_q.max,
@@ -379,6 +738,7 @@ impl FormState {
                     fields.push(FormField {
                         path,
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::Bool,
                         value: if default { "true" } else { "false" }.to_string(),
                         cursor_pos: 0,
@@ -388,10 +748,31 @@ impl FormState {
                         is_top_level,
                     });
                 }
+                QuestionKind::Date(_)
+                | QuestionKind::Time(_)
+                | QuestionKind::DateTime(_)
+                | QuestionKind::Duration(_) => {
+                    let default = temporal_text(question.default(), question.kind());
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        help: question.help().map(str::to_string),
+                        kind: FieldKind::Temporal {
+                            kind: question.kind().clone(),
+                        },
+                        value: default.clone(),
+                        cursor_pos: default.len(),
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                    });
+                }
                 QuestionKind::List(list_q) => {
                     fields.push(FormField {
                         path,
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::List {
                             element_kind: list_q.element_kind.clone(),
                         },
@@ -411,6 +792,7 @@ impl FormState {
                     fields.push(FormField {
                         path: path.clone(),
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::OneOf {
                             variants: one_of.variants.clone(),
                             selected: default_idx,
@@ -461,6 +843,7 @@ impl FormState {
                     fields.push(FormField {
                         path: path.clone(),
                         prompt,
+                        help: question.help().map(str::to_string),
                         kind: FieldKind::AnyOf {
                             variants: any_of.variants.clone(),
                             selected: default_selected,
@@ -489,8 +872,54 @@ impl FormState {
                     }
                 }
                 QuestionKind::AllOf(all_of) => {
+                    if all_of.questions().len() > 1 {
+                        fields.push(FormField {
+                            path: path.clone(),
+                            prompt,
+                            help: question.help().map(str::to_string),
+                            kind: FieldKind::Section { collapsed: false },
+                            value: String::new(),
+                            cursor_pos: 0,
+                            error: None,
+                            assumed,
+                            visibility: VisibilityCondition::Always,
+                            is_top_level,
+                        });
+                    }
                     Self::flatten_questions(all_of.questions(), fields, Some(&path));
                 }
+                QuestionKind::Map(map_q) => {
+                    // Entries start empty and are added with `Action::AddEntry`
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        help: question.help().map(str::to_string),
+                        kind: FieldKind::Map {
+                            map_q: map_q.clone(),
+                        },
+                        value: String::new(),
+                        cursor_pos: 0,
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                    });
+                }
+                QuestionKind::Lazy(lazy) => {
+                    // Expanded by `expand_lazy_fields`
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        help: question.help().map(str::to_string),
+                        kind: FieldKind::Lazy { lazy: lazy.clone() },
+                        value: String::new(),
+                        cursor_pos: 0,
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                    });
+                }
             }
         }
     }
@@ -519,9 +948,10 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt: variant.name.clone(),
+                    help: variant.help.clone(),
                     kind: FieldKind::Text {
                         multiline: false,
-                        masked: false,
+                        masked: None,
                     },
                     value: input_q.default.clone().unwrap_or_default(),
                     cursor_pos: 0,
@@ -537,6 +967,7 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt: variant.name.clone(),
+                    help: variant.help.clone(),
                     kind: FieldKind::Int {
                         min: int_q.min,
                         max: int_q.max,
@@ -555,6 +986,7 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt: variant.name.clone(),
+                    help: variant.help.clone(),
                     kind: FieldKind::Float {
                         min: float_q.min,
                         max: float_q.max,
@@ -567,11 +999,27 @@ impl FormState {
                     is_top_level: false,
                 });
             }
+            kind if kind.is_temporal() => {
+                let path = parent_path.child(&variant.name);
+                fields.push(FormField {
+                    path,
+                    prompt: variant.name.clone(),
+                    help: variant.help.clone(),
+                    kind: FieldKind::Temporal { kind: kind.clone() },
+                    value: temporal_text(&DefaultValue::None, kind),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed: false,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                });
+            }
             QuestionKind::Confirm(confirm_q) => {
                 let path = parent_path.child(&variant.name);
                 fields.push(FormField {
                     path,
                     prompt: variant.name.clone(),
+                    help: variant.help.clone(),
                     kind: FieldKind::Bool,
                     value: if confirm_q.default { "true" } else { "false" }.to_string(),
                     cursor_pos: 0,
@@ -586,6 +1034,7 @@ impl FormState {
                 fields.push(FormField {
                     path: path.clone(),
                     prompt: variant.name.clone(),
+                    help: variant.help.clone(),
                     kind: FieldKind::OneOf {
                         variants: one_of.variants.clone(),
                         selected: one_of.default,
@@ -621,6 +1070,7 @@ impl FormState {
                 fields.push(FormField {
                     path: path.clone(),
                     prompt: variant.name.clone(),
+                    help: variant.help.clone(),
                     kind: FieldKind::AnyOf {
                         variants: any_of.variants.clone(),
                         selected,
@@ -693,9 +1143,10 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::Text {
                         multiline: false,
-                        masked: false,
+                        masked: None,
                     },
                     value: default.clone(),
                     cursor_pos: default.len(),
@@ -713,9 +1164,10 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::Text {
                         multiline: true,
-                        masked: false,
+                        masked: None,
                     },
                     value: default.clone(),
                     cursor_pos: default.len(),
@@ -725,13 +1177,14 @@ impl FormState {
                     is_top_level: false,
                 });
             }
-            QuestionKind::Masked(_) => {
+            QuestionKind::Masked(masked_q) => {
                 fields.push(FormField {
                     path,
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::Text {
                         multiline: false,
-                        masked: true,
+                        masked: Some(Masking::new(masked_q)),
                     },
                     value: String::new(),
                     cursor_pos: 0,
@@ -740,6 +1193,7 @@ impl FormState {
                     visibility: visibility.clone(),
                     is_top_level: false,
                 });
+                push_confirmation(fields, masked_q);
             }
             QuestionKind::Int(int_q) => {
                 let default = match question.default() {
@@ -749,6 +1203,7 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::Int {
                         min: int_q.min,
                         max: int_q.max,
@@ -769,6 +1224,7 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::Float {
                         min: float_q.min,
                         max: float_q.max,
@@ -789,6 +1245,7 @@ impl FormState {
                 fields.push(FormField {
                     path,
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::Bool,
                     value: if default { "true" } else { "false" }.to_string(),
                     cursor_pos: 0,
@@ -798,10 +1255,31 @@ impl FormState {
                     is_top_level: false,
                 });
             }
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                let default = temporal_text(question.default(), question.kind());
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    help: question.help().map(str::to_string),
+                    kind: FieldKind::Temporal {
+                        kind: question.kind().clone(),
+                    },
+                    value: default.clone(),
+                    cursor_pos: default.len(),
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                });
+            }
             QuestionKind::List(list_q) => {
                 fields.push(FormField {
                     path,
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::List {
                         element_kind: list_q.element_kind.clone(),
                     },
@@ -821,6 +1299,7 @@ impl FormState {
                 fields.push(FormField {
                     path: path.clone(),
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::OneOf {
                         variants: one_of.variants.clone(),
                         selected: default_idx,
@@ -871,6 +1350,7 @@ impl FormState {
                 fields.push(FormField {
                     path: path.clone(),
                     prompt,
+                    help: question.help().map(str::to_string),
                     kind: FieldKind::AnyOf {
                         variants: any_of.variants.clone(),
                         selected: default_selected,
@@ -899,12 +1379,218 @@ impl FormState {
                 }
             }
             QuestionKind::AllOf(all_of) => {
+                if all_of.questions().len() > 1 {
+                    fields.push(FormField {
+                        path: path.clone(),
+                        prompt,
+                        help: question.help().map(str::to_string),
+                        kind: FieldKind::Section { collapsed: false },
+                        value: String::new(),
+                        cursor_pos: 0,
+                        error: None,
+                        assumed,
+                        visibility: visibility.clone(),
+                        is_top_level: false,
+                    });
+                }
                 // Recursively add all nested questions with the same visibility
                 for q in all_of.questions() {
                     Self::add_question_with_visibility(q, fields, Some(&path), visibility.clone());
                 }
             }
+            QuestionKind::Map(map_q) => {
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    help: question.help().map(str::to_string),
+                    kind: FieldKind::Map {
+                        map_q: map_q.clone(),
+                    },
+                    value: String::new(),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                });
+            }
+            QuestionKind::Lazy(lazy) => {
+                // Expanded by `expand_lazy_fields`
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    help: question.help().map(str::to_string),
+                    kind: FieldKind::Lazy { lazy: lazy.clone() },
+                    value: String::new(),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                });
+            }
+        }
+    }
+
+    /// Replace the placeholders of recursive questions that have become part
+    /// of the form with their fields. A placeholder nested too deeply stays,
+    /// with the reason as its error.
+    fn expand_lazy_fields(&mut self) {
+        let mut expanded = false;
+        let mut idx = 0;
+        while idx < self.fields.len() {
+            let field = &self.fields[idx];
+            let FieldKind::Lazy { lazy } = &field.kind else {
+                idx += 1;
+                continue;
+            };
+            if !self.is_field_active(field) {
+                idx += 1;
+                continue;
+            }
+            match lazy.expand() {
+                Ok(all_of) => {
+                    let question = Question::new(
+                        field.path.clone(),
+                        field.prompt.clone(),
+                        QuestionKind::AllOf(all_of),
+                    );
+                    let mut nested = Vec::new();
+                    Self::add_question_with_visibility(
+                        &question,
+                        &mut nested,
+                        None,
+                        field.visibility.clone(),
+                    );
+                    let count = nested.len();
+                    self.fields.splice(idx..=idx, nested);
+                    if self.focused_idx > idx {
+                        self.focused_idx = (self.focused_idx + count).saturating_sub(1);
+                    }
+                    // The new fields may hold placeholders of their own, so
+                    // `idx` stays put
+                    expanded = true;
+                }
+                Err(err) => {
+                    self.fields[idx].error = Some(err);
+                    idx += 1;
+                }
+            }
+        }
+        if expanded {
+            // Fields moved, so the history no longer applies
+            self.history = History::default();
+        }
+    }
+
+    /// Add an empty entry at the end of the map whose header is at `map_idx`,
+    /// and focus its key.
+    fn add_map_entry(&mut self, map_idx: usize) {
+        let header = &self.fields[map_idx];
+        let FieldKind::Map { map_q } = &header.kind else {
+            return;
+        };
+        let map_path = header.path.clone();
+        let visibility = header.visibility.clone();
+        let value_q = map_q.value_question(self.map_len(header), "this key");
+
+        // The new entry goes after the fields of the existing ones
+        let end = (map_idx + 1..self.fields.len())
+            .find(|&idx| !is_inside(&self.fields[idx].path, &map_path))
+            .unwrap_or(self.fields.len());
+        let mut entry_fields = vec![FormField {
+            path: map_path.child(MAP_KEYS_KEY).child(value_q.path().as_str()),
+            prompt: "Key".to_string(),
+            help: None,
+            kind: FieldKind::MapKey,
+            value: String::new(),
+            cursor_pos: 0,
+            error: None,
+            assumed: false,
+            visibility: visibility.clone(),
+            is_top_level: false,
+        }];
+        Self::add_question_with_visibility(
+            &value_q,
+            &mut entry_fields,
+            Some(&map_path),
+            visibility,
+        );
+        self.fields.splice(end..end, entry_fields);
+
+        self.focused_idx = end;
+        self.submit_focused = false;
+        // Fields after the entry moved, so the history no longer applies
+        self.history = History::default();
+    }
+
+    /// Remove the map entry holding the focused field. The fields of later
+    /// entries move up one index.
+    fn remove_map_entry(&mut self) {
+        let Some((map_idx, entry)) = self.focused_map_entry() else {
+            return;
+        };
+        let map_path = self.fields[map_idx].path.clone();
+        let start = self
+            .fields
+            .iter()
+            .position(|f| map_entry_index(&f.path, &map_path) == Some(entry))
+            .unwrap_or(self.fields.len());
+        self.fields
+            .retain(|f| map_entry_index(&f.path, &map_path) != Some(entry));
+
+        for field in &mut self.fields {
+            field.path = shift_map_entry(&field.path, &map_path, entry);
+            if let VisibilityCondition::OneOfVariant { parent_path, .. }
+            | VisibilityCondition::AnyOfVariant { parent_path, .. } = &mut field.visibility
+            {
+                *parent_path = shift_map_entry(parent_path, &map_path, entry);
+            }
+        }
+
+        // Focus the entry that moved into its place, else the map itself
+        self.focused_idx = match self.fields.get(start) {
+            Some(field) if is_inside(&field.path, &map_path) => start,
+            _ => map_idx,
+        };
+        self.submit_focused = false;
+        self.history = History::default();
+    }
+
+    /// The innermost map holding the focused field, as the index of its
+    /// header and of the entry.
+    fn focused_map_entry(&self) -> Option<(usize, usize)> {
+        if self.submit_focused {
+            return None;
         }
+        let focused = self.focused_field()?;
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| matches!(f.kind, FieldKind::Map { .. }))
+            .filter_map(|(idx, map)| Some((idx, map_entry_index(&focused.path, &map.path)?)))
+            .max_by_key(|&(idx, _)| self.fields[idx].path.len())
+    }
+
+    /// The map to add an entry to: the focused map, else the innermost map
+    /// holding the focused field.
+    fn focused_map(&self) -> Option<usize> {
+        match self.focused_field() {
+            Some(FormField {
+                kind: FieldKind::Map { .. },
+                ..
+            }) if !self.submit_focused => Some(self.focused_idx),
+            _ => self.focused_map_entry().map(|(idx, _)| idx),
+        }
+    }
+
+    /// Number of entries in a map.
+    fn map_len(&self, map: &FormField) -> usize {
+        let keys_path = map.path.child(MAP_KEYS_KEY);
+        self.fields
+            .iter()
+            .filter(|f| matches!(f.kind, FieldKind::MapKey) && is_inside(&f.path, &keys_path))
+            .count()
     }
 
     fn focused_field(&self) -> Option<&FormField> {
@@ -915,8 +1601,18 @@ impl FormState {
         self.fields.get_mut(self.focused_idx)
     }
 
-    /// Check if a field is currently visible based on its visibility condition
+    /// Check if a field is shown: it is active and not inside a collapsed section.
     fn is_field_visible(&self, field: &FormField) -> bool {
+        self.is_field_active(field)
+            && !self.fields.iter().any(|section| {
+                matches!(section.kind, FieldKind::Section { collapsed: true })
+                    && is_inside(&field.path, &section.path)
+            })
+    }
+
+    /// Check if a field is part of the form based on its visibility condition,
+    /// whether or not its section is collapsed.
+    fn is_field_active(&self, field: &FormField) -> bool {
         if field.assumed {
             return false;
         }
@@ -953,15 +1649,72 @@ impl FormState {
             .unwrap_or(false)
     }
 
-    /// Calculate the Y position of a field (by index) in the virtual scroll area
-    fn field_y_position(&self, target_idx: usize) -> u16 {
-        let mut y: u16 = 0;
-        let mut is_first = true;
-        for (idx, field) in self.fields.iter().enumerate() {
-            if !self.is_field_visible(field) {
+    /// Number of sections and maps enclosing a field, for indentation.
+    fn section_depth(&self, field: &FormField) -> u16 {
+        self.fields
+            .iter()
+            .filter(|section| section.is_header() && is_inside(&field.path, &section.path))
+            .count() as u16
+    }
+
+    /// Progress of the active fields inside a section, for its header badge.
+    fn section_progress(&self, section: &FormField) -> SectionProgress {
+        let mut progress = SectionProgress::default();
+        for field in &self.fields {
+            if field.is_header()
+                || !is_inside(&field.path, &section.path)
+                || !self.is_field_active(field)
+            {
                 continue;
             }
-            if idx == target_idx {
+            progress.total += 1;
+            if field.is_answered() {
+                progress.answered += 1;
+            }
+            if field.error.is_some() {
+                progress.errors += 1;
+            }
+        }
+        progress
+    }
+
+    /// Collapse or expand the focused section.
+    ///
+    /// Returns `false` if the focused field is not a section.
+    fn set_section_collapsed(&mut self, collapse: impl FnOnce(bool) -> bool) -> bool {
+        if self.submit_focused {
+            return false;
+        }
+        match self.focused_field_mut().map(|f| &mut f.kind) {
+            Some(FieldKind::Section { collapsed }) => {
+                *collapsed = collapse(*collapsed);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Expand every section that hides the field at `idx`.
+    fn reveal(&mut self, idx: usize) {
+        let path = self.fields[idx].path.clone();
+        for section in &mut self.fields {
+            if let FieldKind::Section { collapsed } = &mut section.kind
+                && is_inside(&path, &section.path)
+            {
+                *collapsed = false;
+            }
+        }
+    }
+
+    /// Calculate the Y position of a field (by index) in the virtual scroll area
+    fn field_y_position(&self, target_idx: usize) -> u16 {
+        let mut y: u16 = 0;
+        let mut is_first = true;
+        for (idx, field) in self.fields.iter().enumerate() {
+            if !self.is_field_visible(field) {
+                continue;
+            }
+            if idx == target_idx {
                 return y;
             }
             // Include spacing for all except the first visible field
@@ -993,8 +1746,7 @@ impl FormState {
         let is_first_visible = self
             .fields
             .iter()
-            .filter(|f| self.is_field_visible(f))
-            .next()
+            .find(|f| self.is_field_visible(f))
             .map(|f| std::ptr::eq(f, &self.fields[self.focused_idx]))
             .unwrap_or(false);
 
@@ -1071,48 +1823,90 @@ impl FormState {
     }
 
     fn handle_text_input(&mut self, c: char) {
-        if let Some(field) = self.focused_field_mut() {
-            field.value.insert(field.cursor_pos, c);
-            field.cursor_pos += 1;
+        if let Some(field) = self.focused_field_mut()
+            && !field.is_header()
+        {
+            textarea::insert(&mut field.value, &mut field.cursor_pos, c);
             field.error = None;
         }
     }
 
     fn handle_backspace(&mut self) {
         if let Some(field) = self.focused_field_mut()
-            && field.cursor_pos > 0
+            && textarea::backspace(&mut field.value, &mut field.cursor_pos)
         {
-            field.cursor_pos -= 1;
-            field.value.remove(field.cursor_pos);
             field.error = None;
         }
     }
 
     fn handle_delete(&mut self) {
         if let Some(field) = self.focused_field_mut()
-            && field.cursor_pos < field.value.len()
+            && textarea::delete(&mut field.value, &mut field.cursor_pos)
         {
-            field.value.remove(field.cursor_pos);
             field.error = None;
         }
     }
 
+    /// Insert pasted text into the focused text field, or the search bar if
+    /// it is open, as a single edit.
+    pub(crate) fn handle_paste(&mut self, text: &str) {
+        self.show_help = false;
+        if let Some(search) = &mut self.search {
+            search
+                .query
+                .extend(text.chars().filter(|c| !c.is_control()));
+            self.update_search();
+            return;
+        }
+        if !self.is_typing() {
+            return;
+        }
+        let idx = self.focused_idx;
+        let multiline = self.is_multiline();
+        let Some(field) = self.focused_field_mut() else {
+            return;
+        };
+        let before = field.contents();
+        textarea::paste(&mut field.value, &mut field.cursor_pos, text, multiline);
+        field.error = None;
+        self.follow_focus = true;
+        self.record_edit(idx, before, false);
+    }
+
     fn cursor_left(&mut self) {
-        if let Some(field) = self.focused_field_mut()
-            && field.cursor_pos > 0
-        {
-            field.cursor_pos -= 1;
+        if let Some(field) = self.focused_field_mut() {
+            textarea::left(&field.value, &mut field.cursor_pos);
         }
     }
 
     fn cursor_right(&mut self) {
-        if let Some(field) = self.focused_field_mut()
-            && field.cursor_pos < field.value.len()
-        {
-            field.cursor_pos += 1;
+        if let Some(field) = self.focused_field_mut() {
+            textarea::right(&field.value, &mut field.cursor_pos);
         }
     }
 
+    /// Move the cursor up a line in a multi-line field.
+    ///
+    /// Returns `false` if the focused field is not multi-line or the cursor
+    /// is already on its first line.
+    fn cursor_up(&mut self) -> bool {
+        self.is_multiline()
+            && self
+                .focused_field_mut()
+                .is_some_and(|field| textarea::up(&field.value, &mut field.cursor_pos))
+    }
+
+    /// Move the cursor down a line in a multi-line field.
+    ///
+    /// Returns `false` if the focused field is not multi-line or the cursor
+    /// is already on its last line.
+    fn cursor_down(&mut self) -> bool {
+        self.is_multiline()
+            && self
+                .focused_field_mut()
+                .is_some_and(|field| textarea::down(&field.value, &mut field.cursor_pos))
+    }
+
     fn toggle_bool(&mut self) {
         if let Some(field) = self.focused_field_mut()
             && matches!(field.kind, FieldKind::Bool)
@@ -1134,19 +1928,15 @@ impl FormState {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + variants.len() - 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + variants.len() - 1) % variants.len();
                 }
                 FieldKind::AnyOf {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + variants.len() - 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + variants.len() - 1) % variants.len();
                 }
                 _ => {}
             }
@@ -1161,19 +1951,15 @@ impl FormState {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + 1) % variants.len();
                 }
                 FieldKind::AnyOf {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + 1) % variants.len();
                 }
                 _ => {}
             }
@@ -1195,10 +1981,8 @@ impl FormState {
                     highlight,
                     selected,
                     ..
-                } => {
-                    if *highlight < selected.len() {
-                        selected[*highlight] = !selected[*highlight];
-                    }
+                } if *highlight < selected.len() => {
+                    selected[*highlight] = !selected[*highlight];
                 }
                 _ => {}
             }
@@ -1215,6 +1999,93 @@ impl FormState {
             .unwrap_or(false)
     }
 
+    /// Whether the focused field is the header of a map.
+    fn is_map_header(&self) -> bool {
+        !self.submit_focused
+            && self
+                .focused_field()
+                .is_some_and(|f| matches!(f.kind, FieldKind::Map { .. }))
+    }
+
+    /// Whether the focused field is a multi-line text field.
+    fn is_multiline(&self) -> bool {
+        !self.submit_focused
+            && self.focused_field().is_some_and(|f| {
+                matches!(
+                    f.kind,
+                    FieldKind::Text {
+                        multiline: true,
+                        ..
+                    }
+                )
+            })
+    }
+
+    /// Text of the focused field, if it is a text field.
+    pub(crate) fn focused_text(&self) -> Option<String> {
+        self.focused_field()
+            .filter(|f| matches!(f.kind, FieldKind::Text { .. }))
+            .map(|f| f.value.clone())
+    }
+
+    /// Replace the text of the focused multi-line field with the result of
+    /// an external edit, or show why the editor could not be used.
+    pub(crate) fn finish_external_edit(&mut self, result: io::Result<String>) {
+        let idx = self.focused_idx;
+        let Some(field) = self.focused_field_mut() else {
+            return;
+        };
+        let before = field.contents();
+        match result {
+            Ok(text) => {
+                field.cursor_pos = text.len();
+                field.value = text;
+                field.error = None;
+            }
+            Err(err) => field.error = Some(format!("Could not run editor: {err}")),
+        }
+        self.record_edit(idx, before, false);
+    }
+
+    /// Add the change to the field at `idx` since `before` to the undo
+    /// history. If the field did not change, the current typing run ends.
+    fn record_edit(&mut self, idx: usize, before: FieldContents, typing: bool) {
+        let after = self.fields[idx].contents();
+        if after.same_answer(&before) {
+            self.history.close_group();
+        } else {
+            self.history.record(idx, before, after, typing);
+        }
+    }
+
+    /// Undo the last edit and focus the field it changed.
+    fn undo(&mut self) {
+        if let Some(edit) = self.history.undo() {
+            let (idx, contents) = (edit.field, edit.before.clone());
+            self.restore_field(idx, contents);
+        }
+    }
+
+    /// Redo the last undone edit and focus the field it changed.
+    fn redo(&mut self) {
+        if let Some(edit) = self.history.redo() {
+            let (idx, contents) = (edit.field, edit.after.clone());
+            self.restore_field(idx, contents);
+        }
+    }
+
+    fn restore_field(&mut self, idx: usize, contents: FieldContents) {
+        self.fields[idx].restore(contents);
+        self.focused_idx = idx;
+        self.submit_focused = false;
+        self.reveal(idx);
+    }
+
+    /// Whether the focused field takes typed text.
+    fn is_typing(&self) -> bool {
+        !self.submit_focused && self.focused_field().is_some_and(FormField::is_typed)
+    }
+
     fn toggle_anyof(&mut self, idx: usize) {
         if let Some(field) = self.focused_field_mut()
             && let FieldKind::AnyOf { selected, .. } = &mut field.kind
@@ -1224,18 +2095,27 @@ impl FormState {
         }
     }
 
-    fn collect_responses(&self) -> Responses {
+    pub(crate) fn collect_responses(&self) -> Responses {
         let mut responses = Responses::new();
 
         for field in &self.fields {
             // Skip assumed fields and fields that are not visible
             // (except OneOf/AnyOf which always need their selection recorded)
             let dominated_by_variant = !matches!(field.visibility, VisibilityCondition::Always);
-            if field.assumed || (dominated_by_variant && !self.is_field_visible(field)) {
+            if field.assumed || (dominated_by_variant && !self.is_field_active(field)) {
                 continue;
             }
 
             match &field.kind {
+                _ if field.is_confirmation() => {}
+                FieldKind::Text {
+                    masked: Some(_), ..
+                } => {
+                    responses.insert(
+                        field.path.clone(),
+                        ResponseValue::Secret(SecretValue::new(field.value.as_str())),
+                    );
+                }
                 FieldKind::Text { .. } => {
                     responses.insert(
                         field.path.clone(),
@@ -1252,6 +2132,11 @@ impl FormState {
                         responses.insert(field.path.clone(), ResponseValue::Float(n));
                     }
                 }
+                FieldKind::Temporal { kind } => {
+                    if let Some(Ok(rv)) = kind.parse_temporal(&field.value) {
+                        responses.insert(field.path.clone(), rv);
+                    }
+                }
                 FieldKind::Bool => {
                     let b = field.value == "true";
                     responses.insert(field.path.clone(), ResponseValue::Bool(b));
@@ -1315,6 +2200,21 @@ impl FormState {
                             .insert(item_variant_path, ResponseValue::ChosenVariant(variant_idx));
                     }
                 }
+                FieldKind::Map { .. } => {
+                    let keys_path = field.path.child(MAP_KEYS_KEY);
+                    let keys = self
+                        .fields
+                        .iter()
+                        .filter(|key| {
+                            matches!(key.kind, FieldKind::MapKey)
+                                && is_inside(&key.path, &keys_path)
+                        })
+                        .map(|key| key.value.clone())
+                        .collect();
+                    responses.insert(keys_path, ResponseValue::StringList(keys));
+                }
+                // Keys are collected with their map
+                FieldKind::Section { .. } | FieldKind::MapKey | FieldKind::Lazy { .. } => {}
             }
         }
 
@@ -1328,15 +2228,53 @@ impl FormState {
         let responses = self.collect_responses();
         let mut has_errors = false;
 
-        // Collect visible field indices first (before mutable borrow)
+        // Collect active field indices first (before mutable borrow), including
+        // those in collapsed sections
         let visible_indices: Vec<usize> = self
             .fields
             .iter()
             .enumerate()
-            .filter(|(_, f)| self.is_field_visible(f))
+            .filter(|(_, f)| self.is_field_active(f))
             .map(|(i, _)| i)
             .collect();
 
+        // Repeat-entry fields must match the masked field above them
+        let mismatched: Vec<usize> = visible_indices
+            .iter()
+            .copied()
+            .filter(|&idx| {
+                self.fields[idx].is_confirmation()
+                    && self.fields[idx].value != self.fields[idx - 1].value
+            })
+            .collect();
+
+        // Map keys must be unique within their map and pass its key validator
+        let key_errors: Vec<(usize, String)> = visible_indices
+            .iter()
+            .copied()
+            .filter(|&idx| matches!(self.fields[idx].kind, FieldKind::MapKey))
+            .filter_map(|idx| {
+                let field = &self.fields[idx];
+                let keys_path = field.path.parent();
+                let others = self.fields[..idx]
+                    .iter()
+                    .filter(|other| {
+                        matches!(other.kind, FieldKind::MapKey) && other.path.parent() == keys_path
+                    })
+                    .map(|other| other.value.as_str());
+                MapQuestion::check_key(&field.value, others)
+                    .and_then(|()| {
+                        validate(
+                            &ResponseValue::String(field.value.clone()),
+                            &responses,
+                            &keys_path,
+                        )
+                    })
+                    .err()
+                    .map(|err| (idx, err))
+            })
+            .collect();
+
         // First pass: clear all errors and do basic type validation
         for idx in &visible_indices {
             let field = &mut self.fields[*idx];
@@ -1345,11 +2283,13 @@ impl FormState {
 
             // Basic validation (min/max, type parsing, required selections)
             match &field.kind {
-                FieldKind::OneOf { selected, .. } => {
-                    if selected.is_none() {
-                        field.error = Some("Please select an option".to_string());
-                        has_errors = true;
-                    }
+                FieldKind::OneOf { selected, .. } if selected.is_none() => {
+                    field.error = Some("Please select an option".to_string());
+                    has_errors = true;
+                }
+                FieldKind::Text { .. } if mismatched.contains(idx) => {
+                    field.error = Some(CONFIRMATION_MISMATCH.to_string());
+                    has_errors = true;
                 }
                 FieldKind::Int { min, max } => match field.value.parse::<i64>() {
                     Ok(n) => {
@@ -1391,6 +2331,25 @@ impl FormState {
                     }
                     _ => {}
                 },
+                FieldKind::Temporal { kind } if !field.value.is_empty() => {
+                    if let Some(Err(err)) = kind.parse_temporal(&field.value) {
+                        field.error = Some(err);
+                        has_errors = true;
+                    }
+                }
+                FieldKind::MapKey => {
+                    if let Some((_, err)) = key_errors.iter().find(|(key_idx, _)| key_idx == idx) {
+                        field.error = Some(err.clone());
+                        has_errors = true;
+                    }
+                }
+                // Placeholders left in the form are nested too deeply
+                FieldKind::Lazy { lazy } => {
+                    if let Err(err) = lazy.expand() {
+                        field.error = Some(err);
+                        has_errors = true;
+                    }
+                }
                 _ => {}
             }
         }
@@ -1409,7 +2368,7 @@ impl FormState {
         // Third pass: apply custom validation errors to visible fields
         for idx in &visible_indices {
             let field = &mut self.fields[*idx];
-            if field.error.is_some() {
+            if field.error.is_some() || field.is_confirmation() {
                 continue; // Skip if already has a basic validation error
             }
 
@@ -1425,6 +2384,7 @@ impl FormState {
                 if self.fields[*idx].error.is_some() {
                     self.focused_idx = *idx;
                     self.submit_focused = false;
+                    self.reveal(*idx);
                     break;
                 }
             }
@@ -1432,10 +2392,555 @@ impl FormState {
 
         !has_errors
     }
+
+    /// Switch the preview pane to its next format, or close it after the last one.
+    fn cycle_preview(&mut self) {
+        let mut modes = vec![PreviewMode::Tree];
+        #[cfg(feature = "serde")]
+        modes.extend([PreviewMode::Json, PreviewMode::Toml]);
+        if self.preview_formatter.is_some() {
+            modes.push(PreviewMode::Custom);
+        }
+        self.preview = match self.preview {
+            None => Some(PreviewMode::Tree),
+            Some(mode) => modes.into_iter().skip_while(|m| *m != mode).nth(1),
+        };
+    }
+
+    /// Text of the preview pane in the given format.
+    fn preview_text(&self, mode: PreviewMode) -> String {
+        let responses = self.collect_responses();
+        match (mode, &self.preview_formatter) {
+            (PreviewMode::Custom, Some(formatter)) => {
+                formatter.format(&redact_masked(&responses, &self.definition))
+            }
+            #[cfg(feature = "serde")]
+            (PreviewMode::Json, _) => ResponsePreview::new(&responses, &self.definition).to_json(),
+            #[cfg(feature = "serde")]
+            (PreviewMode::Toml, _) => ResponsePreview::new(&responses, &self.definition).to_toml(),
+            _ => ResponsePreview::new(&responses, &self.definition)
+                .lines()
+                .join("\n"),
+        }
+    }
+
+    /// Move focus to the first visible field.
+    pub(crate) fn start(&mut self) {
+        while self.focused_idx < self.fields.len()
+            && !self.is_field_visible_by_idx(self.focused_idx)
+        {
+            self.focused_idx += 1;
+        }
+    }
+
+    /// Handle a single key event, recording any edit it makes in the undo
+    /// history.
+    ///
+    /// Returns `true` once the form has been submitted or cancelled.
+    pub(crate) fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return false;
+        }
+
+        let idx = self.focused_idx;
+        let tracked = !self.submit_focused
+            && !matches!(
+                self.keymap.action_for(&key, self.is_typing()),
+                Some(Action::Undo | Action::Redo | Action::AddEntry | Action::RemoveEntry)
+            );
+        let before = self
+            .fields
+            .get(idx)
+            .filter(|_| tracked)
+            .map(|field| (field.contents(), field.is_typed()));
+
+        let done = self.dispatch_key(key, validate);
+        if let Some((before, typing)) = before {
+            self.record_edit(idx, before, typing);
+        }
+        self.expand_lazy_fields();
+        done
+    }
+
+    /// Act on a key press.
+    fn dispatch_key(
+        &mut self,
+        key: KeyEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> bool {
+        if self.show_help {
+            // Any key closes the help overlay
+            self.show_help = false;
+            return false;
+        }
+
+        self.follow_focus = true;
+
+        if self.search.is_some() {
+            self.handle_search_key(key);
+            return false;
+        }
+
+        match self.keymap.action_for(&key, self.is_typing()) {
+            Some(Action::Cancel) => {
+                self.cancelled = true;
+                return true;
+            }
+            Some(Action::Submit) => {
+                if self.validate_all(validate) {
+                    self.submitted = true;
+                    return true;
+                }
+                return false;
+            }
+            // Submit if on button, select option, or move to next field
+            Some(Action::Edit) => {
+                if self.submit_focused {
+                    if self.validate_all(validate) {
+                        self.submitted = true;
+                        return true;
+                    }
+                } else if self.is_selection_field() {
+                    self.select_option();
+                } else if self.is_map_header() {
+                    self.add_map_entry(self.focused_idx);
+                } else if !self.set_section_collapsed(|collapsed| !collapsed) {
+                    self.next_field();
+                }
+                return false;
+            }
+            Some(Action::NextField) => {
+                self.next_field();
+                return false;
+            }
+            Some(Action::PrevField) => {
+                self.prev_field();
+                return false;
+            }
+            Some(Action::NewLine) => {
+                if self.is_multiline() {
+                    self.handle_text_input('\n');
+                }
+                return false;
+            }
+            Some(Action::ExternalEditor) => {
+                self.editor_requested = self.is_multiline();
+                return false;
+            }
+            Some(Action::Reveal) => {
+                if !self.submit_focused
+                    && let Some(FormField {
+                        kind:
+                            FieldKind::Text {
+                                masked: Some(masking),
+                                ..
+                            },
+                        ..
+                    }) = self.focused_field_mut()
+                {
+                    masking.revealed = !masking.revealed;
+                }
+                return false;
+            }
+            // Navigate options, lines of multi-line text, or fields
+            Some(Action::MoveUp) => {
+                if self.is_selection_field() {
+                    self.option_up();
+                } else if !self.cursor_up() {
+                    self.prev_field();
+                }
+                return false;
+            }
+            Some(Action::MoveDown) => {
+                if self.is_selection_field() {
+                    self.option_down();
+                } else if !self.cursor_down() {
+                    self.next_field();
+                }
+                return false;
+            }
+            // Toggle bool, select OneOf option, toggle AnyOf option
+            Some(Action::Toggle) => {
+                if let Some(field) = self.focused_field() {
+                    match &field.kind {
+                        FieldKind::Bool => self.toggle_bool(),
+                        FieldKind::OneOf { .. } | FieldKind::AnyOf { .. } => {
+                            self.select_option();
+                        }
+                        FieldKind::Section { .. } => {
+                            self.set_section_collapsed(|collapsed| !collapsed);
+                        }
+                        FieldKind::Map { .. } => self.add_map_entry(self.focused_idx),
+                        _ => {}
+                    }
+                }
+                return false;
+            }
+            Some(Action::Help) => {
+                self.show_help = true;
+                return false;
+            }
+            Some(Action::Search) => {
+                self.search = Some(Search::new((self.focused_idx, self.submit_focused)));
+                return false;
+            }
+            Some(Action::NextInvalid) => {
+                self.focus_next_invalid(validate);
+                return false;
+            }
+            Some(Action::Preview) => {
+                self.cycle_preview();
+                return false;
+            }
+            Some(Action::Undo) => {
+                self.undo();
+                return false;
+            }
+            Some(Action::Redo) => {
+                self.redo();
+                return false;
+            }
+            Some(Action::AddEntry) => {
+                if let Some(map_idx) = self.focused_map() {
+                    self.add_map_entry(map_idx);
+                }
+                return false;
+            }
+            Some(Action::RemoveEntry) => {
+                self.remove_map_entry();
+                return false;
+            }
+            None => {}
+        }
+
+        match key.code {
+            // Left/Right: cursor movement in text fields, collapse/expand sections
+            KeyCode::Left if self.set_section_collapsed(|_| true) => {}
+            KeyCode::Right if self.set_section_collapsed(|_| false) => {}
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::ALT) => {
+                if let Some(field) = self.focused_field_mut() {
+                    textarea::word_left(&field.value, &mut field.cursor_pos);
+                }
+            }
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::ALT) => {
+                if let Some(field) = self.focused_field_mut() {
+                    textarea::word_right(&field.value, &mut field.cursor_pos);
+                }
+            }
+            KeyCode::Left => {
+                self.cursor_left();
+            }
+            KeyCode::Right => {
+                self.cursor_right();
+            }
+            // Number keys: quick toggle for AnyOf (1-9)
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if let Some(field) = self.focused_field() {
+                    match &field.kind {
+                        FieldKind::AnyOf { .. } => {
+                            let idx = c.to_digit(10).unwrap() as usize;
+                            if idx > 0 {
+                                self.toggle_anyof(idx - 1);
+                            }
+                        }
+                        _ => self.handle_text_input(c),
+                    }
+                } else {
+                    self.handle_text_input(c);
+                }
+            }
+            KeyCode::Char(c) => {
+                self.handle_text_input(c);
+            }
+            KeyCode::Backspace => {
+                self.handle_backspace();
+            }
+            KeyCode::Delete => {
+                self.handle_delete();
+            }
+            KeyCode::Home => {
+                if let Some(field) = self.focused_field_mut() {
+                    textarea::home(&field.value, &mut field.cursor_pos);
+                }
+            }
+            KeyCode::End => {
+                if let Some(field) = self.focused_field_mut() {
+                    textarea::end(&field.value, &mut field.cursor_pos);
+                }
+            }
+            KeyCode::PageDown => {
+                // Jump multiple fields down
+                for _ in 0..5 {
+                    self.next_field();
+                }
+            }
+            KeyCode::PageUp => {
+                // Jump multiple fields up
+                for _ in 0..5 {
+                    self.prev_field();
+                }
+            }
+            _ => {}
+        }
+
+        false
+    }
+
+    /// Handle a key while the search bar is open.
+    ///
+    /// Typing edits the query and jumps to the best match, Up/Down and
+    /// Tab/Shift+Tab cycle through the matches, Enter keeps the focus on the
+    /// current match and Esc returns to where the search started.
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => self.search = None,
+            KeyCode::Esc => self.cancel_search(),
+            KeyCode::Backspace if search.query.is_empty() => self.cancel_search(),
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.update_search();
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                search.next();
+                self.focus_search_match();
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                search.prev();
+                self.focus_search_match();
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                search.query.push(c);
+                self.update_search();
+            }
+            _ => {}
+        }
+    }
+
+    /// Close the search bar and restore the focus from before the search.
+    fn cancel_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            (self.focused_idx, self.submit_focused) = search.origin;
+        }
+    }
+
+    /// Recompute the matches for the current query and focus the best one.
+    ///
+    /// Fields match on their prompt or their response path; only prompt
+    /// matches are highlighted.
+    fn update_search(&mut self) {
+        let Some(query) = self.search.as_ref().map(|s| s.query.clone()) else {
+            return;
+        };
+        let mut matches: Vec<(i32, usize, Vec<usize>)> = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| self.is_field_visible(field))
+            .filter_map(|(idx, field)| {
+                let prompt = fuzzy_match(&query, &field.prompt);
+                let path = fuzzy_match(&query, field.path.as_str());
+                match (prompt, path) {
+                    (Some(prompt), Some(path)) if path.score > prompt.score => {
+                        Some((path.score, idx, Vec::new()))
+                    }
+                    (Some(prompt), _) => Some((prompt.score, idx, prompt.positions)),
+                    (None, Some(path)) => Some((path.score, idx, Vec::new())),
+                    (None, None) => None,
+                }
+            })
+            .collect();
+        matches.sort_by_key(|(score, idx, _)| (std::cmp::Reverse(*score), *idx));
+
+        if let Some(search) = &mut self.search {
+            search.matches = matches
+                .into_iter()
+                .map(|(_, idx, positions)| (idx, positions))
+                .collect();
+            search.current = 0;
+        }
+        self.focus_search_match();
+    }
+
+    /// Focus the current search match, or the original focus if nothing matches.
+    fn focus_search_match(&mut self) {
+        let Some(search) = &self.search else {
+            return;
+        };
+        match search.current_field() {
+            Some(idx) => {
+                self.focused_idx = idx;
+                self.submit_focused = false;
+            }
+            None => (self.focused_idx, self.submit_focused) = search.origin,
+        }
+    }
+
+    /// Validate the form and focus the next visible field with an error,
+    /// wrapping around to the first one.
+    fn focus_next_invalid(
+        &mut self,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) {
+        self.validate_all(validate);
+        let count = self.fields.len();
+        let start = if self.submit_focused {
+            0
+        } else {
+            self.focused_idx + 1
+        };
+        if let Some(idx) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&idx| {
+                self.fields[idx].error.is_some() && self.is_field_active(&self.fields[idx])
+            })
+        {
+            self.focused_idx = idx;
+            self.submit_focused = false;
+            self.reveal(idx);
+        }
+    }
+
+    /// Handle a single mouse event, using the layout of the last render.
+    ///
+    /// Returns `true` once the form has been submitted or cancelled.
+    pub(crate) fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> bool {
+        let position = Position::new(mouse.column, mouse.row);
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if self.show_help => {
+                self.show_help = false;
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.search = None;
+                if self.submit_area.contains(position) {
+                    self.submit_focused = true;
+                    self.follow_focus = true;
+                    if self.validate_all(validate) {
+                        self.submitted = true;
+                        return true;
+                    }
+                } else if self.cancel_area.contains(position) {
+                    self.cancelled = true;
+                    return true;
+                } else if let Some(&(idx, area)) = self
+                    .field_areas
+                    .iter()
+                    .find(|(_, area)| area.contains(position))
+                {
+                    let before = self.fields[idx].contents();
+                    self.click_field(idx, area, position);
+                    self.record_edit(idx, before, false);
+                    self.expand_lazy_fields();
+                }
+            }
+            MouseEventKind::ScrollDown => self.scroll_by(SCROLL_STEP as i32),
+            MouseEventKind::ScrollUp => self.scroll_by(-(SCROLL_STEP as i32)),
+            _ => {}
+        }
+
+        false
+    }
+
+    /// Focus a clicked field and act on the clicked part of it.
+    fn click_field(&mut self, idx: usize, area: Rect, position: Position) {
+        self.focused_idx = idx;
+        self.submit_focused = false;
+        self.follow_focus = true;
+
+        if self.set_section_collapsed(|collapsed| !collapsed) {
+            return;
+        }
+
+        let inner = area.inner(Margin::new(1, 1));
+        if !inner.contains(position) {
+            // Border or title: just focus
+            return;
+        }
+        let column = (position.x - inner.x) as usize;
+        let row = (position.y - inner.y) as usize;
+        let scroll = match self.textarea {
+            (owner, state) if owner == idx => state.scroll,
+            _ => 0,
+        };
+
+        let Some(field) = self.focused_field_mut() else {
+            return;
+        };
+        match &mut field.kind {
+            FieldKind::Text {
+                multiline: true, ..
+            } => {
+                field.cursor_pos =
+                    textarea::offset_at(&field.value, inner.width, scroll, row, column);
+            }
+            FieldKind::Text {
+                masked: Some(Masking {
+                    revealed: false, ..
+                }),
+                ..
+            } => field.cursor_pos = field.value.len(),
+            FieldKind::Text { .. }
+            | FieldKind::Int { .. }
+            | FieldKind::Float { .. }
+            | FieldKind::Temporal { .. }
+            | FieldKind::List { .. }
+            | FieldKind::MapKey => {
+                field.cursor_pos = textarea::offset_at(&field.value, u16::MAX, 0, 0, column);
+            }
+            FieldKind::Bool => self.toggle_bool(),
+            FieldKind::Section { .. } | FieldKind::Map { .. } | FieldKind::Lazy { .. } => {}
+            FieldKind::OneOf {
+                variants,
+                highlight,
+                ..
+            }
+            | FieldKind::AnyOf {
+                variants,
+                highlight,
+                ..
+            } => {
+                if row < variants.len() {
+                    *highlight = row;
+                    self.select_option();
+                }
+            }
+        }
+    }
+
+    /// Scroll the field area by `delta` rows without moving focus.
+    fn scroll_by(&mut self, delta: i32) {
+        let max = self
+            .total_content_height()
+            .saturating_sub(self.viewport_height);
+        self.scroll_offset = (self.scroll_offset as i32 + delta).clamp(0, max as i32) as u16;
+        self.follow_focus = false;
+    }
 }
 
-fn draw_form(frame: &mut Frame, state: &mut FormState) {
-    let area = frame.area();
+/// Rows scrolled per mouse-wheel step.
+const SCROLL_STEP: u16 = 3;
+
+/// Width of the cancel button next to the submit button.
+const CANCEL_BUTTON_WIDTH: u16 = 12;
+
+/// Render the whole form into `area`, recording the text cursor position in `state`.
+pub(crate) fn render_form(area: Rect, buf: &mut Buffer, state: &mut FormState) {
+    state.cursor = None;
     let theme = state.theme.clone();
 
     // Main layout
@@ -1466,17 +2971,34 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
                 .borders(Borders::BOTTOM)
                 .border_style(Style::default().fg(theme.border)),
         );
-    frame.render_widget(title, chunks[0]);
+    title.render(chunks[0], buf);
+
+    // Result preview pane on the right, if open and there is room
+    let mut form_area = chunks[1];
+    if let Some(mode) = state.preview
+        && form_area.width >= MIN_PREVIEW_SPLIT_WIDTH
+    {
+        let [fields, preview] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(form_area);
+        form_area = fields;
+        render_preview(preview, buf, mode, &state.preview_text(mode), &theme);
+    }
 
     // Form content area - reserve space for scrollbar on the right
-    let form_area = chunks[1];
     let content_width = form_area.width.saturating_sub(2); // 1 for left margin, 1 for scrollbar
     let viewport_height = form_area.height;
 
-    // Adjust scroll to keep focused field visible
-    state.adjust_scroll(viewport_height);
+    // Adjust scroll to keep focused field visible, unless the user scrolled away
+    if state.follow_focus {
+        state.adjust_scroll(viewport_height);
+    }
 
     let total_height = state.total_content_height();
+    state.scroll_offset = state
+        .scroll_offset
+        .min(total_height.saturating_sub(viewport_height));
+    state.viewport_height = viewport_height;
     let scroll_offset = state.scroll_offset;
 
     // Collect visible fields (based on visibility conditions, not just assumed)
@@ -1489,7 +3011,13 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
 
     // Render fields with scroll offset
     let mut virtual_y: u16 = 0;
+    let mut cursor = None;
+    let mut field_areas = Vec::new();
     let mut is_first_visible = true;
+    let mut textarea = match state.textarea {
+        (owner, textarea) if owner == state.focused_idx => textarea,
+        _ => TextAreaState::default(),
+    };
     for (field_idx, field) in visible_fields.iter() {
         // Field is only focused if it's the focused index AND the submit button is not focused
         let is_focused = !state.submit_focused && *field_idx == state.focused_idx;
@@ -1527,27 +3055,78 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
         let visible_height = (field_height_base - clip_top).min(available_height);
 
         if visible_height > 0 {
+            let indent = (state.section_depth(field) * SECTION_INDENT).min(content_width / 2);
             let field_area = Rect {
-                x: form_area.x + 1,
+                x: form_area.x + 1 + indent,
                 y: form_area.y + visible_top,
-                width: content_width,
+                width: content_width - indent,
                 height: visible_height,
             };
 
-            // Only draw if we have the full field height (to avoid partial rendering issues)
-            if clip_top == 0 && visible_height >= field_height_base {
-                draw_field(frame, field, field_area, is_focused, &theme);
+            // Draw fields that are fully visible or partially visible at the
+            // bottom, but skip fields that are clipped at the top (they look weird)
+            if clip_top == 0
+                && let FieldKind::Section { collapsed } = field.kind
+            {
+                let matched = state.search.as_ref().and_then(|s| s.highlights(*field_idx));
+                let progress = state.section_progress(field);
+                draw_section(
+                    buf,
+                    &field.prompt,
+                    field.help.as_deref(),
+                    collapsed,
+                    progress,
+                    field_area,
+                    is_focused,
+                    matched,
+                    &theme,
+                );
+                field_areas.push((*field_idx, field_area));
+            } else if clip_top == 0 && matches!(field.kind, FieldKind::Map { .. }) {
+                let matched = state.search.as_ref().and_then(|s| s.highlights(*field_idx));
+                draw_map_header(
+                    buf,
+                    &field.prompt,
+                    field.help.as_deref(),
+                    state.map_len(field),
+                    state.keymap.hint(Action::AddEntry).as_deref(),
+                    field_area,
+                    is_focused,
+                    matched,
+                    &theme,
+                );
+                field_areas.push((*field_idx, field_area));
             } else if clip_top == 0 {
-                // Field is partially visible at the bottom - draw what we can
-                draw_field(frame, field, field_area, is_focused, &theme);
+                let mut field_textarea = if is_focused {
+                    textarea
+                } else {
+                    TextAreaState::default()
+                };
+                let matched = state.search.as_ref().and_then(|s| s.highlights(*field_idx));
+                cursor = draw_field(
+                    buf,
+                    field,
+                    field_area,
+                    is_focused,
+                    matched,
+                    &theme,
+                    &mut field_textarea,
+                )
+                .or(cursor);
+                if is_focused {
+                    textarea = field_textarea;
+                }
+                field_areas.push((*field_idx, field_area));
             }
-            // Skip fields that are clipped at the top (they look weird)
         }
 
         is_first_visible = false;
 
         virtual_y += field_height_with_spacing;
     }
+    state.cursor = cursor;
+    state.field_areas = field_areas;
+    state.textarea = (state.focused_idx, textarea);
 
     // Draw scrollbar if content exceeds viewport
     if total_height > viewport_height {
@@ -1568,7 +3147,7 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
             .track_symbol(Some("│"))
             .thumb_symbol("█");
 
-        frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+        scrollbar.render(scrollbar_area, buf, &mut scrollbar_state);
     }
 
     // Submit button
@@ -1599,24 +3178,308 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
                     theme.border
                 })),
         );
-    frame.render_widget(submit_button, chunks[2]);
+    let buttons = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(CANCEL_BUTTON_WIDTH)])
+        .split(chunks[2]);
+    submit_button.render(buttons[0], buf);
+    state.submit_area = buttons[0];
+
+    let cancel_button = Paragraph::new("Cancel")
+        .style(Style::default().fg(theme.text))
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    cancel_button.render(buttons[1], buf);
+    state.cancel_area = buttons[1];
+
+    // Help bar, or the search bar while searching
+    if let Some(search) = &state.search {
+        let status = match search.matches.len() {
+            0 => "no matches".to_string(),
+            n => format!("{}/{n}", search.current + 1),
+        };
+        let line = Line::from(vec![
+            Span::styled("/", Style::default().fg(theme.highlight)),
+            Span::styled(search.query.clone(), Style::default().fg(theme.text)),
+            Span::styled(
+                format!("  {status}  ↑/↓: Next match  Enter: Jump  Esc: Cancel"),
+                Style::default().fg(theme.border),
+            ),
+        ]);
+        Paragraph::new(line).render(chunks[3], buf);
+        state.cursor = Some(Position::new(
+            chunks[3].x + 1 + textarea::display_width(&search.query, false) as u16,
+            chunks[3].y,
+        ));
+    } else {
+        let in_map = state.focused_map().is_some();
+        let help_text = status_hints(&state.keymap, state.is_multiline(), in_map);
+        let help = Paragraph::new(help_text).style(Style::default().fg(theme.border));
+        help.render(chunks[3], buf);
+    }
 
-    // Help bar
-    let help_text = "Tab: Next  ↑/↓: Navigate  Space/Enter: Select  Esc: Cancel";
-    let help = Paragraph::new(help_text).style(Style::default().fg(theme.border));
-    frame.render_widget(help, chunks[3]);
+    if state.show_help {
+        render_help_overlay(area, buf, &state.keymap, &theme);
+    }
+}
+
+/// Narrowest form area that is split to show the preview pane.
+const MIN_PREVIEW_SPLIT_WIDTH: u16 = 60;
+
+/// Draw the result preview pane.
+fn render_preview(area: Rect, buf: &mut Buffer, mode: PreviewMode, text: &str, theme: &Theme) {
+    let title = match mode {
+        PreviewMode::Tree => " Preview ",
+        #[cfg(feature = "serde")]
+        PreviewMode::Json => " Preview (JSON) ",
+        #[cfg(feature = "serde")]
+        PreviewMode::Toml => " Preview (TOML) ",
+        PreviewMode::Custom => " Preview (custom) ",
+    };
+    Paragraph::new(text.to_string())
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::LEFT)
+                .border_style(Style::default().fg(theme.border))
+                .title(title)
+                .title_style(Style::default().fg(theme.primary)),
+        )
+        .render(area, buf);
+}
+
+/// Title of a field box, with the characters matched by a search highlighted.
+fn field_title(prompt: &str, matched: Option<&[usize]>, theme: &Theme) -> Line<'static> {
+    let Some(matched) = matched else {
+        return Line::from(format!(" {prompt} "));
+    };
+    let match_style = Style::default()
+        .fg(theme.highlight)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let spans = prompt.chars().enumerate().map(|(idx, c)| {
+        if matched.contains(&idx) {
+            Span::styled(c.to_string(), match_style)
+        } else {
+            Span::raw(c.to_string())
+        }
+    });
+    Line::from_iter(
+        std::iter::once(Span::raw(" "))
+            .chain(spans)
+            .chain(std::iter::once(Span::raw(" "))),
+    )
+}
+
+/// Short key hints for the status bar, based on the active key map.
+///
+/// Multi-line text fields show their editing keys instead of option navigation,
+/// and maps show how to add and remove entries.
+fn status_hints(keymap: &KeyMap, multiline: bool, in_map: bool) -> String {
+    let navigate = match (keymap.hint(Action::MoveUp), keymap.hint(Action::MoveDown)) {
+        (Some(up), Some(down)) => Some(format!("{up}/{down}")),
+        (up, down) => up.or(down),
+    };
+    let select = match (keymap.hint(Action::Toggle), keymap.hint(Action::Edit)) {
+        (Some(toggle), Some(edit)) => Some(format!("{toggle}/{edit}")),
+        (toggle, edit) => toggle.or(edit),
+    };
+
+    let context = if multiline {
+        [
+            (keymap.hint(Action::NewLine), "New line"),
+            (keymap.hint(Action::ExternalEditor), "Editor"),
+        ]
+    } else if in_map {
+        [
+            (keymap.hint(Action::AddEntry), "Add entry"),
+            (keymap.hint(Action::RemoveEntry), "Remove entry"),
+        ]
+    } else {
+        [(navigate, "Navigate"), (select, "Select")]
+    };
+
+    [(keymap.hint(Action::NextField), "Next")]
+        .into_iter()
+        .chain(context)
+        .chain([
+            (keymap.hint(Action::Submit), "Submit"),
+            (keymap.hint(Action::Cancel), "Cancel"),
+            (keymap.hint(Action::Help), "Help"),
+        ])
+        .filter_map(|(key, label)| key.map(|key| format!("{key}: {label}")))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Draw a centered overlay listing all active key bindings.
+fn render_help_overlay(area: Rect, buf: &mut Buffer, keymap: &KeyMap, theme: &Theme) {
+    let lines = keymap.help_lines();
+    let key_width = lines
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = lines
+        .iter()
+        .map(|(keys, description)| {
+            ListItem::new(format!("{keys:<key_width$}  {description}"))
+                .style(Style::default().fg(theme.text))
+        })
+        .collect();
+
+    let width = (key_width as u16 + 30).min(area.width);
+    let height = (items.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    Clear.render(popup, buf);
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary))
+            .title(" Key bindings ")
+            .title_style(Style::default().fg(theme.highlight)),
+    );
+    Widget::render(list, popup, buf);
 }
 
 /// Extra vertical space before top-level fields (section spacing)
 const TOP_LEVEL_SPACING: u16 = 1;
 
+/// Columns each level of section nesting is indented by.
+const SECTION_INDENT: u16 = 2;
+
+/// Draw the one-line header of a section with its progress badge.
+#[allow(clippy::too_many_arguments)]
+fn draw_section(
+    buf: &mut Buffer,
+    prompt: &str,
+    help: Option<&str>,
+    collapsed: bool,
+    progress: SectionProgress,
+    area: Rect,
+    is_focused: bool,
+    matched: Option<&[usize]>,
+    theme: &Theme,
+) {
+    let title_style = if is_focused {
+        Style::default()
+            .fg(theme.highlight)
+            .bg(theme.selected_bg)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD)
+    };
+    let badge_color = if progress.errors > 0 {
+        theme.error
+    } else if progress.answered == progress.total {
+        theme.success
+    } else {
+        theme.border
+    };
+
+    let marker = if collapsed { "▸" } else { "▾" };
+    let mut spans = vec![Span::raw(marker)];
+    spans.extend(field_title(prompt, matched, theme).spans);
+    spans.push(Span::styled(
+        progress.badge(),
+        Style::default().fg(badge_color),
+    ));
+    if let Some(help) = help {
+        spans.push(help_span(help, theme));
+    }
+    Paragraph::new(Line::from(spans).style(title_style)).render(area, buf);
+}
+
+/// Draw the one-line header of a map with its number of entries and the key
+/// that adds one.
+#[allow(clippy::too_many_arguments)]
+fn draw_map_header(
+    buf: &mut Buffer,
+    prompt: &str,
+    help: Option<&str>,
+    entries: usize,
+    add_hint: Option<&str>,
+    area: Rect,
+    is_focused: bool,
+    matched: Option<&[usize]>,
+    theme: &Theme,
+) {
+    let title_style = if is_focused {
+        Style::default()
+            .fg(theme.highlight)
+            .bg(theme.selected_bg)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD)
+    };
+    let count = match entries {
+        1 => "1 entry".to_string(),
+        n => format!("{n} entries"),
+    };
+
+    let mut spans = vec![Span::raw("▾")];
+    spans.extend(field_title(prompt, matched, theme).spans);
+    spans.push(Span::styled(count, Style::default().fg(theme.border)));
+    if let Some(key) = add_hint {
+        spans.push(Span::styled(
+            format!("  {key}: Add entry"),
+            Style::default().fg(theme.border),
+        ));
+    }
+    if let Some(help) = help {
+        spans.push(help_span(help, theme));
+    }
+    Paragraph::new(Line::from(spans).style(title_style)).render(area, buf);
+}
+
+/// Help text folded onto one line, to fit a border or a header.
+fn help_line(help: &str) -> String {
+    help.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The help text trailing a one-line header.
+fn help_span(help: &str, theme: &Theme) -> Span<'static> {
+    Span::styled(
+        format!("  {}", help_line(help)),
+        Style::default()
+            .fg(theme.border)
+            .remove_modifier(Modifier::BOLD),
+    )
+}
+
+/// Visible text rows of a multi-line field; longer text scrolls.
+const MULTILINE_ROWS: u16 = 4;
+
 fn get_field_height(field: &FormField, include_spacing: bool) -> u16 {
     let base_height = match &field.kind {
         FieldKind::Text {
             multiline: true, ..
+        } => 2 + MULTILINE_ROWS,
+        FieldKind::Text {
+            masked:
+                Some(Masking {
+                    strength_meter: true,
+                    ..
+                }),
+            ..
         } => 4,
         FieldKind::OneOf { variants, .. } => 2 + variants.len() as u16,
         FieldKind::AnyOf { variants, .. } => 2 + variants.len() as u16,
+        FieldKind::Section { .. } | FieldKind::Map { .. } => 1,
         _ => 3,
     };
     // Add spacing before top-level fields (except the first one)
@@ -1627,7 +3490,63 @@ fn get_field_height(field: &FormField, include_spacing: bool) -> u16 {
     }
 }
 
-fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool, theme: &Theme) {
+/// The strength meter shown under a masked field: a bar and a label, fitted to `width`.
+fn strength_meter_line(password: &str, width: u16, theme: &Theme) -> Line<'static> {
+    let strength = PasswordStrength::of(password);
+    let color = match strength {
+        PasswordStrength::Weak => theme.error,
+        PasswordStrength::Fair => theme.highlight,
+        PasswordStrength::Good | PasswordStrength::Strong => theme.success,
+    };
+    let bar_width = usize::from(width.saturating_sub(8)).min(20);
+    let filled = (strength.fraction() * bar_width as f32).round() as usize;
+    Line::from(vec![
+        Span::styled("━".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "─".repeat(bar_width - filled),
+            Style::default().fg(theme.border),
+        ),
+        Span::styled(format!(" {}", strength.label()), Style::default().fg(color)),
+    ])
+}
+
+/// Draw a single field, returning the text cursor position if it has one.
+///
+/// `matched` holds the prompt characters to highlight for a search match.
+/// Multi-line fields scroll according to `textarea`, which is updated to keep
+/// the cursor in view.
+fn draw_field(
+    buf: &mut Buffer,
+    field: &FormField,
+    area: Rect,
+    is_focused: bool,
+    matched: Option<&[usize]>,
+    theme: &Theme,
+    textarea: &mut TextAreaState,
+) -> Option<Position> {
+    let mut cursor = None;
+
+    // The strength meter takes the row below the box
+    let mut area = area;
+    if let FieldKind::Text {
+        masked: Some(Masking {
+            strength_meter: true,
+            ..
+        }),
+        ..
+    } = field.kind
+        && area.height > 3
+    {
+        let [field_area, meter_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).areas(area);
+        area = field_area;
+        if !field.value.is_empty() {
+            let meter_area = meter_area.inner(Margin::new(1, 0));
+            Paragraph::new(strength_meter_line(&field.value, meter_area.width, theme))
+                .render(meter_area, buf);
+        }
+    }
+
     let border_color = if field.error.is_some() {
         theme.error
     } else if is_focused {
@@ -1636,46 +3555,102 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
         theme.border
     };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
-        .title(format!(" {} ", field.prompt))
+        .title(field_title(&field.prompt, matched, theme))
         .title_style(Style::default().fg(if is_focused {
             theme.highlight
         } else {
             theme.text
         }));
 
+    // Help goes on the bottom border, unless an error takes it. Options
+    // explain themselves while highlighted.
+    let option_help = match &field.kind {
+        FieldKind::OneOf {
+            variants,
+            highlight,
+            ..
+        }
+        | FieldKind::AnyOf {
+            variants,
+            highlight,
+            ..
+        } if is_focused => variants.get(*highlight).and_then(|v| v.help.as_deref()),
+        _ => None,
+    };
+    if field.error.is_none()
+        && let Some(help) = option_help.or(field.help.as_deref())
+    {
+        block = block.title_bottom(Span::styled(
+            format!(" {} ", help_line(help)),
+            Style::default().fg(theme.border),
+        ));
+    }
+
     let inner = block.inner(area);
-    frame.render_widget(block, area);
+    block.render(area, buf);
 
     match &field.kind {
+        FieldKind::Text {
+            multiline: true, ..
+        } => {
+            TextArea::new(&field.value, field.cursor_pos)
+                .style(Style::default().fg(theme.text))
+                .render(inner, buf, textarea);
+            if is_focused {
+                cursor = textarea.cursor;
+            }
+        }
         FieldKind::Text { masked, .. } => {
-            let display_text = if *masked {
-                "●".repeat(field.value.len())
+            let masked = masked.is_some_and(|masking| !masking.revealed);
+            let display_text = if masked {
+                textarea::mask(&field.value)
             } else {
                 field.value.clone()
             };
             let text = Paragraph::new(display_text).style(Style::default().fg(theme.text));
-            frame.render_widget(text, inner);
+            text.render(inner, buf);
 
             if is_focused {
-                let cursor_x = inner.x + field.cursor_pos as u16;
+                let column = textarea::display_width(&field.value[..field.cursor_pos], masked);
+                let cursor_x = inner.x + column as u16;
                 let cursor_y = inner.y;
                 if cursor_x < inner.x + inner.width {
-                    frame.set_cursor_position((cursor_x, cursor_y));
+                    cursor = Some(Position::new(cursor_x, cursor_y));
                 }
             }
         }
         FieldKind::Int { .. } | FieldKind::Float { .. } => {
             let text = Paragraph::new(field.value.clone()).style(Style::default().fg(theme.text));
-            frame.render_widget(text, inner);
+            text.render(inner, buf);
+
+            if is_focused {
+                let column = textarea::display_width(&field.value[..field.cursor_pos], false);
+                let cursor_x = inner.x + column as u16;
+                let cursor_y = inner.y;
+                if cursor_x < inner.x + inner.width {
+                    cursor = Some(Position::new(cursor_x, cursor_y));
+                }
+            }
+        }
+        FieldKind::Temporal { kind } => {
+            // Show the expected format until something is typed
+            let text = if field.value.is_empty() {
+                Paragraph::new(kind.temporal_format().unwrap_or_default())
+                    .style(Style::default().fg(theme.border))
+            } else {
+                Paragraph::new(field.value.clone()).style(Style::default().fg(theme.text))
+            };
+            text.render(inner, buf);
 
             if is_focused {
-                let cursor_x = inner.x + field.cursor_pos as u16;
+                let column = textarea::display_width(&field.value[..field.cursor_pos], false);
+                let cursor_x = inner.x + column as u16;
                 let cursor_y = inner.y;
                 if cursor_x < inner.x + inner.width {
-                    frame.set_cursor_position((cursor_x, cursor_y));
+                    cursor = Some(Position::new(cursor_x, cursor_y));
                 }
             }
         }
@@ -1684,17 +3659,18 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
             let checkbox = if checked { "[✓]" } else { "[ ]" };
             let text = Paragraph::new(format!("{} Yes", checkbox))
                 .style(Style::default().fg(if checked { theme.success } else { theme.text }));
-            frame.render_widget(text, inner);
+            text.render(inner, buf);
         }
-        FieldKind::List { .. } => {
+        FieldKind::List { .. } | FieldKind::MapKey => {
             let text = Paragraph::new(field.value.clone()).style(Style::default().fg(theme.text));
-            frame.render_widget(text, inner);
+            text.render(inner, buf);
 
             if is_focused {
-                let cursor_x = inner.x + field.cursor_pos as u16;
+                let column = textarea::display_width(&field.value[..field.cursor_pos], false);
+                let cursor_x = inner.x + column as u16;
                 let cursor_y = inner.y;
                 if cursor_x < inner.x + inner.width {
-                    frame.set_cursor_position((cursor_x, cursor_y));
+                    cursor = Some(Position::new(cursor_x, cursor_y));
                 }
             }
         }
@@ -1727,7 +3703,7 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
                 })
                 .collect();
             let list = List::new(items);
-            frame.render_widget(list, inner);
+            Widget::render(list, inner, buf);
         }
         FieldKind::AnyOf {
             variants,
@@ -1755,8 +3731,12 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
                 })
                 .collect();
             let list = List::new(items);
-            frame.render_widget(list, inner);
+            Widget::render(list, inner, buf);
         }
+        // Headers are drawn by `draw_section` and `draw_map_header`
+        FieldKind::Section { .. } | FieldKind::Map { .. } => {}
+        // Only its error is shown
+        FieldKind::Lazy { .. } => {}
     }
 
     // Show error if any
@@ -1771,9 +3751,11 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
                 width: area.width.saturating_sub(2),
                 height: 1,
             };
-            frame.render_widget(error_text, error_area);
+            error_text.render(error_area, buf);
         }
     }
+
+    cursor
 }
 
 impl SurveyBackend for RatatuiFormBackend {
@@ -1785,170 +3767,53 @@ impl SurveyBackend for RatatuiFormBackend {
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<Responses, Self::Error> {
         let mut terminal = self.setup_terminal()?;
-        let mut state = FormState::new(definition, self.theme.clone(), self.title.clone());
-
-        // Skip to first visible field
-        while state.focused_idx < state.fields.len()
-            && !state.is_field_visible_by_idx(state.focused_idx)
-        {
-            state.focused_idx += 1;
-        }
-
-        loop {
-            terminal.draw(|frame| draw_form(frame, &mut state))?;
-
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
+        let mut state = SurveyFormState::new(definition)
+            .with_title(self.title.clone())
+            .with_theme(self.theme.clone())
+            .with_keymap(self.keymap.clone())
+            .with_preview(self.preview);
+        state.form.preview_formatter = self.preview_formatter.clone();
+
+        let result = loop {
+            terminal.draw(|frame| {
+                frame.render_stateful_widget(SurveyWidget, frame.area(), &mut state);
+                if let Some(position) = state.cursor_position() {
+                    frame.set_cursor_position(position);
                 }
+            })?;
 
-                match key.code {
-                    KeyCode::Esc => {
-                        state.cancelled = true;
-                        break;
-                    }
-                    // Ctrl+Enter or F10 to submit the form
-                    KeyCode::Enter if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        if state.validate_all(validate) {
-                            state.submitted = true;
-                            break;
-                        }
-                    }
-                    KeyCode::F(10) => {
-                        if state.validate_all(validate) {
-                            state.submitted = true;
-                            break;
-                        }
-                    }
-                    // Enter: submit if on button, select option, or move to next field
-                    KeyCode::Enter => {
-                        if state.submit_focused {
-                            if state.validate_all(validate) {
-                                state.submitted = true;
-                                break;
-                            }
-                        } else if state.is_selection_field() {
-                            state.select_option();
-                        } else {
-                            state.next_field();
-                        }
-                    }
-                    // Shift+Tab: previous field
-                    KeyCode::BackTab | KeyCode::Tab
-                        if key.modifiers.contains(KeyModifiers::SHIFT) =>
-                    {
-                        state.prev_field();
-                    }
-                    // Tab: next field
-                    KeyCode::Tab => {
-                        state.next_field();
-                    }
-                    // Up/Down: navigate options or fields
-                    KeyCode::Up => {
-                        if state.is_selection_field() {
-                            state.option_up();
-                        } else {
-                            state.prev_field();
-                        }
-                    }
-                    KeyCode::Down => {
-                        if state.is_selection_field() {
-                            state.option_down();
-                        } else {
-                            state.next_field();
-                        }
-                    }
-                    // Ctrl+arrows: navigate between fields
-                    KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        state.prev_field();
-                    }
-                    KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        state.next_field();
-                    }
-                    // Left/Right: cursor movement in text fields
-                    KeyCode::Left => {
-                        state.cursor_left();
-                    }
-                    KeyCode::Right => {
-                        state.cursor_right();
-                    }
-                    // Space: toggle bool, select OneOf option, toggle AnyOf option
-                    KeyCode::Char(' ') => {
-                        if let Some(field) = state.focused_field() {
-                            match &field.kind {
-                                FieldKind::Bool => state.toggle_bool(),
-                                FieldKind::OneOf { .. } | FieldKind::AnyOf { .. } => {
-                                    state.select_option();
-                                }
-                                _ => state.handle_text_input(' '),
-                            }
-                        }
-                    }
-                    // Number keys: quick toggle for AnyOf (1-9)
-                    KeyCode::Char(c) if c.is_ascii_digit() => {
-                        if let Some(field) = state.focused_field() {
-                            match &field.kind {
-                                FieldKind::AnyOf { .. } => {
-                                    let idx = c.to_digit(10).unwrap() as usize;
-                                    if idx > 0 {
-                                        state.toggle_anyof(idx - 1);
-                                    }
-                                }
-                                _ => state.handle_text_input(c),
-                            }
-                        } else {
-                            state.handle_text_input(c);
-                        }
-                    }
-                    KeyCode::Char(c) => {
-                        state.handle_text_input(c);
-                    }
-                    KeyCode::Backspace => {
-                        state.handle_backspace();
-                    }
-                    KeyCode::Delete => {
-                        state.handle_delete();
-                    }
-                    KeyCode::Home => {
-                        if let Some(field) = state.focused_field_mut() {
-                            field.cursor_pos = 0;
-                        }
-                    }
-                    KeyCode::End => {
-                        if let Some(field) = state.focused_field_mut() {
-                            field.cursor_pos = field.value.len();
-                        }
-                    }
-                    KeyCode::PageDown => {
-                        // Jump multiple fields down
-                        for _ in 0..5 {
-                            state.next_field();
-                        }
-                    }
-                    KeyCode::PageUp => {
-                        // Jump multiple fields up
-                        for _ in 0..5 {
-                            state.prev_field();
-                        }
-                    }
-                    _ => {}
-                }
+            let event = match event::read()? {
+                Event::Key(key) => state.handle_key_event_with(key, validate),
+                Event::Mouse(mouse) if self.mouse => state.handle_mouse_event_with(mouse, validate),
+                Event::Paste(text) => state.handle_paste_event(&text),
+                _ => FormEvent::Continue,
+            };
+            match event {
+                FormEvent::Continue => {}
+                FormEvent::Submitted(responses) => break Ok(responses),
+                FormEvent::Cancelled => break Err(RatatuiFormError::Cancelled),
             }
-        }
 
-        self.restore_terminal(&mut terminal)?;
+            if let Some(text) = state.take_editor_request() {
+                // Hand the terminal to the editor, then take it back
+                self.restore_terminal(&mut terminal)?;
+                let result = edit_externally(&text);
+                terminal = self.setup_terminal()?;
+                terminal.clear()?;
+                state.finish_external_edit(result);
+            }
+        };
 
-        if state.cancelled {
-            return Err(RatatuiFormError::Cancelled);
-        }
+        self.restore_terminal(&mut terminal)?;
 
-        Ok(state.collect_responses())
+        result
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::style::Color;
 
     #[test]
     fn backend_creation() {
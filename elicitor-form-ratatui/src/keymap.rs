@@ -0,0 +1,377 @@
+//! Configurable key bindings for the form.
+
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A named form action that can be bound to keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Move focus to the next field.
+    NextField,
+    /// Move focus to the previous field.
+    PrevField,
+    /// Move up: previous option in a selection field, otherwise previous field.
+    MoveUp,
+    /// Move down: next option in a selection field, otherwise next field.
+    MoveDown,
+    /// Toggle a checkbox, the highlighted option or a section.
+    Toggle,
+    /// Activate the focused item: choose the highlighted option, press the
+    /// focused button, or move on from a text field.
+    Edit,
+    /// Start a new line in a multi-line text field.
+    NewLine,
+    /// Edit a multi-line text field in `$VISUAL` or `$EDITOR`.
+    ExternalEditor,
+    /// Show or hide the text of the focused masked field.
+    Reveal,
+    /// Search field prompts and jump to a match.
+    Search,
+    /// Validate the form and focus the next field with an error.
+    NextInvalid,
+    /// Cycle the result preview pane: tree, JSON, TOML, custom format, hidden.
+    Preview,
+    /// Undo the last edit and focus the field it changed.
+    Undo,
+    /// Redo the last undone edit.
+    Redo,
+    /// Add an entry to the focused map.
+    AddEntry,
+    /// Remove the focused entry from its map.
+    RemoveEntry,
+    /// Validate and submit the form.
+    Submit,
+    /// Cancel the form.
+    Cancel,
+    /// Show or hide the key binding overlay.
+    Help,
+}
+
+impl Action {
+    /// All actions, in the order they are listed in the help overlay.
+    pub const ALL: [Action; 19] = [
+        Action::NextField,
+        Action::PrevField,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::Toggle,
+        Action::Edit,
+        Action::NewLine,
+        Action::ExternalEditor,
+        Action::Reveal,
+        Action::Search,
+        Action::NextInvalid,
+        Action::Preview,
+        Action::Undo,
+        Action::Redo,
+        Action::AddEntry,
+        Action::RemoveEntry,
+        Action::Submit,
+        Action::Cancel,
+        Action::Help,
+    ];
+
+    /// Short human-readable description, used in the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::NextField => "Next field",
+            Action::PrevField => "Previous field",
+            Action::MoveUp => "Previous option / field",
+            Action::MoveDown => "Next option / field",
+            Action::Toggle => "Toggle",
+            Action::Edit => "Select / confirm",
+            Action::NewLine => "New line (multi-line text)",
+            Action::ExternalEditor => "Open in external editor",
+            Action::Reveal => "Show / hide masked text",
+            Action::Search => "Search fields",
+            Action::NextInvalid => "Next invalid field",
+            Action::Preview => "Cycle result preview",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::AddEntry => "Add map entry",
+            Action::RemoveEntry => "Remove map entry",
+            Action::Submit => "Submit",
+            Action::Cancel => "Cancel",
+            Action::Help => "Help",
+        }
+    }
+}
+
+/// A key combination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Create a binding for a key with modifiers.
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Create a binding for a key with the Control modifier.
+    pub const fn ctrl(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::CONTROL)
+    }
+
+    /// Whether this binding matches a key event.
+    ///
+    /// Shift is ignored for characters and `BackTab`, since terminals
+    /// disagree on whether to report it.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        if self.code != key.code {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(_) | KeyCode::BackTab => {
+                self.modifiers - KeyModifiers::SHIFT == key.modifiers - KeyModifiers::SHIFT
+            }
+            _ => self.modifiers == key.modifiers,
+        }
+    }
+
+    /// Whether this is a plain character that would otherwise be typed into a text field.
+    fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && (self.modifiers - KeyModifiers::SHIFT).is_empty()
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<char> for KeyBinding {
+    fn from(c: char) -> Self {
+        KeyCode::Char(c).into()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) && !matches!(self.code, KeyCode::Char(_)) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Mapping from keys to form actions.
+///
+/// Start from the defaults and adjust with the builder methods:
+///
+/// ```
+/// use elicitor_form_ratatui::{Action, KeyMap};
+/// use crossterm::event::KeyCode;
+///
+/// let keymap = KeyMap::default()
+///     .with_vim_navigation()
+///     .bind(Action::Submit, KeyCode::F(2));
+/// assert!(keymap.keys_for(Action::MoveDown).any(|k| k == 'j'.into()));
+/// ```
+///
+/// Plain character bindings (such as `j` or `?`) are ignored while a text
+/// field has focus, so they never swallow typed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::empty()
+            .bind(Action::NextField, KeyCode::Tab)
+            .bind(Action::NextField, KeyBinding::ctrl(KeyCode::Right))
+            .bind(Action::PrevField, KeyCode::BackTab)
+            .bind(
+                Action::PrevField,
+                KeyBinding::new(KeyCode::Tab, KeyModifiers::SHIFT),
+            )
+            .bind(Action::PrevField, KeyBinding::ctrl(KeyCode::Left))
+            .bind(Action::MoveUp, KeyCode::Up)
+            .bind(Action::MoveDown, KeyCode::Down)
+            .bind(Action::Toggle, ' ')
+            .bind(Action::Edit, KeyCode::Enter)
+            .bind(
+                Action::NewLine,
+                KeyBinding::new(KeyCode::Enter, KeyModifiers::ALT),
+            )
+            .bind(Action::NewLine, KeyBinding::ctrl(KeyCode::Char('j')))
+            .bind(Action::ExternalEditor, KeyBinding::ctrl(KeyCode::Char('e')))
+            .bind(Action::Reveal, KeyBinding::ctrl(KeyCode::Char('r')))
+            .bind(Action::Search, '/')
+            .bind(Action::Search, KeyBinding::ctrl(KeyCode::Char('f')))
+            .bind(Action::NextInvalid, KeyCode::F(8))
+            .bind(Action::NextInvalid, KeyBinding::ctrl(KeyCode::Char('n')))
+            .bind(Action::Preview, KeyCode::F(2))
+            .bind(Action::Undo, KeyBinding::ctrl(KeyCode::Char('z')))
+            .bind(Action::Redo, KeyBinding::ctrl(KeyCode::Char('y')))
+            .bind(Action::AddEntry, KeyBinding::ctrl(KeyCode::Char('a')))
+            .bind(Action::RemoveEntry, KeyBinding::ctrl(KeyCode::Char('d')))
+            .bind(Action::Submit, KeyCode::F(10))
+            .bind(Action::Submit, KeyBinding::ctrl(KeyCode::Enter))
+            .bind(Action::Cancel, KeyCode::Esc)
+            .bind(Action::Help, KeyCode::F(1))
+            .bind(Action::Help, '?')
+    }
+}
+
+impl KeyMap {
+    /// Create a key map with no bindings.
+    pub fn empty() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Bind a key to an action, replacing any previous binding of that key.
+    pub fn bind(mut self, action: Action, key: impl Into<KeyBinding>) -> Self {
+        let key = key.into();
+        self.bindings.retain(|(k, _)| *k != key);
+        self.bindings.push((key, action));
+        self
+    }
+
+    /// Remove the binding for a key.
+    pub fn unbind(mut self, key: impl Into<KeyBinding>) -> Self {
+        let key = key.into();
+        self.bindings.retain(|(k, _)| *k != key);
+        self
+    }
+
+    /// Remove all bindings for an action.
+    pub fn clear(mut self, action: Action) -> Self {
+        self.bindings.retain(|(_, a)| *a != action);
+        self
+    }
+
+    /// Add vim-style `j`/`k` for moving down/up and `q` for cancelling.
+    pub fn with_vim_navigation(self) -> Self {
+        self.bind(Action::MoveDown, 'j')
+            .bind(Action::MoveUp, 'k')
+            .bind(Action::Cancel, 'q')
+    }
+
+    /// All keys bound to an action.
+    pub fn keys_for(&self, action: Action) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, a)| *a == action)
+            .map(|(k, _)| *k)
+    }
+
+    /// The action bound to a key event, if any.
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.action_for(key, false)
+    }
+
+    /// The action bound to a key event, skipping plain characters while typing text.
+    pub(crate) fn action_for(&self, key: &KeyEvent, typing: bool) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(k, _)| k.matches(key) && !(typing && k.is_text()))
+            .map(|(_, a)| *a)
+    }
+
+    /// Lines for the help overlay: the keys and description of each bound action.
+    pub(crate) fn help_lines(&self) -> Vec<(String, &'static str)> {
+        Action::ALL
+            .iter()
+            .filter_map(|&action| {
+                let mut keys: Vec<String> = self.keys_for(action).map(|k| k.to_string()).collect();
+                keys.dedup();
+                (!keys.is_empty()).then(|| (keys.join(", "), action.description()))
+            })
+            .collect()
+    }
+
+    /// The first key bound to an action, for the status bar.
+    pub(crate) fn hint(&self, action: Action) -> Option<String> {
+        self.keys_for(action).next().map(|k| k.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn default_bindings() {
+        let keymap = KeyMap::default();
+        assert_eq!(
+            keymap.action(&press(KeyCode::Tab, KeyModifiers::NONE)),
+            Some(Action::NextField)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            Some(Action::PrevField)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Enter, KeyModifiers::CONTROL)),
+            Some(Action::Submit)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(Action::Edit)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+            Some(Action::Help)
+        );
+    }
+
+    #[test]
+    fn rebinding_replaces_key() {
+        let keymap = KeyMap::default()
+            .bind(Action::Submit, KeyCode::Enter)
+            .clear(Action::Help);
+        assert_eq!(
+            keymap.action(&press(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(Action::Submit)
+        );
+        assert_eq!(keymap.keys_for(Action::Help).count(), 0);
+    }
+
+    #[test]
+    fn plain_characters_are_ignored_while_typing() {
+        let keymap = KeyMap::default().with_vim_navigation();
+        let j = press(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(&j, false), Some(Action::MoveDown));
+        assert_eq!(keymap.action_for(&j, true), None);
+
+        let f1 = press(KeyCode::F(1), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(&f1, true), Some(Action::Help));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(KeyBinding::ctrl(KeyCode::Enter).to_string(), "Ctrl+Enter");
+        assert_eq!(KeyBinding::from(' ').to_string(), "Space");
+        assert_eq!(KeyBinding::from(KeyCode::F(10)).to_string(), "F10");
+    }
+}
@@ -4,7 +4,52 @@
 //!
 //! This backend displays all survey fields at once in a scrollable TUI form,
 //! similar to the egui backend but for the terminal. Users can navigate
-//! between fields using Tab/Shift+Tab or arrow keys.
+//! between fields using Tab/Shift+Tab or arrow keys. Key bindings can be
+//! customized with a [`KeyMap`]; press `?` or F1 to see the active bindings.
+//! Fields, checkboxes and the Submit/Cancel buttons can also be clicked, and
+//! the mouse wheel scrolls the form; turn this off with
+//! [`RatatuiFormBackend::with_mouse`].
+//!
+//! Text fields edit whole grapheme clusters, so emoji and combining characters
+//! move and delete as one unit, and wide characters are measured in terminal
+//! cells. Alt+Left/Right move by word. Pasted text arrives in one go through
+//! bracketed paste (forward `Event::Paste` to
+//! [`SurveyFormState::handle_paste_event`] when embedding).
+//!
+//! Masked fields show their text while Ctrl+R is toggled on. A field marked
+//! `#[confirm_entry]` is followed by a "Repeat to confirm" field that must match
+//! before the form submits, and `#[strength_meter]` draws a strength bar under
+//! the field.
+//!
+//! Multi-line fields are soft-wrapping text areas: Alt+Enter or Ctrl+J starts a
+//! new line, and Ctrl+E opens the text in `$VISUAL` or `$EDITOR` (see
+//! [`edit_externally`]).
+//!
+//! In long forms, `/` (or Ctrl+F inside a text field) opens a search bar that
+//! fuzzy-matches field prompts and paths and jumps to the best match, and F8
+//! cycles through the fields that fail validation.
+//!
+//! Nested structs are shown as sections whose header carries a progress badge
+//! such as "3/5" or "1 error". Space or Enter on a header (or a click)
+//! collapses and expands it, as do Left and Right.
+//!
+//! Maps start without entries. Enter on a map's header or Ctrl+A inside it
+//! adds an entry with a key field and the fields of its value; Ctrl+D removes
+//! the focused entry.
+//!
+//! F2 cycles a preview pane beside the form that shows the current responses
+//! as a tree, as JSON or TOML (with the `serde` feature), or in a custom
+//! format set with [`RatatuiFormBackend::with_preview_formatter`]; masked
+//! answers are redacted. [`RatatuiFormBackend::with_preview`] opens it from the start.
+//!
+//! Ctrl+Z undoes the last edit and Ctrl+Y redoes it, focusing the field that
+//! changed; text typed into one field is undone in one step.
+//!
+//! Host applications that own the terminal can embed the form in one of their
+//! own panes with [`SurveyWidget`] and [`SurveyFormState::handle_key_event`].
+//!
+//! Enable the `testing` feature for a headless harness that drives the form
+//! with scripted key events (see [`testing`]).
 //!
 //! ## Usage
 //!
@@ -32,5 +77,14 @@
 //! ```
 
 mod backend;
+mod keymap;
+mod search;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod widget;
 
-pub use backend::{RatatuiFormBackend, RatatuiFormError, Theme};
+pub use backend::{RatatuiFormBackend, RatatuiFormError};
+pub use elicitor_ratatui_support::edit_externally;
+pub use elicitor_theme::{Color, Theme, ThemeError};
+pub use keymap::{Action, KeyBinding, KeyMap};
+pub use widget::{FormEvent, SurveyFormState, SurveyWidget};
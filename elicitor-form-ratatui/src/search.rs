@@ -0,0 +1,135 @@
+//! Fuzzy search over field prompts and paths.
+
+/// A successful fuzzy match of a query against a candidate string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FuzzyMatch {
+    /// Higher is better.
+    pub(crate) score: i32,
+    /// Character indices of the candidate that matched the query.
+    pub(crate) positions: Vec<usize>,
+}
+
+/// Match `query` as a case-insensitive subsequence of `candidate`.
+///
+/// Consecutive characters and characters at the start of a word score
+/// higher, so `"em"` ranks "Email" above "Team name". An empty query matches
+/// nothing.
+pub(crate) fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let mut query = query.chars().flat_map(char::to_lowercase).peekable();
+    query.peek()?;
+
+    let mut score = 0;
+    let mut positions = Vec::new();
+    let mut prev: Option<char> = None;
+    for (idx, c) in candidate.chars().enumerate() {
+        let Some(&wanted) = query.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(std::iter::once(wanted)) {
+            score += 1;
+            if positions.last().is_some_and(|&last| last + 1 == idx) {
+                score += 5;
+            }
+            if prev.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+            positions.push(idx);
+            query.next();
+        }
+        prev = Some(c);
+    }
+
+    if query.peek().is_some() {
+        return None;
+    }
+    // Prefer matches that start early
+    score -= positions[0].min(10) as i32;
+    Some(FuzzyMatch { score, positions })
+}
+
+/// State of an active `/` search.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Search {
+    /// What the user typed.
+    pub(crate) query: String,
+    /// Matching fields, best first, with the matched positions in their prompt.
+    pub(crate) matches: Vec<(usize, Vec<usize>)>,
+    /// Index into `matches` of the focused match.
+    pub(crate) current: usize,
+    /// Focused field and whether the submit button was focused before the
+    /// search started, restored if it is cancelled.
+    pub(crate) origin: (usize, bool),
+}
+
+impl Search {
+    pub(crate) fn new(origin: (usize, bool)) -> Self {
+        Self {
+            origin,
+            ..Self::default()
+        }
+    }
+
+    /// Prompt positions to highlight for a field, if it matches.
+    pub(crate) fn highlights(&self, field_idx: usize) -> Option<&[usize]> {
+        self.matches
+            .iter()
+            .find(|(idx, _)| *idx == field_idx)
+            .map(|(_, positions)| positions.as_slice())
+    }
+
+    /// The focused match, if there are any matches.
+    pub(crate) fn current_field(&self) -> Option<usize> {
+        self.matches.get(self.current).map(|(idx, _)| *idx)
+    }
+
+    /// Move to the next match, wrapping around.
+    pub(crate) fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+    }
+
+    /// Move to the previous match, wrapping around.
+    pub(crate) fn prev(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_subsequences_case_insensitively() {
+        let m = fuzzy_match("eml", "Your Email").unwrap();
+        assert_eq!(m.positions, vec![5, 6, 9]);
+        assert!(fuzzy_match("xyz", "Your Email").is_none());
+        assert!(fuzzy_match("", "Your Email").is_none());
+    }
+
+    #[test]
+    fn prefers_word_starts_and_runs() {
+        let email = fuzzy_match("em", "Email address").unwrap();
+        let team = fuzzy_match("em", "Team name").unwrap();
+        assert!(email.score > team.score);
+
+        let run = fuzzy_match("age", "Your age").unwrap();
+        let scattered = fuzzy_match("age", "Language").unwrap();
+        assert!(run.score > scattered.score);
+    }
+
+    #[test]
+    fn cycles_through_matches() {
+        let mut search = Search::new((0, false));
+        search.matches = vec![(3, vec![]), (7, vec![])];
+        assert_eq!(search.current_field(), Some(3));
+        search.next();
+        assert_eq!(search.current_field(), Some(7));
+        search.next();
+        assert_eq!(search.current_field(), Some(3));
+        search.prev();
+        assert_eq!(search.current_field(), Some(7));
+    }
+}
@@ -0,0 +1,234 @@
+//! Headless test harness for the ratatui form.
+//!
+//! [`FormHarness`] drives the same state machine and drawing code as
+//! [`RatatuiFormBackend`], but renders into ratatui's in-memory [`TestBackend`]
+//! instead of a real terminal. Tests feed it a script of key events and then
+//! assert on the collected [`Responses`] or on a textual snapshot of the screen.
+//!
+//! Enable it with the `testing` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! elicitor-form-ratatui = { version = "0.6", features = ["testing"] }
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use elicitor_form_ratatui::{RatatuiFormBackend, testing::{FormHarness, KeyCode}};
+//!
+//! let mut harness = FormHarness::for_survey::<UserProfile>(&RatatuiFormBackend::new());
+//! harness.type_text("Alice").press(KeyCode::Tab);
+//! assert!(harness.screen().contains("Alice"));
+//! ```
+
+use std::fmt;
+
+use elicitor::{ResponsePath, ResponseValue, Responses, Survey, SurveyDefinition};
+use elicitor_ratatui_support::testing;
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, layout::Position};
+
+use crate::backend::{RatatuiFormBackend, RatatuiFormError};
+use crate::widget::{FormEvent, SurveyFormState, SurveyWidget};
+
+pub use crossterm::event::{
+    KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+pub use elicitor_ratatui_support::testing::{buffer_to_string, find_text};
+
+/// Default terminal width used by [`FormHarness`].
+pub const DEFAULT_WIDTH: u16 = 80;
+
+/// Default terminal height used by [`FormHarness`].
+pub const DEFAULT_HEIGHT: u16 = 24;
+
+type EditorFn = Box<dyn FnMut(&str) -> String>;
+
+/// Scriptable, headless driver for the ratatui form.
+pub struct FormHarness {
+    terminal: Terminal<TestBackend>,
+    state: SurveyFormState,
+    outcome: FormEvent,
+    editor: Option<EditorFn>,
+}
+
+impl FormHarness {
+    /// Create a harness for a survey definition and field validator.
+    ///
+    /// Title, theme and key bindings are taken from `backend`.
+    pub fn new(
+        backend: &RatatuiFormBackend,
+        definition: &SurveyDefinition,
+        validate: impl Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String> + 'static,
+    ) -> Self {
+        let mut state = SurveyFormState::new(definition)
+            .with_title(backend.title.clone())
+            .with_theme(backend.theme.clone())
+            .with_keymap(backend.keymap.clone())
+            .with_preview(backend.preview)
+            .with_validator(validate);
+        state.form.preview_formatter = backend.preview_formatter.clone();
+
+        Self {
+            terminal: Terminal::new(TestBackend::new(DEFAULT_WIDTH, DEFAULT_HEIGHT))
+                .expect("TestBackend never fails"),
+            state,
+            outcome: FormEvent::Continue,
+            editor: None,
+        }
+    }
+
+    /// Create a harness for a type deriving `Survey`, using its validators.
+    pub fn for_survey<T: Survey + 'static>(backend: &RatatuiFormBackend) -> Self {
+        Self::new(backend, &T::survey(), T::validate_field)
+    }
+
+    /// Set the size of the virtual terminal.
+    pub fn with_size(mut self, width: u16, height: u16) -> Self {
+        self.terminal =
+            Terminal::new(TestBackend::new(width, height)).expect("TestBackend never fails");
+        self
+    }
+
+    /// Stand in for the external editor.
+    ///
+    /// When the user asks to edit a field externally, `editor` receives the
+    /// current text and returns the edited text. Without one, such requests
+    /// leave the text unchanged.
+    pub fn with_editor(mut self, editor: impl FnMut(&str) -> String + 'static) -> Self {
+        self.editor = Some(Box::new(editor));
+        self
+    }
+
+    /// Send a single key press.
+    ///
+    /// Keys sent after the form has been submitted or cancelled are ignored.
+    pub fn press(&mut self, key: impl Into<KeyEvent>) -> &mut Self {
+        if !self.is_finished() {
+            self.outcome = self.state.handle_key_event(key.into());
+            self.run_editor();
+        }
+        self
+    }
+
+    fn run_editor(&mut self) {
+        if let Some(text) = self.state.take_editor_request() {
+            let edited = match &mut self.editor {
+                Some(editor) => editor(&text),
+                None => text,
+            };
+            self.state.finish_external_edit(Ok(edited));
+        }
+    }
+
+    /// Send a sequence of key presses.
+    pub fn script<K: Into<KeyEvent>>(&mut self, keys: impl IntoIterator<Item = K>) -> &mut Self {
+        for key in keys {
+            self.press(key);
+        }
+        self
+    }
+
+    /// Type each character of `text` as a separate key press.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.script(text.chars().map(KeyCode::Char))
+    }
+
+    /// Paste text, as a terminal with bracketed paste enabled would.
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        if !self.is_finished() {
+            self.outcome = self.state.handle_paste_event(text);
+        }
+        self
+    }
+
+    /// Send a mouse event.
+    ///
+    /// The screen is rendered first so the event is hit-tested against the
+    /// current layout, as it would be in a real terminal.
+    pub fn mouse(&mut self, mouse: MouseEvent) -> &mut Self {
+        if !self.is_finished() {
+            self.buffer();
+            self.outcome = self.state.handle_mouse_event(mouse);
+        }
+        self
+    }
+
+    /// Left-click a screen cell.
+    pub fn click(&mut self, column: u16, row: u16) -> &mut Self {
+        self.mouse(testing::click(column, row))
+    }
+
+    /// Scroll the mouse wheel down by one step.
+    pub fn scroll_down(&mut self) -> &mut Self {
+        self.mouse(testing::scroll_down())
+    }
+
+    /// Scroll the mouse wheel up by one step.
+    pub fn scroll_up(&mut self) -> &mut Self {
+        self.mouse(testing::scroll_up())
+    }
+
+    /// Find the first screen cell where `text` appears, as `(column, row)`.
+    ///
+    /// Handy for clicking on labels without hard-coding coordinates.
+    pub fn find(&mut self, text: &str) -> Option<(u16, u16)> {
+        find_text(self.buffer(), text)
+    }
+
+    /// Render the current screen and return it as a buffer.
+    pub fn buffer(&mut self) -> &Buffer {
+        let state = &mut self.state;
+        self.terminal
+            .draw(|frame| frame.render_stateful_widget(SurveyWidget, frame.area(), state))
+            .expect("TestBackend never fails");
+        self.terminal.backend().buffer()
+    }
+
+    /// Render the current screen and return where the text cursor is shown,
+    /// if a text field has focus.
+    pub fn cursor(&mut self) -> Option<Position> {
+        self.buffer();
+        self.state.cursor_position()
+    }
+
+    /// Render the current screen and return it as plain text.
+    ///
+    /// Each terminal row becomes one line with trailing whitespace removed.
+    /// Styling is discarded, which keeps snapshots stable across themes.
+    pub fn screen(&mut self) -> String {
+        buffer_to_string(self.buffer())
+    }
+
+    /// Whether the form has been submitted or cancelled.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self.outcome, FormEvent::Continue)
+    }
+
+    /// Responses as currently entered in the form.
+    pub fn responses(&self) -> Responses {
+        self.state.responses()
+    }
+
+    /// Consume the harness and return what `collect` would have returned.
+    ///
+    /// Returns [`RatatuiFormError::Terminal`] if the script ended before the form was
+    /// submitted or cancelled.
+    pub fn finish(self) -> Result<Responses, RatatuiFormError> {
+        match self.outcome {
+            FormEvent::Continue => Err(RatatuiFormError::Terminal(
+                "script ended before the form was submitted".to_string(),
+            )),
+            FormEvent::Submitted(responses) => Ok(responses),
+            FormEvent::Cancelled => Err(RatatuiFormError::Cancelled),
+        }
+    }
+}
+
+impl fmt::Debug for FormHarness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormHarness")
+            .field("outcome", &self.outcome)
+            .finish_non_exhaustive()
+    }
+}
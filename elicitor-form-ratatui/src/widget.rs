@@ -0,0 +1,290 @@
+//! Embeddable survey form for host TUI applications.
+//!
+//! [`RatatuiFormBackend`](crate::RatatuiFormBackend) takes over the whole
+//! terminal and runs its own event loop. Applications that already own the
+//! terminal can instead keep a [`SurveyFormState`], render it into any area
+//! with [`SurveyWidget`], and forward key and mouse events via
+//! [`SurveyFormState::handle_key_event`] and
+//! [`SurveyFormState::handle_mouse_event`]. Multi-line fields can ask for an
+//! external editor; see [`SurveyFormState::take_editor_request`].
+
+use std::io;
+
+use crossterm::event::{KeyEvent, MouseEvent};
+use elicitor::{
+    PreviewFormatter, ResponsePath, ResponseValue, Responses, Survey, SurveyDefinition,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    widgets::StatefulWidget,
+};
+
+use elicitor_theme::Theme;
+
+use crate::backend::{FormState, PreviewMode, render_form};
+use crate::keymap::KeyMap;
+
+type ValidateFn = Box<dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>>;
+
+/// Result of handling a key event.
+#[derive(Debug, Clone)]
+pub enum FormEvent {
+    /// The form is still being filled in.
+    Continue,
+    /// The user submitted a valid form.
+    Submitted(Responses),
+    /// The user cancelled the form.
+    Cancelled,
+}
+
+/// Persistent state of an embedded survey form.
+///
+/// Keep this in your application state, render it with [`SurveyWidget`] and
+/// pass key events to [`handle_key_event`](Self::handle_key_event):
+///
+/// ```rust,ignore
+/// let mut form = SurveyFormState::for_survey::<UserProfile>();
+///
+/// loop {
+///     terminal.draw(|frame| {
+///         frame.render_stateful_widget(SurveyWidget, pane, &mut form);
+///         if let Some(position) = form.cursor_position() {
+///             frame.set_cursor_position(position);
+///         }
+///     })?;
+///
+///     if let Event::Key(key) = event::read()? {
+///         match form.handle_key_event(key) {
+///             FormEvent::Continue => {}
+///             FormEvent::Submitted(responses) => break Some(responses),
+///             FormEvent::Cancelled => break None,
+///         }
+///     }
+/// }
+/// ```
+pub struct SurveyFormState {
+    pub(crate) form: FormState,
+    validate: ValidateFn,
+}
+
+impl SurveyFormState {
+    /// Create form state for a survey definition, without custom validation.
+    pub fn new(definition: &SurveyDefinition) -> Self {
+        let mut form = FormState::new(definition, Theme::from_env(), "Survey".to_string());
+        form.start();
+
+        Self {
+            form,
+            validate: Box::new(|_, _, _| Ok(())),
+        }
+    }
+
+    /// Create form state for a type deriving `Survey`, using its field validators.
+    pub fn for_survey<T: Survey + 'static>() -> Self {
+        Self::new(&T::survey()).with_validator(T::validate_field)
+    }
+
+    /// Set the field validator.
+    pub fn with_validator(
+        mut self,
+        validate: impl Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validate = Box::new(validate);
+        self
+    }
+
+    /// Set the title shown at the top of the form.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.form.title = title.into();
+        self
+    }
+
+    /// Set a custom color theme.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.form.theme = theme;
+        self
+    }
+
+    /// Set custom key bindings.
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.form.keymap = keymap;
+        self
+    }
+
+    /// Open the result preview pane, showing the responses entered so far.
+    ///
+    /// F2 cycles the pane through tree, JSON, TOML and custom formats and
+    /// hides it. JSON and TOML need the `serde` feature.
+    pub fn with_preview(mut self, enabled: bool) -> Self {
+        self.form.preview = enabled.then_some(PreviewMode::Tree);
+        self
+    }
+
+    /// Offer a custom format in the preview pane; it receives the responses
+    /// with masked answers redacted.
+    pub fn with_preview_formatter(
+        mut self,
+        format: impl Fn(&Responses) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.form.preview_formatter = Some(PreviewFormatter::new(format));
+        self
+    }
+
+    /// Handle a key event, returning whether the form was submitted or cancelled.
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> FormEvent {
+        let finished = self.form.handle_key(key, &*self.validate);
+        self.event(finished)
+    }
+
+    /// Handle pasted text (`Event::Paste`, sent when bracketed paste is
+    /// enabled), inserting it into the focused text field in one go.
+    pub fn handle_paste_event(&mut self, text: &str) -> FormEvent {
+        self.form.handle_paste(text);
+        FormEvent::Continue
+    }
+
+    /// Handle a mouse event, returning whether the form was submitted or cancelled.
+    ///
+    /// Hit-testing uses the layout of the most recent render, so render the
+    /// widget before forwarding mouse events.
+    pub fn handle_mouse_event(&mut self, mouse: MouseEvent) -> FormEvent {
+        let finished = self.form.handle_mouse(mouse, &*self.validate);
+        self.event(finished)
+    }
+
+    /// Handle a mouse event using a borrowed validator instead of the stored one.
+    pub(crate) fn handle_mouse_event_with(
+        &mut self,
+        mouse: MouseEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> FormEvent {
+        let finished = self.form.handle_mouse(mouse, validate);
+        self.event(finished)
+    }
+
+    /// Handle a key event using a borrowed validator instead of the stored one.
+    pub(crate) fn handle_key_event_with(
+        &mut self,
+        key: KeyEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> FormEvent {
+        let finished = self.form.handle_key(key, validate);
+        self.event(finished)
+    }
+
+    fn event(&self, finished: bool) -> FormEvent {
+        if !finished {
+            FormEvent::Continue
+        } else if self.form.cancelled {
+            FormEvent::Cancelled
+        } else {
+            FormEvent::Submitted(self.form.collect_responses())
+        }
+    }
+
+    /// Responses as currently entered, whether or not the form is valid.
+    pub fn responses(&self) -> Responses {
+        self.form.collect_responses()
+    }
+
+    /// Where the host should place the terminal cursor after rendering, if anywhere.
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.form.cursor
+    }
+
+    /// Text to open in an external editor, if the user asked for one.
+    ///
+    /// Check this after handling each event. When it returns `Some`, suspend
+    /// your UI, edit the text (for example with [`edit_externally`]), restore
+    /// the UI and pass the outcome to
+    /// [`finish_external_edit`](Self::finish_external_edit).
+    ///
+    /// [`edit_externally`]: crate::edit_externally
+    pub fn take_editor_request(&mut self) -> Option<String> {
+        if !std::mem::take(&mut self.form.editor_requested) {
+            return None;
+        }
+        self.form.focused_text()
+    }
+
+    /// Apply the result of an external edit to the focused field.
+    ///
+    /// On error the field keeps its text and shows the error.
+    pub fn finish_external_edit(&mut self, result: io::Result<String>) {
+        self.form.finish_external_edit(result);
+    }
+}
+
+/// Renders a [`SurveyFormState`] into a buffer area.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SurveyWidget;
+
+impl StatefulWidget for SurveyWidget {
+    type State = SurveyFormState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        render_form(area, buf, &mut state.form);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use elicitor::{InputQuestion, Question, QuestionKind};
+
+    fn state() -> SurveyFormState {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "name",
+            "Name",
+            QuestionKind::Input(InputQuestion::default()),
+        )]);
+        SurveyFormState::new(&definition).with_title("Embedded")
+    }
+
+    #[test]
+    fn key_events_report_submission() {
+        let mut form = state();
+        assert!(matches!(
+            form.handle_key_event(KeyCode::Char('A').into()),
+            FormEvent::Continue
+        ));
+
+        match form.handle_key_event(KeyCode::F(10).into()) {
+            FormEvent::Submitted(responses) => {
+                assert_eq!(
+                    responses.get_string(&ResponsePath::new("name")).unwrap(),
+                    "A"
+                );
+            }
+            other => panic!("expected submission, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn key_events_report_cancellation() {
+        let mut form = state();
+        assert!(matches!(
+            form.handle_key_event(KeyCode::Esc.into()),
+            FormEvent::Cancelled
+        ));
+    }
+
+    #[test]
+    fn renders_inside_host_area() {
+        let mut form = state();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 60, 30));
+        let pane = Rect::new(20, 5, 40, 20);
+        SurveyWidget.render(pane, &mut buf, &mut form);
+
+        assert_eq!(buf.cell((0, 5)).unwrap().symbol(), " ");
+        let row: String = (pane.x..pane.right())
+            .map(|x| buf.cell((x, pane.y)).unwrap().symbol())
+            .collect();
+        assert!(row.starts_with("Embedded"));
+
+        let cursor = form.cursor_position().unwrap();
+        assert!(pane.contains(cursor));
+    }
+}
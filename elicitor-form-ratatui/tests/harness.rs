@@ -0,0 +1,635 @@
+//! Headless tests driving the form through the `testing` harness.
+
+use std::time::Duration;
+
+use elicitor::{Date, ResponsePath, Survey, Time};
+use elicitor_form_ratatui::{
+    Action, KeyBinding, KeyMap, RatatuiFormBackend, RatatuiFormError,
+    testing::{FormHarness, KeyCode, KeyEvent, KeyModifiers},
+};
+use example_surveys::{
+    BlogPost, Deployment, Filter, Login, Meeting, SavedSearch, UserProfile, UserRegistration,
+};
+
+fn harness() -> FormHarness {
+    FormHarness::for_survey::<UserProfile>(&RatatuiFormBackend::new().with_title("Profile"))
+        .with_size(80, 40)
+}
+
+fn fill_valid(h: &mut FormHarness) {
+    h.type_text("Alice").press(KeyCode::Tab);
+    h.type_text("30").press(KeyCode::Tab);
+    h.type_text("alice@example.com").press(KeyCode::Tab);
+    h.type_text("Hello").press(KeyCode::Tab);
+    h.press(KeyCode::Char(' '));
+}
+
+#[test]
+fn submits_filled_form() {
+    let mut h = harness();
+    fill_valid(&mut h);
+    h.press(KeyCode::F(10));
+    assert!(h.is_finished());
+
+    let profile = UserProfile::from_responses(&h.finish().unwrap());
+    assert_eq!(profile.name, "Alice");
+    assert_eq!(profile.age, 30);
+    assert_eq!(profile.email, "alice@example.com");
+    assert_eq!(profile.bio, "Hello");
+    assert!(profile.newsletter);
+}
+
+#[test]
+fn screen_shows_all_fields() {
+    let mut h = harness();
+    let screen = h.screen();
+    assert!(screen.contains("Profile"));
+    assert!(screen.contains("What is your name?"));
+    assert!(screen.contains("How old are you?"));
+    assert!(screen.contains("Would you like to receive our newsletter?"));
+
+    h.type_text("Bob");
+    assert!(h.screen().contains("Bob"));
+}
+
+#[test]
+fn invalid_form_is_not_submitted() {
+    let mut h = harness();
+    h.type_text("Alice").press(KeyCode::Tab);
+    h.type_text("200");
+    h.press(KeyCode::F(10));
+
+    assert!(!h.is_finished());
+    assert!(h.screen().contains("150"));
+}
+
+#[test]
+fn escape_cancels() {
+    let mut h = harness();
+    h.press(KeyCode::Esc);
+    assert!(matches!(h.finish(), Err(RatatuiFormError::Cancelled)));
+}
+
+#[test]
+fn unfinished_script_is_an_error() {
+    let mut h = harness();
+    fill_valid(&mut h);
+    assert!(matches!(h.finish(), Err(RatatuiFormError::Terminal(_))));
+}
+
+#[test]
+fn help_overlay_lists_bindings() {
+    let mut h = harness();
+    h.press(KeyCode::F(1));
+    let screen = h.screen();
+    assert!(screen.contains("Key bindings"));
+    assert!(
+        screen
+            .lines()
+            .any(|line| line.contains("F10, Ctrl+Enter") && line.contains("Submit"))
+    );
+
+    // Any key closes the overlay without acting on it
+    h.press(KeyCode::Esc);
+    assert!(!h.is_finished());
+    assert!(!h.screen().contains("Key bindings"));
+}
+
+#[test]
+fn custom_keymap() {
+    let backend = RatatuiFormBackend::new().with_keymap(
+        KeyMap::default()
+            .clear(Action::Submit)
+            .bind(Action::Submit, KeyBinding::ctrl(KeyCode::Char('s'))),
+    );
+    let mut h = FormHarness::for_survey::<UserProfile>(&backend).with_size(80, 40);
+    fill_valid(&mut h);
+    assert!(h.screen().contains("Ctrl+s: Submit"));
+
+    h.press(KeyCode::F(10));
+    assert!(!h.is_finished());
+    h.press(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    assert!(h.finish().is_ok());
+}
+
+#[test]
+fn clicking_a_field_focuses_it() {
+    let mut h = harness();
+    let (column, row) = h.find("How old are you?").unwrap();
+    h.click(column, row + 1);
+    h.type_text("42");
+
+    let responses = h.responses();
+    assert_eq!(responses.get_int(&ResponsePath::new("age")).unwrap(), 42);
+    assert_eq!(
+        responses.get_string(&ResponsePath::new("name")).unwrap(),
+        ""
+    );
+}
+
+#[test]
+fn clicking_checkbox_toggles_it() {
+    let mut h = harness();
+    let (column, row) = h.find("Would you like to receive our newsletter?").unwrap();
+    h.click(column, row + 1);
+    assert!(
+        h.responses()
+            .get_bool(&ResponsePath::new("newsletter"))
+            .unwrap()
+    );
+}
+
+#[test]
+fn clicking_buttons_submits_and_cancels() {
+    let mut h = harness();
+    fill_valid(&mut h);
+    let (column, row) = h.find("Submit").unwrap();
+    h.click(column, row);
+    assert!(h.finish().is_ok());
+
+    let mut h = harness();
+    let (column, row) = h.find("Cancel").unwrap();
+    h.click(column, row);
+    assert!(matches!(h.finish(), Err(RatatuiFormError::Cancelled)));
+}
+
+#[test]
+fn wheel_scrolls_without_moving_focus() {
+    let mut h =
+        FormHarness::for_survey::<UserProfile>(&RatatuiFormBackend::new()).with_size(80, 16);
+    assert!(h.screen().contains("What is your name?"));
+
+    h.scroll_down().scroll_down().scroll_down();
+    assert!(!h.screen().contains("What is your name?"));
+
+    // Typing goes to the focused field and brings it back into view
+    h.type_text("A");
+    assert!(h.screen().contains("What is your name?"));
+}
+
+fn blog_post() -> FormHarness {
+    FormHarness::for_survey::<BlogPost>(&RatatuiFormBackend::new()).with_size(60, 30)
+}
+
+#[test]
+fn multiline_field_edits_across_lines() {
+    let mut h = blog_post();
+    h.type_text("Title").press(KeyCode::Tab);
+    h.type_text("first line")
+        .press(KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT))
+        .type_text("second")
+        .press(KeyCode::Up)
+        .press(KeyCode::End)
+        .type_text("!")
+        .press(KeyCode::Down)
+        .press(KeyCode::Home)
+        .type_text("> ");
+
+    let screen = h.screen();
+    assert!(screen.contains("first line!"), "{screen}");
+    assert!(screen.contains("> second"), "{screen}");
+
+    // Down on the last line moves on to the next field
+    h.press(KeyCode::Down)
+        .type_text("note")
+        .press(KeyCode::F(10));
+    let post = BlogPost::from_responses(&h.finish().unwrap());
+    assert_eq!(post.content, "first line!\n> second");
+    assert_eq!(post.notes, "note");
+}
+
+#[test]
+fn long_multiline_text_wraps_and_scrolls() {
+    let mut h = blog_post();
+    h.press(KeyCode::Tab);
+    for line in 1..=8 {
+        h.type_text(&format!("line {line}"))
+            .press(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL));
+    }
+    h.type_text("the last line is long enough to wrap onto a second row");
+
+    let screen = h.screen();
+    assert!(!screen.contains("line 1"), "{screen}");
+    assert!(screen.contains("the last line is long enough"), "{screen}");
+    assert!(screen.contains("second row"), "{screen}");
+
+    h.script([KeyCode::Up; 8]);
+    let screen = h.screen();
+    assert!(screen.contains("line 1"), "{screen}");
+    assert!(!screen.contains("second row"), "{screen}");
+}
+
+#[test]
+fn external_editor_replaces_text() {
+    let mut h = blog_post().with_editor(|text| format!("{text}\nfrom the editor"));
+    h.type_text("Title").press(KeyCode::Tab).type_text("draft");
+    h.press(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL));
+    h.type_text("!").press(KeyCode::F(10));
+
+    let post = BlogPost::from_responses(&h.finish().unwrap());
+    assert_eq!(post.content, "draft\nfrom the editor!");
+}
+
+fn ctrl(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+}
+
+#[test]
+fn search_jumps_to_matching_field() {
+    let mut h = harness();
+    h.press(ctrl('f')).type_text("news");
+    let screen = h.screen();
+    assert!(screen.contains("/news  1/1"), "{screen}");
+
+    h.press(KeyCode::Enter).press(KeyCode::Char(' '));
+    let responses = h.responses();
+    assert!(
+        responses
+            .get_bool(&ResponsePath::new("newsletter"))
+            .unwrap()
+    );
+}
+
+#[test]
+fn search_matches_response_paths() {
+    let mut h = harness();
+    h.press(ctrl('f')).type_text("bio").press(KeyCode::Enter);
+    h.type_text("Hi");
+    assert_eq!(
+        h.responses().get_string(&ResponsePath::new("bio")).unwrap(),
+        "Hi"
+    );
+}
+
+#[test]
+fn escape_cancels_search_and_restores_focus() {
+    let mut h = harness();
+    h.press(ctrl('f')).type_text("email").press(KeyCode::Esc);
+    assert!(!h.screen().contains("/email"));
+
+    h.type_text("Al");
+    assert_eq!(
+        h.responses()
+            .get_string(&ResponsePath::new("name"))
+            .unwrap(),
+        "Al"
+    );
+}
+
+#[test]
+fn search_without_matches_keeps_focus() {
+    let mut h = harness();
+    h.press(ctrl('f')).type_text("zzz");
+    assert!(h.screen().contains("no matches"));
+    h.press(KeyCode::Enter).type_text("Al");
+    assert_eq!(
+        h.responses()
+            .get_string(&ResponsePath::new("name"))
+            .unwrap(),
+        "Al"
+    );
+}
+
+#[test]
+fn next_invalid_field_focuses_errors() {
+    let mut h = harness();
+    h.type_text("Alice").press(KeyCode::Tab);
+    h.type_text("200").press(KeyCode::Tab);
+    h.type_text("alice@example.com");
+
+    h.press(KeyCode::F(8));
+    assert!(h.screen().contains("150"));
+    h.script([KeyCode::Backspace; 3]).type_text("30");
+
+    // No errors left: focus stays on the age field
+    h.press(KeyCode::F(8)).type_text("1");
+    assert_eq!(
+        h.responses().get_int(&ResponsePath::new("age")).unwrap(),
+        301
+    );
+
+    h.press(KeyCode::Backspace).press(KeyCode::F(10));
+    let profile = UserProfile::from_responses(&h.finish().unwrap());
+    assert_eq!(profile.age, 30);
+    assert_eq!(profile.email, "alice@example.com");
+}
+
+fn registration() -> FormHarness {
+    FormHarness::for_survey::<UserRegistration>(&RatatuiFormBackend::new()).with_size(80, 50)
+}
+
+#[test]
+fn nested_groups_show_progress_badges() {
+    let mut h = registration();
+    let screen = h.screen();
+    assert!(screen.contains("▾ Contact information: 0/2"), "{screen}");
+    assert!(screen.contains("▾ Home address: 0/4"), "{screen}");
+
+    // Name, age, contact header, email
+    h.script([KeyCode::Tab; 3]).type_text("bob@example.com");
+    assert!(h.screen().contains("Contact information: 1/2"));
+    h.press(KeyCode::Tab).type_text("555");
+    assert!(h.screen().contains("Contact information: 2/2 ✓"));
+}
+
+#[test]
+fn collapsed_sections_hide_their_fields() {
+    let mut h = registration();
+    h.script([KeyCode::Tab; 2]).press(KeyCode::Char(' '));
+    let screen = h.screen();
+    assert!(screen.contains("▸ Contact information:"), "{screen}");
+    assert!(!screen.contains("Email address:"), "{screen}");
+
+    // Tab skips the hidden fields and lands on the next section
+    h.press(KeyCode::Tab).press(KeyCode::Left);
+    let screen = h.screen();
+    assert!(!screen.contains("Street address:"), "{screen}");
+
+    h.press(KeyCode::Right);
+    assert!(h.screen().contains("Street address:"));
+    h.press(KeyCode::BackTab).press(KeyCode::Enter);
+    assert!(h.screen().contains("Email address:"));
+}
+
+#[test]
+fn collapsed_fields_are_still_submitted() {
+    let mut h = registration();
+    h.type_text("Bob").press(KeyCode::Tab);
+    h.type_text("30").press(KeyCode::Tab).press(KeyCode::Tab);
+    h.type_text("bob@example.com");
+    h.press(KeyCode::BackTab).press(KeyCode::Char(' '));
+    assert!(!h.screen().contains("Email address:"));
+
+    h.press(KeyCode::F(10));
+    let user = UserRegistration::from_responses(&h.finish().unwrap());
+    assert_eq!(user.contact.email, "bob@example.com");
+}
+
+#[test]
+fn preview_pane_follows_input() {
+    let mut h =
+        FormHarness::for_survey::<UserProfile>(&RatatuiFormBackend::new().with_preview(true))
+            .with_size(100, 40);
+    h.type_text("Alice");
+    let screen = h.screen();
+    assert!(screen.contains("Preview"), "{screen}");
+    assert!(screen.contains("name: \"Alice\""), "{screen}");
+
+    h.press(KeyCode::F(2));
+    let screen = h.screen();
+    assert!(screen.contains("Preview (JSON)"), "{screen}");
+    assert!(screen.contains("\"name\": \"Alice\""), "{screen}");
+
+    h.press(KeyCode::F(2));
+    let screen = h.screen();
+    assert!(screen.contains("Preview (TOML)"), "{screen}");
+    assert!(screen.contains("name = \"Alice\""), "{screen}");
+
+    h.press(KeyCode::F(2));
+    assert!(!h.screen().contains("Preview"));
+}
+
+#[test]
+fn preview_redacts_masked_answers() {
+    let backend = RatatuiFormBackend::new()
+        .with_preview(true)
+        .with_preview_formatter(|responses| format!("{} answers", responses.len()));
+    let mut h = FormHarness::for_survey::<Login>(&backend).with_size(100, 40);
+    h.type_text("ann").press(KeyCode::Tab).type_text("hunter2");
+    let screen = h.screen();
+    assert!(screen.contains("password: \"••••••\""), "{screen}");
+    assert!(!screen.contains("hunter2"), "{screen}");
+
+    h.script([KeyCode::F(2); 3]);
+    assert!(h.screen().contains("Preview (custom)"));
+}
+
+#[test]
+fn undo_reverts_typing_runs_and_focuses_the_field() {
+    let mut h = harness();
+    h.type_text("Alice").press(KeyCode::Tab);
+    h.type_text("30").script([KeyCode::Tab; 3]);
+    h.press(KeyCode::Char(' '));
+
+    // Each undo reverts one field and focuses it
+    h.press(ctrl('z'));
+    assert!(
+        !h.responses()
+            .get_bool(&ResponsePath::new("newsletter"))
+            .unwrap()
+    );
+    h.press(ctrl('z'));
+    assert!(h.responses().get_int(&ResponsePath::new("age")).is_err());
+    h.type_text("4");
+
+    // Typing after an undo clears the redo history
+    h.press(ctrl('y'));
+    let responses = h.responses();
+    assert_eq!(responses.get_int(&ResponsePath::new("age")).unwrap(), 4);
+    assert_eq!(
+        responses.get_string(&ResponsePath::new("name")).unwrap(),
+        "Alice"
+    );
+
+    h.script([ctrl('z'), ctrl('z')]);
+    assert_eq!(
+        h.responses().get_string(&ResponsePath::new("name")).ok(),
+        Some("")
+    );
+    h.press(ctrl('y'));
+    assert_eq!(
+        h.responses()
+            .get_string(&ResponsePath::new("name"))
+            .unwrap(),
+        "Alice"
+    );
+}
+
+#[test]
+fn paste_is_one_edit() {
+    let mut h = harness();
+    h.type_text("Dr. ").paste("Alice\r\nSmith\n");
+    let name = ResponsePath::new("name");
+    assert_eq!(h.responses().get_string(&name).unwrap(), "Dr. Alice Smith");
+
+    h.press(ctrl('z'));
+    assert_eq!(h.responses().get_string(&name).unwrap(), "Dr. ");
+}
+
+#[test]
+fn cursor_accounts_for_wide_characters() {
+    let mut h = harness();
+    let start = h.cursor().unwrap();
+    h.type_text("日本");
+    assert_eq!(h.cursor().unwrap().x, start.x + 4);
+    h.press(KeyCode::Left);
+    assert_eq!(h.cursor().unwrap().x, start.x + 2);
+}
+
+#[test]
+fn masked_field_is_confirmed_and_can_be_revealed() {
+    let mut h = FormHarness::for_survey::<Login>(&RatatuiFormBackend::new()).with_size(80, 30);
+    h.type_text("ann").press(KeyCode::Tab).type_text("hunter2");
+    let screen = h.screen();
+    assert!(!screen.contains("hunter2"), "{screen}");
+    assert!(screen.contains("Weak"), "strength meter is shown: {screen}");
+    assert!(screen.contains("Repeat to confirm"), "{screen}");
+
+    h.press(ctrl('r'));
+    assert!(h.screen().contains("hunter2"));
+
+    h.press(KeyCode::Tab)
+        .type_text("hunter3")
+        .press(KeyCode::F(10));
+    assert!(!h.is_finished());
+    assert!(h.screen().contains("Entries do not match"));
+
+    h.press(KeyCode::Backspace)
+        .type_text("2")
+        .press(KeyCode::F(10));
+    assert!(h.is_finished());
+    let responses = h.finish().unwrap();
+    assert_eq!(
+        responses
+            .get_secret(&ResponsePath::new("password"))
+            .unwrap()
+            .expose_secret(),
+        "hunter2"
+    );
+    assert_eq!(responses.len(), 2, "the confirmation is not collected");
+}
+
+#[test]
+fn temporal_fields_are_parsed_and_bounded() {
+    let mut h = FormHarness::for_survey::<Meeting>(&RatatuiFormBackend::new()).with_size(80, 30);
+    assert!(
+        h.screen().contains("YYYY-MM-DD"),
+        "format is shown while empty"
+    );
+
+    h.type_text("Review").press(KeyCode::Tab);
+    h.type_text("2023-12-31").press(KeyCode::Tab);
+    h.type_text("14:00").press(KeyCode::Tab);
+    h.type_text("45m").press(KeyCode::F(10));
+    assert!(!h.is_finished());
+    assert!(h.screen().contains("Value must be at least 2024-01-01"));
+
+    // The invalid field is focused
+    h.script(std::iter::repeat_n(KeyCode::Backspace, "2023-12-31".len()))
+        .type_text("2024-01-02")
+        .press(KeyCode::F(10));
+    assert!(h.is_finished());
+
+    let meeting = Meeting::from_responses(&h.finish().unwrap());
+    assert_eq!(meeting.day, Date::new(2024, 1, 2).unwrap());
+    assert_eq!(meeting.start, Time::new(14, 0, 0).unwrap());
+    assert_eq!(meeting.length, Duration::from_secs(45 * 60));
+}
+
+#[test]
+fn map_entries_are_added_and_removed() {
+    let mut h = FormHarness::for_survey::<Deployment>(&RatatuiFormBackend::new()).with_size(80, 40);
+    assert!(h.screen().contains("0 entries"));
+
+    let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+    h.type_text("api").press(KeyCode::Tab);
+    // Enter on the map adds an entry and focuses its key
+    h.press(KeyCode::Enter)
+        .type_text("9LIVES")
+        .press(KeyCode::Tab);
+    h.type_text("cat");
+    h.press(ctrl('a')).type_text("PATH").press(KeyCode::Tab);
+    h.type_text("/bin").press(KeyCode::Tab);
+    assert!(h.screen().contains("2 entries"));
+
+    // Struct values are a section below the key
+    h.press(KeyCode::Enter).type_text("web").press(KeyCode::Tab);
+    h.press(KeyCode::Tab)
+        .type_text("2")
+        .press(KeyCode::Tab)
+        .type_text("512");
+
+    h.press(KeyCode::F(10));
+    assert!(!h.is_finished());
+    assert!(
+        h.screen()
+            .contains("Variable names must not start with a digit")
+    );
+
+    // The invalid key is focused; removing its entry moves the next one up
+    h.press(ctrl('d'));
+    assert!(h.screen().contains("1 entry"));
+    h.press(KeyCode::F(10));
+    assert!(h.is_finished());
+
+    let deployment = Deployment::from_responses(&h.finish().unwrap());
+    assert_eq!(deployment.service, "api");
+    assert_eq!(deployment.env.len(), 1);
+    assert_eq!(deployment.env["PATH"], "/bin");
+    assert_eq!(deployment.limits["web"].cpus, 2);
+    assert_eq!(deployment.limits["web"].memory, 512);
+}
+
+#[test]
+fn recursive_fields_are_added_when_chosen() {
+    let mut h =
+        FormHarness::for_survey::<SavedSearch>(&RatatuiFormBackend::new()).with_size(80, 50);
+    h.type_text("sources").press(KeyCode::Tab);
+    assert!(!h.screen().contains("Pattern"));
+
+    // Choosing `Not` adds the questions of the negated filter
+    h.press(KeyCode::Down)
+        .press(KeyCode::Enter)
+        .press(KeyCode::Tab);
+    h.press(KeyCode::Enter).press(KeyCode::Tab);
+    h.type_text("*.rs").press(KeyCode::F(10));
+    assert!(h.is_finished());
+
+    let search = SavedSearch::from_responses(&h.finish().unwrap());
+    assert_eq!(search.name, "sources");
+    let Filter::Not(inner) = search.filter else {
+        panic!("expected a negated filter");
+    };
+    assert!(matches!(*inner, Filter::Matches { ref pattern } if pattern == "*.rs"));
+}
+
+#[test]
+fn highlighted_option_shows_its_help() {
+    let mut h =
+        FormHarness::for_survey::<SavedSearch>(&RatatuiFormBackend::new()).with_size(80, 50);
+    assert!(!h.screen().contains("glob pattern"));
+
+    h.press(KeyCode::Tab);
+    assert!(
+        h.screen()
+            .contains("Match file names against a glob pattern.")
+    );
+    h.press(KeyCode::Down);
+    let screen = h.screen();
+    assert!(
+        screen.contains("Match everything the inner filter does not."),
+        "{screen}"
+    );
+    assert!(!screen.contains("glob pattern"), "{screen}");
+}
+
+#[test]
+fn too_deeply_nested_fields_are_refused() {
+    let mut h =
+        FormHarness::for_survey::<SavedSearch>(&RatatuiFormBackend::new()).with_size(80, 80);
+    h.type_text("deep").press(KeyCode::Tab);
+
+    // Each `And` nests its first filter one level deeper
+    for _ in 0..3 {
+        h.press(KeyCode::Down)
+            .press(KeyCode::Down)
+            .press(KeyCode::Enter)
+            .press(KeyCode::Tab);
+    }
+    assert!(
+        h.screen()
+            .contains("Filter cannot be nested more than 2 levels deep")
+    );
+    h.press(KeyCode::F(10));
+    assert!(!h.is_finished());
+}
@@ -5,10 +5,12 @@
 //! - Type-specific builder with `suggest_*` and `assume_*` methods
 
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote};
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::ext::IdentExt;
 use syn::{
-    Attribute, Data, DeriveInput, Expr, Fields, Ident, Lit, LitStr, Meta, Type, parse_macro_input,
+    Attribute, Data, DeriveInput, Expr, Fields, GenericParam, Generics, Ident, Lit, LitStr, Meta,
+    Type, parse_macro_input, parse_quote,
 };
 
 /// Derive the `Survey` trait for a struct or enum.
@@ -20,27 +22,66 @@ use syn::{
 /// - `#[epilogue("...")]` - Message shown after the survey completes
 /// - `#[validate("fn_name")]` - Composite validator function
 /// - `#[validate_fields("fn_name")]` - Propagate a field-level validator to all numeric child fields
+/// - `#[survey(serde)]` - Follow the type's serde attributes, so response paths
+///   match the serialized keys: `rename` and `rename_all` name fields and
+///   variants, `skip` leaves a field unasked and fills it from `Default` (or
+///   `default = "fn"`), `default` fills in a field without an answer (on a
+///   struct, from the struct's default), and `flatten` asks a nested struct's
+///   questions as the parent's own
 ///
 /// ## On fields
-/// - `#[ask("...")]` - The prompt text shown to the user (required for non-primitive types)
-/// - `#[mask]` - Hide input (for passwords)
+/// - `#[ask("...")]` - The prompt text shown to the user. Without it, the first
+///   paragraph of the doc comment is the prompt and the rest is the question's
+///   help text; the same goes for enum variants
+/// - `#[mask]` - Hide input (for passwords); the answer is kept as a secret.
+///   Validators receive it as `ResponseValue::Secret`, not `ResponseValue::String`.
+///   `secrecy::SecretString` fields are masked without it when written with
+///   their path (needs the `secrecy` feature of `elicitor`); an imported
+///   `SecretString` needs `#[mask]`
+/// - `#[confirm_entry]` - With `#[mask]`, ask twice and require both entries to match
+/// - `#[strength_meter]` - With `#[mask]`, show a password strength meter under the field
 /// - `#[multiline]` - Open text editor / show textarea
-/// - `#[validate("fn_name")]` - Field-level validator function
-/// - `#[min(n)]` / `#[max(n)]` - Numeric bounds
+/// - `#[scalar]` - Ask for the field as text and parse it through `SurveyScalar`
+///   (or `FromStr + Display`); `std::net::IpAddr` and `std::net::SocketAddr` types
+///   need no attribute when written with their path
+/// - `#[temporal]` - Ask for the field as a date, time or duration through
+///   `TemporalValue`; needed only when the type isn't written with its path, as
+///   in `elicitor::Date` or `std::time::Duration`
+/// - `#[validate("fn_name")]` - Field-level validator function (for map fields,
+///   checks each value)
+/// - `#[validate_key("fn_name")]` - For `HashMap<String, T>` and `BTreeMap<String, T>`
+///   fields, checks each key
+/// - `#[min(n)]` / `#[max(n)]` - Numeric bounds, or bounds as text for date, time
+///   and duration fields (e.g. `#[min("2024-01-01")]`, `#[max("2h")]`)
 /// - `#[multiselect]` - For `Vec<Enum>` fields, enables multi-select
+/// - `#[max_depth(n)]` - For `Box`, `Rc` or `Arc` fields of a recursive type, how
+///   deep the type may be nested (default 4)
+/// - `#[skip]` - Don't ask for the field; it is filled from `Default`, or from
+///   the function given with `#[skip(default = "fn")]`
+/// - `#[flatten]` - Ask a nested `Survey` struct's questions as this type's own,
+///   without a path segment for the field
 #[proc_macro_derive(
     Survey,
     attributes(
         ask,
         mask,
+        confirm_entry,
+        strength_meter,
         multiline,
+        scalar,
+        temporal,
         validate,
         validate_fields,
+        validate_key,
         min,
         max,
         prelude,
         epilogue,
-        multiselect
+        multiselect,
+        max_depth,
+        skip,
+        flatten,
+        survey
     )
 )]
 pub fn elicit(input: TokenStream) -> TokenStream {
@@ -81,12 +122,18 @@ fn implement_survey(input: &DeriveInput) -> syn::Result<TokenStream2> {
     // Generate ValidationContext struct for validators
     let validation_context = generate_validation_context(input)?;
 
+    let generics = bounded_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let suggest_builder_name = format_ident!("{}SuggestBuilder", name);
+    let suggest_builder_generics = builder_generics(input);
+    let (_, suggest_builder_args, _) = suggest_builder_generics.split_for_impl();
+
     Ok(quote! {
         #validator_checks
 
         #validation_context
 
-        impl elicitor::Survey for #name {
+        impl #impl_generics elicitor::Survey for #name #ty_generics #where_clause {
             fn survey() -> elicitor::SurveyDefinition {
                 #survey_fn
             }
@@ -110,9 +157,13 @@ fn implement_survey(input: &DeriveInput) -> syn::Result<TokenStream2> {
             }
         }
 
-        impl #name {
+        impl #impl_generics elicitor::SurveySuggest for #name #ty_generics #where_clause {
+            type SuggestBuilder<'a> = #suggest_builder_name #suggest_builder_args;
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
             /// Returns a builder for running this survey.
-            pub fn builder() -> #builder_name {
+            pub fn builder() -> #builder_name #ty_generics {
                 #builder_name::new()
             }
 
@@ -123,6 +174,152 @@ fn implement_survey(input: &DeriveInput) -> syn::Result<TokenStream2> {
     })
 }
 
+// ============================================================================
+// Generics
+// ============================================================================
+
+/// The type's generics, with a bound on each type parameter used by a field:
+/// `Survey` for nested fields and `SurveyScalar` for `#[scalar]` fields.
+fn bounded_generics(input: &DeriveInput) -> Generics {
+    let fields: Vec<&syn::Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    // Skipped fields are never asked; errors in their attributes are reported
+    // by survey()
+    let naming = Naming::of(input).unwrap_or_default();
+    let fields: Vec<&syn::Field> = fields
+        .into_iter()
+        .filter(|f| !FieldLayout::of(f, 0, naming).is_ok_and(|l| l.skip.is_some()))
+        .collect();
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        let users = fields
+            .iter()
+            .filter(|f| type_mentions(&f.ty, std::slice::from_ref(&param.ident)));
+        let (mut nested, mut scalar) = (false, false);
+        for field in users {
+            if FieldFlags::of(&field.attrs).scalar {
+                scalar = true;
+            } else {
+                nested = true;
+            }
+        }
+        if nested {
+            param.bounds.push(parse_quote!(elicitor::Survey));
+        }
+        if scalar {
+            param.bounds.push(parse_quote!(elicitor::SurveyScalar));
+        }
+    }
+    generics
+}
+
+/// The bounded generics of the type with the lifetime `'a` of a builder in front.
+fn builder_generics(input: &DeriveInput) -> Generics {
+    let mut generics = bounded_generics(input);
+    generics.params.insert(0, parse_quote!('a));
+    generics
+}
+
+/// Generic arguments naming a builder of this type with an elided lifetime,
+/// e.g. `<'_, T>`.
+fn elided_builder_args(input: &DeriveInput) -> TokenStream2 {
+    let args = input.generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(l) => l.lifetime.to_token_stream(),
+        GenericParam::Type(t) => t.ident.to_token_stream(),
+        GenericParam::Const(c) => c.ident.to_token_stream(),
+    });
+    quote! { <'_ #(, #args)*> }
+}
+
+/// The type parameters of the type being derived.
+fn type_params(input: &DeriveInput) -> Vec<Ident> {
+    input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect()
+}
+
+/// Whether `ty` refers to any of `params`.
+fn type_mentions(ty: &Type, params: &[Ident]) -> bool {
+    fn walk(tokens: TokenStream2, params: &[Ident]) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree::Ident(ident) => params.contains(&ident),
+            TokenTree::Group(group) => walk(group.stream(), params),
+            _ => false,
+        })
+    }
+    !params.is_empty() && walk(ty.to_token_stream(), params)
+}
+
+/// Whether a field of type `ty` is answered with a single value, so that its
+/// builder methods take the value itself rather than a closure over the
+/// builder of a nested survey.
+fn is_single_answer(ty: &Type, flags: FieldFlags) -> bool {
+    matches!(
+        type_to_string(ty).as_str(),
+        "String"
+            | "bool"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "usize"
+            | "f32"
+            | "f64"
+            | "PathBuf"
+    ) || is_secret_string(ty, flags)
+        || is_temporal(ty, flags)
+        || is_scalar(ty, flags)
+}
+
+/// The `SuggestBuilder` of a nested type and the bounds on the closures that
+/// fill it in. The builder is named through the type's `SurveySuggest` impl,
+/// so type parameters and types from other modules work alike.
+fn suggest_builder_type(ty: &Type) -> (TokenStream2, TokenStream2) {
+    (
+        quote! { <#ty as elicitor::SurveySuggest>::SuggestBuilder<'_> },
+        quote! {
+            #ty: elicitor::SurveySuggest,
+            __ElicitorF: for<'b> elicitor::SuggestFn<'b, #ty>
+        },
+    )
+}
+
+/// The builder behind the `suggest_*`/`assume_*` methods of an `Option<T>`
+/// field. Nested survey types share `elicitor::OptionSuggestBuilder`; text,
+/// number and other single-answer types get one generated per type, generic
+/// over the type parameters that `T` mentions.
+fn option_builder_type(inner_ty: &Type, flags: FieldFlags, params: &[Ident]) -> TokenStream2 {
+    let inner_ty = &strip_pointers(inner_ty);
+    if !is_single_answer(inner_ty, flags) {
+        return quote! { elicitor::OptionSuggestBuilder<'_, #inner_ty> };
+    }
+    let name = format_ident!(
+        "Option{}SuggestBuilder",
+        capitalize_first(&type_to_string(inner_ty))
+    );
+    let args = mentioned_params(inner_ty, params);
+    quote! { #name<'_ #(, #args)*> }
+}
+
+/// The parameters among `params` that `ty` refers to.
+fn mentioned_params<'p>(ty: &Type, params: &'p [Ident]) -> Vec<&'p Ident> {
+    params
+        .iter()
+        .filter(|param| type_mentions(ty, std::slice::from_ref(*param)))
+        .collect()
+}
+
 // ============================================================================
 // Attribute Extraction
 // ============================================================================
@@ -167,52 +364,349 @@ impl TypeAttrs {
 /// Attributes that can appear on fields
 struct FieldAttrs {
     ask: Option<String>,
+    /// Help text from the doc comment, when it gives the prompt
+    help: Option<String>,
     mask: bool,
+    confirm_entry: bool,
+    strength_meter: bool,
     multiline: bool,
+    scalar: bool,
+    temporal: bool,
     validate: Option<Ident>,
+    /// Validator for the keys of a map field
+    validate_key: Option<Ident>,
     min: Option<i64>,
     max: Option<i64>,
+    /// Bounds given as text, for date, time and duration fields
+    min_text: Option<LitStr>,
+    max_text: Option<LitStr>,
     multiselect: bool,
+    /// How deep a recursive field may be nested
+    max_depth: Option<usize>,
 }
 
 impl FieldAttrs {
     fn extract(attrs: &[Attribute]) -> syn::Result<Self> {
         let mut ask = None;
         let mut mask = false;
+        let mut confirm_entry = false;
+        let mut strength_meter = false;
         let mut multiline = false;
+        let mut scalar = false;
+        let mut temporal = false;
         let mut validate = None;
+        let mut validate_key = None;
         let mut min = None;
         let mut max = None;
+        let mut min_text = None;
+        let mut max_text = None;
         let mut multiselect = false;
+        let mut max_depth = None;
 
         for attr in attrs {
             if attr.path().is_ident("ask") {
                 ask = Some(extract_string_attr(attr)?);
             } else if attr.path().is_ident("mask") {
                 mask = true;
+            } else if attr.path().is_ident("confirm_entry") {
+                require_mask(attrs, attr)?;
+                confirm_entry = true;
+            } else if attr.path().is_ident("strength_meter") {
+                require_mask(attrs, attr)?;
+                strength_meter = true;
             } else if attr.path().is_ident("multiline") {
                 multiline = true;
+            } else if attr.path().is_ident("scalar") {
+                scalar = true;
+            } else if attr.path().is_ident("temporal") {
+                temporal = true;
             } else if attr.path().is_ident("validate") {
                 validate = Some(extract_ident_attr(attr)?);
+            } else if attr.path().is_ident("validate_key") {
+                validate_key = Some(extract_ident_attr(attr)?);
             } else if attr.path().is_ident("min") {
-                min = Some(extract_int_attr(attr)?);
+                match extract_text_bound(attr) {
+                    Some(text) => min_text = Some(text),
+                    None => min = Some(extract_int_attr(attr)?),
+                }
             } else if attr.path().is_ident("max") {
-                max = Some(extract_int_attr(attr)?);
+                match extract_text_bound(attr) {
+                    Some(text) => max_text = Some(text),
+                    None => max = Some(extract_int_attr(attr)?),
+                }
             } else if attr.path().is_ident("multiselect") {
                 multiselect = true;
+            } else if attr.path().is_ident("max_depth") {
+                let depth = extract_int_attr(attr)?;
+                max_depth = Some(usize::try_from(depth).map_err(|_| {
+                    syn::Error::new_spanned(attr, "max_depth must not be negative")
+                })?);
             }
         }
 
+        // Without #[ask], the doc comment gives the prompt and help text
+        let mut help = None;
+        if ask.is_none() {
+            (ask, help) = extract_doc(attrs);
+        }
+
         Ok(Self {
             ask,
+            help,
             mask,
+            confirm_entry,
+            strength_meter,
             multiline,
+            scalar,
+            temporal,
             validate,
+            validate_key,
             min,
             max,
+            min_text,
+            max_text,
             multiselect,
+            max_depth,
         })
     }
+
+    fn flags(&self) -> FieldFlags {
+        FieldFlags {
+            mask: self.mask,
+            scalar: self.scalar,
+            temporal: self.temporal,
+        }
+    }
+}
+
+/// The first paragraph of a doc comment, and the paragraphs after it.
+fn extract_doc(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
+    let mut paragraphs = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+    let docs = attrs.iter().filter_map(|attr| match &attr.meta {
+        Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(doc), ..
+            }) => Some(doc.value()),
+            _ => None,
+        },
+        _ => None,
+    });
+    for doc in docs {
+        // Block comments bring several lines at once, and a bare `///` an
+        // empty one
+        for line in doc.split('\n').map(str::trim) {
+            if !line.is_empty() {
+                lines.push(line.to_string());
+            } else if !lines.is_empty() {
+                paragraphs.push(lines.join(" "));
+                lines.clear();
+            }
+        }
+    }
+    if !lines.is_empty() {
+        paragraphs.push(lines.join(" "));
+    }
+
+    let mut paragraphs = paragraphs.into_iter();
+    let prompt = paragraphs.next();
+    let help: Vec<_> = paragraphs.collect();
+    (prompt, (!help.is_empty()).then(|| help.join("\n\n")))
+}
+
+/// Whether the field is marked `#[mask]`.
+fn has_mask(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|a| a.path().is_ident("mask"))
+}
+
+/// Types asked with a single question of their own.
+const PRIMITIVE_TYPES: &[&str] = &[
+    "String", "&str", "bool", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64",
+    "usize", "f32", "f64", "PathBuf",
+];
+
+/// Whether the field is asked as the questions of another `Survey` type.
+fn is_nested_survey(ty: &Type, attrs: &FieldAttrs) -> bool {
+    let flags = attrs.flags();
+    !attrs.mask
+        && !attrs.multiline
+        && extract_option_inner_type(ty).is_none()
+        && extract_vec_inner_type(ty).is_none()
+        && extract_map_types(ty).is_none()
+        && extract_pointer_inner_type(ty).is_none()
+        && !PRIMITIVE_TYPES.contains(&type_to_string(ty).as_str())
+        && !is_secret_string(ty, flags)
+        && !is_temporal(ty, flags)
+        && !is_scalar(ty, flags)
+}
+
+/// `secrecy::SecretString` written with its path, which is masked without
+/// `#[mask]`, like [`TEMPORAL_TYPES`].
+const SECRET_STRINGS: &[&str] = &["secrecy::SecretString", "elicitor::secrecy::SecretString"];
+
+/// Whether the field holds a `secrecy::SecretString`: written with its path,
+/// or named `SecretString` and marked `#[mask]`.
+fn is_secret_string(ty: &Type, flags: FieldFlags) -> bool {
+    let path = type_path_string(ty);
+    SECRET_STRINGS.contains(&path.as_str()) || (flags.mask && path == "SecretString")
+}
+
+/// Field attributes that change how a field's type is read back from responses.
+#[derive(Clone, Copy)]
+struct FieldFlags {
+    /// `#[mask]`: the answer is stored as a secret
+    mask: bool,
+    /// `#[scalar]`: the type is parsed from text through `SurveyScalar`
+    scalar: bool,
+    /// `#[temporal]`: the type is a date, time or duration
+    temporal: bool,
+}
+
+impl FieldFlags {
+    fn of(attrs: &[Attribute]) -> Self {
+        Self {
+            mask: has_mask(attrs),
+            scalar: attrs.iter().any(|a| a.path().is_ident("scalar")),
+            temporal: attrs.iter().any(|a| a.path().is_ident("temporal")),
+        }
+    }
+}
+
+/// Standard library types that are treated as scalars without `#[scalar]`.
+///
+/// They are matched by their full path only, since a bare `IpAddr` could as
+/// well be a `Survey` type of the user's own.
+const STD_SCALARS: &[&str] = &[
+    "std::net::IpAddr",
+    "std::net::Ipv4Addr",
+    "std::net::Ipv6Addr",
+    "std::net::SocketAddr",
+    "std::net::SocketAddrV4",
+    "std::net::SocketAddrV6",
+    "core::net::IpAddr",
+    "core::net::Ipv4Addr",
+    "core::net::Ipv6Addr",
+    "core::net::SocketAddr",
+    "core::net::SocketAddrV4",
+    "core::net::SocketAddrV6",
+];
+
+/// Date, time and duration types asked for through `TemporalValue` without
+/// `#[temporal]`, matched by their full path like [`STD_SCALARS`].
+const TEMPORAL_TYPES: &[&str] = &[
+    "elicitor::Date",
+    "elicitor::Time",
+    "elicitor::DateTime",
+    "elicitor_types::Date",
+    "elicitor_types::Time",
+    "elicitor_types::DateTime",
+    "std::time::Duration",
+    "core::time::Duration",
+    "chrono::NaiveDate",
+    "chrono::NaiveTime",
+    "chrono::NaiveDateTime",
+    "chrono::naive::NaiveDate",
+    "chrono::naive::NaiveTime",
+    "chrono::naive::NaiveDateTime",
+    "time::Date",
+    "time::Time",
+    "time::PrimitiveDateTime",
+];
+
+/// The path of a type as written, e.g. `std::net::IpAddr`, without generic
+/// arguments and looking through `Box`, `Rc` and `Arc`.
+fn type_path_string(ty: &Type) -> String {
+    if let Some(inner_ty) = extract_pointer_inner_type(ty) {
+        return type_path_string(&inner_ty);
+    }
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+        _ => String::new(),
+    }
+}
+
+/// Whether the field is a date, time or duration.
+fn is_temporal(ty: &Type, flags: FieldFlags) -> bool {
+    extract_option_inner_type(ty).is_none()
+        && extract_vec_inner_type(ty).is_none()
+        && extract_map_types(ty).is_none()
+        && (flags.temporal || TEMPORAL_TYPES.contains(&type_path_string(ty).as_str()))
+}
+
+/// Check that a `#[min("...")]` or `#[max("...")]` bound of a temporal field
+/// parses as the field's kind of value.
+fn check_temporal_bound(ty: &Type, bound: &LitStr) -> syn::Result<()> {
+    use elicitor_types::{Date, DateTime, Time, parse_duration};
+
+    let text = bound.value();
+    let path = type_path_string(ty);
+    let result = match path.rsplit("::").next() {
+        Some("Date" | "NaiveDate") => text.parse::<Date>().map(drop),
+        Some("Time" | "NaiveTime") => text.parse::<Time>().map(drop),
+        Some("DateTime" | "NaiveDateTime" | "PrimitiveDateTime") => {
+            text.parse::<DateTime>().map(drop)
+        }
+        Some("Duration") => parse_duration(&text).map(drop),
+        // A type of its own marked #[temporal] may take any of them
+        _ => {
+            let parses = text.parse::<Date>().is_ok()
+                || text.parse::<Time>().is_ok()
+                || text.parse::<DateTime>().is_ok()
+                || parse_duration(&text).is_ok();
+            if parses {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected a date, time, date and time or duration, got '{text}'"
+                ))
+            }
+        }
+    };
+    result.map_err(|err| syn::Error::new_spanned(bound, format!("invalid bound: {err}")))
+}
+
+/// Whether the field is asked for as text and parsed through `SurveyScalar`.
+fn is_scalar(ty: &Type, flags: FieldFlags) -> bool {
+    extract_option_inner_type(ty).is_none()
+        && extract_vec_inner_type(ty).is_none()
+        && extract_map_types(ty).is_none()
+        && (flags.scalar || STD_SCALARS.contains(&type_path_string(ty).as_str()))
+}
+
+/// Parse `text` into the scalar type `ty`, through the type's own
+/// `SurveyScalar` impl if it has one and through `FromStr` otherwise.
+fn scalar_parse(ty: &Type, text: TokenStream2) -> TokenStream2 {
+    quote! {
+        {
+            use elicitor::{ViaFromStr as _, ViaSurveyScalar as _};
+            (&elicitor::ScalarProbe::<#ty>::new()).parse_response(#text)
+        }
+    }
+}
+
+/// The answer for the scalar `value` of type `ty`, the inverse of [`scalar_parse`].
+fn scalar_format(ty: &Type, value: TokenStream2) -> TokenStream2 {
+    quote! {
+        elicitor::ResponseValue::String({
+            use elicitor::{ViaFromStr as _, ViaSurveyScalar as _};
+            (&elicitor::ScalarProbe::<#ty>::new()).format_response(#value)
+        })
+    }
+}
+
+/// Reject `attr` unless the field is also marked `#[mask]`.
+fn require_mask(attrs: &[Attribute], attr: &Attribute) -> syn::Result<()> {
+    if has_mask(attrs) {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(attr, "requires #[mask]"))
+    }
 }
 
 fn extract_string_attr(attr: &Attribute) -> syn::Result<String> {
@@ -247,6 +741,14 @@ fn extract_ident_attr(attr: &Attribute) -> syn::Result<Ident> {
     }
 }
 
+/// A `#[min("...")]` or `#[max("...")]` bound given as a string literal.
+fn extract_text_bound(attr: &Attribute) -> Option<LitStr> {
+    match &attr.meta {
+        Meta::List(list) => list.parse_args::<LitStr>().ok(),
+        _ => None,
+    }
+}
+
 fn extract_int_attr(attr: &Attribute) -> syn::Result<i64> {
     let meta = &attr.meta;
     match meta {
@@ -274,6 +776,345 @@ fn extract_int_attr(attr: &Attribute) -> syn::Result<i64> {
     }
 }
 
+// ============================================================================
+// Field Naming and Layout
+// ============================================================================
+
+/// A serde `rename_all` rule.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    format!("unknown rename rule `{other}`"),
+                ));
+            }
+        })
+    }
+
+    /// Rename a snake_case field the way serde does.
+    fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => field.split('_').map(capitalize_first).collect(),
+            Self::Camel => lowercase_first(&Self::Pascal.apply_to_field(field)),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    /// Rename a PascalCase variant the way serde does.
+    fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_string(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => lowercase_first(variant),
+            Self::Snake => to_snake_case(variant),
+            Self::ScreamingSnake => to_snake_case(variant).to_ascii_uppercase(),
+            Self::Kebab => to_snake_case(variant).replace('_', "-"),
+            Self::ScreamingKebab => to_snake_case(variant)
+                .to_ascii_uppercase()
+                .replace('_', "-"),
+        }
+    }
+}
+
+/// The `#[serde(...)]` attributes that decide where answers are kept.
+///
+/// Everything else serde understands is passed over.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<RenameRule>,
+    rename_all_fields: Option<RenameRule>,
+    /// `#[serde(default)]` or `#[serde(default = "fn")]`
+    default: Option<TokenStream2>,
+    /// `#[serde(skip)]` or `#[serde(skip_deserializing)]`
+    skip: bool,
+    flatten: bool,
+}
+
+impl SerdeAttrs {
+    fn extract(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut serde = Self::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    serde.rename = serde_name(&meta)?.map(|lit| lit.value());
+                } else if meta.path.is_ident("rename_all") {
+                    serde.rename_all = serde_name(&meta)?
+                        .map(|lit| RenameRule::parse(&lit))
+                        .transpose()?;
+                } else if meta.path.is_ident("rename_all_fields") {
+                    serde.rename_all_fields = serde_name(&meta)?
+                        .map(|lit| RenameRule::parse(&lit))
+                        .transpose()?;
+                } else if meta.path.is_ident("default") {
+                    serde.default = Some(default_value(&meta)?);
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    serde.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    serde.flatten = true;
+                } else {
+                    skip_meta_value(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(serde)
+    }
+}
+
+/// `Default::default()`, or a call to the function named by `default = "fn"`.
+fn default_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<TokenStream2> {
+    if !meta.input.peek(syn::Token![=]) {
+        return Ok(quote! { Default::default() });
+    }
+    let path: syn::ExprPath = meta.value()?.parse::<LitStr>()?.parse()?;
+    Ok(quote! { #path() })
+}
+
+/// The value of a `#[skip]` field, given by `#[skip(default = "fn")]` or
+/// `Default`.
+fn extract_skip_default(attr: &Attribute) -> syn::Result<TokenStream2> {
+    let mut default = quote! { Default::default() };
+    if let Meta::List(_) = &attr.meta {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                default = default_value(&meta)?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `default = \"fn\"`"))
+            }
+        })?;
+    }
+    Ok(default)
+}
+
+/// The name given by `name = "..."`, or by `name(deserialize = "...")`,
+/// which is the one that matters when answers are read back.
+fn serde_name(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("deserialize") {
+            name = Some(inner.value()?.parse()?);
+        } else {
+            skip_meta_value(&inner)?;
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+/// Pass over the value of a serde attribute that doesn't concern surveys.
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<TokenTree>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<TokenTree>()?;
+    }
+    Ok(())
+}
+
+/// How the fields or variants of a struct, enum or enum variant are named in
+/// response paths.
+#[derive(Clone, Copy, Default)]
+struct Naming {
+    /// `#[survey(serde)]`: follow the type's serde attributes
+    serde: bool,
+    rename_all: Option<RenameRule>,
+    /// For enums, the rule for the fields of every variant
+    rename_all_fields: Option<RenameRule>,
+    /// `#[serde(default)]` on a struct: unanswered fields are taken from the
+    /// struct's default, which `from_responses` binds to `__default`
+    container_default: bool,
+}
+
+impl Naming {
+    /// The naming of the type's own fields, or of its variants.
+    fn of(input: &DeriveInput) -> syn::Result<Self> {
+        let mut serde = false;
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("survey")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("serde") {
+                    serde = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `serde`"))
+                }
+            })?;
+        }
+        if !serde {
+            return Ok(Self::default());
+        }
+
+        let attrs = SerdeAttrs::extract(&input.attrs)?;
+        Ok(Self {
+            serde,
+            rename_all: attrs.rename_all,
+            rename_all_fields: attrs.rename_all_fields,
+            container_default: attrs.default.is_some() && matches!(input.data, Data::Struct(_)),
+        })
+    }
+
+    /// The struct's own default, for `#[serde(default)]` on the struct.
+    fn container_default(self, input: &DeriveInput) -> syn::Result<Option<TokenStream2>> {
+        if !self.container_default {
+            return Ok(None);
+        }
+        Ok(SerdeAttrs::extract(&input.attrs)?.default)
+    }
+
+    /// The naming of the fields of one of the enum's variants.
+    fn of_variant(self, variant: &syn::Variant) -> syn::Result<Self> {
+        if !self.serde {
+            return Ok(self);
+        }
+        let attrs = SerdeAttrs::extract(&variant.attrs)?;
+        Ok(Self {
+            serde: true,
+            rename_all: attrs.rename_all.or(self.rename_all_fields),
+            rename_all_fields: None,
+            container_default: false,
+        })
+    }
+
+    /// The name a variant is listed under, unless it has `#[ask]`.
+    fn variant_name(self, variant: &syn::Variant) -> syn::Result<String> {
+        if !self.serde {
+            return Ok(variant.ident.to_string());
+        }
+        let name = variant.ident.unraw().to_string();
+        let attrs = SerdeAttrs::extract(&variant.attrs)?;
+        Ok(match (attrs.rename, self.rename_all) {
+            (Some(rename), _) => rename,
+            (None, Some(rule)) => rule.apply_to_variant(&name),
+            (None, None) => name,
+        })
+    }
+}
+
+/// Where the answers for a field are kept.
+struct FieldLayout {
+    /// The field's segment of its response paths; empty when flattened
+    key: String,
+    /// The field isn't asked, and is built from this expression instead
+    skip: Option<TokenStream2>,
+    /// The questions of the field's type are asked as the parent's own
+    flatten: bool,
+    /// Used when the responses hold no answer for the field
+    default: Option<TokenStream2>,
+}
+
+impl FieldLayout {
+    fn of(field: &syn::Field, index: usize, naming: Naming) -> syn::Result<Self> {
+        let mut layout = Self {
+            key: match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => index.to_string(),
+            },
+            skip: None,
+            flatten: false,
+            default: None,
+        };
+
+        if naming.serde {
+            let serde = SerdeAttrs::extract(&field.attrs)?;
+            if let Some(ident) = &field.ident {
+                let name = ident.unraw().to_string();
+                layout.key = match (serde.rename, naming.rename_all) {
+                    (Some(rename), _) => rename,
+                    (None, Some(rule)) => rule.apply_to_field(&name),
+                    (None, None) => name,
+                };
+            }
+            // As in serde, fields without a default of their own take the
+            // one from the struct's default
+            let default = serde.default.or_else(|| {
+                naming.container_default.then(|| {
+                    let member = match &field.ident {
+                        Some(ident) => syn::Member::Named(ident.clone()),
+                        None => syn::Member::Unnamed(syn::Index::from(index)),
+                    };
+                    quote! { __default.#member }
+                })
+            });
+            if serde.skip {
+                layout.skip = Some(
+                    default
+                        .clone()
+                        .unwrap_or_else(|| quote! { Default::default() }),
+                );
+            }
+            layout.flatten = serde.flatten;
+            layout.default = default;
+        }
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("skip") {
+                layout.skip = Some(extract_skip_default(attr)?);
+            } else if attr.path().is_ident("flatten") {
+                layout.flatten = true;
+            }
+        }
+
+        if layout.flatten && layout.skip.is_none() {
+            if !is_nested_survey(&field.ty, &FieldAttrs::extract(&field.attrs)?) {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "only fields of a Survey type can be flattened",
+                ));
+            }
+            layout.key = String::new();
+        }
+        Ok(layout)
+    }
+
+    /// The path of the field below `prefix`.
+    fn path_below(&self, prefix: &str) -> String {
+        match (prefix.is_empty(), self.key.is_empty()) {
+            (true, _) => self.key.clone(),
+            (false, true) => prefix.to_string(),
+            (false, false) => format!("{}.{}", prefix, self.key),
+        }
+    }
+}
+
+/// The layouts of a struct's or variant's fields, paired with the fields.
+fn field_layouts(fields: &Fields, naming: Naming) -> syn::Result<Vec<(&syn::Field, FieldLayout)>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| Ok((field, FieldLayout::of(field, i, naming)?)))
+        .collect()
+}
+
 // ============================================================================
 // Survey Generation
 // ============================================================================
@@ -289,9 +1130,12 @@ fn generate_survey_fn(input: &DeriveInput, type_attrs: &TypeAttrs) -> syn::Resul
         None => quote! { None },
     };
 
+    let naming = Naming::of(input)?;
     let questions = match &input.data {
-        Data::Struct(data) => generate_struct_questions(data, type_attrs.validate_fields.as_ref())?,
-        Data::Enum(data) => generate_enum_questions(data, &input.ident)?,
+        Data::Struct(data) => {
+            generate_fields_questions(&data.fields, naming, type_attrs.validate_fields.as_ref())?
+        }
+        Data::Enum(data) => generate_enum_questions(data, naming)?,
         Data::Union(_) => {
             return Err(syn::Error::new_spanned(
                 input,
@@ -309,95 +1153,81 @@ fn generate_survey_fn(input: &DeriveInput, type_attrs: &TypeAttrs) -> syn::Resul
     })
 }
 
-fn generate_struct_questions(
-    data: &syn::DataStruct,
+/// The questions for the fields of a struct or enum variant, as a `Vec`.
+///
+/// Skipped fields are left out, and flattened fields add the questions of
+/// their type in place.
+fn generate_fields_questions(
+    fields: &Fields,
+    naming: Naming,
     propagated_validator: Option<&Ident>,
 ) -> syn::Result<TokenStream2> {
+    let mut chunks = Vec::new();
     let mut questions = Vec::new();
 
-    match &data.fields {
-        Fields::Named(fields) => {
-            for field in &fields.named {
-                let field_name = field.ident.as_ref().unwrap();
-                let field_name_str = field_name.to_string();
-                let attrs = FieldAttrs::extract(&field.attrs)?;
-                let question = generate_question_for_field(
-                    &field_name_str,
-                    &field.ty,
-                    &attrs,
-                    propagated_validator,
-                )?;
-                questions.push(question);
-            }
+    for (field, layout) in field_layouts(fields, naming)? {
+        if layout.skip.is_some() {
+            continue;
         }
-        Fields::Unnamed(fields) => {
-            for (i, field) in fields.unnamed.iter().enumerate() {
-                let field_name_str = i.to_string();
-                let attrs = FieldAttrs::extract(&field.attrs)?;
-                let question = generate_question_for_field(
-                    &field_name_str,
-                    &field.ty,
-                    &attrs,
-                    propagated_validator,
-                )?;
-                questions.push(question);
-            }
+        if layout.flatten {
+            let ty = &field.ty;
+            chunks.push(quote! { vec![#(#questions),*] });
+            chunks.push(quote! { <#ty as elicitor::Survey>::survey().questions });
+            questions.clear();
+            continue;
         }
-        Fields::Unit => {}
+
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => layout.key.clone(),
+        };
+        let attrs = FieldAttrs::extract(&field.attrs)?;
+        questions.push(generate_question_for_field(
+            &layout.key,
+            &name,
+            &field.ty,
+            &attrs,
+            propagated_validator,
+        )?);
     }
 
-    Ok(quote! { vec![#(#questions),*] })
+    if chunks.is_empty() {
+        return Ok(quote! { vec![#(#questions),*] });
+    }
+    chunks.push(quote! { vec![#(#questions),*] });
+    Ok(quote! {
+        vec![#(#chunks),*].into_iter().flatten().collect::<Vec<_>>()
+    })
 }
 
-fn generate_enum_questions(data: &syn::DataEnum, _enum_name: &Ident) -> syn::Result<TokenStream2> {
+fn generate_enum_questions(data: &syn::DataEnum, naming: Naming) -> syn::Result<TokenStream2> {
     // For enums, we generate a single OneOf question containing all variants
     let mut variants = Vec::new();
 
     for variant in &data.variants {
-        let variant_name = variant.ident.to_string();
-
-        // Check for #[ask] on the variant itself for display text
+        // Check for #[ask] or a doc comment on the variant itself for display text
         let variant_attrs = FieldAttrs::extract(&variant.attrs)?;
-        let display_name = variant_attrs.ask.unwrap_or_else(|| variant_name.clone());
+        let display_name = match variant_attrs.ask {
+            Some(ask) => ask,
+            None => naming.variant_name(variant)?,
+        };
+        let help = variant_attrs
+            .help
+            .as_ref()
+            .map(|help| quote! { .with_help(#help) });
 
         let kind = match &variant.fields {
             Fields::Unit => quote! { elicitor::QuestionKind::Unit },
-            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
-                // Newtype variant - wrap in AllOf with a single Question to preserve prompt
-                let field = &fields.unnamed[0];
-                let attrs = FieldAttrs::extract(&field.attrs)?;
-                let q = generate_question_for_field("0", &field.ty, &attrs, None)?;
-                quote! { elicitor::QuestionKind::AllOf(elicitor::AllOfQuestion::new(vec![#q])) }
-            }
-            Fields::Unnamed(fields) => {
-                // Tuple variant - treat as AllOf
-                let mut qs = Vec::new();
-                for (i, field) in fields.unnamed.iter().enumerate() {
-                    let name = i.to_string();
-                    let attrs = FieldAttrs::extract(&field.attrs)?;
-                    let q = generate_question_for_field(&name, &field.ty, &attrs, None)?;
-                    qs.push(q);
-                }
-                quote! { elicitor::QuestionKind::AllOf(elicitor::AllOfQuestion::new(vec![#(#qs),*])) }
-            }
-            Fields::Named(fields) => {
-                // Struct variant
-                let mut qs = Vec::new();
-                for field in &fields.named {
-                    let name = field.ident.as_ref().unwrap().to_string();
-                    let attrs = FieldAttrs::extract(&field.attrs)?;
-                    let q = generate_question_for_field(&name, &field.ty, &attrs, None)?;
-                    qs.push(q);
-                }
-                quote! { elicitor::QuestionKind::AllOf(elicitor::AllOfQuestion::new(vec![#(#qs),*])) }
+            // Newtype, tuple and struct variants are all asked as AllOf; a
+            // newtype's single Question keeps its prompt
+            fields => {
+                let qs = generate_fields_questions(fields, naming.of_variant(variant)?, None)?;
+                quote! { elicitor::QuestionKind::AllOf(elicitor::AllOfQuestion::new(#qs)) }
             }
         };
 
         variants.push(quote! {
-            elicitor::Variant {
-                name: #display_name.to_string(),
-                kind: #kind,
-            }
+            elicitor::Variant::new(#display_name, #kind)#help
         });
     }
 
@@ -415,6 +1245,7 @@ fn generate_enum_questions(data: &syn::DataEnum, _enum_name: &Ident) -> syn::Res
 }
 
 fn generate_question_for_field(
+    key: &str,
     field_name: &str,
     ty: &Type,
     attrs: &FieldAttrs,
@@ -433,14 +1264,15 @@ fn generate_question_for_field(
         .collect::<Vec<_>>()
         .join(" ");
     let ask = attrs.ask.clone().unwrap_or(default_prompt);
+    let help = attrs.help.as_ref().map(|help| quote! { .with_help(#help) });
     let kind = generate_question_kind(ty, attrs, propagated_validator)?;
 
     Ok(quote! {
         elicitor::Question::new(
-            elicitor::ResponsePath::new(#field_name),
+            elicitor::ResponsePath::new(#key),
             #ask.to_string(),
             #kind,
-        )
+        )#help
     })
 }
 
@@ -449,8 +1281,43 @@ fn generate_question_kind(
     attrs: &FieldAttrs,
     propagated_validator: Option<&Ident>,
 ) -> syn::Result<TokenStream2> {
+    // Smart pointers are asked as their pointee. Nested surveys behind one are
+    // built when they are reached, which keeps recursive types finite
+    if let Some(inner_ty) = extract_pointer_inner_type(ty) {
+        if !is_nested_survey(&inner_ty, attrs) {
+            return generate_question_kind(&inner_ty, attrs, propagated_validator);
+        }
+        let name = type_to_string(&inner_ty);
+        let max_depth = attrs
+            .max_depth
+            .map(|depth| quote! { .with_max_depth(#depth) });
+        return Ok(quote! {
+            elicitor::QuestionKind::Lazy(
+                elicitor::LazyQuestion::new(#name, <#inner_ty as elicitor::Survey>::survey)#max_depth
+            )
+        });
+    }
+
+    // Maps pass their attributes on to the value question
+    if let Some((key_ty, value_ty)) = extract_map_types(ty) {
+        if type_to_string(&key_ty) != "String" {
+            return Err(syn::Error::new_spanned(
+                key_ty,
+                "map fields must have String keys",
+            ));
+        }
+        let value_kind = generate_question_kind(&value_ty, attrs, propagated_validator)?;
+        let key_validator = attrs.validate_key.as_ref().map(|v| {
+            let v_str = v.to_string();
+            quote! { .with_key_validator(#v_str) }
+        });
+        return Ok(quote! {
+            elicitor::QuestionKind::Map(elicitor::MapQuestion::new(#value_kind)#key_validator)
+        });
+    }
+
     // Handle special attributes first
-    if attrs.mask {
+    if attrs.mask || is_secret_string(ty, attrs.flags()) {
         let validate_opt = match (&attrs.validate, propagated_validator) {
             (Some(v), _) => {
                 let v_str = v.to_string();
@@ -462,8 +1329,14 @@ fn generate_question_kind(
             }
             (None, None) => quote! { None },
         };
+        let confirm = attrs.confirm_entry;
+        let strength_meter = attrs.strength_meter;
         return Ok(quote! {
-            elicitor::QuestionKind::Masked(elicitor::MaskedQuestion::with_validator(#validate_opt))
+            elicitor::QuestionKind::Masked(
+                elicitor::MaskedQuestion::with_validator(#validate_opt)
+                    .with_confirmation(#confirm)
+                    .with_strength_meter(#strength_meter)
+            )
         });
     }
 
@@ -639,6 +1512,46 @@ fn generate_question_kind(
                 return Ok(inner_kind);
             }
 
+            let flags = attrs.flags();
+            if is_temporal(ty, flags) {
+                let min_opt = match &attrs.min_text {
+                    Some(m) => {
+                        check_temporal_bound(ty, m)?;
+                        quote! { Some(#m) }
+                    }
+                    None => quote! { None },
+                };
+                let max_opt = match &attrs.max_text {
+                    Some(m) => {
+                        check_temporal_bound(ty, m)?;
+                        quote! { Some(#m) }
+                    }
+                    None => quote! { None },
+                };
+                let validate_opt = match (&attrs.validate, propagated_validator) {
+                    (Some(v), _) | (None, Some(v)) => {
+                        let v_str = v.to_string();
+                        quote! { Some(#v_str.to_string()) }
+                    }
+                    (None, None) => quote! { None },
+                };
+                return Ok(quote! {
+                    <#ty as elicitor::TemporalValue>::question(#min_opt, #max_opt, #validate_opt)
+                });
+            }
+
+            if is_scalar(ty, flags) {
+                // Always name a validator so backends check the text as it is typed;
+                // validate_field runs the parse check alongside any user validator
+                let validate = match (&attrs.validate, propagated_validator) {
+                    (Some(v), _) | (None, Some(v)) => v.to_string(),
+                    (None, None) => format!("<{} as SurveyScalar>::parse_response", type_name),
+                };
+                return Ok(quote! {
+                    elicitor::QuestionKind::Input(elicitor::InputQuestion::with_validator(Some(#validate.to_string())))
+                });
+            }
+
             // Assume it's a nested Survey type
             Ok(quote! {
                 elicitor::QuestionKind::AllOf(
@@ -649,7 +1562,11 @@ fn generate_question_kind(
     }
 }
 
+/// The name of the type, looking through `Box`, `Rc` and `Arc`.
 fn type_to_string(ty: &Type) -> String {
+    if let Some(inner_ty) = extract_pointer_inner_type(ty) {
+        return type_to_string(&inner_ty);
+    }
     match ty {
         Type::Path(path) => path
             .path
@@ -673,6 +1590,27 @@ fn extract_option_inner_type(ty: &Type) -> Option<Type> {
     None
 }
 
+/// The pointee of a `Box<T>`, `Rc<T>` or `Arc<T>`.
+fn extract_pointer_inner_type(ty: &Type) -> Option<Type> {
+    if let Type::Path(path) = ty
+        && let Some(segment) = path.path.segments.last()
+        && (segment.ident == "Box" || segment.ident == "Rc" || segment.ident == "Arc")
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        return Some(inner.clone());
+    }
+    None
+}
+
+/// The type with any `Box`, `Rc` and `Arc` around it removed.
+fn strip_pointers(ty: &Type) -> Type {
+    match extract_pointer_inner_type(ty) {
+        Some(inner_ty) => strip_pointers(&inner_ty),
+        None => ty.clone(),
+    }
+}
+
 fn extract_vec_inner_type(ty: &Type) -> Option<Type> {
     if let Type::Path(path) = ty
         && let Some(segment) = path.path.segments.last()
@@ -685,14 +1623,29 @@ fn extract_vec_inner_type(ty: &Type) -> Option<Type> {
     None
 }
 
+/// The key and value types of a `HashMap<K, V>` or `BTreeMap<K, V>`.
+fn extract_map_types(ty: &Type) -> Option<(Type, Type)> {
+    if let Type::Path(path) = ty
+        && let Some(segment) = path.path.segments.last()
+        && (segment.ident == "HashMap" || segment.ident == "BTreeMap")
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(key)) = args.args.first()
+        && let Some(syn::GenericArgument::Type(value)) = args.args.iter().nth(1)
+    {
+        return Some((key.clone(), value.clone()));
+    }
+    None
+}
+
 // ============================================================================
 // from_responses Generation
 // ============================================================================
 
 fn generate_from_responses_fn(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let naming = Naming::of(input)?;
     match &input.data {
-        Data::Struct(data) => generate_from_responses_struct(&input.ident, data),
-        Data::Enum(data) => generate_from_responses_enum(&input.ident, data),
+        Data::Struct(data) => generate_from_responses_struct(input, data, naming),
+        Data::Enum(data) => generate_from_responses_enum(&input.ident, data, naming),
         Data::Union(_) => Err(syn::Error::new_spanned(
             input,
             "Survey cannot be derived for unions",
@@ -701,95 +1654,41 @@ fn generate_from_responses_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
 }
 
 fn generate_from_responses_struct(
-    name: &Ident,
+    input: &DeriveInput,
     data: &syn::DataStruct,
+    naming: Naming,
 ) -> syn::Result<TokenStream2> {
-    match &data.fields {
-        Fields::Named(fields) => {
-            let field_inits: Vec<_> = fields
-                .named
-                .iter()
-                .map(|f| {
-                    let field_name = f.ident.as_ref().unwrap();
-                    let field_name_str = field_name.to_string();
-                    let ty = &f.ty;
-                    let extraction = generate_value_extraction(&field_name_str, ty);
-                    quote! { #field_name: #extraction }
-                })
-                .collect();
-
-            Ok(quote! {
-                #name {
-                    #(#field_inits),*
-                }
-            })
-        }
-        Fields::Unnamed(fields) => {
-            let field_inits: Vec<_> = fields
-                .unnamed
-                .iter()
-                .enumerate()
-                .map(|(i, f)| {
-                    let field_name_str = i.to_string();
-                    let ty = &f.ty;
-                    generate_value_extraction(&field_name_str, ty)
-                })
-                .collect();
-
-            Ok(quote! {
-                #name(#(#field_inits),*)
-            })
-        }
-        Fields::Unit => Ok(quote! { #name }),
-    }
+    let name = &input.ident;
+    let construction = generate_construction(quote! { #name }, &data.fields, naming)?;
+    Ok(match naming.container_default(input)? {
+        Some(default) => quote! {
+            #[allow(unused_variables)]
+            let __default: Self = #default;
+            #construction
+        },
+        None => construction,
+    })
 }
 
-fn generate_from_responses_enum(name: &Ident, data: &syn::DataEnum) -> syn::Result<TokenStream2> {
-    let variant_arms: Vec<_> = data
+fn generate_from_responses_enum(
+    name: &Ident,
+    data: &syn::DataEnum,
+    naming: Naming,
+) -> syn::Result<TokenStream2> {
+    let variant_arms = data
         .variants
         .iter()
         .enumerate()
         .map(|(idx, variant)| {
             let variant_name = &variant.ident;
-
-            let construction = match &variant.fields {
-                Fields::Unit => quote! { #name::#variant_name },
-                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
-                    let ty = &fields.unnamed[0].ty;
-                    let extraction = generate_value_extraction("0", ty);
-                    quote! { #name::#variant_name(#extraction) }
-                }
-                Fields::Unnamed(fields) => {
-                    let extractions: Vec<_> = fields
-                        .unnamed
-                        .iter()
-                        .enumerate()
-                        .map(|(i, f)| {
-                            let ty = &f.ty;
-                            generate_value_extraction(&i.to_string(), ty)
-                        })
-                        .collect();
-                    quote! { #name::#variant_name(#(#extractions),*) }
-                }
-                Fields::Named(fields) => {
-                    let field_inits: Vec<_> = fields
-                        .named
-                        .iter()
-                        .map(|f| {
-                            let field_name = f.ident.as_ref().unwrap();
-                            let field_name_str = field_name.to_string();
-                            let ty = &f.ty;
-                            let extraction = generate_value_extraction(&field_name_str, ty);
-                            quote! { #field_name: #extraction }
-                        })
-                        .collect();
-                    quote! { #name::#variant_name { #(#field_inits),* } }
-                }
-            };
-
-            quote! { #idx => #construction }
+            let construction = generate_construction(
+                quote! { #name::#variant_name },
+                &variant.fields,
+                naming.of_variant(variant)?,
+            )?;
+            Ok(quote! { #idx => #construction })
         })
-        .collect();
+        .collect::<syn::Result<Vec<_>>>()?;
 
     Ok(quote! {
         let variant_idx = responses
@@ -802,14 +1701,70 @@ fn generate_from_responses_enum(name: &Ident, data: &syn::DataEnum) -> syn::Resu
     })
 }
 
-fn generate_value_extraction(field_name: &str, ty: &Type) -> TokenStream2 {
+/// Build a struct or enum variant with the given path from the responses.
+fn generate_construction(
+    path: TokenStream2,
+    fields: &Fields,
+    naming: Naming,
+) -> syn::Result<TokenStream2> {
+    let layouts = field_layouts(fields, naming)?;
+    let extractions = layouts.iter().map(|(field, layout)| {
+        let extraction = generate_field_extraction(field, layout);
+        match &field.ident {
+            Some(field_name) => quote! { #field_name: #extraction },
+            None => extraction,
+        }
+    });
+
+    Ok(match fields {
+        Fields::Named(_) => quote! { #path { #(#extractions),* } },
+        Fields::Unnamed(_) => quote! { #path(#(#extractions),*) },
+        Fields::Unit => path,
+    })
+}
+
+/// Read a field back from the responses, or fill it in if it's skipped or
+/// has a default and wasn't answered.
+fn generate_field_extraction(field: &syn::Field, layout: &FieldLayout) -> TokenStream2 {
+    if let Some(skip) = &layout.skip {
+        return skip.clone();
+    }
+
+    let key = &layout.key;
+    let extraction = generate_value_extraction(key, &field.ty, FieldFlags::of(&field.attrs));
+    match &layout.default {
+        Some(default) => quote! {
+            if responses.filter_prefix(&elicitor::ResponsePath::new(#key)).is_empty() {
+                #default
+            } else {
+                #extraction
+            }
+        },
+        None => extraction,
+    }
+}
+
+fn generate_value_extraction(field_name: &str, ty: &Type, flags: FieldFlags) -> TokenStream2 {
+    if let Some(inner_ty) = extract_pointer_inner_type(ty) {
+        let inner_extraction = generate_value_extraction(field_name, &inner_ty, flags);
+        return quote! { <#ty>::new(#inner_extraction) };
+    }
+
     let type_name = type_to_string(ty);
     let path_expr = quote! { elicitor::ResponsePath::new(#field_name) };
 
     match type_name.as_str() {
+        "String" if flags.mask => quote! {
+            responses.get_secret(&#path_expr).expect("missing secret").expose_secret().to_string()
+        },
         "String" => quote! {
             responses.get_string(&#path_expr).expect("missing string").to_string()
         },
+        "SecretString" if is_secret_string(ty, flags) => quote! {
+            elicitor::secrecy::SecretString::from(
+                responses.get_secret(&#path_expr).expect("missing secret").expose_secret()
+            )
+        },
         "bool" => quote! {
             responses.get_bool(&#path_expr).expect("missing bool")
         },
@@ -828,7 +1783,7 @@ fn generate_value_extraction(field_name: &str, ty: &Type) -> TokenStream2 {
         _ => {
             // Check for Option<T>
             if let Some(inner_ty) = extract_option_inner_type(ty) {
-                let inner_extraction = generate_value_extraction(field_name, &inner_ty);
+                let inner_extraction = generate_value_extraction(field_name, &inner_ty, flags);
                 return quote! {
                     if responses.has_value(&#path_expr) {
                         Some(#inner_extraction)
@@ -838,6 +1793,27 @@ fn generate_value_extraction(field_name: &str, ty: &Type) -> TokenStream2 {
                 };
             }
 
+            // Maps keep their keys in a list and each value under the entry's index
+            if let Some((_, value_ty)) = extract_map_types(ty) {
+                let value_extraction = generate_value_extraction("", &value_ty, flags);
+                return quote! {
+                    {
+                        let prefix = elicitor::ResponsePath::new(#field_name);
+                        responses
+                            .get_string_list(&prefix.child(elicitor::MAP_KEYS_KEY))
+                            .unwrap_or_default()
+                            .iter()
+                            .enumerate()
+                            .map(|(entry_idx, key)| {
+                                let responses =
+                                    responses.filter_prefix(&prefix.child(&entry_idx.to_string()));
+                                (key.clone(), #value_extraction)
+                            })
+                            .collect()
+                    }
+                };
+            }
+
             // Check for Vec<T>
             if let Some(inner_ty) = extract_vec_inner_type(ty) {
                 let inner_type_name = type_to_string(&inner_ty);
@@ -910,6 +1886,24 @@ fn generate_value_extraction(field_name: &str, ty: &Type) -> TokenStream2 {
                 }
             }
 
+            if is_temporal(ty, flags) {
+                return quote! {
+                    <#ty as elicitor::TemporalValue>::from_response(
+                        responses.get(&#path_expr).expect("missing temporal value")
+                    ).expect("invalid temporal value")
+                };
+            }
+
+            if is_scalar(ty, flags) {
+                let parse = scalar_parse(
+                    ty,
+                    quote! {
+                        responses.get_string(&#path_expr).expect("missing scalar")
+                    },
+                );
+                return quote! { #parse.expect("invalid scalar") };
+            }
+
             // Nested Survey type - filter responses and call its from_responses
             quote! {
                 {
@@ -933,13 +1927,20 @@ fn generate_field_accessors(input: &DeriveInput) -> syn::Result<TokenStream2> {
 
     match &input.data {
         Data::Struct(data) => {
-            if let Fields::Named(fields) = &data.fields {
-                for field in &fields.named {
+            if let Fields::Named(_) = &data.fields {
+                for (field, layout) in field_layouts(&data.fields, Naming::of(input)?)? {
+                    // Skipped fields have no answers to read
+                    if layout.skip.is_some() {
+                        continue;
+                    }
                     let field_name = field.ident.as_ref().unwrap();
-                    let field_name_str = field_name.to_string();
-                    let ty = &field.ty;
 
-                    let accessor = generate_field_accessor_method(&field_name_str, field_name, ty);
+                    let accessor = generate_field_accessor_method(
+                        &layout.key,
+                        field_name,
+                        &field.ty,
+                        FieldFlags::of(&field.attrs),
+                    );
                     accessors.push(accessor);
                 }
             }
@@ -960,12 +1961,29 @@ fn generate_field_accessor_method(
     field_name_str: &str,
     field_name: &Ident,
     ty: &Type,
+    flags: FieldFlags,
 ) -> TokenStream2 {
     let method_name = format_ident!("get_{}", field_name);
+    let ty = &strip_pointers(ty);
     let type_name = type_to_string(ty);
     let path_expr = quote! { elicitor::ResponsePath::new(#field_name_str) };
 
     match type_name.as_str() {
+        "String" if flags.mask => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(responses: &elicitor::Responses) -> Option<String> {
+                responses.get_secret(&#path_expr).ok().map(|s| s.expose_secret().to_string())
+            }
+        },
+        "SecretString" if is_secret_string(ty, flags) => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(responses: &elicitor::Responses) -> Option<elicitor::secrecy::SecretString> {
+                responses
+                    .get_secret(&#path_expr)
+                    .ok()
+                    .map(|s| elicitor::secrecy::SecretString::from(s.expose_secret()))
+            }
+        },
         "String" => quote! {
             /// Get the value of this field from responses, if present.
             pub fn #method_name(responses: &elicitor::Responses) -> Option<String> {
@@ -1002,6 +2020,26 @@ fn generate_field_accessor_method(
                 responses.get_string(&#path_expr).ok().map(std::path::PathBuf::from)
             }
         },
+        _ if is_temporal(ty, flags) => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(responses: &elicitor::Responses) -> Option<#ty> {
+                responses
+                    .get(&#path_expr)
+                    .and_then(<#ty as elicitor::TemporalValue>::from_response)
+            }
+        },
+        _ if is_scalar(ty, flags) => {
+            let parse = scalar_parse(ty, quote! { s });
+            quote! {
+                /// Get the value of this field from responses, if present and valid.
+                pub fn #method_name(responses: &elicitor::Responses) -> Option<#ty> {
+                    responses
+                        .get_string(&#path_expr)
+                        .ok()
+                        .and_then(|s| #parse.ok())
+                }
+            }
+        }
         _ => {
             // For complex types (nested structs, enums, etc.), we don't generate accessors
             // as they would require more complex handling
@@ -1024,14 +2062,19 @@ fn generate_validation_context(input: &DeriveInput) -> syn::Result<TokenStream2>
 
     match &input.data {
         Data::Struct(data) => {
-            if let Fields::Named(fields) = &data.fields {
-                for field in &fields.named {
+            if let Fields::Named(_) = &data.fields {
+                for (field, layout) in field_layouts(&data.fields, Naming::of(input)?)? {
+                    if layout.skip.is_some() {
+                        continue;
+                    }
                     let field_name = field.ident.as_ref().unwrap();
-                    let field_name_str = field_name.to_string();
-                    let ty = &field.ty;
 
-                    let accessor =
-                        generate_context_accessor_method(&field_name_str, field_name, ty);
+                    let accessor = generate_context_accessor_method(
+                        &layout.key,
+                        field_name,
+                        &field.ty,
+                        FieldFlags::of(&field.attrs),
+                    );
                     accessors.push(accessor);
                 }
             }
@@ -1042,17 +2085,26 @@ fn generate_validation_context(input: &DeriveInput) -> syn::Result<TokenStream2>
         Data::Union(_) => {}
     }
 
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, name_generics, _) = input.generics.split_for_impl();
+
     Ok(quote! {
         /// Validation context for #name, providing access to sibling fields.
-        pub struct #context_name<'a> {
+        pub struct #context_name #impl_generics #where_clause {
             responses: &'a elicitor::Responses,
             prefix: elicitor::ResponsePath,
+            _survey: std::marker::PhantomData<fn() -> #name #name_generics>,
         }
 
-        impl<'a> #context_name<'a> {
+        impl #impl_generics #context_name #ty_generics #where_clause {
             /// Create a new validation context with the given prefix path.
             pub fn new(responses: &'a elicitor::Responses, prefix: elicitor::ResponsePath) -> Self {
-                Self { responses, prefix }
+                Self {
+                    responses,
+                    prefix,
+                    _survey: std::marker::PhantomData,
+                }
             }
 
             /// Get the prefix path for this context.
@@ -1075,11 +2127,30 @@ fn generate_context_accessor_method(
     field_name_str: &str,
     field_name: &Ident,
     ty: &Type,
+    flags: FieldFlags,
 ) -> TokenStream2 {
     let method_name = format_ident!("get_{}", field_name);
+    let ty = &strip_pointers(ty);
     let type_name = type_to_string(ty);
 
     match type_name.as_str() {
+        "String" if flags.mask => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(&self) -> Option<String> {
+                let path = self.prefix.child(#field_name_str);
+                self.responses.get_secret(&path).ok().map(|s| s.expose_secret().to_string())
+            }
+        },
+        "SecretString" if is_secret_string(ty, flags) => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(&self) -> Option<elicitor::secrecy::SecretString> {
+                let path = self.prefix.child(#field_name_str);
+                self.responses
+                    .get_secret(&path)
+                    .ok()
+                    .map(|s| elicitor::secrecy::SecretString::from(s.expose_secret()))
+            }
+        },
         "String" => quote! {
             /// Get the value of this field from responses, if present.
             pub fn #method_name(&self) -> Option<String> {
@@ -1122,6 +2193,28 @@ fn generate_context_accessor_method(
                 self.responses.get_string(&path).ok().map(std::path::PathBuf::from)
             }
         },
+        _ if is_temporal(ty, flags) => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(&self) -> Option<#ty> {
+                let path = self.prefix.child(#field_name_str);
+                self.responses
+                    .get(&path)
+                    .and_then(<#ty as elicitor::TemporalValue>::from_response)
+            }
+        },
+        _ if is_scalar(ty, flags) => {
+            let parse = scalar_parse(ty, quote! { s });
+            quote! {
+                /// Get the value of this field from responses, if present and valid.
+                pub fn #method_name(&self) -> Option<#ty> {
+                    let path = self.prefix.child(#field_name_str);
+                    self.responses
+                        .get_string(&path)
+                        .ok()
+                        .and_then(|s| #parse.ok())
+                }
+            }
+        }
         _ => {
             // For complex types, don't generate accessors
             quote! {}
@@ -1141,22 +2234,16 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
     // 2. This function runs ALL validators for ALL fields, so field-specific
     //    min/max checks would incorrectly apply to other fields
 
-    // Helper to check if a path matches a field name
-    // The path could be "field_name" or "parent.field_name" etc.
-    let path_matches_field = |field_name: &str| -> TokenStream2 {
-        quote! {
-            (path.as_str() == #field_name || path.as_str().ends_with(&format!(".{}", #field_name)))
-        }
-    };
+    let naming = Naming::of(input)?;
 
     // Collect field names for path checking in validate_fields
     let field_names: Vec<String> = match &input.data {
         Data::Struct(data) => {
-            if let Fields::Named(fields) = &data.fields {
-                fields
-                    .named
-                    .iter()
-                    .filter_map(|f| f.ident.as_ref().map(|i| i.to_string()))
+            if let Fields::Named(_) = &data.fields {
+                field_layouts(&data.fields, naming)?
+                    .into_iter()
+                    .filter(|(_, layout)| layout.skip.is_none() && !layout.flatten)
+                    .map(|(_, layout)| layout.key)
                     .collect()
             } else {
                 vec![]
@@ -1180,42 +2267,34 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
             validators.push(quote! {
                 // Only run validate_fields if the path matches one of this struct's fields
                 if #(#field_checks)||* {
-                    if let Err(e) = #validator(value, responses, path) {
-                        return Err(e);
-                    }
+                    #validator(value, responses, path)?;
                 }
             });
         } else {
             // Fallback for non-struct types (shouldn't normally happen)
             validators.push(quote! {
-                if let Err(e) = #validator(value, responses, path) {
-                    return Err(e);
-                }
+                #validator(value, responses, path)?;
             });
         }
     }
 
     match &input.data {
         Data::Struct(data) => {
-            if let Fields::Named(fields) = &data.fields {
-                for field in &fields.named {
+            if let Fields::Named(_) = &data.fields {
+                for (field, layout) in field_layouts(&data.fields, naming)? {
+                    if layout.skip.is_some() {
+                        continue;
+                    }
                     let attrs = FieldAttrs::extract(&field.attrs)?;
                     let ty = &field.ty;
-                    let field_name = field.ident.as_ref().unwrap().to_string();
 
-                    if let Some(validator) = &attrs.validate {
-                        let path_check = path_matches_field(&field_name);
-                        // Only run this validator if the path matches this field
-                        validators.push(quote! {
-                            if #path_check {
-                                if let Err(e) = #validator(value, responses, path) {
-                                    return Err(e);
-                                }
-                            }
-                        });
-                    }
+                    let flags = FieldFlags::of(&field.attrs);
+                    validators.extend(generate_field_checks(&layout.key, ty, &attrs, flags));
 
-                    // Delegate to nested Survey types for validation
+                    // Delegate to nested Survey types (or map values) for validation
+                    let ty = &strip_pointers(
+                        &extract_map_types(ty).map_or_else(|| ty.clone(), |(_, v)| v),
+                    );
                     let type_name = type_to_string(ty);
                     let is_primitive = matches!(
                         type_name.as_str(),
@@ -1239,61 +2318,67 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
 
                     // Skip Vec types (they're handled differently) and primitives
                     if !is_primitive
+                        && !is_secret_string(ty, flags)
+                        && !is_scalar(ty, flags)
+                        && !is_temporal(ty, flags)
                         && extract_vec_inner_type(ty).is_none()
                         && extract_option_inner_type(ty).is_none()
                     {
                         validators.push(quote! {
                             // Delegate validation to nested Survey type
-                            if let Err(e) = <#ty as elicitor::Survey>::validate_field(value, responses, path) {
-                                return Err(e);
-                            }
+                            <#ty as elicitor::Survey>::validate_field(value, responses, path)?;
                         });
                     }
                 }
             }
         }
         Data::Enum(data) => {
-            for variant in &data.variants {
-                match &variant.fields {
-                    Fields::Named(fields) => {
-                        for field in &fields.named {
-                            let attrs = FieldAttrs::extract(&field.attrs)?;
-                            let field_name = field.ident.as_ref().unwrap().to_string();
-
-                            if let Some(validator) = &attrs.validate {
-                                let path_check = path_matches_field(&field_name);
-                                validators.push(quote! {
-                                    if #path_check {
-                                        if let Err(e) = #validator(value, responses, path) {
-                                            return Err(e);
-                                        }
-                                    }
-                                });
+            for (variant_idx, variant) in data.variants.iter().enumerate() {
+                match &variant.fields {
+                    Fields::Named(_) => {
+                        let layouts = field_layouts(&variant.fields, naming.of_variant(variant)?)?;
+                        for (field, layout) in layouts {
+                            if layout.skip.is_some() {
+                                continue;
                             }
+                            let attrs = FieldAttrs::extract(&field.attrs)?;
+
+                            validators.extend(generate_field_checks(
+                                &layout.key,
+                                &field.ty,
+                                &attrs,
+                                FieldFlags::of(&field.attrs),
+                            ));
                         }
                     }
-                    Fields::Unnamed(fields) => {
-                        // For unnamed fields (tuple variants), use the variant name as identifier
-                        let variant_name = variant.ident.to_string();
-                        for (idx, field) in fields.unnamed.iter().enumerate() {
+                    Fields::Unnamed(_) => {
+                        // Tuple variants all ask their fields at the same
+                        // indices, so only check the chosen variant's fields
+                        let layouts = field_layouts(&variant.fields, naming.of_variant(variant)?)?;
+                        for (field, layout) in layouts {
+                            if layout.skip.is_some() {
+                                continue;
+                            }
                             let attrs = FieldAttrs::extract(&field.attrs)?;
-                            // For tuple variants, the path ends with the variant name
-                            let field_name = if fields.unnamed.len() == 1 {
-                                variant_name.clone()
-                            } else {
-                                format!("{}.{}", variant_name, idx)
-                            };
-
-                            if let Some(validator) = &attrs.validate {
-                                let path_check = path_matches_field(&field_name);
-                                validators.push(quote! {
-                                    if #path_check {
-                                        if let Err(e) = #validator(value, responses, path) {
-                                            return Err(e);
+
+                            let checks = generate_field_checks(
+                                &layout.key,
+                                &field.ty,
+                                &attrs,
+                                FieldFlags::of(&field.attrs),
+                            );
+                            validators.extend(checks.into_iter().map(|check| {
+                                quote! {
+                                    {
+                                        let chosen = responses.get_chosen_variant(
+                                            &path.parent().child(elicitor::SELECTED_VARIANT_KEY),
+                                        );
+                                        if !matches!(chosen, Ok(chosen) if chosen != #variant_idx) {
+                                            #check
                                         }
                                     }
-                                });
-                            }
+                                }
+                            }));
                         }
                     }
                     Fields::Unit => {}
@@ -1309,6 +2394,110 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
     })
 }
 
+/// Whether the path being validated is the field `field_name`.
+///
+/// The path could be "field_name" or "parent.field_name" etc.
+fn path_matches_field(field_name: &str) -> TokenStream2 {
+    quote! {
+        (path.as_str() == #field_name || path.as_str().ends_with(&format!(".{}", #field_name)))
+    }
+}
+
+/// Whether the path being validated is an entry of the map field `field_name`.
+fn path_matches_map_entry(field_name: &str) -> TokenStream2 {
+    quote! {
+        path.as_str().rsplit_once('.').is_some_and(|(parent, idx)| {
+            idx.parse::<usize>().is_ok()
+                && (parent == #field_name || parent.ends_with(&format!(".{}", #field_name)))
+        })
+    }
+}
+
+/// Generate the `#[validate]`, `#[validate_key]` and scalar checks for one field.
+///
+/// For map fields the field-level checks apply to each value.
+fn generate_field_checks(
+    field_name: &str,
+    ty: &Type,
+    attrs: &FieldAttrs,
+    flags: FieldFlags,
+) -> Vec<TokenStream2> {
+    let mut checks = Vec::new();
+    let (value_ty, path_check) = match extract_map_types(ty) {
+        Some((_, value_ty)) => (value_ty, path_matches_map_entry(field_name)),
+        None => (ty.clone(), path_matches_field(field_name)),
+    };
+
+    if let Some(validator) = &attrs.validate {
+        // Only run this validator if the path matches this field
+        checks.push(quote! {
+            if #path_check {
+                #validator(value, responses, path)?;
+            }
+        });
+    }
+
+    if let Some(validator) = &attrs.validate_key {
+        checks.push(quote! {
+            if path.as_str() == format!("{}.{}", #field_name, elicitor::MAP_KEYS_KEY)
+                || path.as_str().ends_with(&format!(".{}.{}", #field_name, elicitor::MAP_KEYS_KEY))
+            {
+                // Backends that check every response see the whole key list
+                match value.as_string_list() {
+                    Some(keys) => {
+                        for key in keys {
+                            #validator(&elicitor::ResponseValue::String(key.clone()), responses, path)?;
+                        }
+                    }
+                    None => #validator(value, responses, path)?,
+                }
+            }
+        });
+    }
+
+    checks.extend(generate_scalar_check(&value_ty, flags, &path_check));
+    checks
+}
+
+/// Generate the check that a scalar field's text parses, if the field is a scalar.
+fn generate_scalar_check(
+    ty: &Type,
+    flags: FieldFlags,
+    path_check: &TokenStream2,
+) -> Option<TokenStream2> {
+    let ty = &strip_pointers(ty);
+    let (inner_ty, optional) = match extract_option_inner_type(ty) {
+        Some(inner_ty) => (strip_pointers(&inner_ty), true),
+        None => (ty.clone(), false),
+    };
+    if !is_scalar(&inner_ty, flags) {
+        return None;
+    }
+
+    let parse = scalar_parse(&inner_ty, quote! { text });
+    let parse = quote! {
+        #parse?;
+    };
+    // An empty answer to an optional scalar means None
+    let parse = if optional {
+        quote! {
+            if !text.is_empty() {
+                #parse
+            }
+        }
+    } else {
+        parse
+    };
+
+    Some(quote! {
+        if #path_check {
+            if let Some(text) = value.as_str() {
+                #parse
+            }
+        }
+    })
+}
+
 fn generate_validate_all_fn(
     _input: &DeriveInput,
     type_attrs: &TypeAttrs,
@@ -1411,26 +2600,31 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
     )?;
 
     // Generate with_suggestions body
-    let with_suggestions_body = generate_with_suggestions_body(input);
+    let with_suggestions_body = generate_with_suggestions_body(input)?;
+
+    let generics = bounded_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     Ok(quote! {
         /// Builder for running surveys with suggestions and assumptions
-        pub struct #builder_name {
+        pub struct #builder_name #impl_generics #where_clause {
             suggestions: std::collections::HashMap<String, elicitor::ResponseValue>,
             assumptions: std::collections::HashMap<String, elicitor::ResponseValue>,
+            _survey: std::marker::PhantomData<fn() -> #name #ty_generics>,
         }
 
-        impl #builder_name {
+        impl #impl_generics #builder_name #ty_generics #where_clause {
             /// Create a new builder
             pub fn new() -> Self {
                 Self {
                     suggestions: std::collections::HashMap::new(),
                     assumptions: std::collections::HashMap::new(),
+                    _survey: std::marker::PhantomData,
                 }
             }
 
             /// Set suggestions from an existing instance (all fields become suggested defaults)
-            pub fn with_suggestions(mut self, instance: &#name) -> Self {
+            pub fn with_suggestions(mut self, instance: &#name #ty_generics) -> Self {
                 #with_suggestions_body
                 self
             }
@@ -1439,11 +2633,11 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
             #(#assume_methods)*
 
             /// Run the survey with the given backend
-            pub fn run<B: elicitor::SurveyBackend>(
+            pub fn run<__ElicitorBackend: elicitor::SurveyBackend>(
                 self,
-                backend: B,
-            ) -> Result<#name, anyhow::Error> {
-                let mut definition = #name::survey();
+                backend: __ElicitorBackend,
+            ) -> Result<#name #ty_generics, anyhow::Error> {
+                let mut definition = <#name #ty_generics as elicitor::Survey>::survey();
 
                 // Apply suggestions and assumptions to questions
                 self.apply_to_definition(&mut definition);
@@ -1451,11 +2645,13 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
                 // Collect responses
                 let responses = backend.collect(
                     &definition,
-                    &|value, responses, path| #name::validate_field(value, responses, path),
+                    &|value, responses, path| {
+                        <#name #ty_generics as elicitor::Survey>::validate_field(value, responses, path)
+                    },
                 ).map_err(Into::into)?;
 
                 // Reconstruct the type
-                Ok(#name::from_responses(&responses))
+                Ok(<#name #ty_generics as elicitor::Survey>::from_responses(&responses))
             }
 
             fn apply_to_definition(&self, definition: &mut elicitor::SurveyDefinition) {
@@ -1556,7 +2752,7 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
             }
         }
 
-        impl Default for #builder_name {
+        impl #impl_generics Default for #builder_name #ty_generics #where_clause {
             fn default() -> Self {
                 Self::new()
             }
@@ -1576,58 +2772,53 @@ fn generate_suggest_builder(input: &DeriveInput) -> syn::Result<TokenStream2> {
 
     match &input.data {
         Data::Struct(data) => {
-            generate_suggest_builder_for_struct(name, &suggest_builder_name, data)
+            generate_suggest_builder_for_struct(input, &suggest_builder_name, data)
         }
-        Data::Enum(data) => generate_suggest_builder_for_enum(name, &suggest_builder_name, data),
+        Data::Enum(data) => generate_suggest_builder_for_enum(input, &suggest_builder_name, data),
         Data::Union(_) => Ok(quote! {}),
     }
 }
 
 /// Generate SuggestBuilder for a struct type
 fn generate_suggest_builder_for_struct(
-    _name: &Ident,
+    input: &DeriveInput,
     suggest_builder_name: &Ident,
     data: &syn::DataStruct,
 ) -> syn::Result<TokenStream2> {
-    let mut field_methods = Vec::new();
-
-    if let Fields::Named(fields) = &data.fields {
-        for field in &fields.named {
-            let field_name = field.ident.as_ref().unwrap();
-            let field_name_str = field_name.to_string();
-            let ty = &field.ty;
-
-            let method = generate_suggest_builder_field_method(&field_name_str, ty)?;
-            field_methods.push(method);
-        }
-    } else if let Fields::Unnamed(fields) = &data.fields {
-        for (i, field) in fields.unnamed.iter().enumerate() {
-            let field_name_str = i.to_string();
-            let ty = &field.ty;
+    let params = type_params(input);
+    let field_methods =
+        generate_suggest_builder_field_methods(&data.fields, Naming::of(input)?, &params)?;
 
-            let method = generate_suggest_builder_field_method(&field_name_str, ty)?;
-            field_methods.push(method);
-        }
-    }
+    let name = &input.ident;
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, name_generics, _) = input.generics.split_for_impl();
 
     Ok(quote! {
         /// Builder for suggesting/assuming values for nested fields
-        pub struct #suggest_builder_name<'a> {
+        pub struct #suggest_builder_name #impl_generics #where_clause {
             map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
             prefix: String,
+            _survey: std::marker::PhantomData<fn() -> #name #name_generics>,
         }
 
-        impl<'a> #suggest_builder_name<'a> {
+        impl #impl_generics elicitor::SuggestBuilder<'a> for #suggest_builder_name #ty_generics #where_clause {
             fn new(
                 map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
                 prefix: String,
             ) -> Self {
-                Self { map, prefix }
+                Self {
+                    map,
+                    prefix,
+                    _survey: std::marker::PhantomData,
+                }
             }
+        }
 
+        impl #impl_generics #suggest_builder_name #ty_generics #where_clause {
             fn path(&self, field: &str) -> String {
-                if self.prefix.is_empty() {
-                    field.to_string()
+                if self.prefix.is_empty() || field.is_empty() {
+                    format!("{}{}", self.prefix, field)
                 } else {
                     format!("{}.{}", self.prefix, field)
                 }
@@ -1640,10 +2831,17 @@ fn generate_suggest_builder_for_struct(
 
 /// Generate SuggestBuilder for an enum type
 fn generate_suggest_builder_for_enum(
-    name: &Ident,
+    input: &DeriveInput,
     suggest_builder_name: &Ident,
     data: &syn::DataEnum,
 ) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, name_generics, _) = input.generics.split_for_impl();
+    let elided_args = elided_builder_args(input);
+    let params = type_params(input);
+    let naming = Naming::of(input)?;
     let mut select_methods = Vec::new();
     let mut variant_methods = Vec::new();
     let mut variant_builders = Vec::new();
@@ -1673,9 +2871,9 @@ fn generate_suggest_builder_for_enum(
 
                 variant_methods.push(quote! {
                     /// Suggest values for this variant's fields
-                    pub fn #method_name<F>(self, f: F) -> Self
+                    pub fn #method_name<__ElicitorF>(self, f: __ElicitorF) -> Self
                     where
-                        F: FnOnce(#variant_builder_name<'_>) -> #variant_builder_name<'_>,
+                        __ElicitorF: FnOnce(#variant_builder_name #elided_args) -> #variant_builder_name #elided_args,
                     {
                         let builder = #variant_builder_name::new(self.map, self.prefix.clone());
                         f(builder);
@@ -1684,34 +2882,35 @@ fn generate_suggest_builder_for_enum(
                 });
 
                 // Generate the variant's field builder
-                let mut field_methods = Vec::new();
-                for field in &fields.named {
-                    let field_name = field.ident.as_ref().unwrap();
-                    let field_name_str = field_name.to_string();
-                    let ty = &field.ty;
-
-                    let method = generate_suggest_builder_field_method(&field_name_str, ty)?;
-                    field_methods.push(method);
-                }
+                let field_methods = generate_suggest_builder_field_methods(
+                    &variant.fields,
+                    naming.of_variant(variant)?,
+                    &params,
+                )?;
 
                 variant_builders.push(quote! {
                     /// Builder for suggesting values for variant fields
-                    pub struct #variant_builder_name<'a> {
+                    pub struct #variant_builder_name #impl_generics #where_clause {
                         map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
                         prefix: String,
+                        _survey: std::marker::PhantomData<fn() -> #name #name_generics>,
                     }
 
-                    impl<'a> #variant_builder_name<'a> {
+                    impl #impl_generics #variant_builder_name #ty_generics #where_clause {
                         fn new(
                             map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
                             prefix: String,
                         ) -> Self {
-                            Self { map, prefix }
+                            Self {
+                                map,
+                                prefix,
+                                _survey: std::marker::PhantomData,
+                            }
                         }
 
                         fn path(&self, field: &str) -> String {
-                            if self.prefix.is_empty() {
-                                field.to_string()
+                            if self.prefix.is_empty() || field.is_empty() {
+                                format!("{}{}", self.prefix, field)
                             } else {
                                 format!("{}.{}", self.prefix, field)
                             }
@@ -1724,31 +2923,12 @@ fn generate_suggest_builder_for_enum(
             Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
                 // Newtype variant - use the inner type's builder directly for complex types
                 let field = &fields.unnamed[0];
-                let ty = &field.ty;
+                let ty = &strip_pointers(&field.ty);
                 let type_name = type_to_string(ty);
                 let method_name = format_ident!("{}", variant_snake);
+                let flags = FieldFlags::of(&field.attrs);
 
-                // Check if it's a primitive type
-                let is_primitive = matches!(
-                    type_name.as_str(),
-                    "String"
-                        | "bool"
-                        | "i8"
-                        | "i16"
-                        | "i32"
-                        | "i64"
-                        | "isize"
-                        | "u8"
-                        | "u16"
-                        | "u32"
-                        | "u64"
-                        | "usize"
-                        | "f32"
-                        | "f64"
-                        | "PathBuf"
-                );
-
-                if is_primitive {
+                if is_single_answer(ty, flags) {
                     // For primitives, generate a direct value method
                     let (param_type, conversion) = match type_name.as_str() {
                         "String" => (
@@ -1771,11 +2951,19 @@ fn generate_suggest_builder_for_enum(
                             quote! { #ty },
                             quote! { elicitor::ResponseValue::Float(value as f64) },
                         ),
+                        "SecretString" if is_secret_string(ty, flags) => (
+                            quote! { impl Into<String> },
+                            quote! { elicitor::ResponseValue::Secret(elicitor::SecretValue::new(value)) },
+                        ),
                         "PathBuf" => (
                             quote! { impl Into<std::path::PathBuf> },
                             quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
                         ),
-                        _ => unreachable!(),
+                        _ if is_temporal(ty, flags) => (
+                            quote! { #ty },
+                            quote! { elicitor::TemporalValue::to_response(&value) },
+                        ),
+                        _ => (quote! { #ty }, scalar_format(ty, quote! { &value })),
                     };
 
                     variant_methods.push(quote! {
@@ -1790,15 +2978,15 @@ fn generate_suggest_builder_for_enum(
                     });
                 } else {
                     // For complex types, use the inner type's builder directly
-                    let inner_builder_name = format_ident!("{}SuggestBuilder", type_name);
+                    let (inner_builder, suggest_fn) = suggest_builder_type(ty);
 
                     variant_methods.push(quote! {
                         /// Suggest values for this newtype variant's inner type
-                        pub fn #method_name<F>(self, f: F) -> Self
+                        pub fn #method_name<__ElicitorF>(self, f: __ElicitorF) -> Self
                         where
-                            F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                            #suggest_fn,
                         {
-                            let builder = #inner_builder_name::new(
+                            let builder: #inner_builder = elicitor::SuggestBuilder::new(
                                 self.map,
                                 format!("{}.0", self.prefix),
                             );
@@ -1815,9 +3003,9 @@ fn generate_suggest_builder_for_enum(
 
                 variant_methods.push(quote! {
                     /// Suggest values for this variant's fields
-                    pub fn #method_name<F>(self, f: F) -> Self
+                    pub fn #method_name<__ElicitorF>(self, f: __ElicitorF) -> Self
                     where
-                        F: FnOnce(#variant_builder_name<'_>) -> #variant_builder_name<'_>,
+                        __ElicitorF: FnOnce(#variant_builder_name #elided_args) -> #variant_builder_name #elided_args,
                     {
                         let builder = #variant_builder_name::new(self.map, self.prefix.clone());
                         f(builder);
@@ -1826,33 +3014,35 @@ fn generate_suggest_builder_for_enum(
                 });
 
                 // Generate the variant's field builder for tuple variants
-                let mut field_methods = Vec::new();
-                for (i, field) in fields.unnamed.iter().enumerate() {
-                    let field_name_str = i.to_string();
-                    let ty = &field.ty;
-
-                    let method = generate_suggest_builder_field_method(&field_name_str, ty)?;
-                    field_methods.push(method);
-                }
+                let field_methods = generate_suggest_builder_field_methods(
+                    &variant.fields,
+                    naming.of_variant(variant)?,
+                    &params,
+                )?;
 
                 variant_builders.push(quote! {
                     /// Builder for suggesting values for variant fields
-                    pub struct #variant_builder_name<'a> {
+                    pub struct #variant_builder_name #impl_generics #where_clause {
                         map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
                         prefix: String,
+                        _survey: std::marker::PhantomData<fn() -> #name #name_generics>,
                     }
 
-                    impl<'a> #variant_builder_name<'a> {
+                    impl #impl_generics #variant_builder_name #ty_generics #where_clause {
                         fn new(
                             map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
                             prefix: String,
                         ) -> Self {
-                            Self { map, prefix }
+                            Self {
+                                map,
+                                prefix,
+                                _survey: std::marker::PhantomData,
+                            }
                         }
 
                         fn path(&self, field: &str) -> String {
-                            if self.prefix.is_empty() {
-                                field.to_string()
+                            if self.prefix.is_empty() || field.is_empty() {
+                                format!("{}{}", self.prefix, field)
                             } else {
                                 format!("{}.{}", self.prefix, field)
                             }
@@ -1870,19 +3060,26 @@ fn generate_suggest_builder_for_enum(
 
     Ok(quote! {
         /// Builder for suggesting/assuming values for enum variants
-        pub struct #suggest_builder_name<'a> {
+        pub struct #suggest_builder_name #impl_generics #where_clause {
             map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
             prefix: String,
+            _survey: std::marker::PhantomData<fn() -> #name #name_generics>,
         }
 
-        impl<'a> #suggest_builder_name<'a> {
+        impl #impl_generics elicitor::SuggestBuilder<'a> for #suggest_builder_name #ty_generics #where_clause {
             fn new(
                 map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
                 prefix: String,
             ) -> Self {
-                Self { map, prefix }
+                Self {
+                    map,
+                    prefix,
+                    _survey: std::marker::PhantomData,
+                }
             }
+        }
 
+        impl #impl_generics #suggest_builder_name #ty_generics #where_clause {
             #(#select_methods)*
             #(#variant_methods)*
         }
@@ -1891,8 +3088,40 @@ fn generate_suggest_builder_for_enum(
     })
 }
 
+/// Generate the field methods of a SuggestBuilder for a struct or enum variant
+fn generate_suggest_builder_field_methods(
+    fields: &Fields,
+    naming: Naming,
+    params: &[Ident],
+) -> syn::Result<Vec<TokenStream2>> {
+    let mut methods = Vec::new();
+    for (i, (field, layout)) in field_layouts(fields, naming)?.into_iter().enumerate() {
+        if layout.skip.is_some() {
+            continue;
+        }
+        let field_name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => i.to_string(),
+        };
+        methods.push(generate_suggest_builder_field_method(
+            &field_name,
+            &layout.key,
+            &field.ty,
+            FieldFlags::of(&field.attrs),
+            params,
+        )?);
+    }
+    Ok(methods)
+}
+
 /// Generate a single field method for a SuggestBuilder
-fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Result<TokenStream2> {
+fn generate_suggest_builder_field_method(
+    field_name: &str,
+    key: &str,
+    ty: &Type,
+    flags: FieldFlags,
+    params: &[Ident],
+) -> syn::Result<TokenStream2> {
     // For numeric field names (tuple structs), prefix with underscore
     let method_name = if field_name
         .chars()
@@ -1904,15 +3133,16 @@ fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Re
     } else {
         format_ident!("{}", field_name)
     };
+    let ty = &strip_pointers(ty);
     let type_name = type_to_string(ty);
 
     // Check for Option<T>
     if let Some(inner_ty) = extract_option_inner_type(ty) {
-        return generate_option_suggest_method(field_name, &inner_ty);
+        return generate_option_suggest_method(field_name, key, &inner_ty, flags, params);
     }
 
-    // Skip Vec<T> types - they don't have a simple suggest pattern
-    if extract_vec_inner_type(ty).is_some() {
+    // Skip Vec<T> and map types - they don't have a simple suggest pattern
+    if extract_vec_inner_type(ty).is_some() || extract_map_types(ty).is_some() {
         return Ok(quote! {});
     }
 
@@ -1938,12 +3168,24 @@ fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Re
             Some(quote! { #ty }),
             Some(quote! { elicitor::ResponseValue::Float(value as f64) }),
         ),
+        "SecretString" if is_secret_string(ty, flags) => (
+            Some(quote! { impl Into<String> }),
+            Some(quote! { elicitor::ResponseValue::Secret(elicitor::SecretValue::new(value)) }),
+        ),
         "PathBuf" => (
             Some(quote! { impl Into<std::path::PathBuf> }),
             Some(
                 quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
             ),
         ),
+        _ if is_temporal(ty, flags) => (
+            Some(quote! { #ty }),
+            Some(quote! { elicitor::TemporalValue::to_response(&value) }),
+        ),
+        _ if is_scalar(ty, flags) => (
+            Some(quote! { #ty }),
+            Some(scalar_format(ty, quote! { &value })),
+        ),
         _ => (None, None), // Complex type - closure-based
     };
 
@@ -1952,21 +3194,21 @@ fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Re
         Ok(quote! {
             /// Suggest a value for this field
             pub fn #method_name(self, value: #param_type) -> Self {
-                self.map.insert(self.path(#field_name), #conversion);
+                self.map.insert(self.path(#key), #conversion);
                 self
             }
         })
     } else {
         // Complex type - closure-based method
-        let inner_builder_name = format_ident!("{}SuggestBuilder", type_name);
+        let (inner_builder, suggest_fn) = suggest_builder_type(ty);
 
         Ok(quote! {
             /// Suggest values for this nested field
-            pub fn #method_name<F>(self, f: F) -> Self
+            pub fn #method_name<__ElicitorF>(self, f: __ElicitorF) -> Self
             where
-                F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                #suggest_fn,
             {
-                let builder = #inner_builder_name::new(self.map, self.path(#field_name));
+                let builder: #inner_builder = elicitor::SuggestBuilder::new(self.map, self.path(#key));
                 f(builder);
                 self
             }
@@ -1975,149 +3217,114 @@ fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Re
 }
 
 /// Generate suggest method for Option<T> fields within SuggestBuilder
-fn generate_option_suggest_method(field_name: &str, inner_ty: &Type) -> syn::Result<TokenStream2> {
+fn generate_option_suggest_method(
+    field_name: &str,
+    key: &str,
+    inner_ty: &Type,
+    flags: FieldFlags,
+    params: &[Ident],
+) -> syn::Result<TokenStream2> {
     let method_name = format_ident!("{}", field_name);
-    let inner_type_name = type_to_string(inner_ty);
-    let option_builder_name =
-        format_ident!("Option{}SuggestBuilder", capitalize_first(&inner_type_name));
+    let option_builder = option_builder_type(inner_ty, flags, params);
 
     Ok(quote! {
         /// Suggest a value for this optional field
-        pub fn #method_name<F>(self, f: F) -> Self
+        pub fn #method_name<__ElicitorF>(self, f: __ElicitorF) -> Self
         where
-            F: FnOnce(#option_builder_name<'_>) -> #option_builder_name<'_>,
+            __ElicitorF: FnOnce(#option_builder) -> #option_builder,
         {
-            let builder = #option_builder_name::new(self.map, self.path(#field_name));
+            let builder: #option_builder = elicitor::SuggestBuilder::new(self.map, self.path(#key));
             f(builder);
             self
         }
     })
 }
 
-/// Generate an Option<T> SuggestBuilder type
-fn generate_option_builder(inner_ty: &Type) -> TokenStream2 {
+/// Generate the SuggestBuilder of an `Option<T>` whose `T` is answered with a
+/// single value (see [`option_builder_type`])
+fn generate_option_builder(inner_ty: &Type, flags: FieldFlags, params: &[Ident]) -> TokenStream2 {
+    let inner_ty = &strip_pointers(inner_ty);
     let inner_type_name = type_to_string(inner_ty);
     let option_builder_name =
         format_ident!("Option{}SuggestBuilder", capitalize_first(&inner_type_name));
+    let args = mentioned_params(inner_ty, params);
+    let bound = is_scalar(inner_ty, flags).then(|| quote! { : elicitor::SurveyScalar });
+    let bounds: Vec<_> = args.iter().map(|arg| quote! { #arg #bound }).collect();
 
-    // Check if inner type is primitive
-    let is_primitive = matches!(
-        inner_type_name.as_str(),
-        "String"
-            | "bool"
-            | "i8"
-            | "i16"
-            | "i32"
-            | "i64"
-            | "isize"
-            | "u8"
-            | "u16"
-            | "u32"
-            | "u64"
-            | "usize"
-            | "f32"
-            | "f64"
-            | "PathBuf"
-    );
+    let (some_param, some_conversion) = match inner_type_name.as_str() {
+        "String" => (
+            quote! { impl Into<String> },
+            quote! { elicitor::ResponseValue::String(value.into()) },
+        ),
+        "bool" => (
+            quote! { bool },
+            quote! { elicitor::ResponseValue::Bool(value) },
+        ),
+        "i8" | "i16" | "i32" | "i64" | "isize" => (
+            quote! { #inner_ty },
+            quote! { elicitor::ResponseValue::Int(value as i64) },
+        ),
+        "u8" | "u16" | "u32" | "u64" | "usize" => (
+            quote! { #inner_ty },
+            quote! { elicitor::ResponseValue::Int(value as i64) },
+        ),
+        "f32" | "f64" => (
+            quote! { #inner_ty },
+            quote! { elicitor::ResponseValue::Float(value as f64) },
+        ),
+        "SecretString" if is_secret_string(inner_ty, flags) => (
+            quote! { impl Into<String> },
+            quote! { elicitor::ResponseValue::Secret(elicitor::SecretValue::new(value)) },
+        ),
+        "PathBuf" => (
+            quote! { impl Into<std::path::PathBuf> },
+            quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
+        ),
+        _ if is_temporal(inner_ty, flags) => (
+            quote! { #inner_ty },
+            quote! { elicitor::TemporalValue::to_response(&value) },
+        ),
+        _ => (
+            quote! { #inner_ty },
+            scalar_format(inner_ty, quote! { &value }),
+        ),
+    };
 
-    if is_primitive {
-        // For primitive inner types, generate some(value) method
-        let (some_param, some_conversion) = match inner_type_name.as_str() {
-            "String" => (
-                quote! { impl Into<String> },
-                quote! { elicitor::ResponseValue::String(value.into()) },
-            ),
-            "bool" => (
-                quote! { bool },
-                quote! { elicitor::ResponseValue::Bool(value) },
-            ),
-            "i8" | "i16" | "i32" | "i64" | "isize" => (
-                quote! { #inner_ty },
-                quote! { elicitor::ResponseValue::Int(value as i64) },
-            ),
-            "u8" | "u16" | "u32" | "u64" | "usize" => (
-                quote! { #inner_ty },
-                quote! { elicitor::ResponseValue::Int(value as i64) },
-            ),
-            "f32" | "f64" => (
-                quote! { #inner_ty },
-                quote! { elicitor::ResponseValue::Float(value as f64) },
-            ),
-            "PathBuf" => (
-                quote! { impl Into<std::path::PathBuf> },
-                quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
-            ),
-            _ => unreachable!(),
-        };
+    quote! {
+        /// Builder for suggesting Option<T> values
+        pub struct #option_builder_name<'a #(, #bounds)*> {
+            map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+            prefix: String,
+            _value: std::marker::PhantomData<fn() -> #inner_ty>,
+        }
 
-        quote! {
-            /// Builder for suggesting Option<T> values
-            pub struct #option_builder_name<'a> {
+        impl<'a #(, #bounds)*> elicitor::SuggestBuilder<'a> for #option_builder_name<'a #(, #args)*> {
+            fn new(
                 map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
                 prefix: String,
-            }
-
-            impl<'a> #option_builder_name<'a> {
-                fn new(
-                    map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
-                    prefix: String,
-                ) -> Self {
-                    Self { map, prefix }
-                }
-
-                /// Suggest None (leave empty/skip this field)
-                pub fn none(self) -> Self {
-                    self.map.insert(
-                        format!("{}.is_none", self.prefix),
-                        elicitor::ResponseValue::Bool(true),
-                    );
-                    self
-                }
-
-                /// Suggest Some with a value
-                pub fn some(self, value: #some_param) -> Self {
-                    self.map.insert(self.prefix.clone(), #some_conversion);
-                    self
+            ) -> Self {
+                Self {
+                    map,
+                    prefix,
+                    _value: std::marker::PhantomData,
                 }
             }
         }
-    } else {
-        // For complex inner types, generate some(closure) method
-        let inner_builder_name = format_ident!("{}SuggestBuilder", inner_type_name);
 
-        quote! {
-            /// Builder for suggesting Option<T> values
-            pub struct #option_builder_name<'a> {
-                map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
-                prefix: String,
+        impl<'a #(, #bounds)*> #option_builder_name<'a #(, #args)*> {
+            /// Suggest None (leave empty/skip this field)
+            pub fn none(self) -> Self {
+                self.map.insert(
+                    format!("{}.is_none", self.prefix),
+                    elicitor::ResponseValue::Bool(true),
+                );
+                self
             }
 
-            impl<'a> #option_builder_name<'a> {
-                fn new(
-                    map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
-                    prefix: String,
-                ) -> Self {
-                    Self { map, prefix }
-                }
-
-                /// Suggest None (leave empty/skip this field)
-                pub fn none(self) -> Self {
-                    self.map.insert(
-                        format!("{}.is_none", self.prefix),
-                        elicitor::ResponseValue::Bool(true),
-                    );
-                    self
-                }
-
-                /// Suggest Some with nested values
-                pub fn some<F>(self, f: F) -> Self
-                where
-                    F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
-                {
-                    let builder = #inner_builder_name::new(self.map, self.prefix.clone());
-                    f(builder);
-                    self
-                }
+            /// Suggest Some with a value
+            pub fn some(self, value: #some_param) -> Self {
+                self.map.insert(self.prefix.clone(), #some_conversion);
+                self
             }
         }
     }
@@ -2125,9 +3332,10 @@ fn generate_option_builder(inner_ty: &Type) -> TokenStream2 {
 
 /// Collect all Option<T> types used in a struct/enum and generate their builders
 fn collect_option_builders(input: &DeriveInput) -> Vec<TokenStream2> {
-    let mut option_types: Vec<Type> = Vec::new();
+    let mut option_types: Vec<(Type, FieldFlags)> = Vec::new();
     let mut seen_names: std::collections::HashSet<String> = std::collections::HashSet::new();
     let mut builders = Vec::new();
+    let params = type_params(input);
 
     // Collect all Option types
     match &input.data {
@@ -2145,10 +3353,12 @@ fn collect_option_builders(input: &DeriveInput) -> Vec<TokenStream2> {
         }
         Data::Union(_) => {}
     }
+    // Nested survey types share elicitor::OptionSuggestBuilder
+    option_types.retain(|(ty, flags)| is_single_answer(&strip_pointers(ty), *flags));
 
     // Generate builders for each unique Option type
-    for ty in option_types {
-        builders.push(generate_option_builder(&ty));
+    for (ty, flags) in option_types {
+        builders.push(generate_option_builder(&ty, flags, &params));
     }
 
     builders
@@ -2156,7 +3366,7 @@ fn collect_option_builders(input: &DeriveInput) -> Vec<TokenStream2> {
 
 fn collect_option_types_from_fields(
     fields: &Fields,
-    option_types: &mut Vec<Type>,
+    option_types: &mut Vec<(Type, FieldFlags)>,
     seen_names: &mut std::collections::HashSet<String>,
 ) {
     match fields {
@@ -2165,7 +3375,7 @@ fn collect_option_types_from_fields(
                 if let Some(inner) = extract_option_inner_type(&field.ty) {
                     let name = type_to_string(&inner);
                     if seen_names.insert(name) {
-                        option_types.push(inner);
+                        option_types.push((inner, FieldFlags::of(&field.attrs)));
                     }
                 }
             }
@@ -2175,7 +3385,7 @@ fn collect_option_types_from_fields(
                 if let Some(inner) = extract_option_inner_type(&field.ty) {
                     let name = type_to_string(&inner);
                     if seen_names.insert(name) {
-                        option_types.push(inner);
+                        option_types.push((inner, FieldFlags::of(&field.attrs)));
                     }
                 }
             }
@@ -2209,16 +3419,29 @@ fn capitalize_first(s: &str) -> String {
     }
 }
 
+/// Lowercase first letter
+fn lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+    }
+}
+
 fn generate_builder_methods_for_type(
     input: &DeriveInput,
     prefix: &str,
     suggest_methods: &mut Vec<TokenStream2>,
     assume_methods: &mut Vec<TokenStream2>,
 ) -> syn::Result<()> {
+    let params = type_params(input);
+    let naming = Naming::of(input)?;
     match &input.data {
         Data::Struct(data) => {
             generate_builder_methods_for_fields(
                 &data.fields,
+                naming,
+                &params,
                 prefix,
                 suggest_methods,
                 assume_methods,
@@ -2234,6 +3457,8 @@ fn generate_builder_methods_for_type(
                 };
                 generate_builder_methods_for_fields(
                     &variant.fields,
+                    naming.of_variant(variant)?,
+                    &params,
                     &variant_prefix,
                     suggest_methods,
                     assume_methods,
@@ -2247,65 +3472,38 @@ fn generate_builder_methods_for_type(
 
 fn generate_builder_methods_for_fields(
     fields: &Fields,
+    naming: Naming,
+    params: &[Ident],
     prefix: &str,
     suggest_methods: &mut Vec<TokenStream2>,
     assume_methods: &mut Vec<TokenStream2>,
 ) -> syn::Result<()> {
-    match fields {
-        Fields::Named(fields) => {
-            for field in &fields.named {
-                let field_name = field.ident.as_ref().unwrap();
-                let field_name_str = field_name.to_string();
-                let ty = &field.ty;
-
-                let method_suffix = if prefix.is_empty() {
-                    field_name_str.clone()
-                } else {
-                    format!("{}_{}", prefix, field_name_str)
-                };
-
-                let path_key = if prefix.is_empty() {
-                    field_name_str.clone()
-                } else {
-                    format!("{}.{}", prefix.replace('_', "."), field_name_str)
-                };
-
-                generate_suggest_assume_methods(
-                    &method_suffix,
-                    &path_key,
-                    ty,
-                    suggest_methods,
-                    assume_methods,
-                );
-            }
+    for (i, (field, layout)) in field_layouts(fields, naming)?.into_iter().enumerate() {
+        let ty = &field.ty;
+        if layout.skip.is_some() {
+            continue;
         }
-        Fields::Unnamed(fields) => {
-            for (i, field) in fields.unnamed.iter().enumerate() {
-                let field_name_str = i.to_string();
-                let ty = &field.ty;
-
-                let method_suffix = if prefix.is_empty() {
-                    field_name_str.clone()
-                } else {
-                    format!("{}_{}", prefix, field_name_str)
-                };
 
-                let path_key = if prefix.is_empty() {
-                    field_name_str
-                } else {
-                    format!("{}.{}", prefix.replace('_', "."), i)
-                };
-
-                generate_suggest_assume_methods(
-                    &method_suffix,
-                    &path_key,
-                    ty,
-                    suggest_methods,
-                    assume_methods,
-                );
-            }
-        }
-        Fields::Unit => {}
+        let field_name_str = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => i.to_string(),
+        };
+        let method_suffix = if prefix.is_empty() {
+            field_name_str
+        } else {
+            format!("{}_{}", prefix, field_name_str)
+        };
+        let path_key = layout.path_below(&prefix.replace('_', "."));
+
+        generate_suggest_assume_methods(
+            &method_suffix,
+            &path_key,
+            ty,
+            FieldFlags::of(&field.attrs),
+            params,
+            suggest_methods,
+            assume_methods,
+        );
     }
     Ok(())
 }
@@ -2314,32 +3512,35 @@ fn generate_suggest_assume_methods(
     method_suffix: &str,
     path_key: &str,
     ty: &Type,
+    flags: FieldFlags,
+    params: &[Ident],
     suggest_methods: &mut Vec<TokenStream2>,
     assume_methods: &mut Vec<TokenStream2>,
 ) {
     let suggest_name = format_ident!("suggest_{}", method_suffix);
     let assume_name = format_ident!("assume_{}", method_suffix);
 
+    let ty = &strip_pointers(ty);
     let type_name = type_to_string(ty);
 
-    // Skip Vec<T> types - they use multiselect and don't have a simple suggest pattern
-    if extract_vec_inner_type(ty).is_some() {
+    // Skip Vec<T> types - they use multiselect and don't have a simple suggest pattern.
+    // Maps are skipped too, as their entries are added while answering
+    if extract_vec_inner_type(ty).is_some() || extract_map_types(ty).is_some() {
         return;
     }
 
     // Check for Option<T> - generate closure-based method
     if let Some(inner_ty) = extract_option_inner_type(ty) {
-        let inner_type_name = type_to_string(&inner_ty);
-        let option_builder_name =
-            format_ident!("Option{}SuggestBuilder", capitalize_first(&inner_type_name));
+        let option_builder = option_builder_type(&inner_ty, flags, params);
 
         suggest_methods.push(quote! {
             /// Suggest a value for this optional field (user can modify)
-            pub fn #suggest_name<F>(mut self, f: F) -> Self
+            pub fn #suggest_name<__ElicitorF>(mut self, f: __ElicitorF) -> Self
             where
-                F: FnOnce(#option_builder_name<'_>) -> #option_builder_name<'_>,
+                __ElicitorF: FnOnce(#option_builder) -> #option_builder,
             {
-                let builder = #option_builder_name::new(&mut self.suggestions, #path_key.to_string());
+                let builder: #option_builder =
+                    elicitor::SuggestBuilder::new(&mut self.suggestions, #path_key.to_string());
                 f(builder);
                 self
             }
@@ -2347,11 +3548,12 @@ fn generate_suggest_assume_methods(
 
         assume_methods.push(quote! {
             /// Assume a value for this optional field (question is skipped)
-            pub fn #assume_name<F>(mut self, f: F) -> Self
+            pub fn #assume_name<__ElicitorF>(mut self, f: __ElicitorF) -> Self
             where
-                F: FnOnce(#option_builder_name<'_>) -> #option_builder_name<'_>,
+                __ElicitorF: FnOnce(#option_builder) -> #option_builder,
             {
-                let builder = #option_builder_name::new(&mut self.assumptions, #path_key.to_string());
+                let builder: #option_builder =
+                    elicitor::SuggestBuilder::new(&mut self.assumptions, #path_key.to_string());
                 f(builder);
                 self
             }
@@ -2381,12 +3583,24 @@ fn generate_suggest_assume_methods(
             Some(quote! { #ty }),
             Some(quote! { elicitor::ResponseValue::Float(value as f64) }),
         ),
+        "SecretString" if is_secret_string(ty, flags) => (
+            Some(quote! { impl Into<String> }),
+            Some(quote! { elicitor::ResponseValue::Secret(elicitor::SecretValue::new(value)) }),
+        ),
         "PathBuf" => (
             Some(quote! { impl Into<std::path::PathBuf> }),
             Some(
                 quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
             ),
         ),
+        _ if is_temporal(ty, flags) => (
+            Some(quote! { #ty }),
+            Some(quote! { elicitor::TemporalValue::to_response(&value) }),
+        ),
+        _ if is_scalar(ty, flags) => (
+            Some(quote! { #ty }),
+            Some(scalar_format(ty, quote! { &value })),
+        ),
         _ => (None, None), // Complex type
     };
 
@@ -2409,15 +3623,16 @@ fn generate_suggest_assume_methods(
         });
     } else {
         // Complex type - closure-based methods
-        let inner_builder_name = format_ident!("{}SuggestBuilder", type_name);
+        let (inner_builder, suggest_fn) = suggest_builder_type(ty);
 
         suggest_methods.push(quote! {
             /// Suggest values for this nested field (user can modify)
-            pub fn #suggest_name<F>(mut self, f: F) -> Self
+            pub fn #suggest_name<__ElicitorF>(mut self, f: __ElicitorF) -> Self
             where
-                F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                #suggest_fn,
             {
-                let builder = #inner_builder_name::new(&mut self.suggestions, #path_key.to_string());
+                let builder: #inner_builder =
+                    elicitor::SuggestBuilder::new(&mut self.suggestions, #path_key.to_string());
                 f(builder);
                 self
             }
@@ -2425,11 +3640,12 @@ fn generate_suggest_assume_methods(
 
         assume_methods.push(quote! {
             /// Assume values for this nested field (questions are skipped)
-            pub fn #assume_name<F>(mut self, f: F) -> Self
+            pub fn #assume_name<__ElicitorF>(mut self, f: __ElicitorF) -> Self
             where
-                F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                #suggest_fn,
             {
-                let builder = #inner_builder_name::new(&mut self.assumptions, #path_key.to_string());
+                let builder: #inner_builder =
+                    elicitor::SuggestBuilder::new(&mut self.assumptions, #path_key.to_string());
                 f(builder);
                 self
             }
@@ -2438,56 +3654,87 @@ fn generate_suggest_assume_methods(
 }
 
 /// Generate the with_suggestions body for a struct
-fn generate_with_suggestions_struct(data: &syn::DataStruct) -> TokenStream2 {
-    match &data.fields {
-        Fields::Named(fields) => {
-            let insertions: Vec<_> = fields
-                .named
-                .iter()
-                .filter_map(|f| {
+fn generate_with_suggestions_struct(
+    data: &syn::DataStruct,
+    naming: Naming,
+) -> syn::Result<TokenStream2> {
+    Ok(match &data.fields {
+        Fields::Named(_) => {
+            let insertions: Vec<_> = field_layouts(&data.fields, naming)?
+                .into_iter()
+                .filter(|(_, layout)| layout.skip.is_none())
+                .filter_map(|(f, layout)| {
                     let field_name = f.ident.as_ref()?;
-                    let field_name_str = field_name.to_string();
+                    let field_name_str = layout.key;
                     let ty = &f.ty;
                     let type_name = type_to_string(ty);
+                    // Fields behind a smart pointer are read through it
+                    let value = if extract_pointer_inner_type(ty).is_some() {
+                        quote! { (*instance.#field_name) }
+                    } else {
+                        quote! { instance.#field_name }
+                    };
 
                     // Only handle primitive types directly
                     match type_name.as_str() {
                         "String" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::String(instance.#field_name.clone())
+                                elicitor::ResponseValue::String(#value.clone())
                             );
                         }),
                         "bool" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Bool(instance.#field_name)
+                                elicitor::ResponseValue::Bool(#value)
                             );
                         }),
                         "i8" | "i16" | "i32" | "i64" | "isize" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Int(instance.#field_name as i64)
+                                elicitor::ResponseValue::Int(#value as i64)
                             );
                         }),
                         "u8" | "u16" | "u32" | "u64" | "usize" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Int(instance.#field_name as i64)
+                                elicitor::ResponseValue::Int(#value as i64)
                             );
                         }),
                         "f32" | "f64" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Float(instance.#field_name as f64)
+                                elicitor::ResponseValue::Float(#value as f64)
                             );
                         }),
                         "PathBuf" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::String(instance.#field_name.display().to_string())
+                                elicitor::ResponseValue::String(#value.display().to_string())
+                            );
+                        }),
+                        "SecretString" if is_secret_string(ty, FieldFlags::of(&f.attrs)) => {
+                            Some(quote! {
+                                self.suggestions.insert(
+                                    #field_name_str.to_string(),
+                                    elicitor::ResponseValue::Secret(elicitor::SecretValue::new(
+                                        elicitor::secrecy::ExposeSecret::expose_secret(&#value)
+                                    ))
+                                );
+                            })
+                        }
+                        _ if is_temporal(ty, FieldFlags::of(&f.attrs)) => Some(quote! {
+                            self.suggestions.insert(
+                                #field_name_str.to_string(),
+                                elicitor::TemporalValue::to_response(&#value)
                             );
                         }),
+                        _ if is_scalar(ty, FieldFlags::of(&f.attrs)) => {
+                            let answer = scalar_format(ty, quote! { &#value });
+                            Some(quote! {
+                                self.suggestions.insert(#field_name_str.to_string(), #answer);
+                            })
+                        }
                         _ => None, // Skip complex types
                     }
                 })
@@ -2505,38 +3752,55 @@ fn generate_with_suggestions_struct(data: &syn::DataStruct) -> TokenStream2 {
                     let field_name_str = i.to_string();
                     let ty = &f.ty;
                     let type_name = type_to_string(ty);
+                    let value = if extract_pointer_inner_type(ty).is_some() {
+                        quote! { (*instance.#idx) }
+                    } else {
+                        quote! { instance.#idx }
+                    };
 
                     match type_name.as_str() {
                         "String" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::String(instance.#idx.clone())
+                                elicitor::ResponseValue::String(#value.clone())
                             );
                         }),
                         "bool" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Bool(instance.#idx)
+                                elicitor::ResponseValue::Bool(#value)
                             );
                         }),
                         "i8" | "i16" | "i32" | "i64" | "isize" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Int(instance.#idx as i64)
+                                elicitor::ResponseValue::Int(#value as i64)
                             );
                         }),
                         "u8" | "u16" | "u32" | "u64" | "usize" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Int(instance.#idx as i64)
+                                elicitor::ResponseValue::Int(#value as i64)
                             );
                         }),
                         "f32" | "f64" => Some(quote! {
                             self.suggestions.insert(
                                 #field_name_str.to_string(),
-                                elicitor::ResponseValue::Float(instance.#idx as f64)
+                                elicitor::ResponseValue::Float(#value as f64)
+                            );
+                        }),
+                        _ if is_temporal(ty, FieldFlags::of(&f.attrs)) => Some(quote! {
+                            self.suggestions.insert(
+                                #field_name_str.to_string(),
+                                elicitor::TemporalValue::to_response(&#value)
                             );
                         }),
+                        _ if is_scalar(ty, FieldFlags::of(&f.attrs)) => {
+                            let answer = scalar_format(ty, quote! { &#value });
+                            Some(quote! {
+                                self.suggestions.insert(#field_name_str.to_string(), #answer);
+                            })
+                        }
                         _ => None,
                     }
                 })
@@ -2545,17 +3809,17 @@ fn generate_with_suggestions_struct(data: &syn::DataStruct) -> TokenStream2 {
             quote! { #(#insertions)* }
         }
         Fields::Unit => quote! {},
-    }
+    })
 }
 
 /// Generate with_suggestions body based on the input type
-fn generate_with_suggestions_body(input: &DeriveInput) -> TokenStream2 {
+fn generate_with_suggestions_body(input: &DeriveInput) -> syn::Result<TokenStream2> {
     match &input.data {
-        Data::Struct(data) => generate_with_suggestions_struct(data),
+        Data::Struct(data) => generate_with_suggestions_struct(data, Naming::of(input)?),
         Data::Enum(_) => {
             // Enums are complex - skip for now
-            quote! {}
+            Ok(quote! {})
         }
-        Data::Union(_) => quote! {},
+        Data::Union(_) => Ok(quote! {}),
     }
 }
@@ -1,12 +1,12 @@
 //! Basic tests for the Survey derive macro
 
 // We need to create a fake elicit module for the macro to work
+#[allow(unused_imports)]
 mod elicit {
     pub use elicitor_types::*;
 }
 
-use elicitor::Survey; // Import the trait
-use elicitor_macro::Survey;
+use elicitor::Survey; // Import the trait and the derive
 
 #[derive(Survey, Debug, PartialEq)]
 struct SimpleStruct {
@@ -50,7 +50,7 @@ fn simple_struct_from_responses() {
 
     assert_eq!(result.name, "Alice");
     assert_eq!(result.age, 25);
-    assert_eq!(result.student, true);
+    assert!(result.student);
 }
 
 #[test]
@@ -123,3 +123,1315 @@ fn masked_and_multiline() {
         elicitor::QuestionKind::Multiline(_)
     ));
 }
+
+#[derive(Survey, Debug, PartialEq)]
+struct WithConfirmedPassword {
+    #[ask("Password:")]
+    #[mask]
+    #[confirm_entry]
+    #[strength_meter]
+    password: String,
+}
+
+#[test]
+fn confirm_entry_and_strength_meter() {
+    let survey = WithConfirmedPassword::survey();
+
+    match survey.questions[0].kind() {
+        elicitor::QuestionKind::Masked(masked_q) => {
+            assert!(masked_q.confirm);
+            assert!(masked_q.strength_meter);
+        }
+        _ => panic!("Expected Masked question kind"),
+    }
+}
+
+#[test]
+fn masked_answers_are_read_from_secrets() {
+    let mut responses = elicitor::Responses::new();
+    responses.insert("password", elicitor::SecretValue::new("hunter2"));
+    responses.insert("bio", "Hello");
+
+    let result = WithMaskedAndMultiline::from_responses(&responses);
+
+    assert_eq!(result.password, "hunter2");
+    assert_eq!(
+        WithMaskedAndMultiline::get_password(&responses).as_deref(),
+        Some("hunter2")
+    );
+}
+
+#[derive(Survey, Debug)]
+struct WithSecretString {
+    #[ask("API token:")]
+    token: elicitor::secrecy::SecretString,
+}
+
+#[test]
+fn secret_string_fields_are_masked() {
+    use elicitor::secrecy::ExposeSecret;
+
+    let survey = WithSecretString::survey();
+    assert!(matches!(
+        survey.questions[0].kind(),
+        elicitor::QuestionKind::Masked(_)
+    ));
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("token", elicitor::SecretValue::new("s3cr3t"));
+
+    let result = WithSecretString::from_responses(&responses);
+
+    assert_eq!(result.token.expose_secret(), "s3cr3t");
+    assert!(!format!("{responses:?}").contains("s3cr3t"));
+}
+
+/// A `Survey` type of its own named `SecretString`.
+mod vault {
+    use elicitor::Survey;
+
+    #[derive(Survey, Debug)]
+    pub struct SecretString {
+        #[ask("Label:")]
+        pub label: String,
+    }
+}
+
+mod imported {
+    use elicitor::Survey;
+    use elicitor::secrecy::SecretString;
+
+    #[derive(Survey, Debug)]
+    pub struct Credentials {
+        #[ask("Entry:")]
+        pub entry: super::vault::SecretString,
+
+        #[ask("API token:")]
+        #[mask]
+        pub token: SecretString,
+    }
+}
+
+#[test]
+fn secret_strings_are_matched_by_path_or_mask() {
+    use elicitor::secrecy::ExposeSecret;
+
+    let survey = imported::Credentials::survey();
+    assert!(matches!(
+        survey.questions[0].kind(),
+        elicitor::QuestionKind::AllOf(_)
+    ));
+    assert!(matches!(
+        survey.questions[1].kind(),
+        elicitor::QuestionKind::Masked(_)
+    ));
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("entry.label", "bank");
+    responses.insert("token", elicitor::SecretValue::new("s3cr3t"));
+
+    let result = imported::Credentials::from_responses(&responses);
+
+    assert_eq!(result.entry.label, "bank");
+    assert_eq!(result.token.expose_secret(), "s3cr3t");
+}
+
+/// A port number that rejects the privileged range.
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+impl elicitor::SurveyScalar for Port {
+    fn parse_response(text: &str) -> Result<Self, String> {
+        match text.trim().parse::<u16>() {
+            Ok(port) if port >= 1024 => Ok(Port(port)),
+            _ => Err("expected a port from 1024 to 65535".to_string()),
+        }
+    }
+
+    fn format_response(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct WithScalars {
+    #[ask("Address:")]
+    address: std::net::IpAddr,
+
+    #[ask("Port:")]
+    #[scalar]
+    port: Port,
+
+    #[ask("Proxy:")]
+    proxy: Option<std::net::SocketAddr>,
+}
+
+#[test]
+fn scalar_fields_are_parsed_from_text() {
+    let survey = WithScalars::survey();
+    for question in &survey.questions {
+        match question.kind() {
+            elicitor::QuestionKind::Input(input_q) => assert!(input_q.validate.is_some()),
+            _ => panic!("Expected Input question kind"),
+        }
+    }
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("address", "192.168.0.1");
+    responses.insert("port", "8080");
+    responses.insert("proxy", "");
+
+    let result = WithScalars::from_responses(&responses);
+
+    assert_eq!(result.address, std::net::IpAddr::from([192, 168, 0, 1]));
+    assert_eq!(result.port, Port(8080));
+    assert_eq!(result.proxy, None);
+    assert_eq!(WithScalars::get_port(&responses), Some(Port(8080)));
+}
+
+#[test]
+fn scalar_fields_reject_unparsable_text() {
+    let responses = elicitor::Responses::new();
+    let validate = |path: &str, text: &str| {
+        WithScalars::validate_field(
+            &elicitor::ResponseValue::String(text.to_string()),
+            &responses,
+            &elicitor::ResponsePath::new(path),
+        )
+    };
+
+    assert!(validate("address", "10.0.0.1").is_ok());
+    assert!(validate("address", "10.0.0").is_err());
+    assert_eq!(
+        validate("port", "80"),
+        Err("expected a port from 1024 to 65535".to_string())
+    );
+    assert!(validate("proxy", "").is_ok());
+    assert!(validate("proxy", "localhost").is_err());
+}
+
+#[test]
+fn scalar_fields_can_be_suggested() {
+    // Just verify the builder methods compile
+    let _builder = WithScalars::builder()
+        .suggest_address(std::net::IpAddr::from([127, 0, 0, 1]))
+        .assume_port(Port(8443))
+        .suggest_proxy(|proxy| proxy.none());
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Endpoint {
+    Addr(std::net::IpAddr),
+    Name(#[ask("Host name:")] String),
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Upstream {
+    #[ask("Endpoint:")]
+    endpoint: Endpoint,
+}
+
+#[test]
+fn tuple_variant_scalars_reject_unparsable_text() {
+    let result = Upstream::builder().run(
+        elicitor::TestBackend::new()
+            .with_variant("endpoint.selected_variant", 0)
+            .with_string("endpoint.0", "not-an-ip"),
+    );
+    assert!(result.is_err());
+
+    // The other variant's field at the same index isn't parsed as an address
+    let upstream = Upstream::builder()
+        .run(
+            elicitor::TestBackend::new()
+                .with_variant("endpoint.selected_variant", 1)
+                .with_string("endpoint.0", "example.org"),
+        )
+        .unwrap();
+    assert_eq!(upstream.endpoint, Endpoint::Name("example.org".to_string()));
+}
+
+/// A level with `FromStr` and a `SurveyScalar` impl of its own.
+#[derive(Debug, PartialEq)]
+struct Level(u8);
+
+impl std::str::FromStr for Level {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Level)
+    }
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl elicitor::SurveyScalar for Level {
+    fn parse_response(text: &str) -> Result<Self, String> {
+        match text.trim().parse() {
+            Ok(level) if level <= 9 => Ok(Level(level)),
+            _ => Err("expected a level from 0 to 9".to_string()),
+        }
+    }
+
+    fn format_response(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A `Survey` type that shares its name with `std::net::IpAddr`.
+#[derive(Survey, Debug, PartialEq)]
+struct IpAddr {
+    #[ask("Host name:")]
+    host: String,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Tuning {
+    #[ask("Level:")]
+    #[scalar]
+    level: Level,
+
+    #[ask("Retries:")]
+    #[scalar]
+    retries: std::num::NonZeroU32,
+
+    #[ask("Upstream:")]
+    upstream: IpAddr,
+}
+
+#[test]
+fn scalar_impls_take_precedence_over_from_str() {
+    let responses = elicitor::Responses::new();
+    let validate = |path: &str, text: &str| {
+        Tuning::validate_field(
+            &elicitor::ResponseValue::String(text.to_string()),
+            &responses,
+            &elicitor::ResponsePath::new(path),
+        )
+    };
+
+    assert_eq!(
+        validate("level", "12"),
+        Err("expected a level from 0 to 9".to_string())
+    );
+    assert!(validate("retries", "0").is_err());
+    assert!(validate("retries", "3").is_ok());
+}
+
+#[test]
+fn types_named_like_std_scalars_stay_nested() {
+    let survey = Tuning::survey();
+    assert!(matches!(
+        survey.questions[2].kind(),
+        elicitor::QuestionKind::AllOf(_)
+    ));
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("level", "3");
+    responses.insert("retries", "5");
+    responses.insert("upstream.host", "proxy.local");
+
+    assert_eq!(
+        Tuning::from_responses(&responses),
+        Tuning {
+            level: Level(3),
+            retries: std::num::NonZeroU32::new(5).unwrap(),
+            upstream: IpAddr {
+                host: "proxy.local".to_string(),
+            },
+        }
+    );
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct WithTemporalFields {
+    #[ask("Start date:")]
+    #[min("2024-01-01")]
+    start: elicitor::Date,
+
+    #[ask("Birthday:")]
+    birthday: chrono::NaiveDate,
+
+    #[ask("Daily standup:")]
+    standup: Option<elicitor::Time>,
+
+    #[ask("Timeout:")]
+    #[max("1h")]
+    timeout: std::time::Duration,
+}
+
+#[test]
+fn temporal_fields_become_temporal_questions() {
+    let survey = WithTemporalFields::survey();
+
+    match survey.questions[0].kind() {
+        elicitor::QuestionKind::Date(date_q) => {
+            assert_eq!(date_q.min, elicitor::Date::new(2024, 1, 1));
+        }
+        _ => panic!("Expected Date question kind"),
+    }
+    assert!(matches!(
+        survey.questions[1].kind(),
+        elicitor::QuestionKind::Date(_)
+    ));
+    assert!(matches!(
+        survey.questions[2].kind(),
+        elicitor::QuestionKind::Time(_)
+    ));
+    match survey.questions[3].kind() {
+        elicitor::QuestionKind::Duration(duration_q) => {
+            assert_eq!(duration_q.max, Some(std::time::Duration::from_secs(3600)));
+        }
+        _ => panic!("Expected Duration question kind"),
+    }
+}
+
+#[test]
+fn temporal_fields_from_responses() {
+    let mut responses = elicitor::Responses::new();
+    responses.insert("start", elicitor::Date::new(2024, 3, 1).unwrap());
+    responses.insert("birthday", elicitor::Date::new(1990, 7, 14).unwrap());
+    responses.insert("timeout", std::time::Duration::from_secs(90));
+
+    let result = WithTemporalFields::from_responses(&responses);
+
+    assert_eq!(result.start, elicitor::Date::new(2024, 3, 1).unwrap());
+    assert_eq!(
+        result.birthday,
+        chrono::NaiveDate::from_ymd_opt(1990, 7, 14).unwrap()
+    );
+    assert_eq!(result.standup, None);
+    assert_eq!(result.timeout, std::time::Duration::from_secs(90));
+    assert_eq!(
+        WithTemporalFields::get_birthday(&responses),
+        Some(result.birthday)
+    );
+}
+
+#[test]
+fn temporal_fields_can_be_suggested() {
+    // Just verify the builder methods compile
+    let _builder = WithTemporalFields::builder()
+        .suggest_birthday(chrono::NaiveDate::from_ymd_opt(1990, 7, 14).unwrap())
+        .suggest_standup(|standup| standup.some(elicitor::Time::new(9, 30, 0).unwrap()))
+        .assume_timeout(std::time::Duration::from_secs(30));
+}
+
+/// A `Survey` type that shares its name with `elicitor::Date`.
+#[derive(Survey, Debug, PartialEq)]
+struct Date {
+    #[ask("Occasion:")]
+    occasion: String,
+}
+
+type Clock = elicitor::Time;
+
+#[derive(Survey, Debug, PartialEq)]
+struct Appointment {
+    #[ask("Date:")]
+    date: Date,
+
+    #[ask("At:")]
+    #[temporal]
+    at: Clock,
+}
+
+#[test]
+fn temporal_types_are_matched_by_path_or_attribute() {
+    let survey = Appointment::survey();
+    assert!(matches!(
+        survey.questions[0].kind(),
+        elicitor::QuestionKind::AllOf(_)
+    ));
+    assert!(matches!(
+        survey.questions[1].kind(),
+        elicitor::QuestionKind::Time(_)
+    ));
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("date.occasion", "Dentist");
+    responses.insert("at", elicitor::Time::new(9, 30, 0).unwrap());
+
+    let result = Appointment::from_responses(&responses);
+
+    assert_eq!(result.date.occasion, "Dentist");
+    assert_eq!(result.at, elicitor::Time::new(9, 30, 0).unwrap());
+}
+
+fn validate_env_name(
+    value: &elicitor::ResponseValue,
+    _responses: &elicitor::Responses,
+    _path: &elicitor::ResponsePath,
+) -> Result<(), String> {
+    match value.as_str() {
+        Some(key) if key.chars().all(|c| c.is_ascii_uppercase() || c == '_') => Ok(()),
+        _ => Err("use upper case letters and underscores".to_string()),
+    }
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Limits {
+    #[ask("CPUs:")]
+    cpus: u32,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct WithMaps {
+    #[ask("Environment:")]
+    #[validate_key(validate_env_name)]
+    env: std::collections::HashMap<String, String>,
+
+    #[ask("Limits per service:")]
+    limits: std::collections::BTreeMap<String, Limits>,
+}
+
+#[test]
+fn map_fields_become_map_questions() {
+    let survey = WithMaps::survey();
+
+    match survey.questions[0].kind() {
+        elicitor::QuestionKind::Map(map_q) => {
+            assert!(matches!(
+                *map_q.value_kind,
+                elicitor::QuestionKind::Input(_)
+            ));
+            assert_eq!(map_q.validate_key.as_deref(), Some("validate_env_name"));
+        }
+        _ => panic!("Expected Map question kind"),
+    }
+    match survey.questions[1].kind() {
+        elicitor::QuestionKind::Map(map_q) => {
+            assert!(matches!(
+                *map_q.value_kind,
+                elicitor::QuestionKind::AllOf(_)
+            ));
+        }
+        _ => panic!("Expected Map question kind"),
+    }
+}
+
+#[test]
+fn map_fields_from_responses() {
+    let mut responses = elicitor::Responses::new();
+    responses.insert("env.keys", vec!["HOME".to_string(), "SHELL".to_string()]);
+    responses.insert("env.0", "/root");
+    responses.insert("env.1", "/bin/sh");
+    responses.insert("limits.keys", vec!["db".to_string()]);
+    responses.insert("limits.0.cpus", 4);
+
+    let result = WithMaps::from_responses(&responses);
+
+    assert_eq!(result.env.len(), 2);
+    assert_eq!(result.env["HOME"], "/root");
+    assert_eq!(result.env["SHELL"], "/bin/sh");
+    assert_eq!(
+        result.limits.into_iter().collect::<Vec<_>>(),
+        vec![("db".to_string(), Limits { cpus: 4 })]
+    );
+}
+
+#[test]
+fn map_keys_are_validated() {
+    let responses = elicitor::Responses::new();
+    let validate = |path: &str, key: &str| {
+        WithMaps::validate_field(
+            &elicitor::ResponseValue::String(key.to_string()),
+            &responses,
+            &elicitor::ResponsePath::new(path),
+        )
+    };
+
+    assert!(validate("env.keys", "HOME").is_ok());
+    assert!(validate("env.keys", "home").is_err());
+    // Values are not checked against the key validator
+    assert!(validate("env.0", "home").is_ok());
+    assert!(validate("limits.keys", "home").is_ok());
+
+    let keys = elicitor::ResponseValue::StringList(vec!["HOME".to_string(), "path".to_string()]);
+    assert!(
+        WithMaps::validate_field(&keys, &responses, &elicitor::ResponsePath::new("env.keys"))
+            .is_err()
+    );
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Ping {
+    #[ask("Message:")]
+    message: String,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Envelope<T> {
+    #[ask("Request id:")]
+    id: String,
+
+    #[ask("Body:")]
+    body: T,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Reply<T>
+where
+    T: std::fmt::Debug,
+{
+    Done(T),
+    Failed {
+        #[ask("Reason:")]
+        reason: String,
+    },
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Measured<U> {
+    #[ask("Value:")]
+    #[scalar]
+    value: U,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Client {
+    #[ask("Ping:")]
+    ping: Envelope<Ping>,
+
+    #[ask("Last reply:")]
+    reply: Reply<Ping>,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Retried<T, U> {
+    #[ask("First attempt:")]
+    first: T,
+
+    #[ask("Fallback:")]
+    fallback: Option<T>,
+
+    #[ask("Attempts:")]
+    #[scalar]
+    attempts: Option<U>,
+}
+
+/// Type parameters named like the generics of the generated methods.
+#[derive(Survey, Debug, PartialEq)]
+struct Routed<B, F> {
+    #[ask("Primary:")]
+    primary: B,
+
+    #[ask("Fallback:")]
+    fallback: Option<F>,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Route<B, F> {
+    Direct(B),
+    Forwarded {
+        #[ask("Via:")]
+        via: F,
+    },
+}
+
+/// A backend that records the suggested and assumed answers of a survey by
+/// path, then cancels it.
+#[derive(Default)]
+struct RecordDefaults(std::cell::RefCell<Vec<(String, elicitor::DefaultValue)>>);
+
+impl RecordDefaults {
+    fn record(&self, questions: &[elicitor::Question], prefix: &elicitor::ResponsePath) {
+        for question in questions {
+            let path = if question.path().is_empty() {
+                prefix.clone()
+            } else {
+                prefix.child(question.path().as_str())
+            };
+            if !matches!(question.default(), elicitor::DefaultValue::None) {
+                let entry = (path.as_str().to_string(), question.default().clone());
+                self.0.borrow_mut().push(entry);
+            }
+            match question.kind() {
+                elicitor::QuestionKind::AllOf(all_of) => self.record(all_of.questions(), &path),
+                elicitor::QuestionKind::OneOf(one_of) => {
+                    for variant in &one_of.variants {
+                        if let elicitor::QuestionKind::AllOf(all_of) = &variant.kind {
+                            self.record(all_of.questions(), &path);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<elicitor::DefaultValue> {
+        let recorded = self.0.borrow();
+        recorded
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, d)| d.clone())
+    }
+}
+
+impl elicitor::SurveyBackend for &RecordDefaults {
+    type Error = std::fmt::Error;
+
+    fn collect(
+        &self,
+        definition: &elicitor::SurveyDefinition,
+        _validate: &dyn Fn(
+            &elicitor::ResponseValue,
+            &elicitor::Responses,
+            &elicitor::ResponsePath,
+        ) -> Result<(), String>,
+    ) -> Result<elicitor::Responses, Self::Error> {
+        self.record(&definition.questions, &elicitor::ResponsePath::empty());
+        Err(std::fmt::Error)
+    }
+}
+
+#[test]
+fn generic_structs_nest_their_parameter() {
+    let survey = Envelope::<Ping>::survey();
+
+    assert_eq!(survey.questions.len(), 2);
+    assert_eq!(survey.questions[0].path().as_str(), "id");
+    match survey.questions[1].kind() {
+        elicitor::QuestionKind::AllOf(all_of) => {
+            assert_eq!(all_of.questions()[0].path().as_str(), "message");
+        }
+        _ => panic!("Expected AllOf question kind"),
+    }
+}
+
+#[test]
+fn generic_types_from_responses() {
+    let mut responses = elicitor::Responses::new();
+    responses.insert("id", "req-1");
+    responses.insert("body.message", "hello");
+
+    assert_eq!(
+        Envelope::<Ping>::from_responses(&responses),
+        Envelope {
+            id: "req-1".to_string(),
+            body: Ping {
+                message: "hello".to_string()
+            },
+        }
+    );
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert(
+        "selected_variant",
+        elicitor::ResponseValue::ChosenVariant(0),
+    );
+    responses.insert("0.message", "hello");
+    assert_eq!(
+        Reply::<Ping>::from_responses(&responses),
+        Reply::Done(Ping {
+            message: "hello".to_string()
+        })
+    );
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("value", "42");
+    assert_eq!(
+        Measured::<u8>::from_responses(&responses),
+        Measured { value: 42 }
+    );
+}
+
+#[test]
+fn generic_builders_exist() {
+    // Just verify the builder methods compile
+    let _builder = Envelope::<Ping>::builder().suggest_id("req-1");
+    let _builder = Client::builder().suggest_ping(|ping| ping.id("req-2"));
+    let _context = EnvelopeValidationContext::<Ping>::new(
+        &elicitor::Responses::new(),
+        elicitor::ResponsePath::empty(),
+    );
+}
+
+/// A `Survey` written by hand, which has no suggest builder.
+#[derive(Debug, PartialEq)]
+struct Token(String);
+
+impl elicitor::Survey for Token {
+    fn survey() -> elicitor::SurveyDefinition {
+        elicitor::SurveyDefinition {
+            prelude: None,
+            questions: vec![elicitor::Question::new(
+                "value",
+                "Token:",
+                elicitor::QuestionKind::Input(elicitor::InputQuestion::new()),
+            )],
+            epilogue: None,
+        }
+    }
+
+    fn from_responses(responses: &elicitor::Responses) -> Self {
+        let value = responses.get_string(&elicitor::ResponsePath::new("value"));
+        Token(value.unwrap_or_default().to_string())
+    }
+
+    fn validate_field(
+        _value: &elicitor::ResponseValue,
+        _responses: &elicitor::Responses,
+        _path: &elicitor::ResponsePath,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[test]
+fn generic_types_take_hand_written_surveys() {
+    let request = Envelope::<Token>::builder()
+        .suggest_id("req-1")
+        .run(
+            elicitor::TestBackend::new()
+                .with_string("id", "req-1")
+                .with_string("body.value", "secret-token"),
+        )
+        .unwrap();
+
+    assert_eq!(request.body, Token("secret-token".to_string()));
+}
+
+#[test]
+fn builders_reach_into_type_parameters() {
+    use elicitor::{DefaultValue, ResponseValue};
+
+    let backend = RecordDefaults::default();
+    let _ = Retried::<Ping, u8>::builder()
+        .suggest_first(|first| first.message("hello"))
+        .assume_fallback(|fallback| fallback.some(|ping| ping.message("bye")))
+        .suggest_attempts(|attempts| attempts.some(3))
+        .run(&backend);
+    assert_eq!(
+        backend.get("first.message"),
+        Some(DefaultValue::Suggested(ResponseValue::String(
+            "hello".into()
+        )))
+    );
+    assert_eq!(
+        backend.get("fallback.message"),
+        Some(DefaultValue::Assumed(ResponseValue::String("bye".into())))
+    );
+    assert_eq!(
+        backend.get("attempts"),
+        Some(DefaultValue::Suggested(ResponseValue::String("3".into())))
+    );
+
+    let backend = RecordDefaults::default();
+    let _ = Client::builder()
+        .suggest_ping(|ping| ping.body(|body| body.message("nested")))
+        .suggest_reply(|reply| reply.done(|ping| ping.message("done")))
+        .run(&backend);
+    assert_eq!(
+        backend.get("ping.body.message"),
+        Some(DefaultValue::Suggested(ResponseValue::String(
+            "nested".into()
+        )))
+    );
+    assert_eq!(
+        backend.get("reply.0.message"),
+        Some(DefaultValue::Suggested(ResponseValue::String(
+            "done".into()
+        )))
+    );
+}
+
+#[test]
+fn type_parameters_named_b_and_f() {
+    use elicitor::{DefaultValue, ResponseValue};
+
+    let backend = RecordDefaults::default();
+    let _ = Routed::<Ping, Route<Ping, Ping>>::builder()
+        .suggest_primary(|primary| primary.message("first"))
+        .suggest_fallback(|fallback| {
+            fallback.some(|route| {
+                route.forwarded(|forwarded| forwarded.via(|via| via.message("relay")))
+            })
+        })
+        .run(&backend);
+    assert_eq!(
+        backend.get("primary.message"),
+        Some(DefaultValue::Suggested(ResponseValue::String(
+            "first".into()
+        )))
+    );
+    assert_eq!(
+        backend.get("fallback.via.message"),
+        Some(DefaultValue::Suggested(ResponseValue::String(
+            "relay".into()
+        )))
+    );
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Filter {
+    Matches(#[ask("Pattern:")] String),
+    Not(#[ask("Negated filter:")] Box<Filter>),
+    And(
+        #[ask("Left:")]
+        #[max_depth(2)]
+        std::rc::Rc<Filter>,
+        #[ask("Right:")]
+        #[max_depth(2)]
+        std::sync::Arc<Filter>,
+    ),
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Boxed {
+    #[ask("Name:")]
+    name: std::rc::Rc<String>,
+
+    #[ask("Port:")]
+    port: Box<u16>,
+}
+
+#[test]
+fn boxed_fields_are_asked_as_their_pointee() {
+    let survey = Boxed::survey();
+    assert!(matches!(
+        survey.questions[0].kind(),
+        elicitor::QuestionKind::Input(_)
+    ));
+    assert!(matches!(
+        survey.questions[1].kind(),
+        elicitor::QuestionKind::Int(_)
+    ));
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("name", "db");
+    responses.insert("port", 5432);
+    let boxed = Boxed::from_responses(&responses);
+    assert_eq!(*boxed.name, "db");
+    assert_eq!(*boxed.port, 5432);
+
+    let _builder = Boxed::builder()
+        .suggest_name("cache")
+        .suggest_port(6379)
+        .with_suggestions(&boxed);
+}
+
+#[test]
+fn recursive_fields_are_lazy() {
+    let survey = Filter::survey();
+    let elicitor::QuestionKind::OneOf(one_of) = survey.questions[0].kind() else {
+        panic!("Expected OneOf question kind");
+    };
+    let elicitor::QuestionKind::AllOf(not) = &one_of.variants[1].kind else {
+        panic!("Expected AllOf question kind");
+    };
+    let elicitor::QuestionKind::Lazy(lazy) = not.questions()[0].kind() else {
+        panic!("Expected Lazy question kind");
+    };
+    assert_eq!(lazy.name(), "Filter");
+    assert_eq!(lazy.depth(), 0);
+    assert_eq!(lazy.max_depth(), elicitor::LazyQuestion::DEFAULT_MAX_DEPTH);
+
+    // Each expansion is one level deeper
+    let expanded = lazy.expand().unwrap();
+    let elicitor::QuestionKind::OneOf(inner) = expanded.questions()[0].kind() else {
+        panic!("Expected OneOf question kind");
+    };
+    let elicitor::QuestionKind::AllOf(and) = &inner.variants[2].kind else {
+        panic!("Expected AllOf question kind");
+    };
+    let elicitor::QuestionKind::Lazy(left) = and.questions()[0].kind() else {
+        panic!("Expected Lazy question kind");
+    };
+    assert_eq!(left.depth(), 1);
+    assert_eq!(left.max_depth(), 2);
+    let expanded = left.expand().unwrap();
+    let elicitor::QuestionKind::OneOf(inner) = expanded.questions()[0].kind() else {
+        panic!("Expected OneOf question kind");
+    };
+    let elicitor::QuestionKind::AllOf(and) = &inner.variants[2].kind else {
+        panic!("Expected AllOf question kind");
+    };
+    let elicitor::QuestionKind::Lazy(left) = and.questions()[0].kind() else {
+        panic!("Expected Lazy question kind");
+    };
+    assert!(left.expand().is_err());
+}
+
+#[test]
+fn recursive_types_from_responses() {
+    let mut responses = elicitor::Responses::new();
+    responses.insert(
+        "selected_variant",
+        elicitor::ResponseValue::ChosenVariant(1),
+    );
+    responses.insert(
+        "0.selected_variant",
+        elicitor::ResponseValue::ChosenVariant(0),
+    );
+    responses.insert("0.0", "*.rs");
+
+    assert_eq!(
+        Filter::from_responses(&responses),
+        Filter::Not(Box::new(Filter::Matches("*.rs".to_string())))
+    );
+}
+
+#[derive(Survey, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[survey(serde)]
+#[serde(rename_all = "camelCase")]
+struct ServerConfig {
+    #[ask("Host:")]
+    host_name: String,
+
+    #[ask("Port:")]
+    #[serde(rename = "listen-port")]
+    port: u16,
+
+    #[ask("Retries:")]
+    #[serde(default = "default_retries")]
+    max_retries: u8,
+
+    #[serde(skip)]
+    connections: Vec<String>,
+
+    #[serde(flatten)]
+    limits: Timeouts,
+}
+
+fn default_retries() -> u8 {
+    3
+}
+
+#[derive(Survey, Debug, PartialEq, serde::Deserialize)]
+#[survey(serde)]
+#[serde(default)]
+struct WorkerPool {
+    #[ask("Workers:")]
+    workers: u8,
+
+    #[ask("Queue:")]
+    #[serde(default = "default_queue")]
+    queue: String,
+
+    #[serde(skip)]
+    label: String,
+}
+
+impl Default for WorkerPool {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            queue: "jobs".to_string(),
+            label: "pool".to_string(),
+        }
+    }
+}
+
+fn default_queue() -> String {
+    "default".to_string()
+}
+
+#[derive(Survey, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[survey(serde)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Timeouts {
+    #[ask("Timeout:")]
+    timeout_secs: u32,
+}
+
+#[derive(Survey, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[survey(serde)]
+#[serde(rename_all = "kebab-case", rename_all_fields = "camelCase")]
+enum Transport {
+    Tcp,
+    UnixSocket {
+        #[ask("Socket path:")]
+        socket_path: String,
+    },
+    #[serde(rename = "pipe")]
+    NamedPipe(#[ask("Pipe name:")] String),
+}
+
+#[test]
+fn serde_names_are_used_for_paths() {
+    let survey = ServerConfig::survey();
+    let paths: Vec<_> = survey.questions.iter().map(|q| q.path().as_str()).collect();
+    assert_eq!(
+        paths,
+        ["hostName", "listen-port", "maxRetries", "TIMEOUT_SECS"]
+    );
+    // Prompts still come from the field
+    assert_eq!(survey.questions[1].ask(), "Port:");
+}
+
+#[test]
+fn serde_fields_from_responses() {
+    let mut responses = elicitor::Responses::new();
+    responses.insert("hostName", "example.org");
+    responses.insert("listen-port", 8080);
+    responses.insert("maxRetries", 5);
+    responses.insert("TIMEOUT_SECS", 30);
+
+    let config = ServerConfig::from_responses(&responses);
+    assert_eq!(
+        config,
+        ServerConfig {
+            host_name: "example.org".to_string(),
+            port: 8080,
+            max_retries: 5,
+            connections: Vec::new(),
+            limits: Timeouts { timeout_secs: 30 },
+        }
+    );
+
+    // The config serializes to the same keys the answers were given under
+    let table: toml::Table = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+    let mut keys: Vec<_> = table.keys().map(String::as_str).collect();
+    let mut paths: Vec<_> = responses.iter().map(|(p, _)| p.as_str()).collect();
+    keys.sort_unstable();
+    paths.sort_unstable();
+    assert_eq!(keys, paths);
+
+    // Fields with a serde default fall back to it without an answer
+    responses.remove(&elicitor::ResponsePath::new("maxRetries"));
+    assert_eq!(ServerConfig::from_responses(&responses).max_retries, 3);
+}
+
+#[test]
+fn serde_variant_names_match_tags() {
+    let survey = Transport::survey();
+    let elicitor::QuestionKind::OneOf(one_of) = survey.questions[0].kind() else {
+        panic!("Expected OneOf question kind");
+    };
+    let names: Vec<_> = one_of.variants.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["tcp", "unix-socket", "pipe"]);
+
+    let elicitor::QuestionKind::AllOf(fields) = &one_of.variants[1].kind else {
+        panic!("Expected AllOf question kind");
+    };
+    assert_eq!(fields.questions()[0].path().as_str(), "socketPath");
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert(
+        "selected_variant",
+        elicitor::ResponseValue::ChosenVariant(1),
+    );
+    responses.insert("socketPath", "/run/app.sock");
+    assert_eq!(
+        Transport::from_responses(&responses),
+        Transport::UnixSocket {
+            socket_path: "/run/app.sock".to_string()
+        }
+    );
+}
+
+#[test]
+fn serde_builders_use_serde_names() {
+    let config = ServerConfig::builder()
+        .suggest_host_name("localhost")
+        .suggest_limits(|limits| limits.timeout_secs(10))
+        .run(
+            elicitor::TestBackend::new()
+                .with_string("hostName", "example.org")
+                .with_int("listen-port", 443)
+                .with_int("maxRetries", 1)
+                .with_int("TIMEOUT_SECS", 60),
+        )
+        .unwrap();
+    assert_eq!(config.port, 443);
+    assert_eq!(config.limits.timeout_secs, 60);
+
+    // Suggestions and assumptions land on the renamed questions
+    let backend = RecordDefaults::default();
+    let _ = ServerConfig::builder()
+        .suggest_host_name("localhost")
+        .assume_port(8443)
+        .suggest_limits(|limits| limits.timeout_secs(10))
+        .run(&backend);
+    assert_eq!(
+        backend.get("hostName"),
+        Some(elicitor::DefaultValue::Suggested("localhost".into()))
+    );
+    assert_eq!(
+        backend.get("listen-port"),
+        Some(elicitor::DefaultValue::Assumed(8443.into()))
+    );
+    assert_eq!(
+        backend.get("TIMEOUT_SECS"),
+        Some(elicitor::DefaultValue::Suggested(10.into()))
+    );
+}
+
+#[test]
+fn serde_container_default_fills_unanswered_fields() {
+    let responses = elicitor::Responses::new();
+    let pool = WorkerPool::from_responses(&responses);
+    assert_eq!(
+        pool,
+        WorkerPool {
+            workers: 4,
+            queue: "default".to_string(),
+            label: "pool".to_string(),
+        }
+    );
+    // The same as serde fills in for an empty config
+    assert_eq!(pool, toml::from_str::<WorkerPool>("").unwrap());
+
+    let mut responses = elicitor::Responses::new();
+    responses.insert("workers", 8);
+    assert_eq!(WorkerPool::from_responses(&responses).workers, 8);
+}
+
+fn never_started() -> String {
+    "never".to_string()
+}
+
+fn validate_even(
+    value: &elicitor::ResponseValue,
+    _responses: &elicitor::Responses,
+    _path: &elicitor::ResponsePath,
+) -> Result<(), String> {
+    match value.as_int() {
+        Some(n) if n % 2 != 0 => Err("must be even".to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Pool {
+    #[ask("Workers:")]
+    #[validate(validate_even)]
+    workers: u32,
+
+    #[ask("Queue length:")]
+    queue: u32,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Service {
+    #[ask("Service name:")]
+    name: String,
+
+    #[flatten]
+    pool: Pool,
+
+    #[skip]
+    cache: Vec<String>,
+
+    #[skip(default = "never_started")]
+    started: String,
+}
+
+#[test]
+fn skipped_fields_are_not_asked() {
+    let survey = Service::survey();
+    let paths: Vec<_> = survey.questions.iter().map(|q| q.path().as_str()).collect();
+    // The pool's questions sit next to the service's own
+    assert_eq!(paths, ["name", "workers", "queue"]);
+}
+
+#[test]
+fn skipped_and_flattened_fields_from_responses() {
+    let mut responses = elicitor::Responses::new();
+    responses.insert("name", "api");
+    responses.insert("workers", 4);
+    responses.insert("queue", 100);
+
+    assert_eq!(
+        Service::from_responses(&responses),
+        Service {
+            name: "api".to_string(),
+            pool: Pool {
+                workers: 4,
+                queue: 100,
+            },
+            cache: Vec::new(),
+            started: "never".to_string(),
+        }
+    );
+}
+
+#[test]
+fn flattened_fields_are_validated_and_suggested() {
+    let backend = || {
+        elicitor::TestBackend::new()
+            .with_string("name", "api")
+            .with_int("queue", 10)
+    };
+
+    let service = Service::builder()
+        .suggest_pool(|pool| pool.workers(2))
+        .run(backend().with_int("workers", 8))
+        .unwrap();
+    assert_eq!(service.pool.workers, 8);
+
+    let err = Service::builder()
+        .run(backend().with_int("workers", 3))
+        .unwrap_err();
+    assert!(err.to_string().contains("must be even"));
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Documented {
+    /// How many replicas should run?
+    ///
+    /// Each replica is a separate process. Use one per core
+    /// at most.
+    ///
+    /// Defaults to one.
+    replicas: u8,
+
+    /// What should happen on failure?
+    #[multiselect]
+    policy: Vec<Policy>,
+
+    /// Internal note, not shown.
+    #[ask("Label:")]
+    label: String,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Policy {
+    /// Restart
+    ///
+    /// Start the process again after a short delay.
+    Restart,
+
+    /// Give up
+    Stop,
+}
+
+#[test]
+fn doc_comments_give_prompts_and_help() {
+    let survey = Documented::survey();
+    let replicas = &survey.questions[0];
+    assert_eq!(replicas.ask(), "How many replicas should run?");
+    assert_eq!(
+        replicas.help(),
+        Some("Each replica is a separate process. Use one per core at most.\n\nDefaults to one.")
+    );
+
+    let policy = &survey.questions[1];
+    assert_eq!(policy.ask(), "What should happen on failure?");
+    assert_eq!(policy.help(), None);
+
+    // An explicit prompt wins over the doc comment
+    assert_eq!(survey.questions[2].ask(), "Label:");
+    assert_eq!(survey.questions[2].help(), None);
+}
+
+#[test]
+fn doc_comments_name_variants() {
+    let survey = Documented::survey();
+    let elicitor::QuestionKind::AnyOf(any_of) = survey.questions[1].kind() else {
+        panic!("Expected AnyOf question kind");
+    };
+    assert_eq!(any_of.variants[0].name, "Restart");
+    assert_eq!(
+        any_of.variants[0].help.as_deref(),
+        Some("Start the process again after a short delay.")
+    );
+    assert_eq!(any_of.variants[1].name, "Give up");
+    assert_eq!(any_of.variants[1].help, None);
+}
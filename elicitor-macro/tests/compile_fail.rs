@@ -0,0 +1,7 @@
+//! Derives that must be rejected at compile time
+
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,10 @@
+use elicitor::Survey;
+
+#[derive(Survey)]
+struct Meeting {
+    #[ask("Length:")]
+    #[max("8 fortnights")]
+    length: std::time::Duration,
+}
+
+fn main() {}
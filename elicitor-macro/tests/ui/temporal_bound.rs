@@ -0,0 +1,10 @@
+use elicitor::Survey;
+
+#[derive(Survey)]
+struct Meeting {
+    #[ask("Day:")]
+    #[min("2024-13-01")]
+    day: elicitor::Date,
+}
+
+fn main() {}
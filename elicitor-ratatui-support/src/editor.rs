@@ -0,0 +1,63 @@
+//! Editing text in the user's external editor.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set.
+#[cfg(windows)]
+const FALLBACK_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const FALLBACK_EDITOR: &str = "vi";
+
+/// Open `text` in the user's editor and return the edited text.
+///
+/// The editor is taken from `$VISUAL`, then `$EDITOR`, falling back to `vi`
+/// (`notepad` on Windows). The variable may include arguments, as in
+/// `EDITOR="code --wait"`. The text is passed via a temporary file, which is
+/// removed afterwards; a single trailing newline added by the editor is
+/// stripped.
+///
+/// The caller must leave raw mode and the alternate screen before calling
+/// this, and restore them afterwards.
+pub fn edit_externally(text: &str) -> io::Result<String> {
+    let command = editor_command();
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or(FALLBACK_EDITOR);
+
+    let path = temp_path();
+    std::fs::write(&path, text)?;
+    let status = Command::new(program).args(parts).arg(&path).status();
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let status = status?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{program} exited with {status}")));
+    }
+    let mut edited = edited?;
+    if edited.ends_with('\n') {
+        edited.pop();
+        if edited.ends_with('\r') {
+            edited.pop();
+        }
+    }
+    Ok(edited)
+}
+
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .map(|value| value.to_string_lossy().trim().to_string())
+        .find(|value| !value.is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+/// A fresh temporary file path, unique within this process.
+fn temp_path() -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("elicitor-{}-{n}.txt", std::process::id()))
+}
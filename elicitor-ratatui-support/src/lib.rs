@@ -0,0 +1,16 @@
+//! Text editing shared by elicitor's ratatui backends.
+//!
+//! The ratatui wizard and the ratatui form edit text fields the same way:
+//! [`textarea`] holds the cursor movement, soft wrapping and the [`TextArea`]
+//! widget, and [`edit_externally`] hands a field's text to the user's editor.
+//! Behind the `testing` feature, [`testing`] holds what the backends' headless
+//! test harnesses share.
+//!
+//! [`TextArea`]: textarea::TextArea
+
+mod editor;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod textarea;
+
+pub use editor::edit_externally;
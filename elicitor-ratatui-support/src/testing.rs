@@ -0,0 +1,69 @@
+//! Helpers shared by the headless test harnesses of the ratatui backends.
+//!
+//! Both harnesses render into ratatui's in-memory `TestBackend`; these
+//! functions read the rendered buffer and build the mouse events the
+//! harnesses send. The harness modules re-export the buffer helpers.
+
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+
+/// Find the first cell where `text` starts in a rendered buffer, as `(column, row)`.
+pub fn find_text(buffer: &Buffer, text: &str) -> Option<(u16, u16)> {
+    let area = buffer.area;
+    (area.top()..area.bottom()).find_map(|y| {
+        let cells: Vec<&str> = (area.left()..area.right())
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        (0..cells.len()).find_map(|start| {
+            let mut rest = text;
+            for symbol in &cells[start..] {
+                match rest.strip_prefix(symbol) {
+                    Some(r) => rest = r,
+                    None => return None,
+                }
+                if rest.is_empty() {
+                    return Some((area.left() + start as u16, y));
+                }
+            }
+            None
+        })
+    })
+}
+
+/// Convert a rendered buffer into plain text, one line per row.
+pub fn buffer_to_string(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer
+        .content()
+        .chunks(width.max(1))
+        .map(|row| {
+            let line: String = row.iter().map(|cell| cell.symbol()).collect();
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A left click on a screen cell.
+pub fn click(column: u16, row: u16) -> MouseEvent {
+    mouse_event(MouseEventKind::Down(MouseButton::Left), column, row)
+}
+
+/// One step of the mouse wheel down.
+pub fn scroll_down() -> MouseEvent {
+    mouse_event(MouseEventKind::ScrollDown, 0, 0)
+}
+
+/// One step of the mouse wheel up.
+pub fn scroll_up() -> MouseEvent {
+    mouse_event(MouseEventKind::ScrollUp, 0, 0)
+}
+
+fn mouse_event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    }
+}
@@ -0,0 +1,440 @@
+//! Text editing for text fields, including multi-line `#[multiline]` fields.
+//!
+//! The text and cursor stay with the field that owns them; the cursor is a
+//! byte offset into the text that always sits on a grapheme cluster boundary,
+//! so emoji and combining characters move and delete as one unit. Columns are
+//! measured in terminal cells, so wide (for example CJK) characters take two.
+//! This module provides the editing operations, soft wrapping, and a
+//! [`TextArea`] widget that keeps the cursor row scrolled into view.
+
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::Style,
+    widgets::StatefulWidget,
+};
+
+/// Insert a character at the cursor.
+pub fn insert(text: &mut String, cursor: &mut usize, c: char) {
+    text.insert(*cursor, c);
+    *cursor += c.len_utf8();
+}
+
+/// Insert pasted text at the cursor.
+///
+/// Line endings are normalized to `\n`. Text pasted into a single-line field
+/// loses its trailing line breaks and has the others replaced by spaces.
+pub fn paste(text: &mut String, cursor: &mut usize, pasted: &str, multiline: bool) {
+    let mut pasted = pasted.replace("\r\n", "\n").replace('\r', "\n");
+    if !multiline {
+        pasted = pasted.trim_end_matches('\n').replace('\n', " ");
+    }
+    text.insert_str(*cursor, &pasted);
+    *cursor += pasted.len();
+}
+
+/// Delete the character before the cursor. Returns `false` at the start of the text.
+pub fn backspace(text: &mut String, cursor: &mut usize) -> bool {
+    if *cursor == 0 {
+        return false;
+    }
+    let start = prev_boundary(text, *cursor);
+    text.replace_range(start..*cursor, "");
+    *cursor = start;
+    true
+}
+
+/// Delete the character under the cursor. Returns `false` at the end of the text.
+pub fn delete(text: &mut String, cursor: &mut usize) -> bool {
+    if *cursor >= text.len() {
+        return false;
+    }
+    let end = next_boundary(text, *cursor);
+    text.replace_range(*cursor..end, "");
+    true
+}
+
+/// Move the cursor one character left.
+pub fn left(text: &str, cursor: &mut usize) {
+    *cursor = prev_boundary(text, *cursor);
+}
+
+/// Move the cursor one character right.
+pub fn right(text: &str, cursor: &mut usize) {
+    *cursor = next_boundary(text, *cursor);
+}
+
+/// Move the cursor to the start of the previous word.
+pub fn word_left(text: &str, cursor: &mut usize) {
+    let mut graphemes = text[..*cursor].grapheme_indices(true).rev().peekable();
+    while let Some((i, _)) = graphemes.next_if(|(_, g)| !is_word(g)) {
+        *cursor = i;
+    }
+    while let Some((i, _)) = graphemes.next_if(|(_, g)| is_word(g)) {
+        *cursor = i;
+    }
+}
+
+/// Move the cursor to the end of the next word.
+pub fn word_right(text: &str, cursor: &mut usize) {
+    let start = *cursor;
+    let mut graphemes = text[start..].grapheme_indices(true).peekable();
+    while let Some((i, g)) = graphemes.next_if(|(_, g)| !is_word(g)) {
+        *cursor = start + i + g.len();
+    }
+    while let Some((i, g)) = graphemes.next_if(|(_, g)| is_word(g)) {
+        *cursor = start + i + g.len();
+    }
+}
+
+fn is_word(grapheme: &str) -> bool {
+    grapheme.chars().any(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Move the cursor to the start of its line.
+pub fn home(text: &str, cursor: &mut usize) {
+    *cursor = line_start(text, *cursor);
+}
+
+/// Move the cursor to the end of its line.
+pub fn end(text: &str, cursor: &mut usize) {
+    *cursor = line_end(text, *cursor);
+}
+
+/// Move the cursor to the same column of the previous line.
+///
+/// Returns `false` if the cursor is already on the first line.
+pub fn up(text: &str, cursor: &mut usize) -> bool {
+    let start = line_start(text, *cursor);
+    if start == 0 {
+        return false;
+    }
+    let column = text[start..*cursor].width();
+    let prev_start = line_start(text, start - 1);
+    *cursor = column_offset(text, prev_start..start - 1, column);
+    true
+}
+
+/// Move the cursor to the same column of the next line.
+///
+/// Returns `false` if the cursor is already on the last line.
+pub fn down(text: &str, cursor: &mut usize) -> bool {
+    let end = line_end(text, *cursor);
+    if end == text.len() {
+        return false;
+    }
+    let column = text[line_start(text, *cursor)..*cursor].width();
+    let next_end = line_end(text, end + 1);
+    *cursor = column_offset(text, end + 1..next_end, column);
+    true
+}
+
+/// Byte offset of the text position shown at `row` and `column` of a text
+/// area `width` cells wide that is scrolled down by `scroll` rows.
+pub fn offset_at(text: &str, width: u16, scroll: usize, row: usize, column: usize) -> usize {
+    match wrap(text, width as usize).get(scroll + row) {
+        Some(range) => column_offset(text, range.clone(), column),
+        None => text.len(),
+    }
+}
+
+/// Number of terminal cells `text` takes up, or would as masked text.
+pub fn display_width(text: &str, masked: bool) -> usize {
+    if masked {
+        text.graphemes(true).count()
+    } else {
+        text.width()
+    }
+}
+
+/// Text shown for a masked field: one dot per grapheme cluster.
+pub fn mask(text: &str) -> String {
+    "●".repeat(text.graphemes(true).count())
+}
+
+fn prev_boundary(text: &str, cursor: usize) -> usize {
+    text[..cursor]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(i, _)| i)
+}
+
+fn next_boundary(text: &str, cursor: usize) -> usize {
+    text[cursor..]
+        .graphemes(true)
+        .next()
+        .map_or(cursor, |g| cursor + g.len())
+}
+
+fn line_start(text: &str, cursor: usize) -> usize {
+    text[..cursor].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(text: &str, cursor: usize) -> usize {
+    text[cursor..].find('\n').map_or(text.len(), |i| cursor + i)
+}
+
+/// Byte offset of the display `column` within `range`, clamped to its end.
+///
+/// A column in the middle of a wide character maps to the position after it.
+fn column_offset(text: &str, range: Range<usize>, column: usize) -> usize {
+    let mut width = 0;
+    for (i, grapheme) in text[range.clone()].grapheme_indices(true) {
+        if width >= column {
+            return range.start + i;
+        }
+        width += grapheme.width();
+    }
+    range.end
+}
+
+/// Byte offset of the first grapheme of `text` that no longer fits into
+/// `width` cells, if any. The first grapheme always fits.
+fn overflow(text: &str, width: usize) -> Option<usize> {
+    let mut used = 0;
+    text.grapheme_indices(true).find_map(|(i, grapheme)| {
+        used += grapheme.width();
+        (used > width && i > 0).then_some(i)
+    })
+}
+
+/// Split text into display rows no wider than `width` cells.
+///
+/// Lines break at newlines, and long lines wrap after the last space that
+/// fits, or mid-word if there is none. Each row is a byte range of `text`
+/// without the newline.
+pub fn wrap(text: &str, width: usize) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut line_start = 0;
+    for line in text.split('\n') {
+        let line_end = line_start + line.len();
+        let mut start = line_start;
+        loop {
+            let rest = &text[start..line_end];
+            match overflow(rest, width) {
+                None => {
+                    rows.push(start..line_end);
+                    break;
+                }
+                Some(split) => {
+                    let split = rest[..split].rfind(' ').map_or(split, |i| i + 1);
+                    rows.push(start..start + split);
+                    start += split;
+                }
+            }
+        }
+        line_start = line_end + 1;
+    }
+    rows
+}
+
+/// Row and column of the cursor within wrapped rows.
+///
+/// A cursor at a soft wrap point is shown at the start of the next row.
+fn locate(text: &str, rows: &[Range<usize>], cursor: usize) -> (usize, usize) {
+    let row = rows.iter().rposition(|r| r.start <= cursor).unwrap_or(0);
+    let column = text[rows[row].start..cursor].width();
+    (row, column)
+}
+
+/// Scroll position of a [`TextArea`], kept between renders.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextAreaState {
+    /// First visible row.
+    pub scroll: usize,
+    /// Screen position of the cursor from the last render.
+    pub cursor: Option<Position>,
+}
+
+/// Soft-wrapped, vertically scrolling view of multi-line text.
+pub struct TextArea<'a> {
+    text: &'a str,
+    cursor: usize,
+    style: Style,
+}
+
+impl<'a> TextArea<'a> {
+    pub fn new(text: &'a str, cursor: usize) -> Self {
+        Self {
+            text,
+            cursor,
+            style: Style::default(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl StatefulWidget for TextArea<'_> {
+    type State = TextAreaState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        state.cursor = None;
+        if area.is_empty() {
+            return;
+        }
+        buf.set_style(area, self.style);
+
+        let rows = wrap(self.text, area.width as usize);
+        let (row, column) = locate(self.text, &rows, self.cursor);
+        let height = area.height as usize;
+        state.scroll = state.scroll.min(row).max((row + 1).saturating_sub(height));
+
+        for (y, range) in (area.y..area.bottom()).zip(rows[state.scroll..].iter()) {
+            buf.set_stringn(
+                area.x,
+                y,
+                &self.text[range.clone()],
+                area.width as usize,
+                self.style,
+            );
+        }
+
+        state.cursor = Some(Position::new(
+            area.x + (column as u16).min(area.width - 1),
+            area.y + (row - state.scroll) as u16,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_at_spaces_and_newlines() {
+        let text = "hello brave new world\nbye";
+        let rows: Vec<&str> = wrap(text, 10).into_iter().map(|r| &text[r]).collect();
+        assert_eq!(rows, ["hello ", "brave new ", "world", "bye"]);
+
+        let rows: Vec<&str> = wrap("abcdefgh", 3)
+            .into_iter()
+            .map(|r| &"abcdefgh"[r])
+            .collect();
+        assert_eq!(rows, ["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn moves_between_lines_keeping_the_column() {
+        let text = "first line\nab\nthird line";
+        let mut cursor = 7;
+        assert!(down(text, &mut cursor));
+        assert_eq!(cursor, 13, "clamped to the end of the short line");
+        assert!(down(text, &mut cursor));
+        assert_eq!(&text[cursor..], "ird line");
+        assert!(!down(text, &mut cursor));
+        assert!(up(text, &mut cursor));
+        assert!(up(text, &mut cursor));
+        assert_eq!(cursor, 2);
+        assert!(!up(text, &mut cursor));
+    }
+
+    #[test]
+    fn edits_multibyte_text() {
+        let mut text = String::from("né");
+        let mut cursor = text.len();
+        insert(&mut text, &mut cursor, '\n');
+        insert(&mut text, &mut cursor, 'ü');
+        assert_eq!(text, "né\nü");
+        left(&text, &mut cursor);
+        left(&text, &mut cursor);
+        assert!(backspace(&mut text, &mut cursor));
+        assert_eq!(text, "n\nü");
+        home(&text, &mut cursor);
+        assert_eq!(cursor, 0);
+        end(&text, &mut cursor);
+        assert!(delete(&mut text, &mut cursor));
+        assert_eq!(text, "nü");
+    }
+
+    #[test]
+    fn moves_and_deletes_whole_graphemes() {
+        // "e" with a combining acute accent, a family emoji and a flag
+        let mut text = String::from("e\u{301}👨‍👩‍👧🇩🇪");
+        let mut cursor = text.len();
+        left(&text, &mut cursor);
+        assert_eq!(&text[cursor..], "🇩🇪");
+        assert!(backspace(&mut text, &mut cursor));
+        assert_eq!(text, "e\u{301}🇩🇪");
+        home(&text, &mut cursor);
+        right(&text, &mut cursor);
+        assert_eq!(cursor, "e\u{301}".len());
+    }
+
+    #[test]
+    fn measures_columns_in_cells() {
+        let text = "日本語\nabcdef";
+        assert_eq!(display_width("日本語", false), 6);
+        assert_eq!(display_width("日本語", true), 3);
+        assert_eq!(offset_at(text, 80, 0, 0, 2), "日".len());
+
+        let mut cursor = text.len();
+        assert!(up(text, &mut cursor));
+        assert_eq!(cursor, "日本語".len());
+        let mut cursor = "日本語\nabcd".len();
+        assert!(up(text, &mut cursor));
+        assert_eq!(cursor, "日本".len());
+
+        let rows: Vec<&str> = wrap("日本語", 4)
+            .into_iter()
+            .map(|r| &"日本語"[r])
+            .collect();
+        assert_eq!(rows, ["日本", "語"]);
+    }
+
+    #[test]
+    fn moves_by_words() {
+        let text = "hello, wide world";
+        let mut cursor = text.len();
+        word_left(text, &mut cursor);
+        assert_eq!(&text[cursor..], "world");
+        word_left(text, &mut cursor);
+        assert_eq!(&text[cursor..], "wide world");
+        word_left(text, &mut cursor);
+        assert_eq!(cursor, 0);
+        word_right(text, &mut cursor);
+        assert_eq!(&text[cursor..], ", wide world");
+        word_right(text, &mut cursor);
+        assert_eq!(&text[cursor..], " world");
+    }
+
+    #[test]
+    fn pastes_into_single_and_multi_line_text() {
+        let mut text = String::from("ab");
+        let mut cursor = 1;
+        paste(&mut text, &mut cursor, "x\r\ny\n", false);
+        assert_eq!(text, "ax yb");
+        assert_eq!(cursor, 4);
+
+        let mut text = String::new();
+        let mut cursor = 0;
+        paste(&mut text, &mut cursor, "x\r\ny\n", true);
+        assert_eq!(text, "x\ny\n");
+    }
+
+    #[test]
+    fn scrolls_to_keep_the_cursor_visible() {
+        let text = "1\n2\n3\n4\n5";
+        let area = Rect::new(0, 0, 5, 2);
+        let mut buf = Buffer::empty(area);
+        let mut state = TextAreaState::default();
+
+        TextArea::new(text, text.len()).render(area, &mut buf, &mut state);
+        assert_eq!(state.scroll, 3);
+        assert_eq!(state.cursor, Some(Position::new(1, 1)));
+        assert_eq!(buf[(0, 0)].symbol(), "4");
+
+        TextArea::new(text, 0).render(area, &mut buf, &mut state);
+        assert_eq!(state.scroll, 0);
+        assert_eq!(state.cursor, Some(Position::new(0, 0)));
+    }
+}
@@ -0,0 +1,286 @@
+//! Shared color themes for elicitor's terminal backends.
+//!
+//! The ratatui wizard, the ratatui form and the dialoguer wizard all accept the
+//! same [`Theme`], so one configuration styles every terminal backend.
+//!
+//! Themes come from one of three places:
+//! - Built-in presets: [`Theme::dark`], [`Theme::light`], [`Theme::high_contrast`]
+//!   and [`Theme::monochrome`]
+//! - A TOML file, via [`Theme::from_file`] or [`Theme::from_toml_str`]
+//! - The environment, via [`Theme::from_env`], which honors
+//!   [`NO_COLOR`](https://no-color.org)
+//!
+//! ## Theme files
+//!
+//! A theme file names a preset to start from and overrides individual colors.
+//! Every key is optional; `base` defaults to `"dark"`.
+//!
+//! ```toml
+//! base = "light"
+//! primary = "magenta"
+//! highlight = "#d75f00"
+//! selected_bg = "253"
+//! ```
+//!
+//! Colors are written as names (`"red"`, `"light-blue"`, `"dark-gray"`), hex
+//! codes (`"#ff8800"`) or 256-color indices (`"42"`).
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use ratatui_core::style::Color;
+
+/// Name of the environment variable that disables colored output.
+pub const NO_COLOR: &str = "NO_COLOR";
+
+/// Error loading a theme.
+#[derive(Debug, Error)]
+pub enum ThemeError {
+    /// The theme file could not be read.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The theme file is not valid TOML or contains an invalid color.
+    #[error("Invalid theme file: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// The `base` key names a preset that does not exist.
+    #[error("Unknown theme preset: {0}")]
+    UnknownPreset(String),
+}
+
+/// Color theme for the terminal backends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    /// Headers, focused borders and the progress bar.
+    pub primary: Color,
+    /// Secondary accents such as nested section titles.
+    pub secondary: Color,
+    /// Background of the whole screen.
+    pub background: Color,
+    /// Regular text.
+    pub text: Color,
+    /// Focused prompts and the highlighted option.
+    pub highlight: Color,
+    /// Validation errors.
+    pub error: Color,
+    /// Completion messages and checked options.
+    pub success: Color,
+    /// Unfocused borders and help text.
+    pub border: Color,
+    /// Background of the highlighted option.
+    pub selected_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Names accepted by [`Theme::preset`] and the `base` key of theme files.
+    pub const PRESETS: [&'static str; 4] = ["dark", "light", "high-contrast", "monochrome"];
+
+    /// Colors for terminals with a dark background. This is the default.
+    pub fn dark() -> Self {
+        Self {
+            primary: Color::Cyan,
+            secondary: Color::Blue,
+            background: Color::Reset,
+            text: Color::White,
+            highlight: Color::Yellow,
+            error: Color::Red,
+            success: Color::Green,
+            border: Color::Gray,
+            selected_bg: Color::DarkGray,
+        }
+    }
+
+    /// Colors for terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            primary: Color::Blue,
+            secondary: Color::Magenta,
+            background: Color::Reset,
+            text: Color::Black,
+            highlight: Color::Indexed(130),
+            error: Color::Red,
+            success: Color::Green,
+            border: Color::DarkGray,
+            selected_bg: Color::Indexed(252),
+        }
+    }
+
+    /// Bright colors on black, for low-vision users and washed-out displays.
+    pub fn high_contrast() -> Self {
+        Self {
+            primary: Color::LightCyan,
+            secondary: Color::LightBlue,
+            background: Color::Black,
+            text: Color::White,
+            highlight: Color::LightYellow,
+            error: Color::LightRed,
+            success: Color::LightGreen,
+            border: Color::White,
+            selected_bg: Color::Blue,
+        }
+    }
+
+    /// No colors at all: everything uses the terminal's default colors.
+    ///
+    /// The backends still mark the focused field and highlighted option with
+    /// bold text and markers, so the interface stays usable.
+    pub fn monochrome() -> Self {
+        Self {
+            primary: Color::Reset,
+            secondary: Color::Reset,
+            background: Color::Reset,
+            text: Color::Reset,
+            highlight: Color::Reset,
+            error: Color::Reset,
+            success: Color::Reset,
+            border: Color::Reset,
+            selected_bg: Color::Reset,
+        }
+    }
+
+    /// Look up a built-in preset by name (see [`Theme::PRESETS`]).
+    ///
+    /// Underscores are accepted in place of dashes, and case is ignored.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "monochrome" | "none" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// The default theme, or [`Theme::monochrome`] if `NO_COLOR` is set.
+    ///
+    /// This is what the terminal backends use unless a theme is configured
+    /// explicitly.
+    pub fn from_env() -> Self {
+        if no_color() {
+            Self::monochrome()
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Parse a theme from the contents of a TOML theme file.
+    pub fn from_toml_str(source: &str) -> Result<Self, ThemeError> {
+        let file: ThemeFile = toml::from_str(source)?;
+        file.into_theme()
+    }
+
+    /// Load a theme from a TOML file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// Whether this theme leaves all colors at the terminal default.
+    pub fn is_monochrome(&self) -> bool {
+        *self == Self::monochrome()
+    }
+}
+
+/// Whether the user asked for no colors via a non-empty `NO_COLOR` variable.
+pub fn no_color() -> bool {
+    std::env::var_os(NO_COLOR).is_some_and(|value| !value.is_empty())
+}
+
+/// On-disk format: a base preset plus optional per-color overrides.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    primary: Option<Color>,
+    secondary: Option<Color>,
+    background: Option<Color>,
+    text: Option<Color>,
+    highlight: Option<Color>,
+    error: Option<Color>,
+    success: Option<Color>,
+    border: Option<Color>,
+    selected_bg: Option<Color>,
+}
+
+impl ThemeFile {
+    fn into_theme(self) -> Result<Theme, ThemeError> {
+        let base = self.base.as_deref().unwrap_or("dark");
+        let theme =
+            Theme::preset(base).ok_or_else(|| ThemeError::UnknownPreset(base.to_string()))?;
+
+        Ok(Theme {
+            primary: self.primary.unwrap_or(theme.primary),
+            secondary: self.secondary.unwrap_or(theme.secondary),
+            background: self.background.unwrap_or(theme.background),
+            text: self.text.unwrap_or(theme.text),
+            highlight: self.highlight.unwrap_or(theme.highlight),
+            error: self.error.unwrap_or(theme.error),
+            success: self.success.unwrap_or(theme.success),
+            border: self.border.unwrap_or(theme.border),
+            selected_bg: self.selected_bg.unwrap_or(theme.selected_bg),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_by_name() {
+        for name in Theme::PRESETS {
+            assert!(Theme::preset(name).is_some(), "missing preset {name}");
+        }
+        assert_eq!(Theme::preset("High_Contrast"), Some(Theme::high_contrast()));
+        assert_eq!(Theme::preset("solarized"), None);
+        assert_eq!(Theme::default(), Theme::dark());
+        assert!(Theme::monochrome().is_monochrome());
+    }
+
+    #[test]
+    fn toml_overrides_base_preset() {
+        let theme = Theme::from_toml_str(
+            r##"
+            base = "light"
+            primary = "magenta"
+            highlight = "#d75f00"
+            selected_bg = "253"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(theme.primary, Color::Magenta);
+        assert_eq!(theme.highlight, Color::Rgb(0xd7, 0x5f, 0x00));
+        assert_eq!(theme.selected_bg, Color::Indexed(253));
+        assert_eq!(theme.text, Theme::light().text);
+    }
+
+    #[test]
+    fn empty_toml_is_default() {
+        assert_eq!(Theme::from_toml_str("").unwrap(), Theme::default());
+    }
+
+    #[test]
+    fn invalid_toml_is_rejected() {
+        assert!(matches!(
+            Theme::from_toml_str(r#"base = "neon""#),
+            Err(ThemeError::UnknownPreset(name)) if name == "neon"
+        ));
+        assert!(matches!(
+            Theme::from_toml_str(r#"primary = "not-a-color""#),
+            Err(ThemeError::Parse(_))
+        ));
+        assert!(matches!(
+            Theme::from_toml_str(r#"primry = "red""#),
+            Err(ThemeError::Parse(_))
+        ));
+    }
+}
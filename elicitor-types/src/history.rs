@@ -0,0 +1,137 @@
+//! Undo/redo history for form edits, shared by the form backends.
+
+/// Number of undo steps kept; older steps are dropped.
+const MAX_STEPS: usize = 200;
+
+/// One undoable change to a single field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldEdit<K, T> {
+    /// The field that changed.
+    pub field: K,
+    /// Contents of the field before the change.
+    pub before: T,
+    /// Contents of the field after the change.
+    pub after: T,
+}
+
+/// Undo and redo stacks of field edits.
+///
+/// Consecutive typing in one field is merged into a single step until the
+/// group is closed, which happens on any edit that is not typing and when
+/// the caller calls [`History::close_group`] (for example on a focus change).
+#[derive(Debug, Clone)]
+pub struct History<K, T> {
+    undo: Vec<FieldEdit<K, T>>,
+    redo: Vec<FieldEdit<K, T>>,
+    /// Whether the last undo step is a typing run that may still grow.
+    open_group: bool,
+}
+
+impl<K, T> Default for History<K, T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            open_group: false,
+        }
+    }
+}
+
+impl<K: PartialEq, T> History<K, T> {
+    /// Record a change to `field`. Typing extends the open typing run of the
+    /// same field instead of adding a step.
+    pub fn record(&mut self, field: K, before: T, after: T, typing: bool) {
+        self.redo.clear();
+        if typing
+            && self.open_group
+            && let Some(last) = self.undo.last_mut()
+            && last.field == field
+        {
+            last.after = after;
+            return;
+        }
+        self.undo.push(FieldEdit {
+            field,
+            before,
+            after,
+        });
+        if self.undo.len() > MAX_STEPS {
+            self.undo.remove(0);
+        }
+        self.open_group = typing;
+    }
+
+    /// End the current typing run, so the next keystroke starts a new step.
+    pub fn close_group(&mut self) {
+        self.open_group = false;
+    }
+
+    /// Take the most recent step for undoing. Restore its `before` contents.
+    pub fn undo(&mut self) -> Option<&FieldEdit<K, T>> {
+        self.open_group = false;
+        let edit = self.undo.pop()?;
+        self.redo.push(edit);
+        self.redo.last()
+    }
+
+    /// Take the most recently undone step for redoing. Restore its `after`
+    /// contents.
+    pub fn redo(&mut self) -> Option<&FieldEdit<K, T>> {
+        self.open_group = false;
+        let edit = self.redo.pop()?;
+        self.undo.push(edit);
+        self.undo.last()
+    }
+
+    /// Whether there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typing_runs_are_one_step() {
+        let mut history = History::default();
+        history.record(0, "", "a", true);
+        history.record(0, "a", "ab", true);
+        history.record(1, "", "x", true);
+        history.record(1, "x", "xy", true);
+
+        let edit = history.undo().unwrap();
+        assert_eq!((edit.field, edit.before, edit.after), (1, "", "xy"));
+        let edit = history.undo().unwrap();
+        assert_eq!((edit.field, edit.before, edit.after), (0, "", "ab"));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn closing_the_group_starts_a_new_step() {
+        let mut history = History::default();
+        history.record(0, "", "a", true);
+        history.close_group();
+        history.record(0, "a", "ab", true);
+        assert_eq!(history.undo().unwrap().before, "a");
+        assert_eq!(history.undo().unwrap().before, "");
+    }
+
+    #[test]
+    fn new_edits_clear_redo() {
+        let mut history = History::default();
+        history.record(0, false, true, false);
+        history.undo();
+        assert!(history.can_redo());
+        assert!(history.redo().unwrap().after);
+        history.undo();
+        history.record(1, false, true, false);
+        assert!(!history.can_redo());
+    }
+}
@@ -5,6 +5,13 @@
 //! - `Question` and `QuestionKind` - Individual questions and their types
 //! - `Responses` and `ResponsePath` - Collected data and path-based keys
 //! - `Survey` and `SurveyBackend` traits - For implementing surveys and backends
+//! - `SuggestBuilder` - Suggested and assumed answers for the fields of nested surveys
+//! - `SurveyScalar` trait - For field types entered as a single line of text
+//! - `Date`, `Time`, `DateTime` and `TemporalValue` - Answers to temporal questions
+//! - `ResponsePreview` - Responses arranged as a tree for live previews
+//! - `PasswordStrength` - Strength estimate for masked questions
+//! - `SecretValue` - Answer to a masked question, redacted and zeroized on drop
+//! - `History` - Undo/redo stacks for form backends
 
 mod response_path;
 pub use response_path::ResponsePath;
@@ -21,10 +28,23 @@ pub use default_value::DefaultValue;
 mod question;
 pub use question::{
     AllOfQuestion, AnyOfQuestion, ConfirmQuestion, FloatQuestion, InputQuestion, IntQuestion,
-    ListElementKind, ListQuestion, MaskedQuestion, MultilineQuestion, OneOfQuestion, Question,
-    QuestionKind, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, Variant,
+    LazyQuestion, ListElementKind, ListQuestion, MAP_KEYS_KEY, MapQuestion, MaskedQuestion,
+    MultilineQuestion, OneOfQuestion, Question, QuestionKind, SELECTED_VARIANT_KEY,
+    SELECTED_VARIANTS_KEY, Variant,
 };
 
+mod masked;
+pub use masked::{CONFIRMATION_MISMATCH, CONFIRMATION_PROMPT, PasswordStrength};
+
+mod secret;
+pub use secret::SecretValue;
+
+mod preview;
+pub use preview::{PreviewFormatter, PreviewNode, REDACTED, ResponsePreview, redact_masked};
+
+mod history;
+pub use history::{FieldEdit, History};
+
 mod survey_definition;
 pub use survey_definition::SurveyDefinition;
 
@@ -33,3 +53,17 @@ pub use error::SurveyError;
 
 mod traits;
 pub use traits::{Survey, SurveyBackend};
+
+mod suggest;
+pub use suggest::{OptionSuggestBuilder, SuggestBuilder, SuggestFn, SurveySuggest};
+
+mod scalar;
+pub use scalar::{FromStrScalar, SurveyScalar};
+#[doc(hidden)]
+pub use scalar::{ScalarProbe, ViaFromStr, ViaSurveyScalar};
+
+mod temporal;
+pub use temporal::{
+    Date, DateQuestion, DateTime, DateTimeQuestion, DurationQuestion, TemporalInput,
+    TemporalQuestion, TemporalValue, Time, TimeQuestion, format_duration, parse_duration,
+};
@@ -0,0 +1,88 @@
+//! Helpers shared by the backends for masked questions.
+
+/// Prompt for the second entry of a masked question with confirmation.
+pub const CONFIRMATION_PROMPT: &str = "Repeat to confirm:";
+
+/// Error shown when the confirmation of a masked answer differs from the first entry.
+pub const CONFIRMATION_MISMATCH: &str = "Entries do not match";
+
+/// Rough strength of a password, shown by the strength meter of a masked question.
+///
+/// This is a quick estimate from the length and the kinds of characters used,
+/// not a substitute for checking passwords against known breaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PasswordStrength {
+    Weak,
+    Fair,
+    Good,
+    Strong,
+}
+
+impl PasswordStrength {
+    /// Estimate the strength of `password`.
+    pub fn of(password: &str) -> Self {
+        let len = password.chars().count();
+        let length_score = [8, 12, 16].iter().filter(|&&n| len >= n).count();
+
+        let classes = [
+            password.chars().any(|c| c.is_lowercase()),
+            password.chars().any(|c| c.is_uppercase()),
+            password.chars().any(|c| c.is_numeric()),
+            password.chars().any(|c| !c.is_alphanumeric()),
+        ];
+        let variety_score = classes
+            .iter()
+            .filter(|&&used| used)
+            .count()
+            .saturating_sub(1);
+
+        match length_score + variety_score {
+            0 | 1 => Self::Weak,
+            2 | 3 => Self::Fair,
+            4 => Self::Good,
+            _ => Self::Strong,
+        }
+    }
+
+    /// Short label for the meter.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Weak => "Weak",
+            Self::Fair => "Fair",
+            Self::Good => "Good",
+            Self::Strong => "Strong",
+        }
+    }
+
+    /// How full the meter is, from 0.25 for [`Weak`](Self::Weak) to 1.0 for
+    /// [`Strong`](Self::Strong).
+    pub fn fraction(self) -> f32 {
+        match self {
+            Self::Weak => 0.25,
+            Self::Fair => 0.5,
+            Self::Good => 0.75,
+            Self::Strong => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length_and_variety_raise_strength() {
+        assert_eq!(PasswordStrength::of(""), PasswordStrength::Weak);
+        assert_eq!(PasswordStrength::of("password"), PasswordStrength::Weak);
+        assert_eq!(PasswordStrength::of("Password1"), PasswordStrength::Fair);
+        assert_eq!(PasswordStrength::of("Tr0ub4dor&3"), PasswordStrength::Good);
+        assert_eq!(
+            PasswordStrength::of("correct horse battery staple"),
+            PasswordStrength::Good
+        );
+        assert_eq!(
+            PasswordStrength::of("Correct horse battery staple 9"),
+            PasswordStrength::Strong
+        );
+    }
+}
@@ -0,0 +1,593 @@
+//! Live previews of collected responses.
+//!
+//! Form backends show what the resulting data will look like while the user
+//! is still filling in the form. [`ResponsePreview`] arranges the flat
+//! responses into a tree in question order, names chosen enum variants, and
+//! redacts the answers to masked questions. With the `serde` feature, the
+//! preview is `Serialize` and can be rendered as JSON or TOML. A
+//! [`PreviewFormatter`] lets applications render the (redacted) responses
+//! their own way.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{
+    MAP_KEYS_KEY, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyDefinition, Variant,
+};
+
+/// Shown in place of the answer to a masked question.
+pub const REDACTED: &str = "••••••";
+
+/// Stands for the entry index in the paths of map values.
+const ENTRY: &str = "*";
+
+/// A value or group of values in a [`ResponsePreview`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewNode {
+    /// A single answer. Chosen enum variants are given by name.
+    Value(ResponseValue),
+    /// A nested struct or enum, with its entries in question order.
+    Group(Vec<(String, PreviewNode)>),
+}
+
+impl PreviewNode {
+    /// Display text of a single answer, such as `"Ann"`, `42` or `[1, 2]`;
+    /// `None` for a group.
+    pub fn value_text(&self) -> Option<String> {
+        match self {
+            PreviewNode::Value(value) => Some(text(value)),
+            PreviewNode::Group(_) => None,
+        }
+    }
+}
+
+/// Responses arranged as a tree for display.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResponsePreview {
+    /// Top-level entries in question order.
+    pub entries: Vec<(String, PreviewNode)>,
+}
+
+impl ResponsePreview {
+    /// Build the preview of `responses` to the questions in `definition`.
+    pub fn new(responses: &Responses, definition: &SurveyDefinition) -> Self {
+        let info = PathInfo::new(definition, responses);
+        let mut sorted: Vec<(&ResponsePath, &ResponseValue)> = responses.iter().collect();
+        sorted.sort_by_key(|(path, _)| (info.position(path), entry_order(path)));
+
+        let mut preview = Self::default();
+        for (path, value) in sorted {
+            if info.is_map_keys(path) {
+                continue;
+            }
+            let value = info.display_value(path, value);
+            let keys = info.display_keys(path, responses);
+            insert(&mut preview.entries, &keys, value);
+        }
+        preview
+    }
+
+    /// Indented `key: value` lines, one per entry; groups end with a colon.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        push_lines(&self.entries, 0, &mut lines);
+        lines
+    }
+
+    /// Pretty-printed JSON object with the same structure as the tree.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// TOML document with the same structure as the tree.
+    #[cfg(feature = "serde")]
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Copy of `responses` with the answers to masked questions and all secrets
+/// replaced by [`REDACTED`].
+pub fn redact_masked(responses: &Responses, definition: &SurveyDefinition) -> Responses {
+    let info = PathInfo::new(definition, responses);
+    let mut redacted = responses.clone();
+    for (path, value) in responses.iter() {
+        if info.masked.contains(&info.template(path)) || matches!(value, ResponseValue::Secret(_)) {
+            redacted.insert(path.clone(), ResponseValue::String(REDACTED.to_string()));
+        }
+    }
+    redacted
+}
+
+/// Renders responses as text for a preview pane.
+///
+/// Backends pass the responses with masked answers already redacted.
+#[derive(Clone)]
+pub struct PreviewFormatter(Arc<dyn Fn(&Responses) -> String + Send + Sync>);
+
+impl PreviewFormatter {
+    /// Wrap a formatting function.
+    pub fn new(format: impl Fn(&Responses) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(format))
+    }
+
+    /// Format the given responses.
+    pub fn format(&self, responses: &Responses) -> String {
+        (self.0)(responses)
+    }
+}
+
+impl fmt::Debug for PreviewFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PreviewFormatter(..)")
+    }
+}
+
+/// What the survey definition says about response paths.
+#[derive(Default)]
+struct PathInfo {
+    /// Paths of masked questions.
+    masked: HashSet<ResponsePath>,
+    /// Paths in question order.
+    order: Vec<ResponsePath>,
+    /// Variant names of enum questions, by question path.
+    variants: HashMap<ResponsePath, Vec<String>>,
+    /// Paths of multi-select questions.
+    any_of: HashSet<ResponsePath>,
+    /// Paths of map questions.
+    maps: HashSet<ResponsePath>,
+    /// Paths of the responses, to tell which lazy questions were reached.
+    answered: Vec<ResponsePath>,
+}
+
+impl PathInfo {
+    fn new(definition: &SurveyDefinition, responses: &Responses) -> Self {
+        let mut info = Self {
+            answered: responses.iter().map(|(path, _)| path.clone()).collect(),
+            ..Self::default()
+        };
+        info.add_questions(&definition.questions, &ResponsePath::empty());
+        info
+    }
+
+    fn add_questions(&mut self, questions: &[Question], prefix: &ResponsePath) {
+        for question in questions {
+            let path = prefix.child(question.path().as_str());
+            self.add_kind(question.kind(), path);
+        }
+    }
+
+    fn add_kind(&mut self, kind: &QuestionKind, path: ResponsePath) {
+        match kind {
+            QuestionKind::Unit => {}
+            QuestionKind::AllOf(all_of) => self.add_questions(all_of.questions(), &path),
+            QuestionKind::OneOf(one_of) => {
+                self.add_variants(&path, &one_of.variants);
+                self.order.push(path.child(SELECTED_VARIANT_KEY));
+                for variant in &one_of.variants {
+                    self.add_variant(variant, &path);
+                }
+            }
+            QuestionKind::AnyOf(any_of) => {
+                self.add_variants(&path, &any_of.variants);
+                self.any_of.insert(path.clone());
+                self.order.push(path.child(SELECTED_VARIANTS_KEY));
+                self.order.push(path);
+            }
+            QuestionKind::Masked(_) => {
+                self.masked.insert(path.clone());
+                self.order.push(path);
+            }
+            QuestionKind::Map(map) => {
+                self.maps.insert(path.clone());
+                self.order.push(path.clone());
+                self.add_kind(&map.value_kind, path.child(ENTRY));
+            }
+            // Only the levels of a recursive type that were answered are expanded
+            QuestionKind::Lazy(lazy) => {
+                let reached = self
+                    .answered
+                    .iter()
+                    .any(|answered| self.template(answered).strip_path_prefix(&path).is_some());
+                if reached && let Ok(all_of) = lazy.expand() {
+                    self.add_questions(all_of.questions(), &path);
+                }
+            }
+            _ => self.order.push(path),
+        }
+    }
+
+    fn add_variants(&mut self, path: &ResponsePath, variants: &[Variant]) {
+        let names = variants.iter().map(|v| v.name.clone()).collect();
+        self.variants.insert(path.clone(), names);
+    }
+
+    /// Fields of enum variants live under the enum's path; newtype variants
+    /// are stored under the variant name.
+    fn add_variant(&mut self, variant: &Variant, path: &ResponsePath) {
+        match &variant.kind {
+            QuestionKind::AllOf(all_of) => self.add_questions(all_of.questions(), path),
+            kind => self.add_kind(kind, path.child(&variant.name)),
+        }
+    }
+
+    /// The path with the entry indices of maps replaced by [`ENTRY`], as the
+    /// questions for map values are recorded.
+    fn template(&self, path: &ResponsePath) -> ResponsePath {
+        let mut template = ResponsePath::empty();
+        for segment in path.segments() {
+            let is_entry = self.maps.contains(&template) && segment.parse::<usize>().is_ok();
+            template = template.child(if is_entry { ENTRY } else { segment });
+        }
+        template
+    }
+
+    /// Whether `path` holds the keys of a map, which name its entries instead
+    /// of being shown.
+    fn is_map_keys(&self, path: &ResponsePath) -> bool {
+        path.last() == Some(MAP_KEYS_KEY) && self.maps.contains(&self.template(&path.parent()))
+    }
+
+    /// Display names of the segments of `path`; map entries are named by their key.
+    fn display_keys(&self, path: &ResponsePath, responses: &Responses) -> Vec<String> {
+        let mut prefix = ResponsePath::empty();
+        let mut keys = Vec::new();
+        for segment in path.segments() {
+            let entry_key = if self.maps.contains(&self.template(&prefix)) {
+                let keys = responses.get_string_list(&prefix.child(MAP_KEYS_KEY)).ok();
+                segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|idx| keys?.get(idx).cloned())
+            } else {
+                None
+            };
+            keys.push(entry_key.unwrap_or_else(|| display_key(segment)));
+            prefix = prefix.child(segment);
+        }
+        keys
+    }
+
+    /// Sort key placing a path after the questions that come before it.
+    fn position(&self, path: &ResponsePath) -> usize {
+        let mut current = self.template(path);
+        loop {
+            if let Some(idx) = self.order.iter().position(|p| *p == current) {
+                return idx;
+            }
+            if current.is_empty() {
+                return usize::MAX;
+            }
+            current = current.parent();
+        }
+    }
+
+    /// The value to show: masked answers and secrets redacted, variant indices named.
+    fn display_value(&self, path: &ResponsePath, value: &ResponseValue) -> ResponseValue {
+        let path = &self.template(path);
+        if self.masked.contains(path) || matches!(value, ResponseValue::Secret(_)) {
+            return ResponseValue::String(REDACTED.to_string());
+        }
+        let parent = path.parent();
+        let names = match path.last() {
+            Some(SELECTED_VARIANT_KEY) => self.variants.get(&parent).or_else(|| {
+                // Items of a multi-select are stored under `<path>.<index>`
+                let list = parent.parent();
+                self.any_of
+                    .contains(&list)
+                    .then(|| self.variants.get(&list))
+                    .flatten()
+            }),
+            Some(SELECTED_VARIANTS_KEY) => self.variants.get(&parent),
+            _ => None,
+        };
+        let name = |idx: &usize| {
+            names
+                .and_then(|names| names.get(*idx).cloned())
+                .unwrap_or_else(|| idx.to_string())
+        };
+        match value {
+            ResponseValue::ChosenVariant(idx) if names.is_some() => {
+                ResponseValue::String(name(idx))
+            }
+            ResponseValue::ChosenVariants(indices) if names.is_some() => {
+                ResponseValue::StringList(indices.iter().map(name).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// Secondary sort key keeping numbered entries in order, so that `10` comes after `9`.
+fn entry_order(path: &ResponsePath) -> Vec<(usize, &str)> {
+    path.segments()
+        .map(|segment| (segment.parse().unwrap_or(usize::MAX), segment))
+        .collect()
+}
+
+fn display_key(segment: &str) -> String {
+    match segment {
+        SELECTED_VARIANT_KEY => "variant".to_string(),
+        SELECTED_VARIANTS_KEY => "selected".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn insert(entries: &mut Vec<(String, PreviewNode)>, keys: &[String], value: ResponseValue) {
+    let Some((key, rest)) = keys.split_first() else {
+        return;
+    };
+    if rest.is_empty() {
+        entries.push((key.clone(), PreviewNode::Value(value)));
+        return;
+    }
+    let idx = match entries
+        .iter()
+        .position(|(k, node)| k == key && matches!(node, PreviewNode::Group(_)))
+    {
+        Some(idx) => idx,
+        None => {
+            entries.push((key.clone(), PreviewNode::Group(Vec::new())));
+            entries.len() - 1
+        }
+    };
+    if let PreviewNode::Group(children) = &mut entries[idx].1 {
+        insert(children, rest, value);
+    }
+}
+
+fn push_lines(entries: &[(String, PreviewNode)], depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    for (key, node) in entries {
+        match node {
+            PreviewNode::Value(value) => lines.push(format!("{indent}{key}: {}", text(value))),
+            PreviewNode::Group(children) => {
+                lines.push(format!("{indent}{key}:"));
+                push_lines(children, depth + 1, lines);
+            }
+        }
+    }
+}
+
+/// Human-readable text of a value.
+fn text(value: &ResponseValue) -> String {
+    fn join<T: ToString>(items: &[T]) -> String {
+        let items: Vec<String> = items.iter().map(T::to_string).collect();
+        format!("[{}]", items.join(", "))
+    }
+    match value {
+        ResponseValue::String(s) => format!("{s:?}"),
+        ResponseValue::Secret(_) => format!("{REDACTED:?}"),
+        ResponseValue::Int(i) => i.to_string(),
+        ResponseValue::Float(f) => f.to_string(),
+        ResponseValue::Bool(b) => b.to_string(),
+        ResponseValue::Date(_)
+        | ResponseValue::Time(_)
+        | ResponseValue::DateTime(_)
+        | ResponseValue::Duration(_) => value.temporal_text().unwrap_or_default(),
+        ResponseValue::ChosenVariant(idx) => idx.to_string(),
+        ResponseValue::ChosenVariants(indices) => join(indices),
+        ResponseValue::StringList(items) => {
+            let items: Vec<String> = items.iter().map(|s| format!("{s:?}")).collect();
+            format!("[{}]", items.join(", "))
+        }
+        ResponseValue::IntList(items) => join(items),
+        ResponseValue::FloatList(items) => join(items),
+    }
+}
+
+/// Entries become maps in question order, chosen variants their names, and
+/// temporal values their text.
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::ser::{Serialize, SerializeMap, Serializer};
+
+    use super::{PreviewNode, REDACTED, ResponsePreview};
+    use crate::ResponseValue;
+
+    fn serialize_entries<S: Serializer>(
+        entries: &[(String, PreviewNode)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (key, node) in entries {
+            map.serialize_entry(key, node)?;
+        }
+        map.end()
+    }
+
+    impl Serialize for ResponsePreview {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_entries(&self.entries, serializer)
+        }
+    }
+
+    impl Serialize for PreviewNode {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let value = match self {
+                PreviewNode::Group(children) => return serialize_entries(children, serializer),
+                PreviewNode::Value(value) => value,
+            };
+            match value {
+                ResponseValue::String(s) => serializer.serialize_str(s),
+                ResponseValue::Secret(_) => serializer.serialize_str(REDACTED),
+                ResponseValue::Int(i) => serializer.serialize_i64(*i),
+                ResponseValue::Float(f) => serializer.serialize_f64(*f),
+                ResponseValue::Bool(b) => serializer.serialize_bool(*b),
+                ResponseValue::Date(_)
+                | ResponseValue::Time(_)
+                | ResponseValue::DateTime(_)
+                | ResponseValue::Duration(_) => {
+                    serializer.serialize_str(&value.temporal_text().unwrap_or_default())
+                }
+                ResponseValue::ChosenVariant(idx) => serializer.serialize_u64(*idx as u64),
+                ResponseValue::ChosenVariants(items) => serializer.collect_seq(items),
+                ResponseValue::StringList(items) => serializer.collect_seq(items),
+                ResponseValue::IntList(items) => serializer.collect_seq(items),
+                ResponseValue::FloatList(items) => serializer.collect_seq(items),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AllOfQuestion, InputQuestion, IntQuestion, LazyQuestion, MapQuestion, MaskedQuestion,
+        OneOfQuestion, SecretValue,
+    };
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "password",
+                "Password?",
+                QuestionKind::Masked(MaskedQuestion::new()),
+            ),
+            Question::new(
+                "address",
+                "Address",
+                QuestionKind::AllOf(AllOfQuestion::new(vec![
+                    Question::new(
+                        "street",
+                        "Street?",
+                        QuestionKind::Input(InputQuestion::new()),
+                    ),
+                    Question::new("number", "Number?", QuestionKind::Int(IntQuestion::new())),
+                ])),
+            ),
+            Question::new(
+                "pet",
+                "Pet?",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Cat"),
+                    Variant::unit("Dog"),
+                ])),
+            ),
+        ])
+    }
+
+    fn responses() -> Responses {
+        let mut responses = Responses::new();
+        responses.insert("pet.selected_variant", ResponseValue::ChosenVariant(1));
+        responses.insert("address.number", ResponseValue::Int(7));
+        responses.insert("password", SecretValue::new("hunter2"));
+        responses.insert(
+            "address.street",
+            ResponseValue::String("Main \"St\"".into()),
+        );
+        responses.insert("name", ResponseValue::String("Ann".into()));
+        responses
+    }
+
+    #[test]
+    fn builds_a_tree_in_question_order() {
+        let preview = ResponsePreview::new(&responses(), &definition());
+        assert_eq!(
+            preview.lines(),
+            [
+                "name: \"Ann\"",
+                "password: \"••••••\"",
+                "address:",
+                "  street: \"Main \\\"St\\\"\"",
+                "  number: 7",
+                "pet:",
+                "  variant: \"Dog\"",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn renders_json() {
+        let json = ResponsePreview::new(&responses(), &definition()).to_json();
+        assert!(json.starts_with("{\n  \"name\": \"Ann\",\n"), "{json}");
+        assert!(json.contains("\"street\": \"Main \\\"St\\\"\","), "{json}");
+        assert!(json.contains("\"pet\": {\n    \"variant\": \"Dog\"\n  }\n}"));
+        assert!(!json.contains("hunter2"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn renders_toml() {
+        let toml = ResponsePreview::new(&responses(), &definition()).to_toml();
+        assert!(
+            toml.starts_with("name = \"Ann\"\npassword = \"••••••\"\n"),
+            "{toml}"
+        );
+        assert!(
+            toml.contains("[address]\nstreet = 'Main \"St\"'\nnumber = 7\n"),
+            "{toml}"
+        );
+        assert!(toml.contains("[pet]\nvariant = \"Dog\"\n"), "{toml}");
+        assert!(!toml.contains("hunter2"));
+    }
+
+    #[test]
+    fn names_map_entries_by_key() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "env",
+            "Environment",
+            QuestionKind::Map(MapQuestion::new(
+                QuestionKind::Masked(MaskedQuestion::new()),
+            )),
+        )]);
+        let mut responses = Responses::new();
+        let keys: Vec<String> = (0..11).map(|i| format!("VAR_{i}")).collect();
+        for i in 0..11 {
+            responses.insert(format!("env.{i}"), ResponseValue::String(i.to_string()));
+        }
+        responses.insert("env.keys", keys);
+
+        let lines = ResponsePreview::new(&responses, &definition).lines();
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[0], "env:");
+        assert_eq!(lines[1], "  VAR_0: \"••••••\"");
+        assert_eq!(lines[11], "  VAR_10: \"••••••\"");
+    }
+
+    #[test]
+    fn redacts_masked_answers() {
+        let redacted = redact_masked(&responses(), &definition());
+        assert_eq!(
+            redacted.get_string(&ResponsePath::new("password")).unwrap(),
+            REDACTED
+        );
+        assert_eq!(
+            redacted.get_string(&ResponsePath::new("name")).unwrap(),
+            "Ann"
+        );
+    }
+
+    /// A type holding a masked answer and another of itself.
+    fn node() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("pin", "PIN?", QuestionKind::Masked(MaskedQuestion::new())),
+            Question::new(
+                "next",
+                "Next?",
+                QuestionKind::Lazy(LazyQuestion::new("Node", node)),
+            ),
+        ])
+    }
+
+    #[test]
+    fn redacts_answers_of_recursive_types() {
+        let mut responses = Responses::new();
+        for path in ["pin", "next.pin", "next.next.pin"] {
+            responses.insert(path, ResponseValue::String("1234".into()));
+        }
+        let redacted = redact_masked(&responses, &node());
+        for path in ["pin", "next.pin", "next.next.pin"] {
+            assert_eq!(
+                redacted.get_string(&ResponsePath::new(path)).unwrap(),
+                REDACTED
+            );
+        }
+    }
+}
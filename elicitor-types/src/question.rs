@@ -1,4 +1,9 @@
-use crate::{DefaultValue, ResponsePath, ResponseValue};
+use std::fmt;
+
+use crate::{
+    DateQuestion, DateTimeQuestion, DefaultValue, DurationQuestion, ResponsePath, ResponseValue,
+    SecretValue, SurveyDefinition, TemporalInput, TemporalQuestion, TimeQuestion,
+};
 
 /// A single question in a survey.
 #[derive(Debug, Clone, PartialEq)]
@@ -9,6 +14,9 @@ pub struct Question {
     /// The prompt text shown to the user.
     ask: String,
 
+    /// Longer explanation shown alongside the prompt, if any.
+    help: Option<String>,
+
     /// The kind of question (determines input type and nested structure).
     kind: QuestionKind,
 
@@ -22,11 +30,18 @@ impl Question {
         Self {
             path: path.into(),
             ask: ask.into(),
+            help: None,
             kind,
             default: DefaultValue::None,
         }
     }
 
+    /// Add help text explaining the question.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
     /// Get the response path for this question.
     pub fn path(&self) -> &ResponsePath {
         &self.path
@@ -37,6 +52,11 @@ impl Question {
         &self.ask
     }
 
+    /// Get the help text, if any.
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
     /// Get the question kind.
     pub fn kind(&self) -> &QuestionKind {
         &self.kind
@@ -54,12 +74,22 @@ impl Question {
 
     /// Set a suggested default value (user can modify).
     pub fn set_suggestion(&mut self, value: impl Into<ResponseValue>) {
-        self.default = DefaultValue::Suggested(value.into());
+        self.default = DefaultValue::Suggested(self.secret_if_masked(value.into()));
     }
 
     /// Set an assumed value (question is skipped entirely).
     pub fn set_assumption(&mut self, value: impl Into<ResponseValue>) {
-        self.default = DefaultValue::Assumed(value.into());
+        self.default = DefaultValue::Assumed(self.secret_if_masked(value.into()));
+    }
+
+    /// Masked questions keep their defaults as secrets, like their answers.
+    fn secret_if_masked(&self, value: ResponseValue) -> ResponseValue {
+        match (&self.kind, value) {
+            (QuestionKind::Masked(_), ResponseValue::String(s)) => {
+                ResponseValue::Secret(SecretValue::new(s))
+            }
+            (_, value) => value,
+        }
     }
 
     /// Clear any default value.
@@ -97,9 +127,24 @@ pub enum QuestionKind {
     /// Yes/no confirmation.
     Confirm(ConfirmQuestion),
 
+    /// Calendar date with optional min/max bounds.
+    Date(DateQuestion),
+
+    /// Time of day with optional min/max bounds.
+    Time(TimeQuestion),
+
+    /// Date and time of day with optional min/max bounds.
+    DateTime(DateTimeQuestion),
+
+    /// Duration (e.g. `1h 30m`) with optional min/max bounds.
+    Duration(DurationQuestion),
+
     /// List of values (Vec<T> where T is a primitive type).
     List(ListQuestion),
 
+    /// Key/value entries (HashMap<String, T> or BTreeMap<String, T>).
+    Map(MapQuestion),
+
     /// Select any number of options from a list (Vec<Enum>).
     AnyOf(AnyOfQuestion),
 
@@ -108,6 +153,10 @@ pub enum QuestionKind {
 
     /// Choose one variant — pick one, then answer its questions (enums).
     OneOf(OneOfQuestion),
+
+    /// A nested survey behind `Box`, `Rc` or `Arc`, built only when it is
+    /// reached, so that recursive types have a finite definition.
+    Lazy(LazyQuestion),
 }
 
 impl QuestionKind {
@@ -126,21 +175,137 @@ impl QuestionKind {
                 | Self::Float(_)
                 | Self::Confirm(_)
                 | Self::List(_)
+                | Self::Date(_)
+                | Self::Time(_)
+                | Self::DateTime(_)
+                | Self::Duration(_)
         )
     }
 
-    /// Check if this is a structural kind (AllOf, OneOf, AnyOf).
+    /// Check if this is a temporal kind (Date, Time, DateTime, Duration).
+    pub fn is_temporal(&self) -> bool {
+        self.temporal_format().is_some()
+    }
+
+    /// For temporal kinds, how the value is typed (e.g. `YYYY-MM-DD`).
+    pub fn temporal_format(&self) -> Option<&'static str> {
+        fn format<T: TemporalInput>(_: &TemporalQuestion<T>) -> &'static str {
+            T::FORMAT
+        }
+        match self {
+            Self::Date(q) => Some(format(q)),
+            Self::Time(q) => Some(format(q)),
+            Self::DateTime(q) => Some(format(q)),
+            Self::Duration(q) => Some(format(q)),
+            _ => None,
+        }
+    }
+
+    /// For temporal kinds, parse typed text into a response, checking the bounds.
+    pub fn parse_temporal(&self, text: &str) -> Option<Result<ResponseValue, String>> {
+        fn parse<T: TemporalInput>(
+            q: &TemporalQuestion<T>,
+            text: &str,
+        ) -> Result<ResponseValue, String> {
+            q.parse(text).map(Into::into)
+        }
+        match self {
+            Self::Date(q) => Some(parse(q, text)),
+            Self::Time(q) => Some(parse(q, text)),
+            Self::DateTime(q) => Some(parse(q, text)),
+            Self::Duration(q) => Some(parse(q, text)),
+            _ => None,
+        }
+    }
+
+    /// For temporal kinds, the min and max bounds as they are typed.
+    pub fn temporal_bounds(&self) -> Option<(Option<String>, Option<String>)> {
+        fn bounds<T: TemporalInput>(q: &TemporalQuestion<T>) -> (Option<String>, Option<String>) {
+            (
+                q.min.as_ref().map(T::format_input),
+                q.max.as_ref().map(T::format_input),
+            )
+        }
+        match self {
+            Self::Date(q) => Some(bounds(q)),
+            Self::Time(q) => Some(bounds(q)),
+            Self::DateTime(q) => Some(bounds(q)),
+            Self::Duration(q) => Some(bounds(q)),
+            _ => None,
+        }
+    }
+
+    /// For temporal kinds, the question's own default value.
+    pub fn temporal_default(&self) -> Option<ResponseValue> {
+        match self {
+            Self::Date(q) => q.default.map(Into::into),
+            Self::Time(q) => q.default.map(Into::into),
+            Self::DateTime(q) => q.default.map(Into::into),
+            Self::Duration(q) => q.default.map(Into::into),
+            _ => None,
+        }
+    }
+
+    /// Check if this is a structural kind (AllOf, OneOf, AnyOf, Map, Lazy).
     pub fn is_structural(&self) -> bool {
-        matches!(self, Self::AllOf(_) | Self::OneOf(_) | Self::AnyOf(_))
+        matches!(
+            self,
+            Self::AllOf(_) | Self::OneOf(_) | Self::AnyOf(_) | Self::Map(_) | Self::Lazy(_)
+        )
+    }
+
+    /// Why this kind cannot be answered, if a lazy question that must be
+    /// answered with it is nested too deeply.
+    ///
+    /// Backends check the chosen enum variant with this and ask again instead
+    /// of descending. Choices further down (enum variants, map entries) are
+    /// left to the user.
+    pub fn nesting_error(&self) -> Option<String> {
+        match self {
+            Self::AllOf(all_of) => all_of
+                .questions()
+                .iter()
+                .find_map(|question| question.kind().nesting_error()),
+            Self::Lazy(lazy) => match lazy.expand() {
+                Ok(all_of) => Self::AllOf(all_of).nesting_error(),
+                Err(message) => Some(message),
+            },
+            _ => None,
+        }
+    }
+
+    /// Set the depth of the lazy questions in this kind, not counting the
+    /// ones inside them.
+    fn set_lazy_depth(&mut self, depth: usize) {
+        match self {
+            Self::AllOf(all_of) => {
+                for question in all_of.questions_mut() {
+                    question.kind_mut().set_lazy_depth(depth);
+                }
+            }
+            Self::OneOf(OneOfQuestion { variants, .. })
+            | Self::AnyOf(AnyOfQuestion { variants, .. }) => {
+                for variant in variants {
+                    variant.kind.set_lazy_depth(depth);
+                }
+            }
+            Self::Map(map) => map.value_kind.set_lazy_depth(depth),
+            Self::Lazy(lazy) => lazy.depth = depth,
+            _ => {}
+        }
     }
 }
 
 /// A variant in a OneOf question (enum variant).
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct Variant {
     /// Variant name for display (e.g., "Male", "Female", "Other").
     pub name: String,
 
+    /// Longer explanation of the variant, if any.
+    pub help: Option<String>,
+
     /// What to collect for this variant.
     /// - Unit for unit variants (no data)
     /// - Input for newtype variants with String
@@ -154,6 +319,7 @@ impl Variant {
     pub fn new(name: impl Into<String>, kind: QuestionKind) -> Self {
         Self {
             name: name.into(),
+            help: None,
             kind,
         }
     }
@@ -162,6 +328,12 @@ impl Variant {
     pub fn unit(name: impl Into<String>) -> Self {
         Self::new(name, QuestionKind::Unit)
     }
+
+    /// Add help text explaining the variant.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
 }
 
 /// Configuration for an AnyOf question (multi-select with potential follow-up questions).
@@ -329,6 +501,12 @@ pub struct MaskedQuestion {
 
     /// Validation function name.
     pub validate: Option<String>,
+
+    /// Ask for the value twice and require both entries to match.
+    pub confirm: bool,
+
+    /// Show a [`PasswordStrength`](crate::PasswordStrength) meter under the field.
+    pub strength_meter: bool,
 }
 
 impl MaskedQuestion {
@@ -341,17 +519,29 @@ impl MaskedQuestion {
     pub fn with_mask(mask: char) -> Self {
         Self {
             mask: Some(mask),
-            validate: None,
+            ..Self::default()
         }
     }
 
     /// Create with a validator.
     pub fn with_validator(validate: Option<String>) -> Self {
         Self {
-            mask: None,
             validate,
+            ..Self::default()
         }
     }
+
+    /// Set whether the value must be entered twice.
+    pub fn with_confirmation(mut self, confirm: bool) -> Self {
+        self.confirm = confirm;
+        self
+    }
+
+    /// Set whether a strength meter is shown under the field.
+    pub fn with_strength_meter(mut self, strength_meter: bool) -> Self {
+        self.strength_meter = strength_meter;
+        self
+    }
 }
 
 /// Configuration for an integer input question.
@@ -563,6 +753,152 @@ impl ListQuestion {
     }
 }
 
+/// Configuration for a key/value map question (HashMap<String, T>, BTreeMap<String, T>).
+///
+/// Entries are added, edited and removed one at a time. The keys are stored
+/// in entry order at `<path>.keys`; the value of the entry at position `i` is
+/// the answer to [`value_kind`](Self::value_kind) at `<path>.<i>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapQuestion {
+    /// What to ask for the value of each entry.
+    pub value_kind: Box<QuestionKind>,
+
+    /// Validation function name for keys.
+    pub validate_key: Option<String>,
+}
+
+impl MapQuestion {
+    /// Create a map question whose values are asked with `value_kind`.
+    pub fn new(value_kind: QuestionKind) -> Self {
+        Self {
+            value_kind: Box::new(value_kind),
+            validate_key: None,
+        }
+    }
+
+    /// Set a validator function for keys.
+    pub fn with_key_validator(mut self, validate_key: impl Into<String>) -> Self {
+        self.validate_key = Some(validate_key.into());
+        self
+    }
+
+    /// The question for the value of the entry at `index`, relative to the
+    /// map's path.
+    pub fn value_question(&self, index: usize, key: &str) -> Question {
+        Question::new(
+            index.to_string(),
+            format!("Value for {key}:"),
+            (*self.value_kind).clone(),
+        )
+    }
+
+    /// Check a key against the keys of the other entries: it must not be
+    /// empty or already used. Key validators run on top of this.
+    pub fn check_key<'a>(
+        key: &str,
+        other_keys: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), String> {
+        if key.trim().is_empty() {
+            return Err("Key must not be empty".to_string());
+        }
+        if other_keys.into_iter().any(|other| other == key) {
+            return Err(format!("Key \"{key}\" is already used"));
+        }
+        Ok(())
+    }
+}
+
+/// A nested survey that is built when a backend reaches it.
+///
+/// Recursive types (e.g. `And(Box<Filter>, Box<Filter>)`) would otherwise have
+/// an endless definition. [`expand`](Self::expand) builds one level; the lazy
+/// questions inside it are one level deeper, up to [`max_depth`](Self::max_depth).
+#[derive(Clone)]
+pub struct LazyQuestion {
+    /// Name of the nested type, for error messages and documents.
+    name: &'static str,
+
+    /// Builds the nested survey.
+    survey: fn() -> SurveyDefinition,
+
+    /// How many lazy questions this one is nested in.
+    depth: usize,
+
+    /// How deep lazy questions of this type may be nested.
+    max_depth: usize,
+}
+
+impl LazyQuestion {
+    /// How deep lazy questions may be nested unless set otherwise.
+    pub const DEFAULT_MAX_DEPTH: usize = 4;
+
+    /// Create a lazy question for the survey of the type called `name`.
+    pub fn new(name: &'static str, survey: fn() -> SurveyDefinition) -> Self {
+        Self {
+            name,
+            survey,
+            depth: 0,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Set how deep lazy questions of this type may be nested.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Name of the nested type.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// How many lazy questions this one is nested in.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// How deep lazy questions of this type may be nested.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Build the nested questions, as they would be asked for a nested struct.
+    ///
+    /// Fails once the question is nested [`max_depth`](Self::max_depth) levels deep.
+    pub fn expand(&self) -> Result<AllOfQuestion, String> {
+        if self.depth >= self.max_depth {
+            return Err(format!(
+                "{} cannot be nested more than {} levels deep",
+                self.name, self.max_depth
+            ));
+        }
+        let mut all_of = AllOfQuestion::new((self.survey)().questions);
+        for question in all_of.questions_mut() {
+            question.kind_mut().set_lazy_depth(self.depth + 1);
+        }
+        Ok(all_of)
+    }
+}
+
+impl fmt::Debug for LazyQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyQuestion")
+            .field("name", &self.name)
+            .field("depth", &self.depth)
+            .field("max_depth", &self.max_depth)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Lazy questions are equal when they stand for the same type at the same
+/// depth; the survey functions are not compared.
+impl PartialEq for LazyQuestion {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.depth == other.depth && self.max_depth == other.max_depth
+    }
+}
+
 /// The key suffix used to store the selected enum variant index in responses.
 /// For a field "method", the selection is stored at "method.selected_variant".
 pub const SELECTED_VARIANT_KEY: &str = "selected_variant";
@@ -570,3 +906,8 @@ pub const SELECTED_VARIANT_KEY: &str = "selected_variant";
 /// The key suffix used to store selected variant indices for AnyOf questions.
 /// For a field "features", the selections are stored at "features.selected_variants".
 pub const SELECTED_VARIANTS_KEY: &str = "selected_variants";
+
+/// The key suffix used to store the keys of a Map question.
+/// For a field "env", the keys are stored at "env.keys" and the value of the
+/// first entry at "env.0".
+pub const MAP_KEYS_KEY: &str = "keys";
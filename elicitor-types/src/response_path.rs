@@ -62,8 +62,12 @@ impl ResponsePath {
     }
 
     /// Returns a new path with the given prefix segment removed, if it matches.
+    ///
+    /// Every path starts with the empty prefix.
     pub fn strip_prefix(&self, prefix: &str) -> Option<Self> {
-        if self.path == prefix {
+        if prefix.is_empty() {
+            Some(self.clone())
+        } else if self.path == prefix {
             Some(Self::empty())
         } else if self.path.starts_with(prefix) && self.path[prefix.len()..].starts_with('.') {
             Some(Self::new(&self.path[prefix.len() + 1..]))
@@ -160,6 +164,12 @@ mod tests {
         assert!(stripped.is_empty());
     }
 
+    #[test]
+    fn strip_empty_prefix() {
+        let path = ResponsePath::new("address.street");
+        assert_eq!(path.strip_prefix("").unwrap(), path);
+    }
+
     #[test]
     fn segments() {
         let path = ResponsePath::new("address.location.city");
@@ -1,11 +1,22 @@
+use std::time::Duration;
+
+use crate::{Date, DateTime, SecretValue, TemporalInput, Time};
+
 /// A single response value collected from a survey.
 ///
 /// This is the value stored in `Responses` for each answered question.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResponseValue {
-    /// A string value (from Input, Multiline, or Masked questions).
+    /// A string value (from Input or Multiline questions).
     String(String),
 
+    /// A secret string value (from Masked questions), redacted in `Debug` output.
+    ///
+    /// Masked answers used to be stored as [`String`](Self::String). Validators
+    /// of masked fields must match this variant: one that still matches
+    /// `String` and returns `Ok` otherwise accepts every answer unchecked.
+    Secret(SecretValue),
+
     /// An integer value (from Int questions).
     Int(i64),
 
@@ -15,6 +26,18 @@ pub enum ResponseValue {
     /// A boolean value (from Confirm questions).
     Bool(bool),
 
+    /// A calendar date (from Date questions).
+    Date(Date),
+
+    /// A time of day (from Time questions).
+    Time(Time),
+
+    /// A date and time of day (from DateTime questions).
+    DateTime(DateTime),
+
+    /// A duration (from Duration questions).
+    Duration(Duration),
+
     /// The index of the chosen variant in a OneOf question (enum selection).
     ChosenVariant(usize),
 
@@ -40,6 +63,14 @@ impl ResponseValue {
         }
     }
 
+    /// Try to get this value as a secret.
+    pub fn as_secret(&self) -> Option<&SecretValue> {
+        match self {
+            Self::Secret(s) => Some(s),
+            _ => None,
+        }
+    }
+
     /// Try to get this value as an integer.
     pub fn as_int(&self) -> Option<i64> {
         match self {
@@ -64,6 +95,49 @@ impl ResponseValue {
         }
     }
 
+    /// Try to get this value as a date.
+    pub fn as_date(&self) -> Option<Date> {
+        match self {
+            Self::Date(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// Try to get this value as a time of day.
+    pub fn as_time(&self) -> Option<Time> {
+        match self {
+            Self::Time(t) => Some(*t),
+            _ => None,
+        }
+    }
+
+    /// Try to get this value as a date and time.
+    pub fn as_datetime(&self) -> Option<DateTime> {
+        match self {
+            Self::DateTime(dt) => Some(*dt),
+            _ => None,
+        }
+    }
+
+    /// Try to get this value as a duration.
+    pub fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Self::Duration(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// For temporal values, the text they are typed as (e.g. `2024-05-31` or `1h 30m`).
+    pub fn temporal_text(&self) -> Option<String> {
+        match self {
+            Self::Date(d) => Some(d.format_input()),
+            Self::Time(t) => Some(t.format_input()),
+            Self::DateTime(dt) => Some(dt.format_input()),
+            Self::Duration(d) => Some(d.format_input()),
+            _ => None,
+        }
+    }
+
     /// Try to get this value as a chosen variant index.
     pub fn as_chosen_variant(&self) -> Option<usize> {
         match self {
@@ -108,9 +182,14 @@ impl ResponseValue {
     pub fn type_name(&self) -> &'static str {
         match self {
             Self::String(_) => "String",
+            Self::Secret(_) => "Secret",
             Self::Int(_) => "Int",
             Self::Float(_) => "Float",
             Self::Bool(_) => "Bool",
+            Self::Date(_) => "Date",
+            Self::Time(_) => "Time",
+            Self::DateTime(_) => "DateTime",
+            Self::Duration(_) => "Duration",
             Self::ChosenVariant(_) => "ChosenVariant",
             Self::ChosenVariants(_) => "ChosenVariants",
             Self::StringList(_) => "StringList",
@@ -132,6 +211,12 @@ impl From<&str> for ResponseValue {
     }
 }
 
+impl From<SecretValue> for ResponseValue {
+    fn from(s: SecretValue) -> Self {
+        Self::Secret(s)
+    }
+}
+
 impl From<i64> for ResponseValue {
     fn from(i: i64) -> Self {
         Self::Int(i)
@@ -156,6 +241,30 @@ impl From<bool> for ResponseValue {
     }
 }
 
+impl From<Date> for ResponseValue {
+    fn from(d: Date) -> Self {
+        Self::Date(d)
+    }
+}
+
+impl From<Time> for ResponseValue {
+    fn from(t: Time) -> Self {
+        Self::Time(t)
+    }
+}
+
+impl From<DateTime> for ResponseValue {
+    fn from(dt: DateTime) -> Self {
+        Self::DateTime(dt)
+    }
+}
+
+impl From<Duration> for ResponseValue {
+    fn from(d: Duration) -> Self {
+        Self::Duration(d)
+    }
+}
+
 impl From<Vec<usize>> for ResponseValue {
     fn from(indices: Vec<usize>) -> Self {
         Self::ChosenVariants(indices)
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
-use crate::{ResponsePath, ResponseValue};
+use crate::{Date, DateTime, ResponsePath, ResponseValue, SecretValue, Time};
 
 /// Error type for response access operations.
 #[derive(Debug, thiserror::Error)]
@@ -21,6 +22,9 @@ pub enum ResponseError {
 /// Uses `ResponsePath` as keys to support hierarchical field access.
 /// Response paths are flat (not nested) - a nested field like `address.street`
 /// is stored with the key `ResponsePath::from("address.street")`.
+///
+/// Answers to masked questions are stored as [`ResponseValue::Secret`], which
+/// keeps them out of the `Debug` output.
 #[derive(Debug, Clone, Default)]
 pub struct Responses {
     values: HashMap<ResponsePath, ResponseValue>,
@@ -103,6 +107,28 @@ impl Responses {
         filtered
     }
 
+    /// Copy of these responses with secrets turned into plain strings.
+    ///
+    /// Secrets are redacted everywhere else; use this only where the answers
+    /// to masked questions must really be written out, for example when
+    /// serializing the responses on purpose.
+    pub fn expose_secrets(&self) -> Self {
+        let values = self
+            .values
+            .iter()
+            .map(|(path, value)| {
+                let value = match value {
+                    ResponseValue::Secret(s) => {
+                        ResponseValue::String(s.expose_secret().to_string())
+                    }
+                    other => other.clone(),
+                };
+                (path.clone(), value)
+            })
+            .collect();
+        Self { values }
+    }
+
     // === Convenience accessors ===
 
     /// Get a string value at the given path.
@@ -118,6 +144,19 @@ impl Responses {
         }
     }
 
+    /// Get a secret value at the given path.
+    pub fn get_secret(&self, path: &ResponsePath) -> Result<&SecretValue, ResponseError> {
+        match self.get(path) {
+            Some(ResponseValue::Secret(s)) => Ok(s),
+            Some(other) => Err(ResponseError::TypeMismatch {
+                path: path.clone(),
+                expected: "Secret",
+                actual: other.type_name(),
+            }),
+            None => Err(ResponseError::MissingPath(path.clone())),
+        }
+    }
+
     /// Get an integer value at the given path.
     pub fn get_int(&self, path: &ResponsePath) -> Result<i64, ResponseError> {
         match self.get(path) {
@@ -157,6 +196,58 @@ impl Responses {
         }
     }
 
+    /// Get a date at the given path.
+    pub fn get_date(&self, path: &ResponsePath) -> Result<Date, ResponseError> {
+        match self.get(path) {
+            Some(ResponseValue::Date(d)) => Ok(*d),
+            Some(other) => Err(ResponseError::TypeMismatch {
+                path: path.clone(),
+                expected: "Date",
+                actual: other.type_name(),
+            }),
+            None => Err(ResponseError::MissingPath(path.clone())),
+        }
+    }
+
+    /// Get a time of day at the given path.
+    pub fn get_time(&self, path: &ResponsePath) -> Result<Time, ResponseError> {
+        match self.get(path) {
+            Some(ResponseValue::Time(t)) => Ok(*t),
+            Some(other) => Err(ResponseError::TypeMismatch {
+                path: path.clone(),
+                expected: "Time",
+                actual: other.type_name(),
+            }),
+            None => Err(ResponseError::MissingPath(path.clone())),
+        }
+    }
+
+    /// Get a date and time at the given path.
+    pub fn get_datetime(&self, path: &ResponsePath) -> Result<DateTime, ResponseError> {
+        match self.get(path) {
+            Some(ResponseValue::DateTime(dt)) => Ok(*dt),
+            Some(other) => Err(ResponseError::TypeMismatch {
+                path: path.clone(),
+                expected: "DateTime",
+                actual: other.type_name(),
+            }),
+            None => Err(ResponseError::MissingPath(path.clone())),
+        }
+    }
+
+    /// Get a duration at the given path.
+    pub fn get_duration(&self, path: &ResponsePath) -> Result<Duration, ResponseError> {
+        match self.get(path) {
+            Some(ResponseValue::Duration(d)) => Ok(*d),
+            Some(other) => Err(ResponseError::TypeMismatch {
+                path: path.clone(),
+                expected: "Duration",
+                actual: other.type_name(),
+            }),
+            None => Err(ResponseError::MissingPath(path.clone())),
+        }
+    }
+
     /// Get a chosen variant index at the given path.
     pub fn get_chosen_variant(&self, path: &ResponsePath) -> Result<usize, ResponseError> {
         match self.get(path) {
@@ -229,6 +320,7 @@ impl Responses {
     pub fn has_value(&self, path: &ResponsePath) -> bool {
         match self.get(path) {
             Some(ResponseValue::String(s)) => !s.is_empty(),
+            Some(ResponseValue::Secret(s)) => !s.is_empty(),
             Some(_) => true,
             None => false,
         }
@@ -289,6 +381,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn secrets_are_redacted_until_exposed() {
+        let mut responses = Responses::new();
+        responses.insert("password", SecretValue::new("hunter2"));
+
+        assert!(!format!("{responses:?}").contains("hunter2"));
+        assert_eq!(
+            responses
+                .get_secret(&ResponsePath::new("password"))
+                .unwrap()
+                .expose_secret(),
+            "hunter2"
+        );
+        assert_eq!(
+            responses
+                .expose_secrets()
+                .get_string(&ResponsePath::new("password"))
+                .unwrap(),
+            "hunter2"
+        );
+    }
+
     #[test]
     fn type_mismatch_error() {
         let mut responses = Responses::new();
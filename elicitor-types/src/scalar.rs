@@ -0,0 +1,239 @@
+//! Field types that are entered as a single line of text.
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::str::FromStr;
+
+/// A field type that is asked for as text and parsed from the answer.
+///
+/// Fields marked `#[scalar]` become `Input` questions. The answer is stored as
+/// a string, checked with [`parse_response`](Self::parse_response) while the
+/// user types, and parsed again when the value is reconstructed.
+///
+/// `#[scalar]` fields whose type implements `FromStr` and `Display` need no
+/// impl of this trait: the derive goes through [`FromStrScalar`] for them,
+/// which covers types like `Url`, `Uuid` or `semver::Version`. Implement this
+/// trait yourself for types that lack those impls or that need friendlier
+/// error messages; your impl is used instead of `FromStr`.
+pub trait SurveyScalar: Sized {
+    /// Parse the text entered by the user, or explain why it is invalid.
+    fn parse_response(text: &str) -> Result<Self, String>;
+
+    /// The text for this value, used when it is suggested or assumed.
+    fn format_response(&self) -> String;
+}
+
+/// A `FromStr + Display` type used as a [`SurveyScalar`].
+///
+/// The derive wraps `#[scalar]` fields in this adapter when their type has no
+/// `SurveyScalar` impl of its own. It can also be used directly as the type
+/// argument of a generic `#[scalar]` field, whose parameter must implement
+/// `SurveyScalar`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FromStrScalar<T>(pub T);
+
+impl<T> SurveyScalar for FromStrScalar<T>
+where
+    T: FromStr + Display,
+    T::Err: Display,
+{
+    fn parse_response(text: &str) -> Result<Self, String> {
+        text.trim()
+            .parse()
+            .map(FromStrScalar)
+            .map_err(|err: T::Err| err.to_string())
+    }
+
+    fn format_response(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl<T: Display> Display for FromStrScalar<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Implement `SurveyScalar` for standard types through their `FromStr` impl.
+macro_rules! from_str_scalars {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl SurveyScalar for $ty {
+                fn parse_response(text: &str) -> Result<Self, String> {
+                    FromStrScalar::parse_response(text).map(|FromStrScalar(value)| value)
+                }
+
+                fn format_response(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+/// Text is kept as entered, surrounding whitespace included.
+impl SurveyScalar for String {
+    fn parse_response(text: &str) -> Result<Self, String> {
+        Ok(text.to_string())
+    }
+
+    fn format_response(&self) -> String {
+        self.clone()
+    }
+}
+
+from_str_scalars!(
+    bool,
+    char,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6,
+);
+
+/// Picks a scalar field's own `SurveyScalar` impl, falling back to `FromStr`.
+///
+/// Used by the derive as `(&ScalarProbe::<T>::new()).parse_response(text)`
+/// with [`ViaSurveyScalar`] and [`ViaFromStr`] in scope. Method lookup tries
+/// `ScalarProbe<T>` before `&ScalarProbe<T>`, so a `SurveyScalar` impl wins
+/// and `FromStr + Display` types without one still work.
+#[doc(hidden)]
+pub struct ScalarProbe<T>(PhantomData<T>);
+
+impl<T> ScalarProbe<T> {
+    #[doc(hidden)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The [`ScalarProbe`] route for types that implement `SurveyScalar`.
+#[doc(hidden)]
+pub trait ViaSurveyScalar<T> {
+    fn parse_response(&self, text: &str) -> Result<T, String>;
+    fn format_response(&self, value: &T) -> String;
+}
+
+impl<T: SurveyScalar> ViaSurveyScalar<T> for ScalarProbe<T> {
+    fn parse_response(&self, text: &str) -> Result<T, String> {
+        T::parse_response(text)
+    }
+
+    fn format_response(&self, value: &T) -> String {
+        value.format_response()
+    }
+}
+
+/// The [`ScalarProbe`] route for `FromStr + Display` types.
+#[doc(hidden)]
+pub trait ViaFromStr<T> {
+    fn parse_response(&self, text: &str) -> Result<T, String>;
+    fn format_response(&self, value: &T) -> String;
+}
+
+impl<T> ViaFromStr<T> for &ScalarProbe<T>
+where
+    T: FromStr + Display,
+    T::Err: Display,
+{
+    fn parse_response(&self, text: &str) -> Result<T, String> {
+        FromStrScalar::parse_response(text).map(|FromStrScalar(value)| value)
+    }
+
+    fn format_response(&self, value: &T) -> String {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::*;
+
+    /// A type with both `FromStr` and its own `SurveyScalar` impl.
+    #[derive(Debug, PartialEq)]
+    struct Level(u8);
+
+    impl FromStr for Level {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse().map(Level)
+        }
+    }
+
+    impl Display for Level {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl SurveyScalar for Level {
+        fn parse_response(text: &str) -> Result<Self, String> {
+            match text.trim().parse() {
+                Ok(level) if level <= 9 => Ok(Level(level)),
+                _ => Err("expected a level from 0 to 9".to_string()),
+            }
+        }
+
+        fn format_response(&self) -> String {
+            format!("{}", self.0)
+        }
+    }
+
+    #[test]
+    fn std_types_are_scalars() {
+        let addr = IpAddr::parse_response(" 10.0.0.1 ").unwrap();
+        assert_eq!(addr.format_response(), "10.0.0.1");
+        assert!(IpAddr::parse_response("10.0.0").is_err());
+    }
+
+    #[test]
+    fn strings_keep_their_whitespace() {
+        assert_eq!(
+            String::parse_response("  indented "),
+            Ok("  indented ".to_string())
+        );
+        assert_eq!(String::parse_response(" ").unwrap().format_response(), " ");
+    }
+
+    #[test]
+    fn from_str_types_are_scalars_through_the_adapter() {
+        let FromStrScalar(value) = FromStrScalar::<u64>::parse_response(" 42 ").unwrap();
+        assert_eq!(value, 42);
+        assert_eq!(FromStrScalar(value).format_response(), "42");
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)] // the borrow is what picks the impl
+    fn probe_prefers_the_survey_scalar_impl() {
+        assert_eq!(
+            (&ScalarProbe::<Level>::new()).parse_response("12"),
+            Err("expected a level from 0 to 9".to_string())
+        );
+        assert_eq!(
+            (&ScalarProbe::<std::num::NonZeroU8>::new()).parse_response("0"),
+            Err("number would be zero for non-zero type".to_string())
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Builders for suggested and assumed answers of nested fields.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::{ResponseValue, Survey};
+
+/// A [`Survey`] type whose answers can be suggested or assumed when it is
+/// nested in another survey.
+///
+/// `#[derive(Survey)]` implements it alongside [`Survey`]. Hand-written
+/// `Survey` impls don't need it; their fields just can't be reached from the
+/// builder of a survey they are nested in.
+pub trait SurveySuggest: Survey {
+    /// Builder of suggested or assumed answers for this type's fields.
+    type SuggestBuilder<'a>: SuggestBuilder<'a>;
+}
+
+/// A builder of suggested or assumed answers for the fields of a [`Survey`] type.
+///
+/// `#[derive(Survey)]` generates one for each type, available as
+/// `<T as SurveySuggest>::SuggestBuilder`. The `suggest_*` and `assume_*` methods of
+/// nested fields hand it to a closure, which fills in the nested answers.
+pub trait SuggestBuilder<'a> {
+    /// A builder writing its answers into `map`, below the path `prefix`.
+    fn new(map: &'a mut HashMap<String, ResponseValue>, prefix: String) -> Self;
+}
+
+/// The closure given to a `suggest_*`/`assume_*` method of a nested field of
+/// type `T`, which fills in the field's answers through `T`'s builder.
+///
+/// Implemented for every `FnOnce(T::SuggestBuilder<'a>) -> T::SuggestBuilder<'a>`.
+/// The methods require `for<'b> SuggestFn<'b, T>`, a bound that can't be
+/// spelled with `FnOnce` directly.
+pub trait SuggestFn<'a, T: SurveySuggest>:
+    FnOnce(T::SuggestBuilder<'a>) -> T::SuggestBuilder<'a>
+{
+}
+
+impl<'a, T: SurveySuggest, F> SuggestFn<'a, T> for F where
+    F: FnOnce(T::SuggestBuilder<'a>) -> T::SuggestBuilder<'a>
+{
+}
+
+/// Builder for suggesting or assuming an `Option` of a nested [`Survey`] type.
+pub struct OptionSuggestBuilder<'a, T> {
+    map: &'a mut HashMap<String, ResponseValue>,
+    prefix: String,
+    _survey: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Survey> SuggestBuilder<'a> for OptionSuggestBuilder<'a, T> {
+    fn new(map: &'a mut HashMap<String, ResponseValue>, prefix: String) -> Self {
+        Self {
+            map,
+            prefix,
+            _survey: PhantomData,
+        }
+    }
+}
+
+impl<T: Survey> OptionSuggestBuilder<'_, T> {
+    /// Suggest None (leave empty/skip this field)
+    pub fn none(self) -> Self {
+        self.map.insert(
+            format!("{}.is_none", self.prefix),
+            ResponseValue::Bool(true),
+        );
+        self
+    }
+
+    /// Suggest Some with nested values
+    pub fn some<F>(self, f: F) -> Self
+    where
+        T: SurveySuggest,
+        F: for<'b> SuggestFn<'b, T>,
+    {
+        let builder: T::SuggestBuilder<'_> = SuggestBuilder::new(self.map, self.prefix.clone());
+        f(builder);
+        self
+    }
+}
@@ -0,0 +1,862 @@
+//! Dates, times and durations collected by temporal questions.
+//!
+//! Answers are stored as the plain [`Date`], [`Time`], [`DateTime`] and
+//! [`Duration`] types of this module, so backends don't depend on a date
+//! library. Fields of `chrono` or `time` types convert to and from them through
+//! [`TemporalValue`] (behind the `chrono` and `time` features).
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+#[cfg(not(feature = "local-date"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{QuestionKind, ResponseValue};
+
+/// A calendar date without a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Create a date, or `None` if the day doesn't exist.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let valid = (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month);
+        valid.then_some(Self {
+            year,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// Today's date in the local time zone, or in UTC if it is unknown.
+    #[cfg(feature = "local-date")]
+    pub fn today() -> Self {
+        let today = jiff::Zoned::now().date();
+        Self {
+            year: i32::from(today.year()),
+            month: today.month() as u8,
+            day: today.day() as u8,
+        }
+    }
+
+    /// Today's date in UTC (in the local time zone with the `local-date`
+    /// feature).
+    #[cfg(not(feature = "local-date"))]
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_days(secs.div_euclid(86_400) as i64).expect("today's year fits an i32")
+    }
+
+    /// The year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month, from 1 to 12.
+    pub fn month(&self) -> u32 {
+        u32::from(self.month)
+    }
+
+    /// The day of the month, from 1.
+    pub fn day(&self) -> u32 {
+        u32::from(self.day)
+    }
+
+    /// The number of days in this date's month.
+    pub fn days_in_month(&self) -> u32 {
+        days_in_month(self.year, self.month())
+    }
+
+    /// The day of the week, from 0 for Monday to 6 for Sunday.
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.days() + 3).rem_euclid(7) as u32
+    }
+
+    /// The first day of this date's month.
+    pub fn first_of_month(&self) -> Self {
+        Self { day: 1, ..*self }
+    }
+
+    /// The date `days` days later (or earlier, if negative), or `None` if
+    /// the year would overflow.
+    pub fn checked_add_days(&self, days: i64) -> Option<Self> {
+        Self::from_days(self.days().checked_add(days)?)
+    }
+
+    /// The same day `months` months later (or earlier, if negative), clamped
+    /// to the end of the target month, or `None` if the year would overflow.
+    pub fn checked_add_months(&self, months: i32) -> Option<Self> {
+        let index = (i64::from(self.year) * 12 + i64::from(self.month) - 1)
+            .checked_add(i64::from(months))?;
+        let year = i32::try_from(index.div_euclid(12)).ok()?;
+        let month = index.rem_euclid(12) as u32 + 1;
+        let day = self.day().min(days_in_month(year, month));
+        Some(Self {
+            year,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// Days since 1970-01-01.
+    fn days(&self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month_from_march = (i64::from(self.month) + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` days after 1970-01-01, if its year fits an `i32`.
+    fn from_days(days: i64) -> Option<Self> {
+        let days = days.checked_add(719_468)?;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Some(Self {
+            year: i32::try_from(year).ok()?,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Expected a date like 2024-05-31, got '{}'", s.trim());
+        let mut parts = s.trim().splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(error());
+        };
+        let year = year.parse().map_err(|_| error())?;
+        let month = month.parse().map_err(|_| error())?;
+        let day = day.parse().map_err(|_| error())?;
+        Self::new(year, month, day).ok_or_else(|| format!("{} is not a valid date", s.trim()))
+    }
+}
+
+/// A time of day without a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl Time {
+    /// Create a time, or `None` if it is out of range.
+    pub fn new(hour: u32, minute: u32, second: u32) -> Option<Self> {
+        (hour < 24 && minute < 60 && second < 60).then_some(Self {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+        })
+    }
+
+    /// The hour, from 0 to 23.
+    pub fn hour(&self) -> u32 {
+        u32::from(self.hour)
+    }
+
+    /// The minute, from 0 to 59.
+    pub fn minute(&self) -> u32 {
+        u32::from(self.minute)
+    }
+
+    /// The second, from 0 to 59.
+    pub fn second(&self) -> u32 {
+        u32::from(self.second)
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)?;
+        if self.second != 0 {
+            write!(f, ":{:02}", self.second)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Time {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Expected a time like 14:30, got '{}'", s.trim());
+        let parts: Vec<&str> = s.trim().split(':').collect();
+        let numbers = match parts.as_slice() {
+            [hour, minute] => [*hour, *minute, "0"],
+            [hour, minute, second] => [*hour, *minute, *second],
+            _ => return Err(error()),
+        };
+        let [hour, minute, second] = numbers.map(|n| n.parse::<u32>().map_err(|_| error()));
+        Self::new(hour?, minute?, second?)
+            .ok_or_else(|| format!("{} is not a valid time", s.trim()))
+    }
+}
+
+/// A date and time of day without a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    date: Date,
+    time: Time,
+}
+
+impl DateTime {
+    /// Combine a date and a time of day.
+    pub fn new(date: Date, time: Time) -> Self {
+        Self { date, time }
+    }
+
+    /// The date.
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// The time of day.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date, self.time)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((date, time)) = s.trim().split_once(['T', ' ']) else {
+            return Err(format!(
+                "Expected a date and time like 2024-05-31 14:30, got '{}'",
+                s.trim()
+            ));
+        };
+        Ok(Self::new(date.parse()?, time.parse()?))
+    }
+}
+
+/// Parse a duration such as `90s`, `1h 30m` or `2days`; a bare number is seconds.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    if let Ok(secs) = text.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+    let error = |reason: &str| format!("Invalid duration '{text}': {reason}");
+    if text.is_empty() {
+        return Err(error("expected a number"));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(error("expected a number"));
+        }
+        let number: u64 = rest[..digits]
+            .parse()
+            .map_err(|_| error("number is too large"))?;
+        rest = rest[digits..].trim_start();
+        let letters = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (unit, after) = rest.split_at(letters);
+        rest = after.trim_start();
+
+        let secs = |per_unit: u64| number.checked_mul(per_unit).map(Duration::from_secs);
+        let part = match unit {
+            "ns" | "nsec" | "nanos" => Some(Duration::from_nanos(number)),
+            "us" | "usec" | "micros" => Some(Duration::from_micros(number)),
+            "ms" | "msec" | "millis" => Some(Duration::from_millis(number)),
+            "s" | "sec" | "secs" | "second" | "seconds" => secs(1),
+            "m" | "min" | "mins" | "minute" | "minutes" => secs(60),
+            "h" | "hr" | "hrs" | "hour" | "hours" => secs(3600),
+            "d" | "day" | "days" => secs(86_400),
+            "w" | "week" | "weeks" => secs(604_800),
+            "" => return Err(error("expected a unit such as s, m or h")),
+            _ => return Err(error(&format!("unknown unit '{unit}'"))),
+        };
+        total = part
+            .and_then(|part| total.checked_add(part))
+            .ok_or_else(|| error("too long"))?;
+    }
+    Ok(total)
+}
+
+/// Format a duration the way [`parse_duration`] reads it, e.g. `1h 30m`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let nanos = u64::from(duration.subsec_nanos());
+    let parts = [
+        (secs / 86_400, "d"),
+        (secs / 3600 % 24, "h"),
+        (secs / 60 % 60, "m"),
+        (secs % 60, "s"),
+        (nanos / 1_000_000, "ms"),
+        (nanos / 1000 % 1000, "us"),
+        (nanos % 1000, "ns"),
+    ];
+    let text = parts
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| format!("{count}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        "0s".to_string()
+    } else {
+        text
+    }
+}
+
+/// A value collected by a temporal question: [`Date`], [`Time`], [`DateTime`]
+/// or [`Duration`].
+pub trait TemporalInput: Copy + PartialOrd + Into<ResponseValue> {
+    /// How the value is typed, shown as a hint next to the prompt.
+    const FORMAT: &'static str;
+
+    /// Parse typed text.
+    fn parse_input(text: &str) -> Result<Self, String>;
+
+    /// Format the value as it is typed.
+    fn format_input(&self) -> String;
+
+    /// Read the value from a response.
+    fn from_response_value(value: &ResponseValue) -> Option<Self>;
+
+    /// The question kind asking for this value.
+    fn question_kind(question: TemporalQuestion<Self>) -> QuestionKind;
+}
+
+impl TemporalInput for Date {
+    const FORMAT: &'static str = "YYYY-MM-DD";
+
+    fn parse_input(text: &str) -> Result<Self, String> {
+        text.parse()
+    }
+
+    fn format_input(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_response_value(value: &ResponseValue) -> Option<Self> {
+        value.as_date()
+    }
+
+    fn question_kind(question: TemporalQuestion<Self>) -> QuestionKind {
+        QuestionKind::Date(question)
+    }
+}
+
+impl TemporalInput for Time {
+    const FORMAT: &'static str = "HH:MM";
+
+    fn parse_input(text: &str) -> Result<Self, String> {
+        text.parse()
+    }
+
+    fn format_input(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_response_value(value: &ResponseValue) -> Option<Self> {
+        value.as_time()
+    }
+
+    fn question_kind(question: TemporalQuestion<Self>) -> QuestionKind {
+        QuestionKind::Time(question)
+    }
+}
+
+impl TemporalInput for DateTime {
+    const FORMAT: &'static str = "YYYY-MM-DD HH:MM";
+
+    fn parse_input(text: &str) -> Result<Self, String> {
+        text.parse()
+    }
+
+    fn format_input(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_response_value(value: &ResponseValue) -> Option<Self> {
+        value.as_datetime()
+    }
+
+    fn question_kind(question: TemporalQuestion<Self>) -> QuestionKind {
+        QuestionKind::DateTime(question)
+    }
+}
+
+impl TemporalInput for Duration {
+    const FORMAT: &'static str = "e.g. 1h 30m";
+
+    fn parse_input(text: &str) -> Result<Self, String> {
+        parse_duration(text)
+    }
+
+    fn format_input(&self) -> String {
+        format_duration(*self)
+    }
+
+    fn from_response_value(value: &ResponseValue) -> Option<Self> {
+        value.as_duration()
+    }
+
+    fn question_kind(question: TemporalQuestion<Self>) -> QuestionKind {
+        QuestionKind::Duration(question)
+    }
+}
+
+/// Configuration for a date, time, date-time or duration question.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalQuestion<T> {
+    /// Optional default value.
+    pub default: Option<T>,
+
+    /// Optional earliest (or shortest) value.
+    pub min: Option<T>,
+
+    /// Optional latest (or longest) value.
+    pub max: Option<T>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+}
+
+/// Configuration for a date question.
+pub type DateQuestion = TemporalQuestion<Date>;
+
+/// Configuration for a time-of-day question.
+pub type TimeQuestion = TemporalQuestion<Time>;
+
+/// Configuration for a date and time question.
+pub type DateTimeQuestion = TemporalQuestion<DateTime>;
+
+/// Configuration for a duration question.
+pub type DurationQuestion = TemporalQuestion<Duration>;
+
+impl<T> Default for TemporalQuestion<T> {
+    fn default() -> Self {
+        Self {
+            default: None,
+            min: None,
+            max: None,
+            validate: None,
+        }
+    }
+}
+
+impl<T: TemporalInput> TemporalQuestion<T> {
+    /// Create a new question.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create with bounds.
+    pub fn with_bounds(min: Option<T>, max: Option<T>) -> Self {
+        Self {
+            min,
+            max,
+            ..Self::default()
+        }
+    }
+
+    /// Create with bounds and a validator.
+    pub fn with_bounds_and_validator(
+        min: Option<T>,
+        max: Option<T>,
+        validate: Option<String>,
+    ) -> Self {
+        Self {
+            min,
+            max,
+            validate,
+            ..Self::default()
+        }
+    }
+
+    /// Check a value against the bounds.
+    pub fn check_bounds(&self, value: &T) -> Result<(), String> {
+        if let Some(min) = &self.min
+            && value < min
+        {
+            return Err(format!("Value must be at least {}", min.format_input()));
+        }
+        if let Some(max) = &self.max
+            && value > max
+        {
+            return Err(format!("Value must be at most {}", max.format_input()));
+        }
+        Ok(())
+    }
+
+    /// Parse typed text and check it against the bounds.
+    pub fn parse(&self, text: &str) -> Result<T, String> {
+        let value = T::parse_input(text)?;
+        self.check_bounds(&value)?;
+        Ok(value)
+    }
+}
+
+/// A field type filled by a temporal question.
+///
+/// The derive macro asks `Date`, `Time`, `DateTime` and `Duration` fields
+/// through this trait. It is implemented for this module's types,
+/// `std::time::Duration`, and (behind features) `chrono::{NaiveDate,
+/// NaiveTime, NaiveDateTime}` and `time::{Date, Time, PrimitiveDateTime}`.
+pub trait TemporalValue: Sized {
+    /// The value stored in responses.
+    type Input: TemporalInput;
+
+    /// Convert from the stored value.
+    fn from_input(input: Self::Input) -> Self;
+
+    /// Convert to the stored value.
+    fn to_input(&self) -> Self::Input;
+
+    /// The earliest and latest values this type can hold, if it can't hold
+    /// every value of its input.
+    fn input_range() -> (Option<Self::Input>, Option<Self::Input>) {
+        (None, None)
+    }
+
+    /// The question for this type, with bounds given as text.
+    ///
+    /// The bounds are narrowed to [`input_range`](Self::input_range), so
+    /// answers within them always convert.
+    ///
+    /// # Panics
+    ///
+    /// If a bound cannot be parsed.
+    fn question(min: Option<&str>, max: Option<&str>, validate: Option<String>) -> QuestionKind {
+        let bound = |text: &str| {
+            Self::Input::parse_input(text).unwrap_or_else(|err| panic!("invalid bound: {err}"))
+        };
+        let (lowest, highest) = Self::input_range();
+        let min = match (min.map(bound), lowest) {
+            (Some(min), Some(lowest)) if min < lowest => Some(lowest),
+            (min, lowest) => min.or(lowest),
+        };
+        let max = match (max.map(bound), highest) {
+            (Some(max), Some(highest)) if max > highest => Some(highest),
+            (max, highest) => max.or(highest),
+        };
+        Self::Input::question_kind(TemporalQuestion::with_bounds_and_validator(
+            min, max, validate,
+        ))
+    }
+
+    /// Read the value from a response.
+    fn from_response(value: &ResponseValue) -> Option<Self> {
+        Self::Input::from_response_value(value).map(Self::from_input)
+    }
+
+    /// The response for this value.
+    fn to_response(&self) -> ResponseValue {
+        self.to_input().into()
+    }
+}
+
+impl<T: TemporalInput> TemporalValue for T {
+    type Input = T;
+
+    fn from_input(input: T) -> Self {
+        input
+    }
+
+    fn to_input(&self) -> T {
+        *self
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{Datelike, Timelike};
+
+    use super::{Date, DateTime, TemporalValue, Time};
+
+    impl TemporalValue for chrono::NaiveDate {
+        type Input = Date;
+
+        fn input_range() -> (Option<Date>, Option<Date>) {
+            (
+                Some(chrono::NaiveDate::MIN.to_input()),
+                Some(chrono::NaiveDate::MAX.to_input()),
+            )
+        }
+
+        fn from_input(input: Date) -> Self {
+            chrono::NaiveDate::from_ymd_opt(input.year(), input.month(), input.day())
+                .expect("dates within the input range are valid in chrono")
+        }
+
+        fn to_input(&self) -> Date {
+            Date::new(self.year(), self.month(), self.day()).expect("dates are valid")
+        }
+    }
+
+    impl TemporalValue for chrono::NaiveTime {
+        type Input = Time;
+
+        fn from_input(input: Time) -> Self {
+            chrono::NaiveTime::from_hms_opt(input.hour(), input.minute(), input.second())
+                .expect("times are valid in chrono")
+        }
+
+        fn to_input(&self) -> Time {
+            // Leap seconds are folded into the last second of the minute
+            Time::new(self.hour(), self.minute(), self.second().min(59)).expect("times are valid")
+        }
+    }
+
+    impl TemporalValue for chrono::NaiveDateTime {
+        type Input = DateTime;
+
+        fn input_range() -> (Option<DateTime>, Option<DateTime>) {
+            (
+                Some(chrono::NaiveDateTime::MIN.to_input()),
+                Some(chrono::NaiveDateTime::MAX.to_input()),
+            )
+        }
+
+        fn from_input(input: DateTime) -> Self {
+            chrono::NaiveDateTime::new(
+                chrono::NaiveDate::from_input(input.date()),
+                chrono::NaiveTime::from_input(input.time()),
+            )
+        }
+
+        fn to_input(&self) -> DateTime {
+            DateTime::new(self.date().to_input(), self.time().to_input())
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::{Date, DateTime, TemporalValue, Time};
+
+    impl TemporalValue for time::Date {
+        type Input = Date;
+
+        fn input_range() -> (Option<Date>, Option<Date>) {
+            (
+                Some(time::Date::MIN.to_input()),
+                Some(time::Date::MAX.to_input()),
+            )
+        }
+
+        fn from_input(input: Date) -> Self {
+            let month = time::Month::try_from(input.month() as u8).expect("months are valid");
+            time::Date::from_calendar_date(input.year(), month, input.day() as u8)
+                .expect("dates within the input range are valid in time")
+        }
+
+        fn to_input(&self) -> Date {
+            Date::new(
+                self.year(),
+                u8::from(self.month()).into(),
+                self.day().into(),
+            )
+            .expect("dates are valid")
+        }
+    }
+
+    impl TemporalValue for time::Time {
+        type Input = Time;
+
+        fn from_input(input: Time) -> Self {
+            time::Time::from_hms(
+                input.hour() as u8,
+                input.minute() as u8,
+                input.second() as u8,
+            )
+            .expect("times are valid in time")
+        }
+
+        fn to_input(&self) -> Time {
+            Time::new(
+                self.hour().into(),
+                self.minute().into(),
+                self.second().into(),
+            )
+            .expect("times are valid")
+        }
+    }
+
+    impl TemporalValue for time::PrimitiveDateTime {
+        type Input = DateTime;
+
+        fn input_range() -> (Option<DateTime>, Option<DateTime>) {
+            (
+                Some(time::PrimitiveDateTime::MIN.to_input()),
+                Some(time::PrimitiveDateTime::MAX.to_input()),
+            )
+        }
+
+        fn from_input(input: DateTime) -> Self {
+            time::PrimitiveDateTime::new(
+                time::Date::from_input(input.date()),
+                time::Time::from_input(input.time()),
+            )
+        }
+
+        fn to_input(&self) -> DateTime {
+            DateTime::new(self.date().to_input(), self.time().to_input())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_parse_and_do_calendar_arithmetic() {
+        let date: Date = "2024-02-29".parse().unwrap();
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(date.weekday(), 3);
+        assert_eq!(date.checked_add_days(1), Date::new(2024, 3, 1));
+        assert_eq!(date.checked_add_months(12), Date::new(2025, 2, 28));
+        assert_eq!(
+            date.checked_add_days(-366)
+                .and_then(|d| d.checked_add_days(366)),
+            Some(date)
+        );
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("tomorrow".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn date_arithmetic_stops_at_the_ends_of_the_year_range() {
+        let last = Date::new(i32::MAX, 12, 31).unwrap();
+        assert_eq!(last.checked_add_days(1), None);
+        assert_eq!(last.checked_add_months(1), None);
+        assert_eq!(
+            Date::new(i32::MIN, 1, 1).unwrap().checked_add_months(-1),
+            None
+        );
+    }
+
+    #[test]
+    fn times_and_datetimes_parse() {
+        assert_eq!("9:05".parse::<Time>().unwrap().to_string(), "09:05");
+        assert_eq!("23:59:30".parse::<Time>().unwrap().to_string(), "23:59:30");
+        assert!("24:00".parse::<Time>().is_err());
+
+        let datetime: DateTime = "2024-05-31T14:30".parse().unwrap();
+        assert_eq!(datetime.to_string(), "2024-05-31 14:30");
+    }
+
+    #[test]
+    fn durations_parse_with_units() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("1h 30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(
+            parse_duration("2days").unwrap(),
+            Duration::from_secs(172_800)
+        );
+        assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h 30m");
+        assert_eq!(
+            format_duration(Duration::from_millis(90_250)),
+            "1m 30s 250ms"
+        );
+        assert_eq!(format_duration(Duration::ZERO), "0s");
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("5 parsecs").is_err());
+        assert!(parse_duration("1h 30").is_err());
+        assert!(parse_duration("99999999999999999999w").is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_questions_stop_at_the_ends_of_its_year_range() {
+        let QuestionKind::Date(question) =
+            <chrono::NaiveDate as TemporalValue>::question(None, Some("300000-01-01"), None)
+        else {
+            panic!("Expected a date question");
+        };
+        assert!(question.parse("262142-12-31").is_ok());
+        assert_eq!(
+            question.parse("262143-01-01"),
+            Err("Value must be at most 262142-12-31".to_string())
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_questions_stop_at_the_ends_of_its_year_range() {
+        let QuestionKind::DateTime(question) =
+            <time::PrimitiveDateTime as TemporalValue>::question(None, None, None)
+        else {
+            panic!("Expected a date and time question");
+        };
+        let last = question.parse("9999-12-31 23:59:59").unwrap();
+        assert_eq!(time::PrimitiveDateTime::from_input(last).year(), 9999);
+        assert_eq!(
+            question.parse("10000-01-01 00:00"),
+            Err("Value must be at most 9999-12-31 23:59:59".to_string())
+        );
+    }
+
+    #[test]
+    fn questions_check_bounds() {
+        let question = DateQuestion::with_bounds(Date::new(2024, 1, 1), None);
+        assert!(question.parse("2024-06-01").is_ok());
+        assert_eq!(
+            question.parse("2023-12-31"),
+            Err("Value must be at least 2024-01-01".to_string())
+        );
+    }
+}
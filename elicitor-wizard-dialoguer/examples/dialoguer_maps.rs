@@ -0,0 +1,11 @@
+//! Map example. Run with: cargo run -p elicitor-wizard-dialoguer --example dialoguer_maps
+
+use elicitor_wizard_dialoguer::DialoguerBackend;
+use example_surveys::Deployment;
+
+fn main() -> anyhow::Result<()> {
+    let backend = DialoguerBackend::new();
+    let deployment = Deployment::builder().run(backend)?;
+    println!("{deployment:#?}");
+    Ok(())
+}
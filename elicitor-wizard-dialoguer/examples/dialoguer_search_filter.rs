@@ -0,0 +1,11 @@
+//! Recursive type example. Run with: cargo run -p elicitor-wizard-dialoguer --example dialoguer_search_filter
+
+use elicitor_wizard_dialoguer::DialoguerBackend;
+use example_surveys::SavedSearch;
+
+fn main() -> anyhow::Result<()> {
+    let backend = DialoguerBackend::new();
+    let search = SavedSearch::builder().run(backend)?;
+    println!("{search:#?}");
+    Ok(())
+}
@@ -1,12 +1,16 @@
 //! Dialoguer backend implementation for SurveyBackend trait.
 
-use dialoguer::{Confirm, Editor, Input, MultiSelect, Password, Select, theme::ColorfulTheme};
+use dialoguer::{Confirm, Editor, Input, MultiSelect, Password, Select};
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition,
+    DefaultValue, ListElementKind, MAP_KEYS_KEY, MapQuestion, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SecretValue,
+    SurveyBackend, SurveyDefinition,
 };
+use elicitor_theme::Theme;
 use thiserror::Error;
 
+use crate::theme::colorful_theme;
+
 /// Error type for the Dialoguer backend.
 #[derive(Debug, Error)]
 pub enum DialoguerError {
@@ -21,6 +25,10 @@ pub enum DialoguerError {
     /// Validation error.
     #[error("Validation error: {0}")]
     ValidationError(String),
+
+    /// A recursive type was nested deeper than it may be.
+    #[error("{0}")]
+    TooDeep(String),
 }
 
 /// Helper to check if a dialoguer error is a cancellation (Ctrl+C / Escape)
@@ -34,19 +42,29 @@ fn is_cancelled(err: &dialoguer::Error) -> bool {
 /// to the user in a command-line interface with colorful themes.
 #[derive(Debug, Default, Clone)]
 pub struct DialoguerBackend {
-    /// Use colorful theme for prompts.
-    colorful: bool,
+    /// Colors for prompts, or `None` for plain output.
+    theme: Option<Theme>,
 }
 
 impl DialoguerBackend {
     /// Create a new Dialoguer backend with default (colorful) theme.
+    ///
+    /// Falls back to plain output if the `NO_COLOR` environment variable is set.
     pub fn new() -> Self {
-        Self { colorful: true }
+        Self::plain().with_theme(Theme::from_env())
     }
 
     /// Create a backend with plain (no color) theme.
     pub fn plain() -> Self {
-        Self { colorful: false }
+        Self { theme: None }
+    }
+
+    /// Set the color theme, shared with the ratatui backends.
+    ///
+    /// A monochrome theme produces the same output as [`plain`](Self::plain).
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = (!theme.is_monochrome()).then_some(theme);
+        self
     }
 
     /// Ask a single question and store the response.
@@ -66,7 +84,7 @@ impl DialoguerBackend {
         let prompt = if question.ask().is_empty() {
             path.as_str()
                 .split('.')
-                .last()
+                .next_back()
                 .unwrap_or("")
                 .split('_')
                 .map(|word| {
@@ -135,6 +153,18 @@ impl DialoguerBackend {
                 self.ask_confirm(&path, &prompt, confirm_q, question.default(), responses)
             }
 
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => self.ask_temporal(
+                &path,
+                &prompt,
+                question.kind(),
+                question.default(),
+                responses,
+                validate,
+            ),
+
             QuestionKind::List(list_q) => self.ask_list(
                 &path,
                 &prompt,
@@ -158,6 +188,16 @@ impl DialoguerBackend {
                 }
                 Ok(())
             }
+
+            QuestionKind::Map(map_q) => self.ask_map(&path, &prompt, map_q, responses, validate),
+
+            QuestionKind::Lazy(lazy) => {
+                let all_of = lazy.expand().map_err(DialoguerError::TooDeep)?;
+                for nested_q in all_of.questions() {
+                    self.ask_question(nested_q, responses, validate, Some(&path))?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -173,8 +213,8 @@ impl DialoguerBackend {
         loop {
             let mut _theme;
             let mut builder: Input<String>;
-            if self.colorful {
-                _theme = ColorfulTheme::default();
+            if let Some(theme) = &self.theme {
+                _theme = colorful_theme(theme);
                 builder = Input::with_theme(&_theme);
             } else {
                 builder = Input::new();
@@ -225,12 +265,8 @@ impl DialoguerBackend {
             let editor = Editor::new();
 
             // Get default text if available
-            let default_text = if let Some(default_val) = default.value() {
-                if let ResponseValue::String(s) = default_val {
-                    s.as_str()
-                } else {
-                    ""
-                }
+            let default_text = if let Some(ResponseValue::String(s)) = default.value() {
+                s.as_str()
             } else {
                 ""
             };
@@ -269,7 +305,7 @@ impl DialoguerBackend {
         &self,
         path: &ResponsePath,
         prompt: &str,
-        _masked_q: &elicitor::MaskedQuestion,
+        masked_q: &elicitor::MaskedQuestion,
         _default: &DefaultValue, // Passwords don't have visible defaults
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
@@ -277,20 +313,26 @@ impl DialoguerBackend {
         loop {
             let mut _theme;
             let mut builder: Password;
-            if self.colorful {
-                _theme = ColorfulTheme::default();
+            if let Some(theme) = &self.theme {
+                _theme = colorful_theme(theme);
                 builder = Password::with_theme(&_theme);
             } else {
                 builder = Password::new();
             }
 
             builder = builder.with_prompt(prompt);
+            if masked_q.confirm {
+                builder = builder.with_confirmation(
+                    elicitor::CONFIRMATION_PROMPT,
+                    elicitor::CONFIRMATION_MISMATCH,
+                );
+            }
 
             let result = builder.interact();
 
             match result {
                 Ok(value) => {
-                    let rv = ResponseValue::String(value.clone());
+                    let rv = ResponseValue::Secret(SecretValue::new(value));
                     if let Err(msg) = validate(&rv, responses, path) {
                         println!("Error: {msg}");
                         continue;
@@ -318,8 +360,8 @@ impl DialoguerBackend {
         loop {
             let mut _theme;
             let mut builder: Input<i64>;
-            if self.colorful {
-                _theme = ColorfulTheme::default();
+            if let Some(theme) = &self.theme {
+                _theme = colorful_theme(theme);
                 builder = Input::with_theme(&_theme);
             } else {
                 builder = Input::new();
@@ -341,17 +383,17 @@ impl DialoguerBackend {
             match result {
                 Ok(value) => {
                     // Check bounds
-                    if let Some(min) = int_q.min {
-                        if value < min {
-                            println!("Error: Value must be at least {min}");
-                            continue;
-                        }
+                    if let Some(min) = int_q.min
+                        && value < min
+                    {
+                        println!("Error: Value must be at least {min}");
+                        continue;
                     }
-                    if let Some(max) = int_q.max {
-                        if value > max {
-                            println!("Error: Value must be at most {max}");
-                            continue;
-                        }
+                    if let Some(max) = int_q.max
+                        && value > max
+                    {
+                        println!("Error: Value must be at most {max}");
+                        continue;
                     }
 
                     let rv = ResponseValue::Int(value);
@@ -370,6 +412,68 @@ impl DialoguerBackend {
         }
     }
 
+    /// Ask for a date, time, date-time or duration as text.
+    fn ask_temporal(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        kind: &QuestionKind,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        let format = kind.temporal_format().unwrap_or_default();
+        let prompt = format!("{prompt} ({format})");
+        loop {
+            let mut _theme;
+            let mut builder: Input<String>;
+            if let Some(theme) = &self.theme {
+                _theme = colorful_theme(theme);
+                builder = Input::with_theme(&_theme);
+            } else {
+                builder = Input::new();
+            }
+
+            builder = builder.with_prompt(&prompt);
+
+            // Apply default value
+            if let Some(text) = default
+                .value()
+                .cloned()
+                .or_else(|| kind.temporal_default())
+                .and_then(|value| value.temporal_text())
+            {
+                builder = builder.default(text);
+            }
+
+            let result = builder.interact_text();
+
+            match result {
+                Ok(value) => {
+                    // Parse and check bounds
+                    let rv = match kind.parse_temporal(&value) {
+                        Some(Ok(rv)) => rv,
+                        Some(Err(msg)) => {
+                            println!("Error: {msg}");
+                            continue;
+                        }
+                        None => ResponseValue::String(value),
+                    };
+                    if let Err(msg) = validate(&rv, responses, path) {
+                        println!("Error: {msg}");
+                        continue;
+                    }
+                    responses.insert(path.clone(), rv);
+                    return Ok(());
+                }
+                Err(e) if is_cancelled(&e) => {
+                    return Err(DialoguerError::Cancelled);
+                }
+                Err(e) => return Err(DialoguerError::Dialoguer(e)),
+            }
+        }
+    }
+
     fn ask_float(
         &self,
         path: &ResponsePath,
@@ -382,8 +486,8 @@ impl DialoguerBackend {
         loop {
             let mut _theme;
             let mut builder: Input<f64>;
-            if self.colorful {
-                _theme = ColorfulTheme::default();
+            if let Some(theme) = &self.theme {
+                _theme = colorful_theme(theme);
                 builder = Input::with_theme(&_theme);
             } else {
                 builder = Input::new();
@@ -405,17 +509,17 @@ impl DialoguerBackend {
             match result {
                 Ok(value) => {
                     // Check bounds
-                    if let Some(min) = float_q.min {
-                        if value < min {
-                            println!("Error: Value must be at least {min}");
-                            continue;
-                        }
+                    if let Some(min) = float_q.min
+                        && value < min
+                    {
+                        println!("Error: Value must be at least {min}");
+                        continue;
                     }
-                    if let Some(max) = float_q.max {
-                        if value > max {
-                            println!("Error: Value must be at most {max}");
-                            continue;
-                        }
+                    if let Some(max) = float_q.max
+                        && value > max
+                    {
+                        println!("Error: Value must be at most {max}");
+                        continue;
                     }
 
                     let rv = ResponseValue::Float(value);
@@ -450,8 +554,8 @@ impl DialoguerBackend {
 
         let mut builder: Confirm;
         let _theme;
-        if self.colorful {
-            _theme = ColorfulTheme::default();
+        if let Some(theme) = &self.theme {
+            _theme = colorful_theme(theme);
             builder = Confirm::with_theme(&_theme);
         } else {
             builder = Confirm::new();
@@ -492,8 +596,8 @@ impl DialoguerBackend {
                 ListElementKind::String => {
                     let mut _theme;
                     let mut builder: Input<String>;
-                    if self.colorful {
-                        _theme = ColorfulTheme::default();
+                    if let Some(theme) = &self.theme {
+                        _theme = colorful_theme(theme);
                         builder = Input::with_theme(&_theme);
                     } else {
                         builder = Input::new();
@@ -511,8 +615,8 @@ impl DialoguerBackend {
                 ListElementKind::Int { min, max } => {
                     let mut _theme;
                     let mut builder: Input<String>;
-                    if self.colorful {
-                        _theme = ColorfulTheme::default();
+                    if let Some(theme) = &self.theme {
+                        _theme = colorful_theme(theme);
                         builder = Input::with_theme(&_theme);
                     } else {
                         builder = Input::new();
@@ -524,17 +628,17 @@ impl DialoguerBackend {
                         Ok(s) if s.is_empty() => break,
                         Ok(s) => match s.parse::<i64>() {
                             Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
+                                if let Some(min_val) = min
+                                    && n < *min_val
+                                {
+                                    println!("    Error: Value must be at least {min_val}");
+                                    continue;
                                 }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
+                                if let Some(max_val) = max
+                                    && n > *max_val
+                                {
+                                    println!("    Error: Value must be at most {max_val}");
+                                    continue;
                                 }
                                 Some(ResponseValue::Int(n))
                             }
@@ -550,8 +654,8 @@ impl DialoguerBackend {
                 ListElementKind::Float { min, max } => {
                     let mut _theme;
                     let mut builder: Input<String>;
-                    if self.colorful {
-                        _theme = ColorfulTheme::default();
+                    if let Some(theme) = &self.theme {
+                        _theme = colorful_theme(theme);
                         builder = Input::with_theme(&_theme);
                     } else {
                         builder = Input::new();
@@ -563,17 +667,17 @@ impl DialoguerBackend {
                         Ok(s) if s.is_empty() => break,
                         Ok(s) => match s.parse::<f64>() {
                             Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
+                                if let Some(min_val) = min
+                                    && n < *min_val
+                                {
+                                    println!("    Error: Value must be at least {min_val}");
+                                    continue;
                                 }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
+                                if let Some(max_val) = max
+                                    && n > *max_val
+                                {
+                                    println!("    Error: Value must be at most {max_val}");
+                                    continue;
                                 }
                                 Some(ResponseValue::Float(n))
                             }
@@ -659,8 +763,8 @@ impl DialoguerBackend {
 
         let mut builder: Select;
         let _theme;
-        if self.colorful {
-            _theme = ColorfulTheme::default();
+        if let Some(theme) = &self.theme {
+            _theme = colorful_theme(theme);
             builder = Select::with_theme(&_theme);
         } else {
             builder = Select::new();
@@ -672,14 +776,19 @@ impl DialoguerBackend {
             builder = builder.default(default_idx);
         }
 
-        let result = builder.interact();
-
-        let selection = match result {
-            Ok(idx) => idx,
-            Err(e) if is_cancelled(&e) => {
-                return Err(DialoguerError::Cancelled);
+        // Variants of a recursive type that would nest too deeply are refused
+        let selection = loop {
+            let selection = match builder.clone().interact() {
+                Ok(idx) => idx,
+                Err(e) if is_cancelled(&e) => {
+                    return Err(DialoguerError::Cancelled);
+                }
+                Err(e) => return Err(DialoguerError::Dialoguer(e)),
+            };
+            match one_of.variants[selection].kind.nesting_error() {
+                Some(msg) => println!("Error: {msg}"),
+                None => break selection,
             }
-            Err(e) => return Err(DialoguerError::Dialoguer(e)),
         };
 
         // Store the selected variant index
@@ -703,7 +812,13 @@ impl DialoguerBackend {
             | QuestionKind::Confirm(_)
             | QuestionKind::Masked(_)
             | QuestionKind::Multiline(_)
-            | QuestionKind::List(_) => {
+            | QuestionKind::List(_)
+            | QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_)
+            | QuestionKind::Map(_)
+            | QuestionKind::Lazy(_) => {
                 let variant_q = Question::new(
                     selected_variant.name.clone(),
                     format!("Enter {} value:", selected_variant.name),
@@ -732,6 +847,159 @@ impl DialoguerBackend {
         Ok(())
     }
 
+    fn ask_map(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        map_q: &MapQuestion,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        const ADD: &str = "Add entry";
+        const EDIT: &str = "Edit entry";
+        const REMOVE: &str = "Remove entry";
+        const DONE: &str = "Done";
+
+        // Each entry's value responses, relative to the entry's path
+        let mut entries: Vec<(String, Responses)> = Vec::new();
+        loop {
+            let keys: Vec<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+            println!("{prompt} {{{}}}", keys.join(", "));
+
+            let actions = if entries.is_empty() {
+                vec![ADD, DONE]
+            } else {
+                vec![ADD, EDIT, REMOVE, DONE]
+            };
+            let action = actions[self.select("Entries", &actions)?];
+            match action {
+                ADD => {
+                    let idx = entries.len();
+                    let key = self.ask_map_key(path, "", &keys, responses, validate)?;
+                    let value = self.ask_map_value(path, map_q, idx, &key, responses, validate)?;
+                    entries.push((key, value));
+                }
+                EDIT => {
+                    let idx = self.select("Entry to edit", &keys)?;
+                    let others: Vec<&str> = keys
+                        .iter()
+                        .enumerate()
+                        .filter(|&(other, _)| other != idx)
+                        .map(|(_, key)| *key)
+                        .collect();
+                    let key = self.ask_map_key(path, keys[idx], &others, responses, validate)?;
+                    let value = self.ask_map_value(path, map_q, idx, &key, responses, validate)?;
+                    entries[idx] = (key, value);
+                }
+                REMOVE => {
+                    let idx = self.select("Entry to remove", &keys)?;
+                    entries.remove(idx);
+                }
+                _ => break,
+            }
+        }
+
+        for (idx, (_, values)) in entries.iter().enumerate() {
+            let entry_path = path.child(&idx.to_string());
+            for (relative, value) in values.iter() {
+                responses.insert(entry_path.child(relative.as_str()), value.clone());
+            }
+        }
+        let keys = entries.into_iter().map(|(key, _)| key).collect();
+        responses.insert(path.child(MAP_KEYS_KEY), ResponseValue::StringList(keys));
+        Ok(())
+    }
+
+    /// Pick one of `items`, returning its index.
+    fn select(&self, prompt: &str, items: &[&str]) -> Result<usize, DialoguerError> {
+        let builder: Select;
+        let _theme;
+        if let Some(theme) = &self.theme {
+            _theme = colorful_theme(theme);
+            builder = Select::with_theme(&_theme);
+        } else {
+            builder = Select::new();
+        }
+
+        match builder
+            .with_prompt(prompt)
+            .items(items)
+            .default(0)
+            .interact()
+        {
+            Ok(idx) => Ok(idx),
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::Cancelled),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
+
+    /// Ask for the key of a map entry that differs from `other_keys`.
+    fn ask_map_key(
+        &self,
+        path: &ResponsePath,
+        default: &str,
+        other_keys: &[&str],
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<String, DialoguerError> {
+        loop {
+            let mut _theme;
+            let mut builder: Input<String>;
+            if let Some(theme) = &self.theme {
+                _theme = colorful_theme(theme);
+                builder = Input::with_theme(&_theme);
+            } else {
+                builder = Input::new();
+            }
+
+            builder = builder.with_prompt("Key").allow_empty(false);
+            if !default.is_empty() {
+                builder = builder.default(default.to_string());
+            }
+
+            match builder.interact_text() {
+                Ok(key) => {
+                    let checked = MapQuestion::check_key(&key, other_keys.iter().copied())
+                        .and_then(|()| {
+                            validate(
+                                &ResponseValue::String(key.clone()),
+                                responses,
+                                &path.child(MAP_KEYS_KEY),
+                            )
+                        });
+                    if let Err(msg) = checked {
+                        println!("Error: {msg}");
+                        continue;
+                    }
+                    return Ok(key);
+                }
+                Err(e) if is_cancelled(&e) => return Err(DialoguerError::Cancelled),
+                Err(e) => return Err(DialoguerError::Dialoguer(e)),
+            }
+        }
+    }
+
+    /// Ask for the value of the map entry at `idx`, returning the answers
+    /// relative to the entry's path.
+    fn ask_map_value(
+        &self,
+        path: &ResponsePath,
+        map_q: &MapQuestion,
+        idx: usize,
+        key: &str,
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, DialoguerError> {
+        let mut scratch = responses.clone();
+        self.ask_question(
+            &map_q.value_question(idx, key),
+            &mut scratch,
+            validate,
+            Some(path),
+        )?;
+        Ok(scratch.filter_prefix(&path.child(&idx.to_string())))
+    }
+
     fn ask_any_of(
         &self,
         path: &ResponsePath,
@@ -750,8 +1018,8 @@ impl DialoguerBackend {
 
             let mut builder: MultiSelect;
             let _theme;
-            if self.colorful {
-                _theme = ColorfulTheme::default();
+            if let Some(theme) = &self.theme {
+                _theme = colorful_theme(theme);
                 builder = MultiSelect::with_theme(&_theme);
             } else {
                 builder = MultiSelect::new();
@@ -858,6 +1126,15 @@ mod tests {
         let _plain = DialoguerBackend::plain();
     }
 
+    #[test]
+    fn monochrome_theme_is_plain() {
+        let backend = DialoguerBackend::new().with_theme(Theme::monochrome());
+        assert!(backend.theme.is_none());
+
+        let backend = DialoguerBackend::plain().with_theme(Theme::light());
+        assert_eq!(backend.theme, Some(Theme::light()));
+    }
+
     #[test]
     fn error_types() {
         let err = DialoguerError::Cancelled;
@@ -30,5 +30,7 @@
 //! ```
 
 mod backend;
+mod theme;
 
 pub use backend::{DialoguerBackend, DialoguerError};
+pub use elicitor_theme::{Color, Theme, ThemeError};
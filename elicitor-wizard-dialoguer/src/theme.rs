@@ -0,0 +1,109 @@
+//! Conversion from the shared [`Theme`] to dialoguer's [`ColorfulTheme`].
+
+use dialoguer::{
+    console::{self, Style},
+    theme::ColorfulTheme,
+};
+use elicitor_theme::{Color, Theme};
+
+/// Build a dialoguer theme using the colors of a shared theme.
+pub(crate) fn colorful_theme(theme: &Theme) -> ColorfulTheme {
+    let style = |color| paint(Style::new().for_stderr(), color);
+
+    ColorfulTheme {
+        defaults_style: style(theme.primary),
+        prompt_style: style(theme.text).bold(),
+        prompt_prefix: style(theme.highlight).apply_to("?".to_string()),
+        prompt_suffix: style(theme.border).apply_to("›".to_string()),
+        success_prefix: style(theme.success).apply_to("✔".to_string()),
+        success_suffix: style(theme.border).apply_to("·".to_string()),
+        error_prefix: style(theme.error).apply_to("✘".to_string()),
+        error_style: style(theme.error),
+        hint_style: style(theme.border),
+        values_style: style(theme.success),
+        active_item_style: style(theme.primary),
+        inactive_item_style: style(theme.text),
+        active_item_prefix: style(theme.success).apply_to("❯".to_string()),
+        inactive_item_prefix: style(theme.text).apply_to(" ".to_string()),
+        checked_item_prefix: style(theme.success).apply_to("✔".to_string()),
+        unchecked_item_prefix: style(theme.secondary).apply_to("⬚".to_string()),
+        picked_item_prefix: style(theme.success).apply_to("❯".to_string()),
+        unpicked_item_prefix: style(theme.text).apply_to(" ".to_string()),
+        fuzzy_cursor_style: paint_bg(style(theme.text), theme.selected_bg),
+        fuzzy_match_highlight_style: style(theme.highlight).bold(),
+    }
+}
+
+/// Set the foreground color of a style.
+fn paint(style: Style, color: Color) -> Style {
+    match console_color(color) {
+        Some((color, true)) => style.fg(color).bright(),
+        Some((color, false)) => style.fg(color),
+        None => style,
+    }
+}
+
+/// Set the background color of a style.
+fn paint_bg(style: Style, color: Color) -> Style {
+    match console_color(color) {
+        Some((color, true)) => style.bg(color).on_bright(),
+        Some((color, false)) => style.bg(color),
+        None => style,
+    }
+}
+
+/// Map a ratatui color to a console color and whether it is the bright variant.
+///
+/// Returns `None` for [`Color::Reset`], which leaves the terminal default in place.
+fn console_color(color: Color) -> Option<(console::Color, bool)> {
+    use console::Color as C;
+
+    Some(match color {
+        Color::Reset => return None,
+        Color::Black => (C::Black, false),
+        Color::Red => (C::Red, false),
+        Color::Green => (C::Green, false),
+        Color::Yellow => (C::Yellow, false),
+        Color::Blue => (C::Blue, false),
+        Color::Magenta => (C::Magenta, false),
+        Color::Cyan => (C::Cyan, false),
+        Color::Gray => (C::White, false),
+        Color::DarkGray => (C::Black, true),
+        Color::LightRed => (C::Red, true),
+        Color::LightGreen => (C::Green, true),
+        Color::LightYellow => (C::Yellow, true),
+        Color::LightBlue => (C::Blue, true),
+        Color::LightMagenta => (C::Magenta, true),
+        Color::LightCyan => (C::Cyan, true),
+        Color::White => (C::White, true),
+        Color::Indexed(index) => (C::Color256(index), false),
+        Color::Rgb(r, g, b) => (C::Color256(rgb_to_ansi256(r, g, b)), false),
+    })
+}
+
+/// Nearest entry in the 6x6x6 color cube of the 256-color palette.
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| ((c as u16 + 25) / 51) as u8;
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_named_and_rgb_colors() {
+        assert_eq!(console_color(Color::Reset), None);
+        assert_eq!(
+            console_color(Color::Cyan),
+            Some((console::Color::Cyan, false))
+        );
+        assert_eq!(
+            console_color(Color::LightRed),
+            Some((console::Color::Red, true))
+        );
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+    }
+}
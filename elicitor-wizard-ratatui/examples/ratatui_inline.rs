@@ -0,0 +1,18 @@
+//! Inline viewport example: the wizard renders below the cursor instead of
+//! taking over the screen, and leaves a transcript of the answers behind.
+//!
+//! Run with: cargo run -p elicitor-wizard-ratatui --example ratatui_inline
+
+use elicitor_wizard_ratatui::RatatuiBackend;
+use example_surveys::UserProfile;
+
+fn main() -> anyhow::Result<()> {
+    println!("Setting up your profile...");
+
+    let backend = RatatuiBackend::new().with_inline_viewport(6);
+    let profile: UserProfile = UserProfile::builder().run(backend)?;
+
+    println!("Welcome, {}!", profile.name);
+
+    Ok(())
+}
@@ -28,6 +28,7 @@ fn main() -> anyhow::Result<()> {
         error: Color::Red,
         success: Color::Green,
         border: Color::DarkGray,
+        ..Theme::default()
     };
 
     let backend = RatatuiBackend::new()
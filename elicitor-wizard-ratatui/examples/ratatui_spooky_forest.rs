@@ -31,6 +31,7 @@ fn main() -> anyhow::Result<()> {
         error: Color::LightRed,
         success: Color::LightGreen,
         border: Color::DarkGray,
+        ..Theme::default()
     };
 
     let backend = RatatuiBackend::new()
@@ -5,14 +5,17 @@
 
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition,
+    CONFIRMATION_MISMATCH, CONFIRMATION_PROMPT, Date, DateQuestion, DefaultValue, ListElementKind,
+    MAP_KEYS_KEY, MapQuestion, PasswordStrength, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SecretValue,
+    SurveyBackend, SurveyDefinition,
 };
 
 /// Helper function to get the parent path by stripping the last segment.
@@ -25,15 +28,25 @@ fn parent_path(path: &ResponsePath) -> ResponsePath {
     }
 }
 use ratatui::{
-    Frame, Terminal,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    Frame, Terminal, TerminalOptions, Viewport,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Position, Rect},
     prelude::CrosstermBackend,
-    style::{Color, Style, Stylize},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget, Wrap},
 };
 use std::io::{self, Stdout};
 use thiserror::Error;
 
+use elicitor_ratatui_support::edit_externally;
+use elicitor_ratatui_support::textarea::{self, TextArea, TextAreaState};
+use elicitor_theme::Theme;
+
+use crate::keymap::{Action, KeyMap};
+
+/// Smallest usable inline viewport: prompt, one line of input, error and help.
+const MIN_INLINE_HEIGHT: u16 = 4;
+
 /// Error type for the Ratatui backend.
 #[derive(Debug, Error)]
 pub enum RatatuiError {
@@ -50,34 +63,6 @@ pub enum RatatuiError {
     Terminal(String),
 }
 
-/// Color theme for the TUI.
-#[derive(Debug, Clone)]
-pub struct Theme {
-    pub primary: Color,
-    pub secondary: Color,
-    pub background: Color,
-    pub text: Color,
-    pub highlight: Color,
-    pub error: Color,
-    pub success: Color,
-    pub border: Color,
-}
-
-impl Default for Theme {
-    fn default() -> Self {
-        Self {
-            primary: Color::Cyan,
-            secondary: Color::Blue,
-            background: Color::Reset,
-            text: Color::White,
-            highlight: Color::Yellow,
-            error: Color::Red,
-            success: Color::Green,
-            border: Color::Gray,
-        }
-    }
-}
-
 /// Ratatui-based TUI backend with rich visual interface.
 ///
 /// This backend presents questions one at a time in a styled terminal UI
@@ -85,9 +70,15 @@ impl Default for Theme {
 #[derive(Debug, Clone)]
 pub struct RatatuiBackend {
     /// Title shown at the top of the wizard.
-    title: String,
+    pub(crate) title: String,
     /// Color theme for the UI.
-    theme: Theme,
+    pub(crate) theme: Theme,
+    /// Key bindings.
+    pub(crate) keymap: KeyMap,
+    /// Height of the inline viewport, or `None` for fullscreen.
+    pub(crate) inline_height: Option<u16>,
+    /// Whether to capture and handle mouse events in fullscreen mode.
+    mouse: bool,
 }
 
 impl Default for RatatuiBackend {
@@ -101,7 +92,10 @@ impl RatatuiBackend {
     pub fn new() -> Self {
         Self {
             title: "Survey".to_string(),
-            theme: Theme::default(),
+            theme: Theme::from_env(),
+            keymap: KeyMap::default(),
+            inline_height: None,
+            mouse: true,
         }
     }
 
@@ -117,12 +111,59 @@ impl RatatuiBackend {
         self
     }
 
+    /// Set custom key bindings.
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Render in an inline viewport of `height` rows below the cursor
+    /// instead of taking over the whole screen.
+    ///
+    /// The rest of the terminal, including scrollback, is left untouched. On
+    /// completion the viewport is replaced by a compact transcript of the
+    /// answers, one line per question.
+    pub fn with_inline_viewport(mut self, height: u16) -> Self {
+        self.inline_height = Some(height.max(MIN_INLINE_HEIGHT));
+        self
+    }
+
+    /// Enable or disable mouse support (enabled by default).
+    ///
+    /// With mouse support, clicking an option highlights or toggles it,
+    /// clicking the highlighted option confirms it, and the wheel moves the
+    /// highlight. Capturing the mouse disables the terminal's native text
+    /// selection; turn it off to keep that. The inline viewport never captures
+    /// the mouse.
+    pub fn with_mouse(mut self, enabled: bool) -> Self {
+        self.mouse = enabled;
+        self
+    }
+
+    /// Whether the mouse is captured: only in fullscreen mode, and only if enabled.
+    fn captures_mouse(&self) -> bool {
+        self.mouse && self.inline_height.is_none()
+    }
+
     fn setup_terminal(&self) -> Result<Terminal<CrosstermBackend<Stdout>>, RatatuiError> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let terminal = Terminal::new(backend)?;
+        execute!(stdout, EnableBracketedPaste)?;
+        let terminal = match self.inline_height {
+            Some(height) => Terminal::with_options(
+                CrosstermBackend::new(stdout),
+                TerminalOptions {
+                    viewport: Viewport::Inline(height),
+                },
+            )?,
+            None => {
+                execute!(stdout, EnterAlternateScreen)?;
+                if self.captures_mouse() {
+                    execute!(stdout, EnableMouseCapture)?;
+                }
+                Terminal::new(CrosstermBackend::new(stdout))?
+            }
+        };
         Ok(terminal)
     }
 
@@ -131,24 +172,29 @@ impl RatatuiBackend {
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<(), RatatuiError> {
         disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
+        execute!(terminal.backend_mut(), DisableBracketedPaste)?;
+        if self.inline_height.is_some() {
+            // Leave the cursor at the top of the cleared viewport
+            terminal.clear()?;
+        } else {
+            if self.captures_mouse() {
+                execute!(terminal.backend_mut(), DisableMouseCapture)?;
+            }
+            execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        }
         terminal.show_cursor()?;
         Ok(())
     }
 }
 
 /// State for the entire wizard.
-struct WizardState {
+pub(crate) struct WizardState {
     /// All flattened questions.
     questions: Vec<FlatQuestion>,
     /// Current question index.
     current_index: usize,
     /// Collected responses.
-    responses: Responses,
+    pub(crate) responses: Responses,
     /// Current input buffer.
     input: String,
     /// Cursor position in input.
@@ -162,13 +208,32 @@ struct WizardState {
     /// Whether wizard is complete.
     complete: bool,
     /// Whether user cancelled.
-    cancelled: bool,
+    pub(crate) cancelled: bool,
     /// Theme.
     theme: Theme,
     /// Title.
     title: String,
     /// Epilogue text.
     epilogue: Option<String>,
+    /// Key bindings.
+    pub(crate) keymap: KeyMap,
+    /// Whether the key binding overlay is shown.
+    show_help: bool,
+    /// Whether to use the compact layout of the inline viewport.
+    pub(crate) inline: bool,
+    /// Screen areas of the options drawn by the last draw, for mouse hit-testing.
+    option_areas: Vec<(usize, Rect)>,
+    /// Scroll position of the multi-line text area.
+    textarea: TextAreaState,
+    /// Screen area of the multi-line text area from the last draw.
+    text_area: Rect,
+    /// Set when the user asked to edit the answer in an external editor.
+    editor_requested: bool,
+    /// For a masked question with confirmation: the first entry, while the
+    /// user repeats it.
+    first_entry: Option<String>,
+    /// Whether the typed value of a masked question is shown in clear.
+    revealed: bool,
 }
 
 /// A flattened question for easier processing.
@@ -192,7 +257,10 @@ struct FlatQuestion {
 enum FlatQuestionKind {
     Input,
     Multiline,
-    Masked,
+    Masked {
+        confirm: bool,
+        strength_meter: bool,
+    },
     Int {
         min: Option<i64>,
         max: Option<i64>,
@@ -204,6 +272,10 @@ enum FlatQuestionKind {
     Confirm {
         default: bool,
     },
+    /// Date, time, date-time or duration, typed as text.
+    Temporal {
+        kind: QuestionKind,
+    },
     List {
         element_kind: ListElementKind,
     },
@@ -219,10 +291,14 @@ enum FlatQuestionKind {
         /// For AnyOf: the variants.
         variants: Option<Vec<elicitor::Variant>>,
     },
+    /// The keys of a map, typed as a list; a question for each value follows.
+    Map {
+        map_q: MapQuestion,
+    },
 }
 
 impl WizardState {
-    fn new(definition: &SurveyDefinition, theme: Theme, title: String) -> Self {
+    pub(crate) fn new(definition: &SurveyDefinition, theme: Theme, title: String) -> Self {
         let questions = Self::flatten_questions(definition.questions(), &ResponsePath::empty());
 
         // If there's a prelude, include it in the title
@@ -268,6 +344,15 @@ impl WizardState {
             theme,
             title: display_title,
             epilogue: definition.epilogue.clone(),
+            keymap: KeyMap::default(),
+            show_help: false,
+            inline: false,
+            option_areas: Vec::new(),
+            textarea: TextAreaState::default(),
+            text_area: Rect::default(),
+            editor_requested: false,
+            first_entry: None,
+            revealed: false,
         }
     }
 
@@ -324,7 +409,10 @@ impl WizardState {
                     flat.push(FlatQuestion {
                         path,
                         prompt: question.ask().to_string(),
-                        kind: FlatQuestionKind::Masked,
+                        kind: FlatQuestionKind::Masked {
+                            confirm: masked_q.confirm,
+                            strength_meter: masked_q.strength_meter,
+                        },
                         default_value: None,
                         assumed,
                         has_validation: masked_q.validate.is_some(),
@@ -378,6 +466,32 @@ impl WizardState {
                         has_validation: false,
                     });
                 }
+                QuestionKind::Date(_)
+                | QuestionKind::Time(_)
+                | QuestionKind::DateTime(_)
+                | QuestionKind::Duration(_) => {
+                    let kind = question.kind();
+                    let default_value = match question.default() {
+                        DefaultValue::Suggested(value) => value.temporal_text(),
+                        _ => None,
+                    }
+                    .or_else(|| kind.temporal_default()?.temporal_text());
+                    let has_validation = match kind {
+                        QuestionKind::Date(q) => q.validate.is_some(),
+                        QuestionKind::Time(q) => q.validate.is_some(),
+                        QuestionKind::DateTime(q) => q.validate.is_some(),
+                        QuestionKind::Duration(q) => q.validate.is_some(),
+                        _ => false,
+                    };
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Temporal { kind: kind.clone() },
+                        default_value,
+                        assumed,
+                        has_validation,
+                    });
+                }
                 QuestionKind::List(list_q) => {
                     flat.push(FlatQuestion {
                         path,
@@ -434,16 +548,39 @@ impl WizardState {
                     // This handles the case of enum fields where the #[ask(...)] is on the
                     // struct field but the enum generates a OneOf with an empty prompt.
                     let parent_prompt = question.ask();
-                    if !parent_prompt.is_empty() {
-                        if let Some(first) = nested.first_mut() {
-                            if first.prompt.is_empty() {
-                                first.prompt = parent_prompt.to_string();
-                            }
-                        }
+                    if !parent_prompt.is_empty()
+                        && let Some(first) = nested.first_mut()
+                        && first.prompt.is_empty()
+                    {
+                        first.prompt = parent_prompt.to_string();
                     }
 
                     flat.extend(nested);
                 }
+                QuestionKind::Lazy(lazy) => {
+                    // A variant that would nest too deeply is refused when it is chosen
+                    let Ok(all_of) = lazy.expand() else {
+                        continue;
+                    };
+                    let expanded = Question::new(
+                        question.path().clone(),
+                        question.ask(),
+                        QuestionKind::AllOf(all_of),
+                    );
+                    flat.extend(Self::flatten_questions(&[expanded], prefix));
+                }
+                QuestionKind::Map(map_q) => {
+                    flat.push(FlatQuestion {
+                        path: path.child(MAP_KEYS_KEY),
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Map {
+                            map_q: map_q.clone(),
+                        },
+                        default_value: None,
+                        assumed,
+                        has_validation: map_q.validate_key.is_some(),
+                    });
+                }
             }
         }
 
@@ -454,50 +591,82 @@ impl WizardState {
         self.questions.get(self.current_index)
     }
 
+    /// Whether the current question is answered by picking from options.
+    fn is_selection_question(&self) -> bool {
+        matches!(
+            self.current_question().map(|q| &q.kind),
+            Some(FlatQuestionKind::Confirm { .. })
+                | Some(FlatQuestionKind::Select { .. })
+                | Some(FlatQuestionKind::MultiSelect { .. })
+        )
+    }
+
+    /// Whether the current question takes typed text.
+    fn is_typing(&self) -> bool {
+        !self.complete && !self.is_selection_question()
+    }
+
     fn progress(&self) -> (usize, usize) {
         (self.current_index + 1, self.questions.len())
     }
 
+    /// Whether the current question takes multi-line text.
+    fn is_multiline(&self) -> bool {
+        !self.complete
+            && matches!(
+                self.current_question().map(|q| &q.kind),
+                Some(FlatQuestionKind::Multiline)
+            )
+    }
+
     fn handle_input(&mut self, key: KeyCode) {
         match key {
             KeyCode::Char(c) => {
-                self.input.insert(self.cursor_pos, c);
-                self.cursor_pos += 1;
+                textarea::insert(&mut self.input, &mut self.cursor_pos, c);
                 self.error_message = None;
             }
-            KeyCode::Backspace => {
-                if self.cursor_pos > 0 {
-                    self.cursor_pos -= 1;
-                    self.input.remove(self.cursor_pos);
-                    self.error_message = None;
-                }
-            }
-            KeyCode::Delete => {
-                if self.cursor_pos < self.input.len() {
-                    self.input.remove(self.cursor_pos);
-                    self.error_message = None;
-                }
-            }
-            KeyCode::Left => {
-                if self.cursor_pos > 0 {
-                    self.cursor_pos -= 1;
-                }
-            }
-            KeyCode::Right => {
-                if self.cursor_pos < self.input.len() {
-                    self.cursor_pos += 1;
-                }
-            }
-            KeyCode::Home => {
-                self.cursor_pos = 0;
+            KeyCode::Backspace if textarea::backspace(&mut self.input, &mut self.cursor_pos) => {
+                self.error_message = None;
             }
-            KeyCode::End => {
-                self.cursor_pos = self.input.len();
+            KeyCode::Delete if textarea::delete(&mut self.input, &mut self.cursor_pos) => {
+                self.error_message = None;
             }
+            KeyCode::Left => textarea::left(&self.input, &mut self.cursor_pos),
+            KeyCode::Right => textarea::right(&self.input, &mut self.cursor_pos),
+            KeyCode::Home => textarea::home(&self.input, &mut self.cursor_pos),
+            KeyCode::End => textarea::end(&self.input, &mut self.cursor_pos),
             _ => {}
         }
     }
 
+    /// Insert pasted text into the current answer in one go.
+    pub(crate) fn handle_paste(&mut self, text: &str) {
+        self.show_help = false;
+        if self.is_typing() {
+            let multiline = self.is_multiline();
+            textarea::paste(&mut self.input, &mut self.cursor_pos, text, multiline);
+            self.error_message = None;
+        }
+    }
+
+    /// The current answer, if the user asked to edit it in an external editor.
+    pub(crate) fn take_editor_request(&mut self) -> Option<String> {
+        std::mem::take(&mut self.editor_requested).then(|| self.input.clone())
+    }
+
+    /// Replace the current answer with the result of an external edit, or
+    /// show why the editor could not be used.
+    pub(crate) fn finish_external_edit(&mut self, result: io::Result<String>) {
+        match result {
+            Ok(text) => {
+                self.cursor_pos = text.len();
+                self.input = text;
+                self.error_message = None;
+            }
+            Err(err) => self.error_message = Some(format!("Could not run editor: {err}")),
+        }
+    }
+
     fn validate_and_submit(
         &mut self,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
@@ -519,18 +688,32 @@ impl WizardState {
         let old_value = self.responses.remove(&question.path);
 
         match &question.kind {
-            FlatQuestionKind::Input | FlatQuestionKind::Multiline | FlatQuestionKind::Masked => {
-                let rv = ResponseValue::String(value.clone());
+            FlatQuestionKind::Input
+            | FlatQuestionKind::Multiline
+            | FlatQuestionKind::Masked { .. } => {
+                let rv = if let FlatQuestionKind::Masked { .. } = question.kind {
+                    ResponseValue::Secret(SecretValue::new(value.as_str()))
+                } else {
+                    ResponseValue::String(value.clone())
+                };
                 // Run validation if field has it
-                if question.has_validation {
-                    if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                        self.error_message = Some(err);
-                        // Restore old value on validation failure
-                        if let Some(old) = old_value {
-                            self.responses.insert(question.path.clone(), old);
-                        }
-                        return false;
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+                if let FlatQuestionKind::Masked { confirm: true, .. } = question.kind
+                    && !self.confirm_entry(&value)
+                {
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
                     }
+                    return false;
                 }
                 self.responses.insert(question.path.clone(), rv);
             }
@@ -557,15 +740,15 @@ impl WizardState {
                         return false;
                     }
                     let rv = ResponseValue::Int(n);
-                    if question.has_validation {
-                        if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                            self.error_message = Some(err);
-                            // Restore old value on validation failure
-                            if let Some(old) = old_value {
-                                self.responses.insert(question.path.clone(), old);
-                            }
-                            return false;
+                    if question.has_validation
+                        && let Err(err) = validate(&rv, &self.responses, &question.path)
+                    {
+                        self.error_message = Some(err);
+                        // Restore old value on validation failure
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
                         }
+                        return false;
                     }
                     self.responses.insert(question.path.clone(), rv);
                 }
@@ -601,15 +784,15 @@ impl WizardState {
                         return false;
                     }
                     let rv = ResponseValue::Float(n);
-                    if question.has_validation {
-                        if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                            self.error_message = Some(err);
-                            // Restore old value on validation failure
-                            if let Some(old) = old_value {
-                                self.responses.insert(question.path.clone(), old);
-                            }
-                            return false;
+                    if question.has_validation
+                        && let Err(err) = validate(&rv, &self.responses, &question.path)
+                    {
+                        self.error_message = Some(err);
+                        // Restore old value on validation failure
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
                         }
+                        return false;
                     }
                     self.responses.insert(question.path.clone(), rv);
                 }
@@ -622,6 +805,28 @@ impl WizardState {
                     return false;
                 }
             },
+            FlatQuestionKind::Temporal { kind } => {
+                let parsed = kind.parse_temporal(&value).expect("temporal question kind");
+                let result = parsed.and_then(|rv| {
+                    if question.has_validation {
+                        validate(&rv, &self.responses, &question.path)?;
+                    }
+                    Ok(rv)
+                });
+                match result {
+                    Ok(rv) => {
+                        self.responses.insert(question.path.clone(), rv);
+                    }
+                    Err(err) => {
+                        self.error_message = Some(err);
+                        // Restore old value on validation failure
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
+                        }
+                        return false;
+                    }
+                }
+            }
             FlatQuestionKind::Confirm { .. } => {
                 let answer = self.selected_option == 0; // 0 = Yes, 1 = No
                 self.responses
@@ -631,7 +836,7 @@ impl WizardState {
                 // Parse the input as a list (comma or newline separated)
                 let items: Vec<&str> = self
                     .input
-                    .split(|c| c == ',' || c == '\n')
+                    .split([',', '\n'])
                     .map(|s| s.trim())
                     .filter(|s| !s.is_empty())
                     .collect();
@@ -645,29 +850,29 @@ impl WizardState {
                         for item in &items {
                             match item.parse::<i64>() {
                                 Ok(n) => {
-                                    if let Some(min_val) = min {
-                                        if n < *min_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at least {}",
-                                                n, min_val
-                                            ));
-                                            if let Some(old) = old_value {
-                                                self.responses.insert(question.path.clone(), old);
-                                            }
-                                            return false;
+                                    if let Some(min_val) = min
+                                        && n < *min_val
+                                    {
+                                        self.error_message = Some(format!(
+                                            "Value {} must be at least {}",
+                                            n, min_val
+                                        ));
+                                        if let Some(old) = old_value {
+                                            self.responses.insert(question.path.clone(), old);
                                         }
+                                        return false;
                                     }
-                                    if let Some(max_val) = max {
-                                        if n > *max_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at most {}",
-                                                n, max_val
-                                            ));
-                                            if let Some(old) = old_value {
-                                                self.responses.insert(question.path.clone(), old);
-                                            }
-                                            return false;
+                                    if let Some(max_val) = max
+                                        && n > *max_val
+                                    {
+                                        self.error_message = Some(format!(
+                                            "Value {} must be at most {}",
+                                            n, max_val
+                                        ));
+                                        if let Some(old) = old_value {
+                                            self.responses.insert(question.path.clone(), old);
                                         }
+                                        return false;
                                     }
                                     ints.push(n);
                                 }
@@ -688,29 +893,29 @@ impl WizardState {
                         for item in &items {
                             match item.parse::<f64>() {
                                 Ok(n) => {
-                                    if let Some(min_val) = min {
-                                        if n < *min_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at least {}",
-                                                n, min_val
-                                            ));
-                                            if let Some(old) = old_value {
-                                                self.responses.insert(question.path.clone(), old);
-                                            }
-                                            return false;
+                                    if let Some(min_val) = min
+                                        && n < *min_val
+                                    {
+                                        self.error_message = Some(format!(
+                                            "Value {} must be at least {}",
+                                            n, min_val
+                                        ));
+                                        if let Some(old) = old_value {
+                                            self.responses.insert(question.path.clone(), old);
                                         }
+                                        return false;
                                     }
-                                    if let Some(max_val) = max {
-                                        if n > *max_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at most {}",
-                                                n, max_val
-                                            ));
-                                            if let Some(old) = old_value {
-                                                self.responses.insert(question.path.clone(), old);
-                                            }
-                                            return false;
+                                    if let Some(max_val) = max
+                                        && n > *max_val
+                                    {
+                                        self.error_message = Some(format!(
+                                            "Value {} must be at most {}",
+                                            n, max_val
+                                        ));
+                                        if let Some(old) = old_value {
+                                            self.responses.insert(question.path.clone(), old);
                                         }
+                                        return false;
                                     }
                                     floats.push(n);
                                 }
@@ -728,18 +933,31 @@ impl WizardState {
                     }
                 };
 
-                if question.has_validation {
-                    if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                        self.error_message = Some(err);
-                        if let Some(old) = old_value {
-                            self.responses.insert(question.path.clone(), old);
-                        }
-                        return false;
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
                     }
+                    return false;
                 }
                 self.responses.insert(question.path.clone(), rv);
             }
             FlatQuestionKind::Select { variants, .. } => {
+                // Variants of a recursive type that would nest too deeply are refused
+                if let Some(err) = variants
+                    .as_ref()
+                    .and_then(|vars| vars.get(self.selected_option))
+                    .and_then(|variant| variant.kind.nesting_error())
+                {
+                    self.error_message = Some(err);
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+
                 // Get the base path (strip the selected_variant suffix)
                 let base_path = parent_path(&question.path);
 
@@ -818,6 +1036,9 @@ impl WizardState {
                                         QuestionKind::Confirm(cq) => FlatQuestionKind::Confirm {
                                             default: cq.default,
                                         },
+                                        kind if kind.is_temporal() => {
+                                            FlatQuestionKind::Temporal { kind: kind.clone() }
+                                        }
                                         _ => FlatQuestionKind::Input,
                                     },
                                     default_value: None,
@@ -908,11 +1129,145 @@ impl WizardState {
                     }
                 }
             }
+            FlatQuestionKind::Map { map_q } => {
+                let keys: Vec<String> = self
+                    .input
+                    .split([',', '\n'])
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect();
+                for (idx, key) in keys.iter().enumerate() {
+                    let others = keys[..idx].iter().map(String::as_str);
+                    let result = MapQuestion::check_key(key, others).and_then(|()| {
+                        if question.has_validation {
+                            validate(
+                                &ResponseValue::String(key.clone()),
+                                &self.responses,
+                                &question.path,
+                            )?;
+                        }
+                        Ok(())
+                    });
+                    if let Err(err) = result {
+                        self.error_message = Some(err);
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
+                        }
+                        return false;
+                    }
+                }
+
+                // Get base path (strip the keys suffix)
+                let base_path = parent_path(&question.path);
+
+                // Remove the value questions of the previous keys
+                let i = self.current_index + 1;
+                while i < self.questions.len()
+                    && self.questions[i]
+                        .path
+                        .strip_path_prefix(&base_path)
+                        .is_some()
+                {
+                    self.questions.remove(i);
+                }
+
+                // Keep the values of keys that are still there, at their new index
+                let old_keys = match old_value {
+                    Some(ResponseValue::StringList(old_keys)) => old_keys,
+                    _ => Vec::new(),
+                };
+                let mut kept = Vec::new();
+                for (old_idx, key) in old_keys.iter().enumerate() {
+                    let entry_path = base_path.child(&old_idx.to_string());
+                    let entry_paths: Vec<ResponsePath> = self
+                        .responses
+                        .iter()
+                        .map(|(path, _)| path.clone())
+                        .filter(|path| path.strip_path_prefix(&entry_path).is_some())
+                        .collect();
+                    let mut values = Responses::new();
+                    for path in entry_paths {
+                        let value = self.responses.remove(&path).expect("response exists");
+                        let relative = path.strip_path_prefix(&entry_path).expect("entry path");
+                        values.insert(relative, value);
+                    }
+                    kept.push((key.clone(), values));
+                }
+                for (new_idx, key) in keys.iter().enumerate() {
+                    let entry_path = base_path.child(&new_idx.to_string());
+                    if let Some((_, values)) = kept.iter().find(|(old_key, _)| old_key == key) {
+                        for (relative, value) in values.iter() {
+                            self.responses
+                                .insert(entry_path.child(relative.as_str()), value.clone());
+                        }
+                    }
+                }
+
+                // Ask for each entry's value after the keys
+                let value_questions: Vec<Question> = keys
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, key)| map_q.value_question(idx, key))
+                    .collect();
+                let flat = Self::flatten_questions(&value_questions, &base_path);
+                let insert_pos = self.current_index + 1;
+                for (i, q) in flat.into_iter().enumerate() {
+                    self.questions.insert(insert_pos + i, q);
+                }
+
+                self.responses
+                    .insert(question.path.clone(), ResponseValue::StringList(keys));
+            }
+        }
+
+        true
+    }
+
+    /// Check an entry of a masked question with confirmation. The first entry
+    /// is kept and the field cleared for the second; returns `true` once both
+    /// entries match. On a mismatch the user starts over.
+    fn confirm_entry(&mut self, value: &str) -> bool {
+        let confirmed = match self.first_entry.take() {
+            None => {
+                self.first_entry = Some(value.to_string());
+                false
+            }
+            Some(first) if first != value => {
+                self.error_message = Some(CONFIRMATION_MISMATCH.to_string());
+                false
+            }
+            Some(_) => true,
+        };
+        if !confirmed {
+            self.input.clear();
+            self.cursor_pos = 0;
         }
+        confirmed
+    }
 
+    /// Leave the second entry of a masked question and edit the first again.
+    /// Returns `false` if no confirmation was in progress.
+    fn cancel_confirmation(&mut self) -> bool {
+        let Some(first) = self.first_entry.take() else {
+            return false;
+        };
+        self.cursor_pos = first.len();
+        self.input = first;
+        self.error_message = None;
         true
     }
 
+    /// The prompt of a question, or the confirmation prompt while a masked
+    /// answer is being repeated.
+    fn prompt_text(&self, question: &FlatQuestion) -> String {
+        if self.first_entry.is_some() {
+            CONFIRMATION_PROMPT.to_string()
+        } else {
+            question.prompt.clone()
+        }
+    }
+
     fn next_question(
         &mut self,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
@@ -921,6 +1276,8 @@ impl WizardState {
             self.current_index += 1;
             self.input.clear();
             self.cursor_pos = 0;
+            self.textarea = TextAreaState::default();
+            self.revealed = false;
             self.selected_option = 0;
             self.multi_selected.clear();
             self.error_message = None;
@@ -985,6 +1342,10 @@ impl WizardState {
                                             self.input = s;
                                             self.cursor_pos = self.input.len();
                                         }
+                                        ResponseValue::Secret(s) => {
+                                            self.input = s.expose_secret().to_string();
+                                            self.cursor_pos = self.input.len();
+                                        }
                                         ResponseValue::Int(n) => {
                                             self.input = n.to_string();
                                             self.cursor_pos = self.input.len();
@@ -993,6 +1354,18 @@ impl WizardState {
                                             self.input = n.to_string();
                                             self.cursor_pos = self.input.len();
                                         }
+                                        ResponseValue::Date(_)
+                                        | ResponseValue::Time(_)
+                                        | ResponseValue::DateTime(_)
+                                        | ResponseValue::Duration(_) => {
+                                            self.input =
+                                                response.temporal_text().unwrap_or_default();
+                                            self.cursor_pos = self.input.len();
+                                        }
+                                        ResponseValue::StringList(list) => {
+                                            self.input = list.join(", ");
+                                            self.cursor_pos = self.input.len();
+                                        }
                                         _ => {
                                             if let Some(def) = &q.default_value {
                                                 self.input = def.clone();
@@ -1022,90 +1395,453 @@ impl WizardState {
             self.current_index -= 1;
             self.input.clear();
             self.cursor_pos = 0;
+            self.textarea = TextAreaState::default();
+            self.first_entry = None;
+            self.revealed = false;
             self.multi_selected.clear();
             self.error_message = None;
 
             // Restore previous response as input
-            if let Some(q) = self.current_question() {
-                if let Some(prev_response) = self.responses.get(&q.path) {
-                    match prev_response {
-                        ResponseValue::String(s) => {
-                            self.input = s.clone();
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::Int(n) => {
-                            self.input = n.to_string();
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::Float(n) => {
-                            self.input = n.to_string();
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::Bool(b) => {
-                            self.selected_option = if *b { 0 } else { 1 };
-                        }
-                        ResponseValue::ChosenVariant(idx) => {
-                            self.selected_option = *idx;
-                        }
-                        ResponseValue::ChosenVariants(indices) => {
-                            if let FlatQuestionKind::MultiSelect { options, .. } = &q.kind {
-                                self.multi_selected = vec![false; options.len()];
-                                for &idx in indices {
-                                    if idx < self.multi_selected.len() {
-                                        self.multi_selected[idx] = true;
-                                    }
+            if let Some(q) = self.current_question()
+                && let Some(prev_response) = self.responses.get(&q.path)
+            {
+                match prev_response {
+                    ResponseValue::String(s) => {
+                        self.input = s.clone();
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::Secret(s) => {
+                        self.input = s.expose_secret().to_string();
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::Int(n) => {
+                        self.input = n.to_string();
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::Float(n) => {
+                        self.input = n.to_string();
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::Date(_)
+                    | ResponseValue::Time(_)
+                    | ResponseValue::DateTime(_)
+                    | ResponseValue::Duration(_) => {
+                        self.input = prev_response.temporal_text().unwrap_or_default();
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::Bool(b) => {
+                        self.selected_option = if *b { 0 } else { 1 };
+                    }
+                    ResponseValue::ChosenVariant(idx) => {
+                        self.selected_option = *idx;
+                    }
+                    ResponseValue::ChosenVariants(indices) => {
+                        if let FlatQuestionKind::MultiSelect { options, .. } = &q.kind {
+                            self.multi_selected = vec![false; options.len()];
+                            for &idx in indices {
+                                if idx < self.multi_selected.len() {
+                                    self.multi_selected[idx] = true;
                                 }
                             }
                         }
-                        ResponseValue::StringList(list) => {
-                            self.input = list.join(", ");
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::IntList(list) => {
-                            self.input = list
-                                .iter()
-                                .map(|n| n.to_string())
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::FloatList(list) => {
-                            self.input = list
-                                .iter()
-                                .map(|n| n.to_string())
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            self.cursor_pos = self.input.len();
-                        }
+                    }
+                    ResponseValue::StringList(list) => {
+                        self.input = list.join(", ");
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::IntList(list) => {
+                        self.input = list
+                            .iter()
+                            .map(|n| n.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::FloatList(list) => {
+                        self.input = list
+                            .iter()
+                            .map(|n| n.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        self.cursor_pos = self.input.len();
                     }
                 }
             }
         }
     }
-}
-
-fn draw_ui(frame: &mut Frame, state: &WizardState) {
-    let area = frame.area();
 
-    // Main layout
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Length(2), // Progress bar
-            Constraint::Min(10),   // Content
-            Constraint::Length(3), // Help
-        ])
-        .split(area);
+    /// One line per answered question, left in the scrollback by the inline viewport.
+    pub(crate) fn transcript(&self) -> Vec<Line<'static>> {
+        self.questions
+            .iter()
+            .filter(|q| q.assumed.is_none())
+            .filter_map(|q| {
+                let answer = format_answer(q, self.responses.get(&q.path)?);
+                let prompt = if q.prompt.is_empty() {
+                    q.path.as_str().to_string()
+                } else {
+                    q.prompt.clone()
+                };
+                Some(Line::from(vec![
+                    Span::styled("✔ ", Style::default().fg(self.theme.success)),
+                    Span::styled(prompt, Style::default().fg(self.theme.text)),
+                    Span::styled(" · ", Style::default().fg(self.theme.border)),
+                    Span::styled(answer, Style::default().fg(self.theme.primary)),
+                ]))
+            })
+            .collect()
+    }
 
-    // Header
-    let header = Paragraph::new(state.title.clone())
-        .style(Style::default().fg(state.theme.primary).bold())
-        .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::BOTTOM)
+    /// Skip leading questions with assumed answers and prime the first one that is asked.
+    pub(crate) fn start(&mut self) {
+        while self.current_index < self.questions.len() {
+            if let Some(assumed) = &self.questions[self.current_index].assumed {
+                self.responses.insert(
+                    self.questions[self.current_index].path.clone(),
+                    assumed.clone(),
+                );
+                self.current_index += 1;
+            } else {
+                // Initialize first question's defaults
+                // Extract values first to avoid borrow issues
+                let init_data = self.current_question().map(|q| match &q.kind {
+                    FlatQuestionKind::Confirm { default } => {
+                        (Some(if *default { 0 } else { 1 }), None, None)
+                    }
+                    FlatQuestionKind::Select { default_idx, .. } => {
+                        (Some(*default_idx), None, None)
+                    }
+                    FlatQuestionKind::MultiSelect {
+                        options, defaults, ..
+                    } => {
+                        let mut selected = vec![false; options.len()];
+                        for &idx in defaults {
+                            if idx < selected.len() {
+                                selected[idx] = true;
+                            }
+                        }
+                        (None, Some(selected), None)
+                    }
+                    _ => (None, None, q.default_value.clone()),
+                });
+
+                if let Some((selected_opt, multi_sel, default_val)) = init_data {
+                    if let Some(sel) = selected_opt {
+                        self.selected_option = sel;
+                    }
+                    if let Some(multi) = multi_sel {
+                        self.multi_selected = multi;
+                    }
+                    if let Some(def) = default_val {
+                        self.input = def;
+                        self.cursor_pos = self.input.len();
+                    }
+                }
+                break;
+            }
+        }
+
+        if self.current_index >= self.questions.len() {
+            self.complete = true;
+        }
+    }
+
+    /// Highlight the previous option of a selection question.
+    fn option_up(&mut self) {
+        if self.is_selection_question() && self.selected_option > 0 {
+            self.selected_option -= 1;
+        }
+    }
+
+    /// Highlight the next option of a selection question.
+    fn option_down(&mut self) {
+        let count = match self.current_question().map(|q| &q.kind) {
+            Some(FlatQuestionKind::Confirm { .. }) => 2,
+            Some(FlatQuestionKind::Select { options, .. })
+            | Some(FlatQuestionKind::MultiSelect { options, .. }) => options.len(),
+            _ => return,
+        };
+        if self.selected_option + 1 < count {
+            self.selected_option += 1;
+        }
+    }
+
+    /// The current question, if it asks for a date.
+    fn date_question(&self) -> Option<&DateQuestion> {
+        match self.current_question().map(|q| &q.kind) {
+            Some(FlatQuestionKind::Temporal {
+                kind: QuestionKind::Date(date_q),
+            }) => Some(date_q),
+            _ => None,
+        }
+    }
+
+    /// The date shown in the picker: the typed date, else the default or
+    /// today, kept within the bounds.
+    fn picked_date(&self) -> Option<Date> {
+        let date_q = self.date_question()?;
+        let typed = match (self.input.is_empty(), self.current_question()) {
+            (true, Some(q)) => q.default_value.as_deref().unwrap_or_default(),
+            _ => self.input.as_str(),
+        };
+        let date = typed.trim().parse().unwrap_or_else(|_| Date::today());
+        Some(clamp_date(date, date_q))
+    }
+
+    /// Move the date picked for a date question, replacing the typed text.
+    fn shift_date(&mut self, shift: impl FnOnce(Date) -> Option<Date>) {
+        let (Some(date), Some(date_q)) = (self.picked_date(), self.date_question()) else {
+            return;
+        };
+        let Some(date) = shift(date) else {
+            return;
+        };
+        self.input = clamp_date(date, date_q).to_string();
+        self.cursor_pos = self.input.len();
+        self.error_message = None;
+    }
+
+    /// Toggle the highlighted option of a multi-select question.
+    fn toggle_option(&mut self) {
+        if let Some(FlatQuestionKind::MultiSelect { options, .. }) =
+            self.current_question().map(|q| &q.kind)
+        {
+            // Ensure multi_selected is properly sized
+            if self.multi_selected.len() != options.len() {
+                self.multi_selected = vec![false; options.len()];
+            }
+            if self.selected_option < self.multi_selected.len() {
+                self.multi_selected[self.selected_option] =
+                    !self.multi_selected[self.selected_option];
+            }
+        }
+    }
+
+    /// Handle a single key event.
+    ///
+    /// Returns `true` once the wizard has finished, either by completion or cancellation.
+    pub(crate) fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return false;
+        }
+
+        // Any key closes the help overlay without acting on it
+        if self.show_help {
+            self.show_help = false;
+            return false;
+        }
+
+        let action = self.keymap.action_for(&key, self.is_typing());
+
+        if self.complete {
+            return matches!(action, Some(Action::Next | Action::Cancel));
+        }
+
+        match action {
+            Some(Action::Cancel) => {
+                self.cancelled = true;
+                return true;
+            }
+            Some(Action::Next) => {
+                self.next_question(validate);
+            }
+            Some(Action::Back) => {
+                if !self.cancel_confirmation() && self.current_index > 0 {
+                    self.prev_question();
+                }
+            }
+            Some(Action::Help) => {
+                self.show_help = true;
+            }
+            Some(Action::WordLeft) if self.is_typing() => {
+                textarea::word_left(&self.input, &mut self.cursor_pos);
+            }
+            Some(Action::WordRight) if self.is_typing() => {
+                textarea::word_right(&self.input, &mut self.cursor_pos);
+            }
+            Some(Action::MoveUp) if self.is_multiline() => {
+                textarea::up(&self.input, &mut self.cursor_pos);
+            }
+            Some(Action::MoveDown) if self.is_multiline() => {
+                textarea::down(&self.input, &mut self.cursor_pos);
+            }
+            Some(Action::MoveUp) if self.date_question().is_some() => {
+                self.shift_date(|date| date.checked_add_days(-1));
+            }
+            Some(Action::MoveDown) if self.date_question().is_some() => {
+                self.shift_date(|date| date.checked_add_days(1));
+            }
+            Some(Action::PreviousMonth) => self.shift_date(|date| date.checked_add_months(-1)),
+            Some(Action::NextMonth) => self.shift_date(|date| date.checked_add_months(1)),
+            Some(Action::MoveUp) => self.option_up(),
+            Some(Action::MoveDown) => self.option_down(),
+            Some(Action::NewLine) => {
+                if self.is_multiline() {
+                    self.handle_input(KeyCode::Char('\n'));
+                }
+            }
+            Some(Action::ExternalEditor) => self.editor_requested = self.is_multiline(),
+            Some(Action::Reveal) => {
+                self.revealed = !self.revealed
+                    && matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Masked { .. })
+                    );
+            }
+            Some(Action::Toggle)
+                if matches!(
+                    self.current_question().map(|q| &q.kind),
+                    Some(FlatQuestionKind::MultiSelect { .. })
+                ) =>
+            {
+                self.toggle_option();
+            }
+            _ => match key.code {
+                KeyCode::Backspace => {
+                    // For text input questions, backspace deletes or goes back when empty
+                    // For Select/MultiSelect/Confirm, backspace does nothing
+                    if !self.is_selection_question() {
+                        if self.input.is_empty()
+                            && (self.first_entry.is_some() || self.current_index > 0)
+                        {
+                            // For text input, backspace goes back only when empty:
+                            // to the first entry of a masked answer, or to the
+                            // previous question
+                            if !self.cancel_confirmation() {
+                                self.prev_question();
+                            }
+                        } else {
+                            // Otherwise, handle as normal backspace in text
+                            self.handle_input(key.code);
+                        }
+                    }
+                }
+                _ => {
+                    if !self.is_selection_question() {
+                        self.handle_input(key.code);
+                    }
+                }
+            },
+        }
+
+        false
+    }
+
+    /// Handle a single mouse event, using the option positions of the last draw.
+    ///
+    /// Clicking an option highlights it (and toggles it in a multi-select);
+    /// clicking the already highlighted option of a single choice confirms it.
+    /// The wheel moves the highlight.
+    ///
+    /// Returns `true` once the wizard has finished, either by completion or cancellation.
+    pub(crate) fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> bool {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if self.show_help => {
+                self.show_help = false;
+            }
+            MouseEventKind::Down(MouseButton::Left) if !self.complete => {
+                let position = Position::new(mouse.column, mouse.row);
+                if self.is_multiline() && self.text_area.contains(position) {
+                    self.cursor_pos = textarea::offset_at(
+                        &self.input,
+                        self.text_area.width,
+                        self.textarea.scroll,
+                        (position.y - self.text_area.y) as usize,
+                        (position.x - self.text_area.x) as usize,
+                    );
+                    return false;
+                }
+                let Some(&(idx, _)) = self
+                    .option_areas
+                    .iter()
+                    .find(|(_, area)| area.contains(position))
+                else {
+                    return false;
+                };
+
+                let multi = matches!(
+                    self.current_question().map(|q| &q.kind),
+                    Some(FlatQuestionKind::MultiSelect { .. })
+                );
+                if multi {
+                    self.selected_option = idx;
+                    self.toggle_option();
+                } else if self.selected_option == idx {
+                    self.next_question(validate);
+                } else {
+                    self.selected_option = idx;
+                }
+            }
+            MouseEventKind::ScrollUp if self.is_multiline() => {
+                textarea::up(&self.input, &mut self.cursor_pos);
+            }
+            MouseEventKind::ScrollDown if self.is_multiline() => {
+                textarea::down(&self.input, &mut self.cursor_pos);
+            }
+            MouseEventKind::ScrollUp => self.option_up(),
+            MouseEventKind::ScrollDown => self.option_down(),
+            _ => {}
+        }
+
+        false
+    }
+}
+
+/// Screen rows of the options shown by a list widget, for mouse hit-testing.
+fn list_option_areas(area: Rect, state: &ListState, count: usize) -> Vec<(usize, Rect)> {
+    (0..area.height)
+        .map(|row| (state.offset() + row as usize, row))
+        .take_while(|(idx, _)| *idx < count)
+        .map(|(idx, row)| (idx, Rect::new(area.x, area.y + row, area.width, 1)))
+        .collect()
+}
+
+/// Draw the current wizard screen.
+pub(crate) fn draw(frame: &mut Frame, state: &mut WizardState) {
+    state.text_area = Rect::default();
+    state.option_areas = match (state.inline, state.complete) {
+        (true, true) => draw_inline_completion(frame, state),
+        (true, false) => draw_inline(frame, state),
+        (false, true) => draw_completion(frame, state),
+        (false, false) => draw_ui(frame, state),
+    };
+    if state.show_help {
+        draw_help_overlay(frame, &state.keymap, &state.theme);
+    }
+}
+
+/// Draw the fullscreen layout, returning the screen rows of the visible options.
+fn draw_ui(frame: &mut Frame, state: &mut WizardState) -> Vec<(usize, Rect)> {
+    let area = frame.area();
+    let mut option_areas = Vec::new();
+
+    // Main layout
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(2), // Progress bar
+            Constraint::Min(10),   // Content
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    // Header
+    let header = Paragraph::new(state.title.clone())
+        .style(Style::default().fg(state.theme.primary).bold())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::BOTTOM)
                 .border_style(Style::default().fg(state.theme.border)),
         );
     frame.render_widget(header, chunks[0]);
@@ -1155,9 +1891,9 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
         ])
         .split(chunks[2]);
 
-    if let Some(question) = state.current_question() {
+    if let Some(question) = state.current_question().cloned() {
         // Question prompt
-        let prompt = Paragraph::new(question.prompt.clone())
+        let prompt = Paragraph::new(state.prompt_text(&question))
             .style(Style::default().fg(state.theme.text))
             .wrap(Wrap { trim: true })
             .block(
@@ -1171,10 +1907,25 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
 
         // Input area based on question type
         match &question.kind {
+            FlatQuestionKind::Multiline => {
+                let default_hint = question
+                    .default_value
+                    .as_ref()
+                    .map(|d| format!(" [default: {}]", d.lines().next().unwrap_or_default()))
+                    .unwrap_or_default();
+                let input_block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(state.theme.border))
+                    .title(format!(" Text{} ", default_hint))
+                    .title_style(Style::default().fg(state.theme.secondary));
+                let inner = input_block.inner(content_chunks[1]);
+                frame.render_widget(input_block, content_chunks[1]);
+                draw_textarea(frame, state, &question, inner);
+            }
             FlatQuestionKind::Input
-            | FlatQuestionKind::Multiline
             | FlatQuestionKind::Int { .. }
-            | FlatQuestionKind::Float { .. } => {
+            | FlatQuestionKind::Float { .. }
+            | FlatQuestionKind::Temporal { .. } => {
                 let hint = match &question.kind {
                     FlatQuestionKind::Int { min, max } => {
                         let mut hints = vec![];
@@ -1204,6 +1955,19 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                             format!(" ({})", hints.join(", "))
                         }
                     }
+                    FlatQuestionKind::Temporal { kind } => {
+                        let mut hints =
+                            vec![kind.temporal_format().unwrap_or_default().to_string()];
+                        if let Some((min, max)) = kind.temporal_bounds() {
+                            if let Some(m) = min {
+                                hints.push(format!("min: {}", m));
+                            }
+                            if let Some(m) = max {
+                                hints.push(format!("max: {}", m));
+                            }
+                        }
+                        format!(" ({})", hints.join(", "))
+                    }
                     _ => "".to_string(),
                 };
 
@@ -1231,30 +1995,66 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                     state.input.clone()
                 };
 
+                // Date questions show a calendar of the picked month below the input
+                let picked_date = state.picked_date();
+                let input_area = if let Some(date) = picked_date {
+                    let [input_area, calendar_area] =
+                        Layout::vertical([Constraint::Length(3), Constraint::Min(0)])
+                            .areas(content_chunks[1]);
+                    let calendar = Paragraph::new(calendar_lines(date, &state.theme));
+                    frame.render_widget(calendar, calendar_area.inner(Margin::new(1, 0)));
+                    input_area
+                } else {
+                    content_chunks[1]
+                };
+
                 let input_widget = Paragraph::new(display_text)
                     .style(Style::default().fg(state.theme.text))
                     .block(input_block);
-                frame.render_widget(input_widget, content_chunks[1]);
+                frame.render_widget(input_widget, input_area);
 
                 // Show cursor
-                let cursor_x = content_chunks[1].x + 1 + state.cursor_pos as u16;
-                let cursor_y = content_chunks[1].y + 1;
+                let column = textarea::display_width(&state.input[..state.cursor_pos], false);
+                let cursor_x = input_area.x + 1 + column as u16;
+                let cursor_y = input_area.y + 1;
                 frame.set_cursor_position((cursor_x, cursor_y));
             }
-            FlatQuestionKind::Masked => {
-                let masked_input = "●".repeat(state.input.len());
+            FlatQuestionKind::Masked { strength_meter, .. } => {
+                let [input_area, meter_area, _] = Layout::vertical([
+                    Constraint::Length(3),
+                    Constraint::Length(1),
+                    Constraint::Min(0),
+                ])
+                .areas(content_chunks[1]);
+
+                let masked = !state.revealed;
+                let text = if masked {
+                    textarea::mask(&state.input)
+                } else {
+                    state.input.clone()
+                };
                 let input_block = Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(state.theme.border))
                     .title(" Password ")
                     .title_style(Style::default().fg(state.theme.secondary));
-                let input_widget = Paragraph::new(masked_input)
+                let input_widget = Paragraph::new(text)
                     .style(Style::default().fg(state.theme.text))
                     .block(input_block);
-                frame.render_widget(input_widget, content_chunks[1]);
+                frame.render_widget(input_widget, input_area);
 
-                let cursor_x = content_chunks[1].x + 1 + state.input.len() as u16;
-                let cursor_y = content_chunks[1].y + 1;
+                if *strength_meter && state.first_entry.is_none() && !state.input.is_empty() {
+                    let meter = strength_meter_line(
+                        &state.input,
+                        meter_area.width.saturating_sub(2),
+                        &state.theme,
+                    );
+                    frame.render_widget(Paragraph::new(meter), meter_area.inner(Margin::new(1, 0)));
+                }
+
+                let column = textarea::display_width(&state.input[..state.cursor_pos], masked);
+                let cursor_x = input_area.x + 1 + column as u16;
+                let cursor_y = input_area.y + 1;
                 frame.set_cursor_position((cursor_x, cursor_y));
             }
             FlatQuestionKind::Confirm { .. } => {
@@ -1284,18 +2084,26 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 let mut list_state = ListState::default();
                 list_state.select(Some(state.selected_option));
                 frame.render_stateful_widget(list, content_chunks[1], &mut list_state);
+                option_areas =
+                    list_option_areas(content_chunks[1].inner(Margin::new(1, 1)), &list_state, 2);
             }
-            FlatQuestionKind::List { element_kind } => {
-                let type_hint = match element_kind {
-                    ListElementKind::String => "strings",
-                    ListElementKind::Int { .. } => "integers",
-                    ListElementKind::Float { .. } => "numbers",
+            FlatQuestionKind::List { .. } | FlatQuestionKind::Map { .. } => {
+                let title = match &question.kind {
+                    FlatQuestionKind::List { element_kind } => {
+                        let type_hint = match element_kind {
+                            ListElementKind::String => "strings",
+                            ListElementKind::Int { .. } => "integers",
+                            ListElementKind::Float { .. } => "numbers",
+                        };
+                        format!(" List of {} (comma-separated) ", type_hint)
+                    }
+                    _ => " Keys (comma-separated) ".to_string(),
                 };
 
                 let input_block = Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(state.theme.border))
-                    .title(format!(" List of {} (comma-separated) ", type_hint))
+                    .title(title)
                     .title_style(Style::default().fg(state.theme.secondary));
 
                 let input_widget = Paragraph::new(state.input.clone())
@@ -1304,7 +2112,8 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 frame.render_widget(input_widget, content_chunks[1]);
 
                 // Show cursor
-                let cursor_x = content_chunks[1].x + 1 + state.cursor_pos as u16;
+                let column = textarea::display_width(&state.input[..state.cursor_pos], false);
+                let cursor_x = content_chunks[1].x + 1 + column as u16;
                 let cursor_y = content_chunks[1].y + 1;
                 frame.set_cursor_position((cursor_x, cursor_y));
             }
@@ -1335,6 +2144,11 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 let mut list_state = ListState::default();
                 list_state.select(Some(state.selected_option));
                 frame.render_stateful_widget(list, content_chunks[1], &mut list_state);
+                option_areas = list_option_areas(
+                    content_chunks[1].inner(Margin::new(1, 1)),
+                    &list_state,
+                    options.len(),
+                );
             }
             FlatQuestionKind::MultiSelect { options, .. } => {
                 let items: Vec<ListItem> = options
@@ -1368,6 +2182,11 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 let mut list_state = ListState::default();
                 list_state.select(Some(state.selected_option));
                 frame.render_stateful_widget(list, content_chunks[1], &mut list_state);
+                option_areas = list_option_areas(
+                    content_chunks[1].inner(Margin::new(1, 1)),
+                    &list_state,
+                    options.len(),
+                );
             }
         }
 
@@ -1381,18 +2200,7 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
     }
 
     // Help bar
-    let help_text = match state.current_question().map(|q| &q.kind) {
-        Some(FlatQuestionKind::Confirm { .. }) | Some(FlatQuestionKind::Select { .. }) => {
-            "↑/↓: Select  Enter: Confirm  Ctrl+←: Back  Esc: Cancel"
-        }
-        Some(FlatQuestionKind::MultiSelect { .. }) => {
-            "↑/↓: Navigate  Space: Toggle  Enter: Confirm  Ctrl+←: Back  Esc: Cancel"
-        }
-        Some(FlatQuestionKind::List { .. }) => {
-            "Enter values separated by commas  Enter: Submit  Ctrl+←: Back  Esc: Cancel"
-        }
-        _ => "Enter: Submit  Ctrl+←: Back  Esc: Cancel",
-    };
+    let help_text = status_hints(&state.keymap, state.current_question().map(|q| &q.kind));
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(state.theme.border))
         .alignment(Alignment::Center)
@@ -1402,9 +2210,394 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 .border_style(Style::default().fg(state.theme.border)),
         );
     frame.render_widget(help, chunks[3]);
+
+    option_areas
+}
+
+/// Draw the multi-line answer, or the dimmed default while it is empty.
+fn draw_textarea(frame: &mut Frame, state: &mut WizardState, question: &FlatQuestion, area: Rect) {
+    let style = Style::default().fg(state.theme.text);
+    state.text_area = area;
+    match &question.default_value {
+        Some(default) if state.input.is_empty() => {
+            frame.render_widget(Paragraph::new(default.clone()).style(style).dim(), area);
+            frame.set_cursor_position(area.as_position());
+        }
+        _ => {
+            frame.render_stateful_widget(
+                TextArea::new(&state.input, state.cursor_pos).style(style),
+                area,
+                &mut state.textarea,
+            );
+            if let Some(cursor) = state.textarea.cursor {
+                frame.set_cursor_position(cursor);
+            }
+        }
+    }
+}
+
+/// The strength meter shown under a masked answer: a bar and a label, fitted to `width`.
+fn strength_meter_line(password: &str, width: u16, theme: &Theme) -> Line<'static> {
+    let strength = PasswordStrength::of(password);
+    let color = match strength {
+        PasswordStrength::Weak => theme.error,
+        PasswordStrength::Fair => theme.highlight,
+        PasswordStrength::Good | PasswordStrength::Strong => theme.success,
+    };
+    let bar_width = usize::from(width.saturating_sub(8)).min(20);
+    let filled = (strength.fraction() * bar_width as f32).round() as usize;
+    Line::from(vec![
+        Span::styled("━".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "─".repeat(bar_width - filled),
+            Style::default().fg(theme.border),
+        ),
+        Span::styled(format!(" {}", strength.label()), Style::default().fg(color)),
+    ])
+}
+
+/// The month of the picked date as calendar lines, with the picked day highlighted.
+fn calendar_lines(picked: Date, theme: &Theme) -> Vec<Line<'static>> {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+
+    let mut lines = vec![
+        Line::styled(
+            format!("{} {}", MONTHS[picked.month() as usize - 1], picked.year()),
+            Style::default().fg(theme.secondary).bold(),
+        ),
+        Line::styled("Mo Tu We Th Fr Sa Su", Style::default().fg(theme.border)),
+    ];
+
+    let offset = picked.first_of_month().weekday();
+    let mut week = vec![Span::raw("   ".repeat(offset as usize))];
+    for day in 1..=picked.days_in_month() {
+        let style = if day == picked.day() {
+            Style::default().fg(theme.highlight).bold().reversed()
+        } else {
+            Style::default().fg(theme.text)
+        };
+        week.push(Span::styled(format!("{day:>2}"), style));
+        if (offset + day).is_multiple_of(7) {
+            lines.push(Line::from(std::mem::take(&mut week)));
+        } else {
+            week.push(Span::raw(" "));
+        }
+    }
+    if !week.is_empty() {
+        lines.push(Line::from(week));
+    }
+    lines
+}
+
+/// Clamp a date picked in the calendar to the bounds of its question.
+fn clamp_date(date: Date, date_q: &DateQuestion) -> Date {
+    let date = date_q.min.map_or(date, |min| date.max(min));
+    date_q.max.map_or(date, |max| date.min(max))
+}
+
+/// Key hints for the help bar, depending on the kind of the current question.
+fn status_hints(keymap: &KeyMap, kind: Option<&FlatQuestionKind>) -> String {
+    let navigate = match (keymap.hint(Action::MoveUp), keymap.hint(Action::MoveDown)) {
+        (Some(up), Some(down)) => Some(format!("{up}/{down}")),
+        (up, down) => up.or(down),
+    };
+
+    let mut hints = Vec::new();
+    match kind {
+        Some(FlatQuestionKind::Confirm { .. }) | Some(FlatQuestionKind::Select { .. }) => {
+            hints.push((navigate, "Select"));
+            hints.push((keymap.hint(Action::Next), "Confirm"));
+        }
+        Some(FlatQuestionKind::MultiSelect { .. }) => {
+            hints.push((navigate, "Navigate"));
+            hints.push((keymap.hint(Action::Toggle), "Toggle"));
+            hints.push((keymap.hint(Action::Next), "Confirm"));
+        }
+        Some(FlatQuestionKind::Multiline) => {
+            hints.push((keymap.hint(Action::Next), "Submit"));
+            hints.push((keymap.hint(Action::NewLine), "New line"));
+            hints.push((keymap.hint(Action::ExternalEditor), "Editor"));
+        }
+        Some(FlatQuestionKind::Masked { .. }) => {
+            hints.push((keymap.hint(Action::Next), "Submit"));
+            hints.push((keymap.hint(Action::Reveal), "Show/hide"));
+        }
+        Some(FlatQuestionKind::Temporal {
+            kind: QuestionKind::Date(_),
+        }) => {
+            let month = match (
+                keymap.hint(Action::PreviousMonth),
+                keymap.hint(Action::NextMonth),
+            ) {
+                (Some(prev), Some(next)) => Some(format!("{prev}/{next}")),
+                (prev, next) => prev.or(next),
+            };
+            hints.push((keymap.hint(Action::Next), "Submit"));
+            hints.push((navigate, "Day"));
+            hints.push((month, "Month"));
+        }
+        _ => hints.push((keymap.hint(Action::Next), "Submit")),
+    }
+    hints.push((keymap.hint(Action::Back), "Back"));
+    hints.push((keymap.hint(Action::Cancel), "Cancel"));
+    hints.push((keymap.hint(Action::Help), "Help"));
+
+    let hints = hints
+        .into_iter()
+        .filter_map(|(key, label)| key.map(|key| format!("{key}: {label}")))
+        .collect::<Vec<_>>()
+        .join("  ");
+
+    if matches!(kind, Some(FlatQuestionKind::List { .. })) {
+        format!("Enter values separated by commas  {hints}")
+    } else if matches!(kind, Some(FlatQuestionKind::Map { .. })) {
+        format!("Enter keys separated by commas  {hints}")
+    } else {
+        hints
+    }
 }
 
-fn draw_completion(frame: &mut Frame, state: &WizardState) {
+/// Draw a centered overlay listing all active key bindings.
+fn draw_help_overlay(frame: &mut Frame, keymap: &KeyMap, theme: &Theme) {
+    let area = frame.area();
+    let lines = keymap.help_lines();
+    let key_width = lines
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = lines
+        .iter()
+        .map(|(keys, description)| {
+            ListItem::new(format!("{keys:<key_width$}  {description}"))
+                .style(Style::default().fg(theme.text))
+        })
+        .collect();
+
+    let width = (key_width as u16 + 30).min(area.width);
+    let height = (items.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, popup);
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary))
+            .title(" Key bindings ")
+            .title_style(Style::default().fg(theme.highlight)),
+    );
+    frame.render_widget(list, popup);
+}
+
+/// Compact single-question layout for the inline viewport, returning the
+/// screen areas of the visible options.
+fn draw_inline(frame: &mut Frame, state: &mut WizardState) -> Vec<(usize, Rect)> {
+    let mut option_areas = Vec::new();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Prompt and progress
+            Constraint::Min(1),    // Input or options
+            Constraint::Length(1), // Error message
+            Constraint::Length(1), // Help
+        ])
+        .split(frame.area());
+
+    let Some(question) = state.current_question().cloned() else {
+        return option_areas;
+    };
+
+    let (current, total) = state.progress();
+    let prompt = Line::from(vec![
+        Span::styled("? ", Style::default().fg(state.theme.highlight)),
+        Span::styled(
+            state.prompt_text(&question),
+            Style::default().fg(state.theme.text).bold(),
+        ),
+        Span::styled(
+            format!(" ({current}/{total})"),
+            Style::default().fg(state.theme.border),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(prompt), chunks[0]);
+
+    let content = chunks[1];
+    let option_style = |highlighted: bool| {
+        if highlighted {
+            Style::default().fg(state.theme.highlight).bold()
+        } else {
+            Style::default().fg(state.theme.text)
+        }
+    };
+    match &question.kind {
+        FlatQuestionKind::Confirm { .. } => {
+            let line = Line::from(vec![
+                Span::styled("  Yes", option_style(state.selected_option == 0)),
+                Span::raw("  /  "),
+                Span::styled("No", option_style(state.selected_option == 1)),
+            ]);
+            frame.render_widget(Paragraph::new(line), content);
+            option_areas = vec![
+                (0, Rect::new(content.x, content.y, 5, 1)),
+                (1, Rect::new(content.x + 10, content.y, 2, 1)),
+            ];
+        }
+        FlatQuestionKind::Select { options, .. }
+        | FlatQuestionKind::MultiSelect { options, .. } => {
+            let multi = matches!(question.kind, FlatQuestionKind::MultiSelect { .. });
+            let items: Vec<ListItem> = options
+                .iter()
+                .enumerate()
+                .map(|(i, opt)| {
+                    let text = if multi {
+                        let checked = state.multi_selected.get(i).copied().unwrap_or(false);
+                        format!("{} {}", if checked { "[✓]" } else { "[ ]" }, opt)
+                    } else {
+                        opt.clone()
+                    };
+                    ListItem::new(text).style(option_style(i == state.selected_option))
+                })
+                .collect();
+
+            let list = List::new(items).highlight_symbol("► ");
+            let mut list_state = ListState::default();
+            list_state.select(Some(state.selected_option));
+            frame.render_stateful_widget(list, content, &mut list_state);
+            option_areas = list_option_areas(content, &list_state, options.len());
+        }
+        FlatQuestionKind::Multiline => {
+            frame.render_widget(
+                Paragraph::new("› ").style(Style::default().fg(state.theme.border)),
+                content,
+            );
+            let text_area = Rect {
+                x: content.x + 2,
+                width: content.width.saturating_sub(2),
+                ..content
+            };
+            draw_textarea(frame, state, &question, text_area);
+        }
+        _ => {
+            let masked =
+                matches!(question.kind, FlatQuestionKind::Masked { .. }) && !state.revealed;
+            let text = if masked {
+                Span::raw(textarea::mask(&state.input))
+            } else if state.input.is_empty() && question.default_value.is_some() {
+                Span::raw(question.default_value.clone().unwrap_or_default()).dim()
+            } else {
+                Span::raw(state.input.clone())
+            };
+            let line = Line::from(vec![
+                Span::styled("› ", Style::default().fg(state.theme.border)),
+                text.fg(state.theme.text),
+            ]);
+            frame.render_widget(Paragraph::new(line), content);
+
+            let cursor = textarea::display_width(&state.input[..state.cursor_pos], masked);
+            frame.set_cursor_position((content.x + 2 + cursor as u16, content.y));
+        }
+    }
+
+    if let Some(error) = &state.error_message {
+        let error_widget =
+            Paragraph::new(error.clone()).style(Style::default().fg(state.theme.error).bold());
+        frame.render_widget(error_widget, chunks[2]);
+    } else if let FlatQuestionKind::Masked {
+        strength_meter: true,
+        ..
+    } = question.kind
+        && state.first_entry.is_none()
+        && !state.input.is_empty()
+    {
+        let meter = strength_meter_line(&state.input, chunks[2].width, &state.theme);
+        frame.render_widget(Paragraph::new(meter), chunks[2]);
+    }
+
+    let help = Paragraph::new(status_hints(&state.keymap, Some(&question.kind)))
+        .style(Style::default().fg(state.theme.border));
+    frame.render_widget(help, chunks[3]);
+
+    option_areas
+}
+
+/// Completion message for the inline viewport.
+fn draw_inline_completion(frame: &mut Frame, state: &WizardState) -> Vec<(usize, Rect)> {
+    let text = match &state.epilogue {
+        Some(epilogue) => epilogue.clone(),
+        None => "All questions answered! Press Enter to finish.".to_string(),
+    };
+    let paragraph = Paragraph::new(Line::from(vec![
+        Span::styled("✔ ", Style::default().fg(state.theme.success)),
+        Span::styled(text, Style::default().fg(state.theme.text)),
+    ]))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, frame.area());
+
+    Vec::new()
+}
+
+/// Human-readable form of an answer, for the transcript.
+fn format_answer(question: &FlatQuestion, value: &ResponseValue) -> String {
+    fn join<T: ToString>(items: &[T]) -> String {
+        items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    match (&question.kind, value) {
+        (FlatQuestionKind::Masked { .. }, _) | (_, ResponseValue::Secret(_)) => {
+            "********".to_string()
+        }
+        (FlatQuestionKind::Select { options, .. }, ResponseValue::ChosenVariant(idx)) => {
+            options.get(*idx).cloned().unwrap_or_default()
+        }
+        (FlatQuestionKind::MultiSelect { options, .. }, ResponseValue::ChosenVariants(indices)) => {
+            let chosen: Vec<&str> = indices
+                .iter()
+                .filter_map(|&idx| options.get(idx).map(String::as_str))
+                .collect();
+            chosen.join(", ")
+        }
+        (_, ResponseValue::String(s)) => s.lines().next().unwrap_or_default().to_string(),
+        (_, ResponseValue::Int(n)) => n.to_string(),
+        (_, ResponseValue::Float(n)) => n.to_string(),
+        (_, ResponseValue::Bool(b)) => if *b { "Yes" } else { "No" }.to_string(),
+        (_, ResponseValue::StringList(list)) => list.join(", "),
+        (_, ResponseValue::IntList(list)) => join(list),
+        (_, ResponseValue::FloatList(list)) => join(list),
+        (_, ResponseValue::ChosenVariant(idx)) => idx.to_string(),
+        (_, ResponseValue::ChosenVariants(indices)) => join(indices),
+        (
+            _,
+            temporal @ (ResponseValue::Date(_)
+            | ResponseValue::Time(_)
+            | ResponseValue::DateTime(_)
+            | ResponseValue::Duration(_)),
+        ) => temporal.temporal_text().unwrap_or_default(),
+    }
+}
+
+fn draw_completion(frame: &mut Frame, state: &WizardState) -> Vec<(usize, Rect)> {
     let area = frame.area();
 
     let block = Block::default()
@@ -1437,6 +2630,8 @@ fn draw_completion(frame: &mut Frame, state: &WizardState) {
         .split(inner);
 
     frame.render_widget(paragraph, centered[1]);
+
+    Vec::new()
 }
 
 impl SurveyBackend for RatatuiBackend {
@@ -1449,191 +2644,42 @@ impl SurveyBackend for RatatuiBackend {
     ) -> Result<Responses, Self::Error> {
         let mut terminal = self.setup_terminal()?;
         let mut state = WizardState::new(definition, self.theme.clone(), self.title.clone());
+        state.keymap = self.keymap.clone();
+        state.inline = self.inline_height.is_some();
 
-        // Skip initially assumed questions
-        while state.current_index < state.questions.len() {
-            if let Some(assumed) = &state.questions[state.current_index].assumed {
-                state.responses.insert(
-                    state.questions[state.current_index].path.clone(),
-                    assumed.clone(),
-                );
-                state.current_index += 1;
-            } else {
-                // Initialize first question's defaults
-                // Extract values first to avoid borrow issues
-                let init_data = state.current_question().map(|q| match &q.kind {
-                    FlatQuestionKind::Confirm { default } => {
-                        (Some(if *default { 0 } else { 1 }), None, None)
-                    }
-                    FlatQuestionKind::Select { default_idx, .. } => {
-                        (Some(*default_idx), None, None)
-                    }
-                    FlatQuestionKind::MultiSelect {
-                        options, defaults, ..
-                    } => {
-                        let mut selected = vec![false; options.len()];
-                        for &idx in defaults {
-                            if idx < selected.len() {
-                                selected[idx] = true;
-                            }
-                        }
-                        (None, Some(selected), None)
-                    }
-                    _ => (None, None, q.default_value.clone()),
-                });
+        state.start();
 
-                if let Some((selected_opt, multi_sel, default_val)) = init_data {
-                    if let Some(sel) = selected_opt {
-                        state.selected_option = sel;
-                    }
-                    if let Some(multi) = multi_sel {
-                        state.multi_selected = multi;
-                    }
-                    if let Some(def) = default_val {
-                        state.input = def;
-                        state.cursor_pos = state.input.len();
-                    }
+        loop {
+            terminal.draw(|frame| draw(frame, &mut state))?;
+
+            let finished = match event::read()? {
+                Event::Key(key) => state.handle_key(key, validate),
+                Event::Mouse(mouse) if self.captures_mouse() => state.handle_mouse(mouse, validate),
+                Event::Paste(text) => {
+                    state.handle_paste(&text);
+                    false
                 }
+                _ => false,
+            };
+            if finished {
                 break;
             }
-        }
 
-        if state.current_index >= state.questions.len() {
-            state.complete = true;
+            if let Some(text) = state.take_editor_request() {
+                // Hand the terminal to the editor, then take it back
+                self.restore_terminal(&mut terminal)?;
+                let result = edit_externally(&text);
+                terminal = self.setup_terminal()?;
+                terminal.clear()?;
+                state.finish_external_edit(result);
+            }
         }
 
-        loop {
-            terminal.draw(|frame| {
-                if state.complete {
-                    draw_completion(frame, &state);
-                } else {
-                    draw_ui(frame, &state);
-                }
+        if state.inline && !state.cancelled {
+            let transcript = state.transcript();
+            terminal.insert_before(transcript.len() as u16, |buf| {
+                Paragraph::new(transcript).render(buf.area, buf);
             })?;
-
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
-                }
-
-                if state.complete {
-                    match key.code {
-                        KeyCode::Enter | KeyCode::Esc => break,
-                        _ => {}
-                    }
-                } else {
-                    match key.code {
-                        KeyCode::Esc => {
-                            state.cancelled = true;
-                            break;
-                        }
-                        KeyCode::Enter => {
-                            state.next_question(validate);
-                        }
-                        KeyCode::Up => {
-                            if matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            ) && state.selected_option > 0
-                            {
-                                state.selected_option -= 1;
-                            }
-                        }
-                        KeyCode::Down => {
-                            if let Some(q) = state.current_question() {
-                                match &q.kind {
-                                    FlatQuestionKind::Confirm { .. } => {
-                                        if state.selected_option < 1 {
-                                            state.selected_option += 1;
-                                        }
-                                    }
-                                    FlatQuestionKind::Select { options, .. } => {
-                                        if state.selected_option < options.len() - 1 {
-                                            state.selected_option += 1;
-                                        }
-                                    }
-                                    FlatQuestionKind::MultiSelect { options, .. } => {
-                                        if state.selected_option < options.len() - 1 {
-                                            state.selected_option += 1;
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                        KeyCode::Char(' ') => {
-                            // Space toggles selection in multi-select
-                            if let Some(FlatQuestionKind::MultiSelect { options, .. }) =
-                                state.current_question().map(|q| &q.kind)
-                            {
-                                // Ensure multi_selected is properly sized
-                                if state.multi_selected.len() != options.len() {
-                                    state.multi_selected = vec![false; options.len()];
-                                }
-                                if state.selected_option < state.multi_selected.len() {
-                                    state.multi_selected[state.selected_option] =
-                                        !state.multi_selected[state.selected_option];
-                                }
-                            } else {
-                                // For other question types, treat space as regular input
-                                state.handle_input(key.code);
-                            }
-                        }
-                        KeyCode::Backspace => {
-                            // For text input questions, backspace deletes or goes back when empty
-                            // For Select/MultiSelect/Confirm, backspace does nothing (use Left to go back)
-                            let is_selection_question = matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            );
-
-                            if !is_selection_question {
-                                if state.input.is_empty() && state.current_index > 0 {
-                                    // For text input, backspace goes back only when empty
-                                    state.prev_question();
-                                } else {
-                                    // Otherwise, handle as normal backspace in text
-                                    state.handle_input(key.code);
-                                }
-                            }
-                            // For selection questions, backspace does nothing
-                        }
-                        KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            // Ctrl+Left always goes back to previous question
-                            if state.current_index > 0 {
-                                state.prev_question();
-                            }
-                        }
-                        KeyCode::Left => {
-                            // Left arrow moves cursor in text input, does nothing for selection
-                            let is_selection_question = matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            );
-
-                            if !is_selection_question {
-                                state.handle_input(key.code);
-                            }
-                        }
-                        _ => {
-                            if !matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            ) {
-                                state.handle_input(key.code);
-                            }
-                        }
-                    }
-                }
-            }
         }
 
         self.restore_terminal(&mut terminal)?;
@@ -1649,6 +2695,7 @@ impl SurveyBackend for RatatuiBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::style::Color;
 
     #[test]
     fn backend_creation() {
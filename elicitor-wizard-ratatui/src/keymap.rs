@@ -0,0 +1,333 @@
+//! Configurable key bindings for the wizard.
+
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A named wizard action that can be bound to keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Confirm the current answer and continue to the next question.
+    Next,
+    /// Go back to the previous question.
+    Back,
+    /// Move the cursor to the start of the previous word.
+    WordLeft,
+    /// Move the cursor to the end of the next word.
+    WordRight,
+    /// Highlight the previous option, or pick the previous day in the date picker.
+    MoveUp,
+    /// Highlight the next option, or pick the next day in the date picker.
+    MoveDown,
+    /// Go back one month in the date picker.
+    PreviousMonth,
+    /// Go forward one month in the date picker.
+    NextMonth,
+    /// Toggle the highlighted option in a multi-select.
+    Toggle,
+    /// Start a new line in a multi-line answer.
+    NewLine,
+    /// Edit a multi-line answer in `$VISUAL` or `$EDITOR`.
+    ExternalEditor,
+    /// Show or hide the typed value of a masked answer.
+    Reveal,
+    /// Cancel the survey.
+    Cancel,
+    /// Show or hide the key binding overlay.
+    Help,
+}
+
+impl Action {
+    /// All actions, in the order they are listed in the help overlay.
+    pub const ALL: [Action; 14] = [
+        Action::Next,
+        Action::Back,
+        Action::WordLeft,
+        Action::WordRight,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::PreviousMonth,
+        Action::NextMonth,
+        Action::Toggle,
+        Action::NewLine,
+        Action::ExternalEditor,
+        Action::Reveal,
+        Action::Cancel,
+        Action::Help,
+    ];
+
+    /// Short human-readable description, used in the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Next => "Confirm / next question",
+            Action::Back => "Previous question",
+            Action::WordLeft => "Previous word",
+            Action::WordRight => "Next word",
+            Action::MoveUp => "Previous option / day",
+            Action::MoveDown => "Next option / day",
+            Action::PreviousMonth => "Previous month (date picker)",
+            Action::NextMonth => "Next month (date picker)",
+            Action::Toggle => "Toggle option",
+            Action::NewLine => "New line (multi-line text)",
+            Action::ExternalEditor => "Open in external editor",
+            Action::Reveal => "Show / hide masked text",
+            Action::Cancel => "Cancel",
+            Action::Help => "Help",
+        }
+    }
+}
+
+/// A key combination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Create a binding for a key with modifiers.
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Create a binding for a key with the Control modifier.
+    pub const fn ctrl(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::CONTROL)
+    }
+
+    /// Whether this binding matches a key event.
+    ///
+    /// Shift is ignored for characters and `BackTab`, since terminals
+    /// disagree on whether to report it.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        if self.code != key.code {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(_) | KeyCode::BackTab => {
+                self.modifiers - KeyModifiers::SHIFT == key.modifiers - KeyModifiers::SHIFT
+            }
+            _ => self.modifiers == key.modifiers,
+        }
+    }
+
+    /// Whether this is a plain character that would otherwise be typed into a text field.
+    fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && (self.modifiers - KeyModifiers::SHIFT).is_empty()
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<char> for KeyBinding {
+    fn from(c: char) -> Self {
+        KeyCode::Char(c).into()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) && !matches!(self.code, KeyCode::Char(_)) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Mapping from keys to wizard actions.
+///
+/// Start from the defaults and adjust with the builder methods:
+///
+/// ```
+/// use elicitor_wizard_ratatui::{Action, KeyMap};
+/// use crossterm::event::KeyCode;
+///
+/// let keymap = KeyMap::default()
+///     .with_vim_navigation()
+///     .bind(Action::Back, KeyCode::F(2));
+/// assert!(keymap.keys_for(Action::MoveDown).any(|k| k == 'j'.into()));
+/// ```
+///
+/// Plain character bindings (such as `j` or `?`) are ignored while answering
+/// a text question, so they never swallow typed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::empty()
+            .bind(Action::Next, KeyCode::Enter)
+            .bind(
+                Action::Back,
+                KeyBinding::new(KeyCode::Left, KeyModifiers::ALT),
+            )
+            .bind(Action::WordLeft, KeyBinding::ctrl(KeyCode::Left))
+            .bind(Action::WordRight, KeyBinding::ctrl(KeyCode::Right))
+            .bind(Action::MoveUp, KeyCode::Up)
+            .bind(Action::MoveDown, KeyCode::Down)
+            .bind(Action::PreviousMonth, KeyCode::PageUp)
+            .bind(Action::NextMonth, KeyCode::PageDown)
+            .bind(Action::Toggle, ' ')
+            .bind(
+                Action::NewLine,
+                KeyBinding::new(KeyCode::Enter, KeyModifiers::ALT),
+            )
+            .bind(Action::NewLine, KeyBinding::ctrl(KeyCode::Char('j')))
+            .bind(Action::ExternalEditor, KeyBinding::ctrl(KeyCode::Char('e')))
+            .bind(Action::Reveal, KeyBinding::ctrl(KeyCode::Char('r')))
+            .bind(Action::Cancel, KeyCode::Esc)
+            .bind(Action::Help, KeyCode::F(1))
+            .bind(Action::Help, '?')
+    }
+}
+
+impl KeyMap {
+    /// Create a key map with no bindings.
+    pub fn empty() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Bind a key to an action, replacing any previous binding of that key.
+    pub fn bind(mut self, action: Action, key: impl Into<KeyBinding>) -> Self {
+        let key = key.into();
+        self.bindings.retain(|(k, _)| *k != key);
+        self.bindings.push((key, action));
+        self
+    }
+
+    /// Remove the binding for a key.
+    pub fn unbind(mut self, key: impl Into<KeyBinding>) -> Self {
+        let key = key.into();
+        self.bindings.retain(|(k, _)| *k != key);
+        self
+    }
+
+    /// Remove all bindings for an action.
+    pub fn clear(mut self, action: Action) -> Self {
+        self.bindings.retain(|(_, a)| *a != action);
+        self
+    }
+
+    /// Add vim-style `j`/`k` for moving down/up and `q` for cancelling.
+    pub fn with_vim_navigation(self) -> Self {
+        self.bind(Action::MoveDown, 'j')
+            .bind(Action::MoveUp, 'k')
+            .bind(Action::Cancel, 'q')
+    }
+
+    /// All keys bound to an action.
+    pub fn keys_for(&self, action: Action) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, a)| *a == action)
+            .map(|(k, _)| *k)
+    }
+
+    /// The action bound to a key event, if any.
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.action_for(key, false)
+    }
+
+    /// The action bound to a key event, skipping plain characters while typing text.
+    pub(crate) fn action_for(&self, key: &KeyEvent, typing: bool) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(k, _)| k.matches(key) && !(typing && k.is_text()))
+            .map(|(_, a)| *a)
+    }
+
+    /// Lines for the help overlay: the keys and description of each bound action.
+    pub(crate) fn help_lines(&self) -> Vec<(String, &'static str)> {
+        Action::ALL
+            .iter()
+            .filter_map(|&action| {
+                let mut keys: Vec<String> = self.keys_for(action).map(|k| k.to_string()).collect();
+                keys.dedup();
+                (!keys.is_empty()).then(|| (keys.join(", "), action.description()))
+            })
+            .collect()
+    }
+
+    /// The first key bound to an action, for the status bar.
+    pub(crate) fn hint(&self, action: Action) -> Option<String> {
+        self.keys_for(action).next().map(|k| k.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn default_bindings() {
+        let keymap = KeyMap::default();
+        assert_eq!(
+            keymap.action(&press(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(Action::Next)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Left, KeyModifiers::ALT)),
+            Some(Action::Back)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Left, KeyModifiers::CONTROL)),
+            Some(Action::WordLeft)
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Left, KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+            Some(Action::Help)
+        );
+    }
+
+    #[test]
+    fn plain_characters_are_ignored_while_typing() {
+        let keymap = KeyMap::default().with_vim_navigation();
+        let k = press(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(&k, false), Some(Action::MoveUp));
+        assert_eq!(keymap.action_for(&k, true), None);
+    }
+
+    #[test]
+    fn rebinding_replaces_key() {
+        let keymap = KeyMap::default().bind(Action::Back, KeyCode::Esc);
+        assert_eq!(
+            keymap.action(&press(KeyCode::Esc, KeyModifiers::NONE)),
+            Some(Action::Back)
+        );
+        assert_eq!(keymap.keys_for(Action::Cancel).count(), 0);
+    }
+}
@@ -10,10 +10,24 @@
 //!
 //! - Rich TUI with panels and borders
 //! - Progress indicator showing current question
-//! - Keyboard navigation (arrow keys, Enter, Esc)
+//! - Keyboard navigation (arrow keys, Enter, Esc), configurable via [`KeyMap`], with an
+//!   F1/`?` overlay listing the active bindings
+//! - Mouse support: click options to pick them, scroll to move the highlight
+//!   (disable with [`RatatuiBackend::with_mouse`] to keep native text selection)
+//! - Soft-wrapping text area for multi-line answers: Alt+Enter or Ctrl+J starts a
+//!   new line, Ctrl+E opens the answer in `$VISUAL` or `$EDITOR`
+//! - Unicode-aware text editing: the cursor moves over whole grapheme clusters and
+//!   accounts for wide characters, Ctrl+Left/Right move by word, and pasted text
+//!   is inserted in one go (bracketed paste)
+//! - Masked answers: Ctrl+R shows or hides the typed text, `#[confirm_entry]` asks
+//!   for the value again on the same step, and `#[strength_meter]` draws a
+//!   strength bar under the field
 //! - Real-time validation with error display
 //! - Customizable color themes
+//! - Optional inline viewport that keeps the scrollback and leaves a transcript of
+//!   the answers (see [`RatatuiBackend::with_inline_viewport`])
 //! - Support for all question types (input, select, multi-select, confirm, etc.)
+//! - Headless test harness behind the `testing` feature (see [`testing`])
 //!
 //! ## Example
 //!
@@ -40,5 +54,10 @@
 //! ```
 
 mod backend;
+mod keymap;
+#[cfg(feature = "testing")]
+pub mod testing;
 
-pub use backend::{RatatuiBackend, RatatuiError, Theme};
+pub use backend::{RatatuiBackend, RatatuiError};
+pub use elicitor_theme::{Color, Theme, ThemeError};
+pub use keymap::{Action, KeyBinding, KeyMap};
@@ -0,0 +1,257 @@
+//! Headless test harness for the ratatui wizard.
+//!
+//! [`WizardHarness`] drives the same state machine and drawing code as
+//! [`RatatuiBackend`], but renders into ratatui's in-memory [`TestBackend`]
+//! instead of a real terminal. Tests feed it a script of key events and then
+//! assert on the collected [`Responses`] or on a textual snapshot of the screen.
+//!
+//! Enable it with the `testing` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! elicitor-wizard-ratatui = { version = "0.6", features = ["testing"] }
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use elicitor::Survey;
+//! use elicitor_wizard_ratatui::{RatatuiBackend, testing::{KeyCode, WizardHarness}};
+//!
+//! let mut harness = WizardHarness::for_survey::<UserProfile>(&RatatuiBackend::new());
+//! harness.type_text("Alice");
+//! harness.press(KeyCode::Enter);
+//! assert!(harness.screen().contains("How old are you?"));
+//! ```
+
+use std::fmt;
+
+use elicitor::{ResponsePath, ResponseValue, Responses, Survey, SurveyDefinition};
+use elicitor_ratatui_support::testing;
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, layout::Position};
+
+use crate::backend::{RatatuiBackend, RatatuiError, WizardState, draw};
+
+pub use crossterm::event::{
+    KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+pub use elicitor_ratatui_support::testing::{buffer_to_string, find_text};
+
+/// Default terminal width used by [`WizardHarness`].
+pub const DEFAULT_WIDTH: u16 = 80;
+
+/// Default terminal height used by [`WizardHarness`].
+pub const DEFAULT_HEIGHT: u16 = 24;
+
+type ValidateFn = Box<dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>>;
+type EditorFn = Box<dyn FnMut(&str) -> String>;
+
+/// Scriptable, headless driver for the ratatui wizard.
+pub struct WizardHarness {
+    terminal: Terminal<TestBackend>,
+    state: WizardState,
+    validate: ValidateFn,
+    finished: bool,
+    editor: Option<EditorFn>,
+}
+
+impl WizardHarness {
+    /// Create a harness for a survey definition and field validator.
+    ///
+    /// Title, theme and key bindings are taken from `backend`. If the backend
+    /// uses an inline viewport, the virtual terminal is the viewport itself:
+    /// [`DEFAULT_WIDTH`] columns by the configured number of rows.
+    pub fn new(
+        backend: &RatatuiBackend,
+        definition: &SurveyDefinition,
+        validate: impl Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String> + 'static,
+    ) -> Self {
+        let mut state = WizardState::new(definition, backend.theme.clone(), backend.title.clone());
+        state.keymap = backend.keymap.clone();
+        state.inline = backend.inline_height.is_some();
+        state.start();
+
+        let height = backend.inline_height.unwrap_or(DEFAULT_HEIGHT);
+        Self {
+            terminal: Terminal::new(TestBackend::new(DEFAULT_WIDTH, height))
+                .expect("TestBackend never fails"),
+            state,
+            validate: Box::new(validate),
+            finished: false,
+            editor: None,
+        }
+    }
+
+    /// Create a harness for a type deriving `Survey`, using its validators.
+    pub fn for_survey<T: Survey + 'static>(backend: &RatatuiBackend) -> Self {
+        Self::new(backend, &T::survey(), T::validate_field)
+    }
+
+    /// Set the size of the virtual terminal.
+    pub fn with_size(mut self, width: u16, height: u16) -> Self {
+        self.terminal =
+            Terminal::new(TestBackend::new(width, height)).expect("TestBackend never fails");
+        self
+    }
+
+    /// Stand in for the external editor.
+    ///
+    /// When the user asks to edit an answer externally, `editor` receives the
+    /// current text and returns the edited text. Without one, such requests
+    /// leave the text unchanged.
+    pub fn with_editor(mut self, editor: impl FnMut(&str) -> String + 'static) -> Self {
+        self.editor = Some(Box::new(editor));
+        self
+    }
+
+    /// Send a single key press.
+    ///
+    /// Keys sent after the wizard has finished are ignored.
+    pub fn press(&mut self, key: impl Into<KeyEvent>) -> &mut Self {
+        if !self.finished {
+            self.finished = self.state.handle_key(key.into(), &*self.validate);
+            self.run_editor();
+        }
+        self
+    }
+
+    fn run_editor(&mut self) {
+        if let Some(text) = self.state.take_editor_request() {
+            let edited = match &mut self.editor {
+                Some(editor) => editor(&text),
+                None => text,
+            };
+            self.state.finish_external_edit(Ok(edited));
+        }
+    }
+
+    /// Send a sequence of key presses.
+    pub fn script<K: Into<KeyEvent>>(&mut self, keys: impl IntoIterator<Item = K>) -> &mut Self {
+        for key in keys {
+            self.press(key);
+        }
+        self
+    }
+
+    /// Type each character of `text` as a separate key press.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.script(text.chars().map(KeyCode::Char))
+    }
+
+    /// Paste text, as a terminal with bracketed paste enabled would.
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        if !self.finished {
+            self.state.handle_paste(text);
+        }
+        self
+    }
+
+    /// Send a mouse event.
+    ///
+    /// The screen is rendered first so the event is hit-tested against the
+    /// current layout, as it would be in a real terminal.
+    pub fn mouse(&mut self, mouse: MouseEvent) -> &mut Self {
+        if !self.finished {
+            self.buffer();
+            self.finished = self.state.handle_mouse(mouse, &*self.validate);
+        }
+        self
+    }
+
+    /// Left-click a screen cell.
+    pub fn click(&mut self, column: u16, row: u16) -> &mut Self {
+        self.mouse(testing::click(column, row))
+    }
+
+    /// Scroll the mouse wheel down by one step.
+    pub fn scroll_down(&mut self) -> &mut Self {
+        self.mouse(testing::scroll_down())
+    }
+
+    /// Scroll the mouse wheel up by one step.
+    pub fn scroll_up(&mut self) -> &mut Self {
+        self.mouse(testing::scroll_up())
+    }
+
+    /// Find the first screen cell where `text` appears, as `(column, row)`.
+    ///
+    /// Handy for clicking on labels without hard-coding coordinates.
+    pub fn find(&mut self, text: &str) -> Option<(u16, u16)> {
+        find_text(self.buffer(), text)
+    }
+
+    /// Render the current screen and return it as a buffer.
+    pub fn buffer(&mut self) -> &Buffer {
+        let state = &mut self.state;
+        self.terminal
+            .draw(|frame| draw(frame, state))
+            .expect("TestBackend never fails");
+        self.terminal.backend().buffer()
+    }
+
+    /// Render the current screen and return where the terminal cursor is left.
+    pub fn cursor(&mut self) -> Position {
+        self.buffer();
+        self.terminal
+            .get_cursor_position()
+            .expect("TestBackend never fails")
+    }
+
+    /// Render the current screen and return it as plain text.
+    ///
+    /// Each terminal row becomes one line with trailing whitespace removed.
+    /// Styling is discarded, which keeps snapshots stable across themes.
+    pub fn screen(&mut self) -> String {
+        buffer_to_string(self.buffer())
+    }
+
+    /// Whether the wizard has finished, either by completion or cancellation.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The transcript an inline viewport leaves in the scrollback on completion.
+    ///
+    /// One plain-text line per answered question, in the order asked.
+    pub fn transcript(&self) -> Vec<String> {
+        self.state
+            .transcript()
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Responses collected so far.
+    pub fn responses(&self) -> &Responses {
+        &self.state.responses
+    }
+
+    /// Consume the harness and return what `collect` would have returned.
+    ///
+    /// Returns [`RatatuiError::Terminal`] if the script ended before the wizard finished.
+    pub fn finish(self) -> Result<Responses, RatatuiError> {
+        if !self.finished {
+            return Err(RatatuiError::Terminal(
+                "script ended before the wizard finished".to_string(),
+            ));
+        }
+        if self.state.cancelled {
+            return Err(RatatuiError::Cancelled);
+        }
+        Ok(self.state.responses)
+    }
+}
+
+impl fmt::Debug for WizardHarness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WizardHarness")
+            .field("finished", &self.finished)
+            .field("responses", &self.state.responses)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,435 @@
+//! Headless tests driving the wizard through the `testing` harness.
+
+use std::time::Duration;
+
+use elicitor::{Date, ResponsePath, Survey, Time};
+use elicitor_wizard_ratatui::{
+    Action, KeyMap, RatatuiBackend, RatatuiError,
+    testing::{KeyCode, KeyEvent, KeyModifiers, WizardHarness},
+};
+use example_surveys::{
+    BlogPost, Deployment, DeveloperProfile, Filter, Login, Meeting, SavedSearch, UserProfile,
+};
+
+fn harness() -> WizardHarness {
+    WizardHarness::for_survey::<UserProfile>(&RatatuiBackend::new().with_title("Profile"))
+}
+
+#[test]
+fn completes_survey_from_script() {
+    let mut h = harness();
+    h.type_text("Alice").press(KeyCode::Enter);
+    h.type_text("30").press(KeyCode::Enter);
+    h.type_text("alice@example.com").press(KeyCode::Enter);
+    h.type_text("Hello").press(KeyCode::Enter);
+    // Confirm defaults to "Yes"; move to "No".
+    h.press(KeyCode::Down).press(KeyCode::Enter);
+
+    assert!(h.screen().contains("All questions answered!"));
+    h.press(KeyCode::Enter);
+    assert!(h.is_finished());
+
+    let responses = h.finish().unwrap();
+    let profile = UserProfile::from_responses(&responses);
+    assert_eq!(profile.name, "Alice");
+    assert_eq!(profile.age, 30);
+    assert_eq!(profile.email, "alice@example.com");
+    assert_eq!(profile.bio, "Hello");
+    assert!(!profile.newsletter);
+}
+
+#[test]
+fn screen_shows_current_question_and_input() {
+    let mut h = harness();
+    let screen = h.screen();
+    assert!(screen.contains("Profile"));
+    assert!(screen.contains("What is your name?"));
+
+    h.type_text("Bob");
+    assert!(h.screen().contains("Bob"));
+
+    h.press(KeyCode::Enter);
+    let screen = h.screen();
+    assert!(screen.contains("How old are you?"));
+    assert!(!screen.contains("What is your name?"));
+}
+
+#[test]
+fn out_of_range_value_shows_error() {
+    let mut h = harness();
+    h.type_text("Alice").press(KeyCode::Enter);
+    h.type_text("200").press(KeyCode::Enter);
+
+    let screen = h.screen();
+    assert!(screen.contains("How old are you?"));
+    assert!(screen.contains("150"));
+    assert!(h.responses().get(&ResponsePath::new("age")).is_none());
+}
+
+#[test]
+fn escape_cancels() {
+    let mut h = harness();
+    h.type_text("Alice").press(KeyCode::Esc);
+    assert!(h.is_finished());
+    assert!(matches!(h.finish(), Err(RatatuiError::Cancelled)));
+}
+
+#[test]
+fn unfinished_script_is_an_error() {
+    let mut h = harness();
+    h.type_text("Alice");
+    assert!(!h.is_finished());
+    assert!(matches!(h.finish(), Err(RatatuiError::Terminal(_))));
+}
+
+#[test]
+fn custom_terminal_size() {
+    let mut h = harness().with_size(60, 20);
+    let screen = h.screen();
+    assert_eq!(screen.split('\n').count(), 20);
+    assert!(screen.split('\n').all(|line| line.chars().count() <= 60));
+}
+
+#[test]
+fn help_overlay_lists_bindings() {
+    let mut h = harness();
+    h.press(KeyCode::F(1));
+    let screen = h.screen();
+    assert!(screen.contains("Key bindings"));
+    assert!(
+        screen
+            .lines()
+            .any(|line| line.contains("Alt+←") && line.contains("Previous question"))
+    );
+
+    // Any key closes the overlay without acting on it
+    h.press(KeyCode::Esc);
+    assert!(!h.is_finished());
+    assert!(!h.screen().contains("Key bindings"));
+}
+
+#[test]
+fn question_mark_is_typed_into_text_questions() {
+    let mut h = harness();
+    h.type_text("Al?");
+    assert!(!h.screen().contains("Key bindings"));
+    assert!(h.screen().contains("Al?"));
+}
+
+#[test]
+fn custom_keymap() {
+    let backend = RatatuiBackend::new().with_keymap(
+        KeyMap::default()
+            .clear(Action::Back)
+            .bind(Action::Back, KeyCode::PageUp),
+    );
+    let mut h = WizardHarness::for_survey::<UserProfile>(&backend);
+    h.type_text("Alice").press(KeyCode::Enter);
+    assert!(h.screen().contains("PgUp: Back"));
+
+    h.press(KeyCode::PageUp);
+    assert!(h.screen().contains("What is your name?"));
+}
+
+#[test]
+fn inline_viewport_is_compact() {
+    let backend = RatatuiBackend::new().with_inline_viewport(6);
+    let mut h = WizardHarness::for_survey::<UserProfile>(&backend);
+    let screen = h.screen();
+    assert_eq!(screen.split('\n').count(), 6);
+    assert!(screen.starts_with("? What is your name? (1/5)"));
+
+    h.type_text("Alice");
+    assert!(h.screen().contains("› Alice"));
+}
+
+#[test]
+fn inline_transcript_lists_answers() {
+    let backend = RatatuiBackend::new().with_inline_viewport(6);
+    let mut h = WizardHarness::for_survey::<UserProfile>(&backend);
+    h.type_text("Alice").press(KeyCode::Enter);
+    h.type_text("30").press(KeyCode::Enter);
+    h.type_text("alice@example.com").press(KeyCode::Enter);
+    h.type_text("Hello").press(KeyCode::Enter);
+    h.press(KeyCode::Up).press(KeyCode::Enter);
+
+    assert!(h.screen().contains("All questions answered!"));
+    assert_eq!(
+        h.transcript(),
+        [
+            "✔ What is your name? · Alice",
+            "✔ How old are you? · 30",
+            "✔ What is your email? · alice@example.com",
+            "✔ Tell us about yourself · Hello",
+            "✔ Would you like to receive our newsletter? · Yes",
+        ]
+    );
+}
+
+#[test]
+fn clicking_options_selects_and_confirms() {
+    let mut h = harness();
+    h.type_text("Alice").press(KeyCode::Enter);
+    h.type_text("30").press(KeyCode::Enter);
+    h.type_text("alice@example.com").press(KeyCode::Enter);
+    h.type_text("Hello").press(KeyCode::Enter);
+
+    // First click highlights "Yes", second click confirms it
+    let (column, row) = h.find("Yes").unwrap();
+    h.click(column, row);
+    assert!(
+        h.screen()
+            .contains("Would you like to receive our newsletter?")
+    );
+    h.click(column, row);
+    assert!(h.screen().contains("All questions answered!"));
+
+    h.press(KeyCode::Enter);
+    assert!(UserProfile::from_responses(&h.finish().unwrap()).newsletter);
+}
+
+#[test]
+fn clicking_toggles_multiselect_options() {
+    let mut h = WizardHarness::for_survey::<DeveloperProfile>(&RatatuiBackend::new());
+    h.type_text("Alice").press(KeyCode::Enter);
+
+    let (column, row) = h.find("Python").unwrap();
+    h.click(column, row);
+    let (column, row) = h.find("Go").unwrap();
+    h.click(column, row);
+    h.click(column, row);
+    assert!(h.screen().contains("(1 selected)"));
+
+    // The wheel moves the highlight without toggling
+    h.scroll_up();
+    h.press(KeyCode::Char(' '));
+    assert!(h.screen().contains("(2 selected)"));
+}
+
+#[test]
+fn multiline_answer_spans_lines() {
+    let mut h = WizardHarness::for_survey::<BlogPost>(&RatatuiBackend::new());
+    h.type_text("Title").press(KeyCode::Enter);
+    h.type_text("first")
+        .press(KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT))
+        .type_text("second")
+        .press(KeyCode::Up)
+        .press(KeyCode::End)
+        .type_text(" line");
+
+    let screen = h.screen();
+    assert!(screen.contains("first line"), "{screen}");
+    assert!(screen.contains("second"), "{screen}");
+    assert!(screen.contains("New line"), "{screen}");
+
+    h.press(KeyCode::Enter).type_text("-").press(KeyCode::Enter);
+    h.press(KeyCode::Enter);
+    let post = BlogPost::from_responses(&h.finish().unwrap());
+    assert_eq!(post.content, "first line\nsecond");
+}
+
+#[test]
+fn external_editor_replaces_answer() {
+    let mut h = WizardHarness::for_survey::<BlogPost>(&RatatuiBackend::new())
+        .with_editor(|text| text.replace("draft", "final\ntext"));
+    h.type_text("Title").press(KeyCode::Enter);
+    h.type_text("draft")
+        .press(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL))
+        .press(KeyCode::Enter)
+        .type_text("-")
+        .press(KeyCode::Enter)
+        .press(KeyCode::Enter);
+
+    let post = BlogPost::from_responses(&h.finish().unwrap());
+    assert_eq!(post.content, "final\ntext");
+}
+
+#[test]
+fn inline_multiline_answer_scrolls() {
+    let backend = RatatuiBackend::new().with_inline_viewport(6);
+    let mut h = WizardHarness::for_survey::<BlogPost>(&backend);
+    h.type_text("Title").press(KeyCode::Enter);
+    for line in ["one", "two", "three", "four"] {
+        h.type_text(line)
+            .press(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL));
+    }
+    h.type_text("five");
+
+    let screen = h.screen();
+    assert!(!screen.contains("one"), "{screen}");
+    assert!(screen.contains("four"), "{screen}");
+    assert!(screen.contains("five"), "{screen}");
+}
+
+#[test]
+fn pasted_text_is_inserted_at_once() {
+    let mut h = harness();
+    h.paste("Alice Smith\n").press(KeyCode::Enter);
+    assert_eq!(
+        h.responses()
+            .get_string(&ResponsePath::new("name"))
+            .unwrap(),
+        "Alice Smith"
+    );
+}
+
+#[test]
+fn cursor_moves_by_graphemes_and_words() {
+    let mut h = harness();
+    h.type_text("日本 word");
+    let end = h.cursor();
+
+    h.press(KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL));
+    assert_eq!(h.cursor().x, end.x - 4);
+    h.script([KeyCode::Left, KeyCode::Left]);
+    assert_eq!(h.cursor().x, end.x - 7, "wide characters take two cells");
+
+    h.press(KeyCode::Backspace).press(KeyCode::Enter);
+    assert_eq!(
+        h.responses()
+            .get_string(&ResponsePath::new("name"))
+            .unwrap(),
+        "本 word"
+    );
+}
+
+#[test]
+fn masked_answer_is_confirmed_and_can_be_revealed() {
+    let mut h = WizardHarness::for_survey::<Login>(&RatatuiBackend::new());
+    h.type_text("alice").press(KeyCode::Enter);
+
+    h.type_text("hunter2");
+    assert!(!h.screen().contains("hunter2"));
+    assert!(h.screen().contains("Weak"), "strength meter is shown");
+    h.press(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+    assert!(h.screen().contains("hunter2"));
+
+    h.press(KeyCode::Enter);
+    assert!(h.screen().contains("Repeat to confirm"));
+    h.type_text("hunter3").press(KeyCode::Enter);
+    assert!(h.screen().contains("Entries do not match"));
+
+    h.type_text("hunter2").press(KeyCode::Enter);
+    h.type_text("hunter2").press(KeyCode::Enter);
+    assert!(h.screen().contains("All questions answered!"));
+    assert_eq!(
+        h.responses()
+            .get_secret(&ResponsePath::new("password"))
+            .unwrap()
+            .expose_secret(),
+        "hunter2"
+    );
+}
+
+#[test]
+fn date_picker_moves_by_day_and_month() {
+    let mut h = WizardHarness::for_survey::<Meeting>(&RatatuiBackend::new());
+    h.type_text("Planning").press(KeyCode::Enter);
+
+    h.type_text("2024-01-31");
+    let screen = h.screen();
+    assert!(screen.contains("YYYY-MM-DD, min: 2024-01-01"));
+    assert!(screen.contains("January 2024"));
+    assert!(screen.contains("Mo Tu We Th Fr Sa Su"));
+
+    h.press(KeyCode::PageDown);
+    assert!(
+        h.screen().contains("2024-02-29"),
+        "clamped to the end of February"
+    );
+    h.press(KeyCode::Down);
+    assert!(h.screen().contains("March 2024"));
+    h.press(KeyCode::PageUp)
+        .press(KeyCode::PageUp)
+        .press(KeyCode::PageUp);
+    assert!(h.screen().contains("2024-01-01"), "clamped to the minimum");
+    h.press(KeyCode::Enter);
+
+    h.type_text("9:30").press(KeyCode::Enter);
+    h.type_text("10 hours").press(KeyCode::Enter);
+    assert!(h.screen().contains("Value must be at most 8h"));
+    h.script(std::iter::repeat_n(KeyCode::Backspace, "10 hours".len()));
+    h.type_text("1h 30m").press(KeyCode::Enter);
+    assert!(h.screen().contains("All questions answered!"));
+
+    let meeting = Meeting::from_responses(h.responses());
+    assert_eq!(meeting.day, Date::new(2024, 1, 1).unwrap());
+    assert_eq!(meeting.start, Time::new(9, 30, 0).unwrap());
+    assert_eq!(meeting.length, Duration::from_secs(90 * 60));
+}
+
+#[test]
+fn map_keys_are_typed_and_each_value_asked() {
+    let back = KeyEvent::new(KeyCode::Left, KeyModifiers::ALT);
+    let mut h = WizardHarness::for_survey::<Deployment>(&RatatuiBackend::new());
+    h.type_text("api").press(KeyCode::Enter);
+
+    h.type_text("PORT, host").press(KeyCode::Enter);
+    assert!(
+        h.screen()
+            .contains("Use upper case letters, digits and underscores")
+    );
+    h.script(std::iter::repeat_n(KeyCode::Backspace, "host".len()));
+    h.type_text("PORT").press(KeyCode::Enter);
+    assert!(h.screen().contains("Key \"PORT\" is already used"));
+    h.script(std::iter::repeat_n(KeyCode::Backspace, "PORT".len()));
+    h.type_text("HOST").press(KeyCode::Enter);
+
+    assert!(h.screen().contains("Value for PORT:"));
+    h.type_text("8080").press(KeyCode::Enter);
+    assert!(h.screen().contains("Value for HOST:"));
+    h.type_text("0.0.0.0").press(KeyCode::Enter);
+
+    // Going back to the keys and removing one keeps the other's value
+    h.press(back).press(back).press(back);
+    assert!(h.screen().contains("PORT, HOST"));
+    h.script(std::iter::repeat_n(KeyCode::Backspace, "PORT, HOST".len()));
+    h.type_text("HOST").press(KeyCode::Enter);
+    assert!(h.screen().contains("0.0.0.0"));
+    h.press(KeyCode::Enter);
+
+    h.type_text("web").press(KeyCode::Enter);
+    h.type_text("2").press(KeyCode::Enter);
+    h.type_text("512").press(KeyCode::Enter);
+    assert!(h.screen().contains("All questions answered!"));
+
+    let deployment = Deployment::from_responses(h.responses());
+    assert_eq!(deployment.env.len(), 1);
+    assert_eq!(deployment.env["HOST"], "0.0.0.0");
+    assert_eq!(deployment.limits["web"].cpus, 2);
+    assert_eq!(deployment.limits["web"].memory, 512);
+}
+
+#[test]
+fn recursive_variants_are_refused_when_too_deep() {
+    let mut h = WizardHarness::for_survey::<SavedSearch>(&RatatuiBackend::new());
+    h.type_text("sources").press(KeyCode::Enter);
+
+    // `And` twice, then a third time, one level deeper than allowed
+    h.script([KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    assert!(h.screen().contains("First filter:"));
+    h.script([KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    h.script([KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    assert!(
+        h.screen()
+            .contains("Filter cannot be nested more than 2 levels deep")
+    );
+
+    h.script([KeyCode::Up, KeyCode::Up, KeyCode::Enter]);
+    h.type_text("*.rs").press(KeyCode::Enter);
+    assert!(h.screen().contains("Second filter:"));
+    h.press(KeyCode::Enter)
+        .type_text("*.toml")
+        .press(KeyCode::Enter);
+    h.press(KeyCode::Enter)
+        .type_text("*.md")
+        .press(KeyCode::Enter);
+    assert!(h.screen().contains("All questions answered!"));
+
+    let search = SavedSearch::from_responses(h.responses());
+    let Filter::And { left, right } = search.filter else {
+        panic!("expected both filters to match");
+    };
+    assert!(matches!(*left, Filter::And { .. }));
+    assert!(matches!(*right, Filter::Matches { ref pattern } if pattern == "*.md"));
+}
@@ -1,8 +1,9 @@
 //! Requestty backend implementation for SurveyBackend trait.
 
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition,
+    DefaultValue, ListElementKind, MAP_KEYS_KEY, MapQuestion, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SecretValue,
+    SurveyBackend, SurveyDefinition,
 };
 use thiserror::Error;
 
@@ -20,6 +21,10 @@ pub enum RequesttyError {
     /// Unexpected answer type received.
     #[error("Unexpected answer type: expected {expected}, got {got}")]
     UnexpectedAnswerType { expected: String, got: String },
+
+    /// A recursive type was nested deeper than it may be.
+    #[error("{0}")]
+    TooDeep(String),
 }
 
 impl From<requestty::ErrorKind> for RequesttyError {
@@ -62,7 +67,7 @@ impl RequesttyBackend {
             // Convert path like "role" or "user_name" to "Role" or "User Name"
             path.as_str()
                 .split('.')
-                .last()
+                .next_back()
                 .unwrap_or("")
                 .split('_')
                 .map(|word| {
@@ -139,6 +144,18 @@ impl RequesttyBackend {
                 self.ask_confirm(&path, &prompt, confirm_q, question.default(), responses)
             }
 
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => self.ask_temporal(
+                &path,
+                &prompt,
+                question.kind(),
+                question.default(),
+                responses,
+                validate,
+            ),
+
             QuestionKind::List(list_q) => self.ask_list(
                 &path,
                 &prompt,
@@ -163,6 +180,16 @@ impl RequesttyBackend {
                 }
                 Ok(())
             }
+
+            QuestionKind::Map(map_q) => self.ask_map(&path, &prompt, map_q, responses, validate),
+
+            QuestionKind::Lazy(lazy) => {
+                let all_of = lazy.expand().map_err(RequesttyError::TooDeep)?;
+                for nested_q in all_of.questions() {
+                    self.ask_question(nested_q, responses, validate, Some(&path))?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -220,6 +247,71 @@ impl RequesttyBackend {
         }
     }
 
+    /// Ask for a date, time, date-time or duration as text.
+    fn ask_temporal(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        kind: &QuestionKind,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        let format = kind.temporal_format().unwrap_or_default();
+        let prompt = format!("{prompt} ({format})");
+        loop {
+            let mut q = requestty::Question::input(path.as_str()).message(prompt.clone());
+
+            // Apply default value
+            if let Some(text) = default
+                .value()
+                .cloned()
+                .or_else(|| kind.temporal_default())
+                .and_then(|value| value.temporal_text())
+            {
+                q = q.default(text);
+            }
+
+            // Parse the text, then validate the parsed value
+            let responses_clone = responses.clone();
+            let path_clone = path.clone();
+            let kind_clone = kind.clone();
+            let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+                match kind_clone.parse_temporal(value) {
+                    Some(Ok(rv)) => validate(&rv, &responses_clone, &path_clone),
+                    Some(Err(msg)) => Err(msg),
+                    None => Ok(()),
+                }
+            };
+
+            let result = requestty::prompt_one(q.validate(validate_fn).build());
+
+            match result {
+                Ok(requestty::Answer::String(s)) => {
+                    let rv = match kind.parse_temporal(&s) {
+                        Some(Ok(rv)) => rv,
+                        _ => ResponseValue::String(s),
+                    };
+                    responses.insert(path.clone(), rv);
+                    return Ok(());
+                }
+                Ok(other) => {
+                    return Err(RequesttyError::UnexpectedAnswerType {
+                        expected: "String".to_string(),
+                        got: format!("{other:?}"),
+                    });
+                }
+                Err(e) => {
+                    if matches!(e, requestty::ErrorKind::Interrupted) {
+                        return Err(RequesttyError::Cancelled);
+                    }
+                    eprintln!("Error: {e}");
+                    continue;
+                }
+            }
+        }
+    }
+
     fn ask_multiline(
         &self,
         path: &ResponsePath,
@@ -293,7 +385,7 @@ impl RequesttyBackend {
             let responses_clone = responses.clone();
             let path_clone = path.clone();
             let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
-                let rv = ResponseValue::String(value.to_string());
+                let rv = ResponseValue::Secret(SecretValue::new(value));
                 validate(&rv, &responses_clone, &path_clone)
             };
 
@@ -301,7 +393,14 @@ impl RequesttyBackend {
 
             match result {
                 Ok(requestty::Answer::String(s)) => {
-                    responses.insert(path.clone(), ResponseValue::String(s));
+                    let s = SecretValue::new(s);
+                    if masked_q.confirm
+                        && SecretValue::new(self.ask_confirmation(path, masked_q)?) != s
+                    {
+                        eprintln!("Error: {}", elicitor::CONFIRMATION_MISMATCH);
+                        continue;
+                    }
+                    responses.insert(path.clone(), ResponseValue::Secret(s));
                     return Ok(());
                 }
                 Ok(other) => {
@@ -321,6 +420,28 @@ impl RequesttyBackend {
         }
     }
 
+    /// Ask for the second entry of a masked question with confirmation.
+    fn ask_confirmation(
+        &self,
+        path: &ResponsePath,
+        masked_q: &elicitor::MaskedQuestion,
+    ) -> Result<String, RequesttyError> {
+        let mut q =
+            requestty::Question::password(path.as_str()).message(elicitor::CONFIRMATION_PROMPT);
+        if let Some(mask) = masked_q.mask {
+            q = q.mask(mask);
+        }
+
+        match requestty::prompt_one(q.build()) {
+            Ok(requestty::Answer::String(s)) => Ok(s),
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn ask_int(
         &self,
         path: &ResponsePath,
@@ -516,17 +637,17 @@ impl RequesttyBackend {
                         ListElementKind::String => Some(ResponseValue::String(s)),
                         ListElementKind::Int { min, max } => match s.parse::<i64>() {
                             Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
+                                if let Some(min_val) = min
+                                    && n < *min_val
+                                {
+                                    println!("    Error: Value must be at least {min_val}");
+                                    continue;
                                 }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
+                                if let Some(max_val) = max
+                                    && n > *max_val
+                                {
+                                    println!("    Error: Value must be at most {max_val}");
+                                    continue;
                                 }
                                 Some(ResponseValue::Int(n))
                             }
@@ -537,17 +658,17 @@ impl RequesttyBackend {
                         },
                         ListElementKind::Float { min, max } => match s.parse::<f64>() {
                             Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
+                                if let Some(min_val) = min
+                                    && n < *min_val
+                                {
+                                    println!("    Error: Value must be at least {min_val}");
+                                    continue;
                                 }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
+                                if let Some(max_val) = max
+                                    && n > *max_val
+                                {
+                                    println!("    Error: Value must be at most {max_val}");
+                                    continue;
                                 }
                                 Some(ResponseValue::Float(n))
                             }
@@ -629,23 +750,30 @@ impl RequesttyBackend {
         // Build choices from variant names
         let choices: Vec<String> = one_of.variants.iter().map(|v| v.name.clone()).collect();
 
-        let mut q = requestty::Question::select(path.as_str())
-            .message(prompt)
-            .choices(choices);
+        // Variants of a recursive type that would nest too deeply are refused
+        let selection = loop {
+            let mut q = requestty::Question::select(path.as_str())
+                .message(prompt)
+                .choices(choices.clone());
 
-        if let Some(default_idx) = one_of.default {
-            q = q.default(default_idx);
-        }
+            if let Some(default_idx) = one_of.default {
+                q = q.default(default_idx);
+            }
 
-        let result = requestty::prompt_one(q.build())?;
+            let result = requestty::prompt_one(q.build())?;
 
-        let selection = match result {
-            requestty::Answer::ListItem(item) => item.index,
-            other => {
-                return Err(RequesttyError::UnexpectedAnswerType {
-                    expected: "ListItem".to_string(),
-                    got: format!("{other:?}"),
-                });
+            let selection = match result {
+                requestty::Answer::ListItem(item) => item.index,
+                other => {
+                    return Err(RequesttyError::UnexpectedAnswerType {
+                        expected: "ListItem".to_string(),
+                        got: format!("{other:?}"),
+                    });
+                }
+            };
+            match one_of.variants[selection].kind.nesting_error() {
+                Some(msg) => println!("Error: {msg}"),
+                None => break selection,
             }
         };
 
@@ -670,7 +798,13 @@ impl RequesttyBackend {
             | QuestionKind::Confirm(_)
             | QuestionKind::Masked(_)
             | QuestionKind::Multiline(_)
-            | QuestionKind::List(_) => {
+            | QuestionKind::List(_)
+            | QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_)
+            | QuestionKind::Map(_)
+            | QuestionKind::Lazy(_) => {
                 // Create a synthetic question for the variant's data
                 let variant_q = Question::new(
                     selected_variant.name.clone(),
@@ -701,6 +835,145 @@ impl RequesttyBackend {
         Ok(())
     }
 
+    fn ask_map(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        map_q: &MapQuestion,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        const ADD: &str = "Add entry";
+        const EDIT: &str = "Edit entry";
+        const REMOVE: &str = "Remove entry";
+        const DONE: &str = "Done";
+
+        // Each entry's value responses, relative to the entry's path
+        let mut entries: Vec<(String, Responses)> = Vec::new();
+        loop {
+            let keys: Vec<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+            println!("{prompt} {{{}}}", keys.join(", "));
+
+            let actions = if entries.is_empty() {
+                vec![ADD, DONE]
+            } else {
+                vec![ADD, EDIT, REMOVE, DONE]
+            };
+            let action = actions[self.select(path, "Entries", &actions)?];
+            match action {
+                ADD => {
+                    let idx = entries.len();
+                    let key = self.ask_map_key(path, "", &keys, responses, validate)?;
+                    let value = self.ask_map_value(path, map_q, idx, &key, responses, validate)?;
+                    entries.push((key, value));
+                }
+                EDIT => {
+                    let idx = self.select(path, "Entry to edit", &keys)?;
+                    let others: Vec<&str> = keys
+                        .iter()
+                        .enumerate()
+                        .filter(|&(other, _)| other != idx)
+                        .map(|(_, key)| *key)
+                        .collect();
+                    let key = self.ask_map_key(path, keys[idx], &others, responses, validate)?;
+                    let value = self.ask_map_value(path, map_q, idx, &key, responses, validate)?;
+                    entries[idx] = (key, value);
+                }
+                REMOVE => {
+                    let idx = self.select(path, "Entry to remove", &keys)?;
+                    entries.remove(idx);
+                }
+                _ => break,
+            }
+        }
+
+        for (idx, (_, values)) in entries.iter().enumerate() {
+            let entry_path = path.child(&idx.to_string());
+            for (relative, value) in values.iter() {
+                responses.insert(entry_path.child(relative.as_str()), value.clone());
+            }
+        }
+        let keys = entries.into_iter().map(|(key, _)| key).collect();
+        responses.insert(path.child(MAP_KEYS_KEY), ResponseValue::StringList(keys));
+        Ok(())
+    }
+
+    /// Pick one of `items`, returning its index.
+    fn select(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        items: &[&str],
+    ) -> Result<usize, RequesttyError> {
+        let q = requestty::Question::select(path.as_str())
+            .message(prompt)
+            .choices(items.iter().map(|item| item.to_string()))
+            .build();
+
+        match requestty::prompt_one(q)? {
+            requestty::Answer::ListItem(item) => Ok(item.index),
+            other => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "ListItem".to_string(),
+                got: format!("{other:?}"),
+            }),
+        }
+    }
+
+    /// Ask for the key of a map entry that differs from `other_keys`.
+    fn ask_map_key(
+        &self,
+        path: &ResponsePath,
+        default: &str,
+        other_keys: &[&str],
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<String, RequesttyError> {
+        let mut q = requestty::Question::input(path.as_str()).message("Key");
+        if !default.is_empty() {
+            q = q.default(default.to_string());
+        }
+
+        let keys_path = path.child(MAP_KEYS_KEY);
+        let validate_fn = move |key: &str, _: &requestty::Answers| -> Result<(), String> {
+            MapQuestion::check_key(key, other_keys.iter().copied()).and_then(|()| {
+                validate(
+                    &ResponseValue::String(key.to_string()),
+                    responses,
+                    &keys_path,
+                )
+            })
+        };
+
+        match requestty::prompt_one(q.validate(validate_fn).build())? {
+            requestty::Answer::String(key) => Ok(key),
+            other => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+        }
+    }
+
+    /// Ask for the value of the map entry at `idx`, returning the answers
+    /// relative to the entry's path.
+    fn ask_map_value(
+        &self,
+        path: &ResponsePath,
+        map_q: &MapQuestion,
+        idx: usize,
+        key: &str,
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, RequesttyError> {
+        let mut scratch = responses.clone();
+        self.ask_question(
+            &map_q.value_question(idx, key),
+            &mut scratch,
+            validate,
+            Some(path),
+        )?;
+        Ok(scratch.filter_prefix(&path.child(&idx.to_string())))
+    }
+
     fn ask_any_of(
         &self,
         path: &ResponsePath,
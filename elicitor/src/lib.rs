@@ -6,6 +6,10 @@ pub use elicitor_types::*;
 // Re-export the derive macro
 pub use elicitor_macro::Survey;
 
+// Re-export secrecy for `SecretString` fields in derived surveys
+#[cfg(feature = "secrecy")]
+pub use secrecy;
+
 // Test backend for testing surveys without user interaction
 mod test_backend;
 pub use test_backend::TestBackend;
@@ -30,7 +30,7 @@
 
 use std::collections::HashMap;
 
-use crate::{ResponsePath, ResponseValue, Responses, SurveyBackend, SurveyDefinition};
+use crate::{ResponsePath, ResponseValue, Responses, SecretValue, SurveyBackend, SurveyDefinition};
 
 /// A test backend that returns pre-configured responses.
 ///
@@ -49,6 +49,9 @@ pub enum TestBackendError {
 
     #[error("Validation failed for '{path}': {message}")]
     ValidationFailed { path: String, message: String },
+
+    #[error("Cannot ask '{path}': {message}")]
+    TooDeep { path: String, message: String },
 }
 
 impl TestBackend {
@@ -147,7 +150,27 @@ fn collect_question_responses(
             QuestionKind::Unit => {
                 // No response needed for unit types
             }
-            QuestionKind::Input(_) | QuestionKind::Multiline(_) | QuestionKind::Masked(_) => {
+            QuestionKind::Masked(_) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // Masked answers are secrets, as with the interactive backends
+                    let value = match value {
+                        ResponseValue::String(s) => {
+                            ResponseValue::Secret(SecretValue::new(s.as_str()))
+                        }
+                        other => other.clone(),
+                    };
+                    if let Err(msg) = validate(&value, responses, &full_path) {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value);
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
+            QuestionKind::Input(_) | QuestionKind::Multiline(_) => {
                 if let Some(value) = test_responses.get(&path_str) {
                     // Validate before inserting
                     if let Err(msg) = validate(value, responses, &full_path) {
@@ -189,6 +212,39 @@ fn collect_question_responses(
                     return Err(TestBackendError::MissingResponse(path_str));
                 }
             }
+            QuestionKind::Date(_)
+            | QuestionKind::Time(_)
+            | QuestionKind::DateTime(_)
+            | QuestionKind::Duration(_) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // Text answers are parsed as the interactive backends do
+                    let parsed = match value {
+                        ResponseValue::String(text) => question.kind().parse_temporal(text),
+                        other => other
+                            .temporal_text()
+                            .and_then(|text| question.kind().parse_temporal(&text)),
+                    };
+                    let value = match parsed {
+                        Some(Ok(value)) => value,
+                        Some(Err(msg)) => {
+                            return Err(TestBackendError::ValidationFailed {
+                                path: path_str,
+                                message: msg,
+                            });
+                        }
+                        None => value.clone(),
+                    };
+                    if let Err(msg) = validate(&value, responses, &full_path) {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value);
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
             QuestionKind::Confirm(_) => {
                 if let Some(value) = test_responses.get(&path_str) {
                     responses.insert(full_path, value.clone());
@@ -263,6 +319,40 @@ fn collect_question_responses(
                     return Err(TestBackendError::MissingResponse(variants_key));
                 }
             }
+            QuestionKind::Map(map_q) => {
+                let keys_path = full_path.child(crate::MAP_KEYS_KEY);
+                if let Some(ResponseValue::StringList(keys)) =
+                    test_responses.get(keys_path.as_str())
+                {
+                    for (idx, key) in keys.iter().enumerate() {
+                        let key_check = crate::MapQuestion::check_key(
+                            key,
+                            keys[..idx].iter().map(String::as_str),
+                        )
+                        .and_then(|()| {
+                            validate(&ResponseValue::String(key.clone()), responses, &keys_path)
+                        });
+                        if let Err(msg) = key_check {
+                            return Err(TestBackendError::ValidationFailed {
+                                path: keys_path.to_string(),
+                                message: msg,
+                            });
+                        }
+
+                        // Each value is answered at the entry's index
+                        collect_question_responses(
+                            &[map_q.value_question(idx, key)],
+                            &full_path,
+                            test_responses,
+                            responses,
+                            validate,
+                        )?;
+                    }
+                    responses.insert(keys_path, ResponseValue::StringList(keys.clone()));
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(keys_path.to_string()));
+                }
+            }
             QuestionKind::AllOf(all_of) => {
                 collect_question_responses(
                     all_of.questions(),
@@ -272,6 +362,19 @@ fn collect_question_responses(
                     validate,
                 )?;
             }
+            QuestionKind::Lazy(lazy) => {
+                let all_of = lazy.expand().map_err(|message| TestBackendError::TooDeep {
+                    path: path_str,
+                    message,
+                })?;
+                collect_question_responses(
+                    all_of.questions(),
+                    &full_path,
+                    test_responses,
+                    responses,
+                    validate,
+                )?;
+            }
         }
     }
 
@@ -253,3 +253,130 @@ fn combined_suggest_and_assume() {
         .suggest_payment(|p| p.suggest_cash())
         .assume_nickname(|opt| opt.none());
 }
+
+// ============================================================================
+// Map Tests
+// ============================================================================
+
+#[derive(Survey, Debug, PartialEq)]
+struct Inventory {
+    #[ask("Stock per item:")]
+    #[min(0)]
+    stock: std::collections::BTreeMap<String, i32>,
+
+    #[ask("Payment per customer:")]
+    payments: std::collections::HashMap<String, PaymentMethod>,
+}
+
+#[test]
+fn map_entries_with_test_backend() {
+    let inventory: Inventory = Inventory::builder()
+        .run(
+            TestBackend::new()
+                .with_response(
+                    "stock.keys",
+                    vec!["apples".to_string(), "pears".to_string()],
+                )
+                .with_int("stock.0", 12)
+                .with_int("stock.1", 3)
+                .with_response("payments.keys", vec!["alice".to_string()])
+                .with_variant("payments.0.selected_variant", 2)
+                .with_string("payments.0.iban", "DE89370400440532013000"),
+        )
+        .unwrap();
+
+    assert_eq!(inventory.stock["apples"], 12);
+    assert_eq!(inventory.stock["pears"], 3);
+    assert_eq!(
+        inventory.payments["alice"],
+        PaymentMethod::BankTransfer {
+            iban: "DE89370400440532013000".to_string()
+        }
+    );
+}
+
+#[test]
+fn map_keys_must_be_unique() {
+    let result = Inventory::builder().run(
+        TestBackend::new()
+            .with_response(
+                "stock.keys",
+                vec!["apples".to_string(), "apples".to_string()],
+            )
+            .with_int("stock.0", 12)
+            .with_int("stock.1", 3)
+            .with_response("payments.keys", Vec::<String>::new()),
+    );
+
+    assert!(result.is_err());
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Request<T> {
+    #[ask("Request id:")]
+    id: String,
+
+    #[ask("Payload:")]
+    payload: T,
+}
+
+#[test]
+fn generic_survey_with_test_backend() {
+    let request: Request<SimpleConfig> = Request::builder()
+        .run(
+            TestBackend::new()
+                .with_string("id", "req-7")
+                .with_string("payload.name", "Alice")
+                .with_int("payload.age", 30)
+                .with_bool("payload.developer", false),
+        )
+        .unwrap();
+
+    assert_eq!(request.id, "req-7");
+    assert_eq!(request.payload.name, "Alice");
+    assert_eq!(request.payload.age, 30);
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Expr {
+    Literal(#[ask("Value:")] i64),
+    Negate(
+        #[ask("Operand:")]
+        #[max_depth(1)]
+        Box<Expr>,
+    ),
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Formula {
+    #[ask("Expression:")]
+    expr: Expr,
+}
+
+#[test]
+fn recursive_survey_with_test_backend() {
+    let formula: Formula = Formula::builder()
+        .run(
+            TestBackend::new()
+                .with_variant("expr.selected_variant", 1)
+                .with_variant("expr.0.selected_variant", 0)
+                .with_int("expr.0.0", 5),
+        )
+        .unwrap();
+
+    assert_eq!(formula.expr, Expr::Negate(Box::new(Expr::Literal(5))));
+}
+
+#[test]
+fn recursive_survey_nested_too_deeply() {
+    let result = Formula::builder().run(
+        TestBackend::new()
+            .with_variant("expr.selected_variant", 1)
+            .with_variant("expr.0.selected_variant", 1)
+            .with_variant("expr.0.0.selected_variant", 0)
+            .with_int("expr.0.0.0", 5),
+    );
+
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Expr cannot be nested more than 1 levels deep"));
+}
@@ -9,7 +9,12 @@ pub fn validate_email(
     let ResponseValue::String(email) = value else {
         return Ok(());
     };
-    if !email.contains('@') || !email.split('@').last().is_some_and(|d| d.contains('.')) {
+    if !email.contains('@')
+        || !email
+            .split('@')
+            .next_back()
+            .is_some_and(|d| d.contains('.'))
+    {
         return Err("Enter a valid email (e.g., you@example.com)".into());
     }
     Ok(())
@@ -20,9 +25,10 @@ pub fn validate_password(
     _: &Responses,
     _path: &elicitor::ResponsePath,
 ) -> Result<(), String> {
-    let ResponseValue::String(pw) = value else {
+    let ResponseValue::Secret(pw) = value else {
         return Ok(());
     };
+    let pw = pw.expose_secret();
     if pw.len() < 6 {
         return Err("Password must be at least 6 characters".into());
     }
@@ -2,6 +2,7 @@ pub mod app_settings;
 pub mod basic_fields;
 pub mod enum_oneof;
 pub mod job_application;
+pub mod maps;
 pub mod masked_input;
 pub mod min_max_bounds;
 pub mod multiline_text;
@@ -11,6 +12,8 @@ pub mod optional_fields;
 pub mod order_form;
 pub mod prelude_epilogue;
 pub mod sandwich;
+pub mod scheduling;
+pub mod search_filter;
 pub mod simple_spooky_forest;
 pub mod spooky_forest;
 pub mod user_profile;
@@ -32,6 +35,9 @@ pub use job_application::{
     WorkStyle,
 };
 
+// Re-export maps types
+pub use maps::{Deployment, ResourceLimits, validate_env_var};
+
 // Re-export spooky_forest types
 pub use spooky_forest::{
     Cast, CharacterStats, Companion, CompanionDetails, CompanionSpecies, FamiliarForm,
@@ -39,7 +45,7 @@ pub use spooky_forest::{
 };
 
 // Re-export masked_input types
-pub use masked_input::{Login, Passwords, passwords_match};
+pub use masked_input::Login;
 
 // Re-export min_max_bounds types
 pub use min_max_bounds::GameSettings;
@@ -68,6 +74,12 @@ pub use sandwich::{
     validate_nutrition, validate_toppings,
 };
 
+// Re-export scheduling types
+pub use scheduling::Meeting;
+
+// Re-export search_filter types
+pub use search_filter::{Filter, SavedSearch};
+
 // Re-export simple_spooky_forest types
 pub use simple_spooky_forest::{
     SimpleItem, SimpleRole, SimpleSpookyForest, is_valid_name, is_within_starting_budget,
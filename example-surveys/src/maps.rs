@@ -0,0 +1,55 @@
+//! Map example types
+//!
+//! Demonstrates:
+//! - HashMap<String, String> for key/value entries
+//! - BTreeMap<String, T> with a nested Survey type as the value
+//! - #[validate_key("fn_name")] to check each key
+
+use std::collections::{BTreeMap, HashMap};
+
+use elicitor::{ResponsePath, ResponseValue, Responses, Survey};
+
+pub fn validate_env_var(
+    value: &ResponseValue,
+    _responses: &Responses,
+    _path: &ResponsePath,
+) -> Result<(), String> {
+    let ResponseValue::String(name) = value else {
+        return Ok(());
+    };
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err("Variable names must not start with a digit".to_string());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err("Use upper case letters, digits and underscores".to_string());
+    }
+    Ok(())
+}
+
+#[derive(Survey, Debug)]
+pub struct ResourceLimits {
+    #[ask("CPU cores:")]
+    #[min(1)]
+    #[max(64)]
+    pub cpus: u32,
+
+    #[ask("Memory (MiB):")]
+    #[min(64)]
+    pub memory: u32,
+}
+
+#[derive(Survey, Debug)]
+pub struct Deployment {
+    #[ask("Service name:")]
+    pub service: String,
+
+    #[ask("Environment variables:")]
+    #[validate_key(validate_env_var)]
+    pub env: HashMap<String, String>,
+
+    #[ask("Resource limits per container:")]
+    pub limits: BTreeMap<String, ResourceLimits>,
+}
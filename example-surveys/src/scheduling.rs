@@ -0,0 +1,31 @@
+//! Date, time and duration example types
+//!
+//! Demonstrates:
+//! - Date, Time and Duration fields, asked with their own question kinds
+//! - #[min]/#[max] with text bounds for temporal fields
+//! - #[temporal] for temporal types imported by name
+
+use std::time::Duration;
+
+use elicitor::{Date, Survey, Time};
+
+#[derive(Survey, Debug)]
+pub struct Meeting {
+    #[ask("What is the meeting about?")]
+    pub topic: String,
+
+    #[ask("On which day?")]
+    #[temporal]
+    #[min("2024-01-01")]
+    pub day: Date,
+
+    #[ask("At what time does it start?")]
+    #[temporal]
+    pub start: Time,
+
+    #[ask("How long will it take?")]
+    #[temporal]
+    #[min("15m")]
+    #[max("8h")]
+    pub length: Duration,
+}
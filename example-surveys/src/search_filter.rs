@@ -0,0 +1,46 @@
+//! Recursive type example
+//!
+//! Demonstrates:
+//! - Box<T> fields, asked as the type they point to
+//! - A recursive enum whose nested filters are expanded on demand
+//! - #[max_depth(n)] to limit how deeply a recursive field may be nested
+
+use elicitor::Survey;
+
+#[derive(Survey, Debug)]
+pub enum Filter {
+    /// Name matches
+    ///
+    /// Match file names against a glob pattern.
+    Matches {
+        #[ask("Pattern:")]
+        pattern: String,
+    },
+
+    /// Not
+    ///
+    /// Match everything the inner filter does not.
+    Not(#[ask("Filter to negate:")] Box<Filter>),
+
+    /// Both
+    ///
+    /// Match what both filters match.
+    And {
+        #[ask("First filter:")]
+        #[max_depth(2)]
+        left: Box<Filter>,
+
+        #[ask("Second filter:")]
+        #[max_depth(2)]
+        right: Box<Filter>,
+    },
+}
+
+#[derive(Survey, Debug)]
+pub struct SavedSearch {
+    #[ask("Name of the search:")]
+    pub name: String,
+
+    #[ask("Which files should it find?")]
+    pub filter: Filter,
+}
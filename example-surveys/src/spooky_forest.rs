@@ -50,9 +50,10 @@ pub fn validate_passphrase(
     _responses: &Responses,
     _path: &elicitor::ResponsePath,
 ) -> Result<(), String> {
-    let ResponseValue::String(pass) = value else {
+    let ResponseValue::Secret(pass) = value else {
         return Ok(());
     };
+    let pass = pass.expose_secret();
     if pass.len() < 8 {
         return Err("Passphrase must be at least 8 characters".to_string());
     }
@@ -29,9 +29,10 @@ pub fn validate_password(
     _responses: &Responses,
     _path: &ResponsePath,
 ) -> Result<(), String> {
-    let ResponseValue::String(password) = value else {
+    let ResponseValue::Secret(password) = value else {
         return Ok(());
     };
+    let password = password.expose_secret();
     if password.len() < 8 {
         return Err("Password must be at least 8 characters".to_string());
     }